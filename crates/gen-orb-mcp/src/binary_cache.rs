@@ -0,0 +1,139 @@
+//! Content-addressed cache of compiled MCP server binaries.
+//!
+//! `generate --format binary --binary-cache <DIR>` keys each build by the
+//! hash of the generated source, the `rustc -vV` output of the toolchain
+//! that would build it, and the Rust flags in the environment. When
+//! `<DIR>/<key>/` already holds the binary, it is copied into place instead
+//! of running cargo, so regenerating an unchanged orb in CI is near-instant.
+//!
+//! ```text
+//! <DIR>/
+//! └── 3f1c…e9/
+//!     └── toolkit_mcp
+//! ```
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use sha2::{Digest, Sha256};
+
+/// Environment variables that change the compiled binary.
+const KEY_ENV_VARS: &[&str] = &["RUSTFLAGS", "CARGO_ENCODED_RUSTFLAGS"];
+
+/// A cache directory of compiled binaries.
+#[derive(Debug, Clone)]
+pub struct BinaryCache {
+    dir: PathBuf,
+}
+
+impl BinaryCache {
+    /// Use `dir` as the cache root; it is created on first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Cache key for a build of `source_hash` with `toolchain` (the
+    /// `rustc -vV` output) and the current Rust flags.
+    pub fn key(source_hash: &str, toolchain: &str) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(source_hash.as_bytes());
+        hasher.update([0]);
+        hasher.update(toolchain.as_bytes());
+        for var in KEY_ENV_VARS {
+            hasher.update([0]);
+            hasher.update(var.as_bytes());
+            hasher.update(b"=");
+            if let Some(value) = std::env::var_os(var) {
+                hasher.update(value.to_string_lossy().as_bytes());
+            }
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// The cached binary named `file_name` for `key`, if present.
+    pub fn lookup(&self, key: &str, file_name: &str) -> Option<PathBuf> {
+        let path = self.dir.join(key).join(file_name);
+        path.is_file().then_some(path)
+    }
+
+    /// Copy `binary` into the cache under `key`, returning the cached path.
+    ///
+    /// The copy is written to a temporary name and renamed, so concurrent
+    /// pipelines never see a partial binary.
+    pub fn store(&self, key: &str, binary: &Path) -> io::Result<PathBuf> {
+        let entry = self.dir.join(key);
+        fs::create_dir_all(&entry)?;
+        let file_name = binary.file_name().ok_or_else(|| {
+            io::Error::new(io::ErrorKind::InvalidInput, "binary has no file name")
+        })?;
+        let cached = entry.join(file_name);
+        let partial = entry.join(format!(
+            ".{}.{}.partial",
+            file_name.to_string_lossy(),
+            std::process::id()
+        ));
+        fs::copy(binary, &partial)?;
+        fs::rename(&partial, &cached)?;
+        Ok(cached)
+    }
+
+    /// Copy a cached binary to `dest`, creating parent directories.
+    pub fn restore(cached: &Path, dest: &Path) -> io::Result<()> {
+        if let Some(parent) = dest.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::copy(cached, dest).map(|_| ())
+    }
+}
+
+/// `rustc -vV` output for the toolchain cargo would use in `crate_dir`
+/// (honouring `RUSTC` and `rust-toolchain` files), or `None` when rustc
+/// cannot be run.
+pub fn toolchain_fingerprint(crate_dir: &Path) -> Option<String> {
+    let rustc = std::env::var_os("RUSTC").unwrap_or_else(|| "rustc".into());
+    let output = Command::new(rustc)
+        .arg("-vV")
+        .current_dir(crate_dir)
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_key_depends_on_source_and_toolchain() {
+        let key = BinaryCache::key("abc", "rustc 1.90.0");
+        assert_eq!(key.len(), 64);
+        assert_eq!(key, BinaryCache::key("abc", "rustc 1.90.0"));
+        assert_ne!(key, BinaryCache::key("abd", "rustc 1.90.0"));
+        assert_ne!(key, BinaryCache::key("abc", "rustc 1.91.0"));
+    }
+
+    #[test]
+    fn test_store_lookup_restore() {
+        let dir = TempDir::new().unwrap();
+        let cache = BinaryCache::new(dir.path().join("cache"));
+        let built = dir.path().join("toolkit_mcp");
+        fs::write(&built, b"binary").unwrap();
+
+        assert_eq!(cache.lookup("k", "toolkit_mcp"), None);
+        let cached = cache.store("k", &built).unwrap();
+        assert_eq!(cache.lookup("k", "toolkit_mcp"), Some(cached.clone()));
+        assert_eq!(fs::read_dir(cached.parent().unwrap()).unwrap().count(), 1);
+
+        let dest = dir.path().join("out/target/release/toolkit_mcp");
+        BinaryCache::restore(&cached, &dest).unwrap();
+        assert_eq!(fs::read(dest).unwrap(), b"binary");
+    }
+}
@@ -22,6 +22,7 @@
 //!     commands
 
 pub mod heuristics;
+pub mod render;
 
 use std::collections::{HashMap, HashSet};
 
@@ -0,0 +1,236 @@
+//! Human-readable rendering of orb diffs for the `diff` subcommand.
+//!
+//! Rules are listed one per line, coloured by impact: removals in red,
+//! renames and absorptions in yellow, newly required parameters in green.
+//! The optional side-by-side view lines up the definitions of the previous
+//! and current orb per category, showing only the rows that changed.
+
+use std::{collections::BTreeSet, io::IsTerminal};
+
+use crate::{conformance_rule::ConformanceRule, parser::OrbDefinition};
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const YELLOW: &str = "\x1b[33m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+
+/// When to colour diff output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum ColorChoice {
+    /// Colour when stdout is a terminal and `NO_COLOR` is unset
+    #[default]
+    Auto,
+    /// Always colour
+    Always,
+    /// Never colour
+    Never,
+}
+
+impl ColorChoice {
+    /// Whether ANSI colour should be emitted on stdout.
+    pub fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                let no_color = std::env::var_os("NO_COLOR").is_some_and(|v| !v.is_empty());
+                !no_color && std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+/// Render the rule list, one bullet per rule.
+pub fn render_rules(rules: &[ConformanceRule], color: bool) -> String {
+    rules
+        .iter()
+        .map(|rule| {
+            format!(
+                "  • {}\n",
+                paint(&rule.description(), rule_color(rule), color)
+            )
+        })
+        .collect()
+}
+
+/// Render changed definitions per category in two columns, previous on the
+/// left and current on the right.
+///
+/// Renamed commands and jobs share a row; unchanged definitions are counted
+/// rather than listed.
+pub fn render_side_by_side(
+    previous: &OrbDefinition,
+    current: &OrbDefinition,
+    rules: &[ConformanceRule],
+    color: bool,
+) -> String {
+    let renamed: Vec<(&str, &str)> = rules
+        .iter()
+        .filter_map(|rule| match rule {
+            ConformanceRule::JobRenamed { from, to, .. }
+            | ConformanceRule::CommandRenamed { from, to, .. } => {
+                Some((from.as_str(), to.as_str()))
+            }
+            _ => None,
+        })
+        .collect();
+
+    let categories = [
+        (
+            "Commands",
+            names(previous.commands.keys()),
+            names(current.commands.keys()),
+        ),
+        (
+            "Jobs",
+            names(previous.jobs.keys()),
+            names(current.jobs.keys()),
+        ),
+        (
+            "Executors",
+            names(previous.executors.keys()),
+            names(current.executors.keys()),
+        ),
+    ];
+
+    let width = categories
+        .iter()
+        .flat_map(|(_, old, _)| old.iter().map(|n| n.chars().count()))
+        .chain(["previous".len()])
+        .max()
+        .unwrap_or_default();
+
+    let mut out = String::new();
+    for (title, old, new) in &categories {
+        let mut rows: Vec<(&str, &str, &str)> = Vec::new();
+        for name in old.difference(new) {
+            match renamed.iter().find(|(from, _)| from == name) {
+                Some((from, to)) => rows.push((from, to, YELLOW)),
+                None => rows.push((name, "", RED)),
+            }
+        }
+        for name in new.difference(old) {
+            if !renamed.iter().any(|(_, to)| to == name) {
+                rows.push(("", name, GREEN));
+            }
+        }
+        if rows.is_empty() {
+            continue;
+        }
+        rows.sort_by(|a, b| a.0.max(a.1).cmp(b.0.max(b.1)));
+
+        out.push_str(&format!("{}\n", paint(title, BOLD, color)));
+        out.push_str(&format!("  {:<width$} │ current\n", "previous"));
+        for (left, right, style) in rows {
+            let line = format!("{left:<width$} │ {right}");
+            out.push_str(&format!("  {}\n", paint(&line, style, color)));
+        }
+        let unchanged = old.intersection(new).count();
+        if unchanged > 0 {
+            out.push_str(&format!("  ({unchanged} unchanged)\n"));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+fn names<'a>(keys: impl Iterator<Item = &'a String>) -> BTreeSet<&'a str> {
+    keys.map(String::as_str).collect()
+}
+
+fn rule_color(rule: &ConformanceRule) -> &'static str {
+    match rule {
+        ConformanceRule::JobRemoved { .. }
+        | ConformanceRule::CommandRemoved { .. }
+        | ConformanceRule::ParameterRemoved { .. }
+        | ConformanceRule::CommandParameterRemoved { .. }
+        | ConformanceRule::ParameterEnumValueRemoved { .. } => RED,
+        ConformanceRule::JobRenamed { .. }
+        | ConformanceRule::CommandRenamed { .. }
+        | ConformanceRule::ParameterRenamed { .. }
+        | ConformanceRule::JobAbsorbed { .. } => YELLOW,
+        ConformanceRule::ParameterAdded { .. } | ConformanceRule::CommandParameterAdded { .. } => {
+            GREEN
+        }
+    }
+}
+
+fn paint(text: &str, style: &str, color: bool) -> String {
+    if color {
+        format!("{style}{text}{RESET}")
+    } else {
+        text.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{Command, Job};
+
+    fn removed_job(name: &str) -> ConformanceRule {
+        ConformanceRule::JobRemoved {
+            name: name.to_string(),
+            since_version: "2.0.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_rules_plain_and_colored() {
+        let rules = vec![removed_job("deploy")];
+
+        let plain = render_rules(&rules, false);
+        assert_eq!(
+            plain,
+            "  • Job `deploy` was removed in 2.0.0 with no replacement\n"
+        );
+
+        let colored = render_rules(&rules, true);
+        assert!(colored
+            .contains("\x1b[31mJob `deploy` was removed in 2.0.0 with no replacement\x1b[0m"));
+    }
+
+    #[test]
+    fn test_render_side_by_side_pairs_renames_and_skips_unchanged() {
+        let mut previous = OrbDefinition::default();
+        previous.jobs.insert("build".to_string(), Job::default());
+        previous.jobs.insert("deploy".to_string(), Job::default());
+        previous
+            .commands
+            .insert("setup".to_string(), Command::default());
+
+        let mut current = OrbDefinition::default();
+        current.jobs.insert("build".to_string(), Job::default());
+        current.jobs.insert("release".to_string(), Job::default());
+
+        let rules = vec![
+            ConformanceRule::JobRenamed {
+                from: "deploy".to_string(),
+                to: "release".to_string(),
+                removed_parameters: vec![],
+                since_version: "2.0.0".to_string(),
+            },
+            ConformanceRule::CommandRemoved {
+                name: "setup".to_string(),
+                since_version: "2.0.0".to_string(),
+            },
+        ];
+
+        let out = render_side_by_side(&previous, &current, &rules, false);
+
+        assert!(out.contains("Commands\n"));
+        assert!(out.contains("  setup    │ \n"));
+        assert!(out.contains("Jobs\n"));
+        assert!(out.contains("  deploy   │ release\n"));
+        assert!(out.contains("(1 unchanged)"));
+        assert!(!out.contains("Executors"));
+        assert!(!out.contains("\x1b["));
+    }
+
+    #[test]
+    fn test_color_choice_explicit() {
+        assert!(ColorChoice::Always.enabled());
+        assert!(!ColorChoice::Never.enabled());
+    }
+}
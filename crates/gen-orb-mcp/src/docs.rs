@@ -0,0 +1,406 @@
+//! Markdown documentation for the `docs` subcommand.
+//!
+//! Renders one page per command, job and executor (description, parameter
+//! table, executor and docker images, where `steps` parameters run, and an
+//! example invocation) plus an
+//! index, from the same [`GeneratorContext`] the MCP server is generated
+//! from, so the human docs and the server's resources cannot drift apart.
+//!
+//! ```text
+//! docs/
+//! ├── README.md              # index linking every page
+//! ├── commands/<name>.md
+//! ├── jobs/<name>.md
+//! └── executors/<name>.md
+//! ```
+
+use std::{collections::BTreeMap, path::PathBuf};
+
+use handlebars::Handlebars;
+use serde::Serialize;
+use serde_json::json;
+
+use crate::{
+    generator::{
+        context::{InjectedStepsContext, ParameterContext},
+        templates, GeneratorContext, GeneratorError,
+    },
+    parser::OrbDefinition,
+};
+
+/// One row of an index table.
+#[derive(Serialize)]
+struct IndexEntry<'a> {
+    name: &'a str,
+    link: String,
+    summary: String,
+}
+
+/// A command, job or executor page.
+#[derive(Serialize)]
+struct Page<'a> {
+    #[serde(skip)]
+    kind: Kind,
+    orb_name: &'a str,
+    name: &'a str,
+    description: Option<&'a str>,
+    uri: &'a str,
+    executor: Option<&'a str>,
+    docker_images: &'a [String],
+    resource_class: Option<&'a str>,
+    parameters: Vec<ParameterRow<'a>>,
+    extension_points: Vec<&'a InjectedStepsContext>,
+    usage: String,
+}
+
+/// One row of a page's parameter table.
+#[derive(Serialize)]
+struct ParameterRow<'a> {
+    name: &'a str,
+    param_type: String,
+    required: bool,
+    default: Option<&'a str>,
+    description: String,
+}
+
+/// What a page documents.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Command,
+    Job,
+    Executor,
+}
+
+impl Kind {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Command => "Command",
+            Self::Job => "Job",
+            Self::Executor => "Executor",
+        }
+    }
+
+    fn dir(self) -> &'static str {
+        match self {
+            Self::Command => "commands",
+            Self::Job => "jobs",
+            Self::Executor => "executors",
+        }
+    }
+}
+
+/// Markdown pages for `orb`, keyed by path relative to the docs directory.
+pub fn render(
+    orb: &OrbDefinition,
+    orb_name: &str,
+) -> Result<BTreeMap<PathBuf, String>, GeneratorError> {
+    let mut handlebars = Handlebars::new();
+    handlebars.register_escape_fn(handlebars::no_escape);
+    for (name, template) in [
+        ("index", templates::DOCS_INDEX_MD),
+        ("page", templates::DOCS_PAGE_MD),
+    ] {
+        handlebars
+            .register_template_string(name, template)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: name.to_string(),
+                source: e,
+            })?;
+    }
+    let render = |name: &str, data: serde_json::Value| {
+        handlebars
+            .render(name, &data)
+            .map_err(|e| GeneratorError::TemplateRender {
+                name: name.to_string(),
+                source: e,
+            })
+    };
+
+    let context = GeneratorContext::from_orb(orb, orb_name, "");
+    let mut pages = Vec::new();
+    for command in &context.commands {
+        pages.push(Page {
+            extension_points: extension_points(&command.injected_steps, &command.parameters),
+            ..page(
+                orb_name,
+                Kind::Command,
+                &command.name,
+                command.description.as_deref(),
+                &command.uri,
+                &command.parameters,
+            )
+        });
+    }
+    for job in &context.jobs {
+        pages.push(Page {
+            extension_points: extension_points(&job.injected_steps, &job.parameters),
+            executor: job.executor.as_deref(),
+            docker_images: &job.config.docker_images,
+            resource_class: job.config.resource_class.as_deref(),
+            ..page(
+                orb_name,
+                Kind::Job,
+                &job.name,
+                job.description.as_deref(),
+                &job.uri,
+                &job.parameters,
+            )
+        });
+    }
+    for executor in &context.executors {
+        pages.push(Page {
+            docker_images: &executor.config.docker_images,
+            resource_class: executor.config.resource_class.as_deref(),
+            ..page(
+                orb_name,
+                Kind::Executor,
+                &executor.name,
+                executor.description.as_deref(),
+                &executor.uri,
+                &executor.parameters,
+            )
+        });
+    }
+
+    let mut files = BTreeMap::new();
+    let mut index: BTreeMap<&str, Vec<IndexEntry>> = BTreeMap::new();
+    for page in &pages {
+        let path = format!("{}/{}.md", page.kind.dir(), page.name);
+        index.entry(page.kind.dir()).or_default().push(IndexEntry {
+            name: page.name,
+            link: path.clone(),
+            summary: table_cell(page.description.and_then(|d| d.lines().next())),
+        });
+        let mut data = json!(page);
+        data["kind"] = json!(page.kind.label());
+        data["kind_lower"] = json!(page.kind.label().to_lowercase());
+        files.insert(PathBuf::from(path), render("page", data)?);
+    }
+    let index = json!({
+        "orb_name": orb_name,
+        "description": context.description.as_deref().map(str::trim),
+        "commands": index.remove("commands"),
+        "jobs": index.remove("jobs"),
+        "executors": index.remove("executors"),
+    });
+    files.insert(PathBuf::from("README.md"), render("index", index)?);
+    Ok(files)
+}
+
+/// A page for the `kind` definition `name`, with no executor, images or
+/// extension points.
+fn page<'a>(
+    orb_name: &'a str,
+    kind: Kind,
+    name: &'a str,
+    description: Option<&'a str>,
+    uri: &'a str,
+    parameters: &'a [ParameterContext],
+) -> Page<'a> {
+    Page {
+        kind,
+        orb_name,
+        name,
+        description: description.map(str::trim),
+        uri,
+        executor: None,
+        docker_images: &[],
+        resource_class: None,
+        extension_points: Vec::new(),
+        parameters: parameters
+            .iter()
+            .map(|p| ParameterRow {
+                name: &p.name,
+                param_type: match &p.enum_values {
+                    Some(values) => {
+                        format!("enum: {}", table_cell(Some(values.join(", ").as_str())))
+                    }
+                    None => p.param_type.clone(),
+                },
+                required: p.required,
+                default: p.default.as_deref(),
+                description: table_cell(p.description.as_deref()),
+            })
+            .collect(),
+        usage: usage(kind, orb_name, name, parameters),
+    }
+}
+
+/// Where the `steps` parameters among `parameters` run; a job's
+/// `pre-steps` and `post-steps` are left out as every job has them.
+fn extension_points<'a>(
+    injected: &'a [InjectedStepsContext],
+    parameters: &[ParameterContext],
+) -> Vec<&'a InjectedStepsContext> {
+    injected
+        .iter()
+        .filter(|i| {
+            parameters
+                .iter()
+                .any(|p| p.name == i.name && p.param_type == "steps")
+        })
+        .collect()
+}
+
+/// An example invocation of `name` passing its required parameters, as a
+/// config excerpt.
+fn usage(kind: Kind, orb_name: &str, name: &str, parameters: &[ParameterContext]) -> String {
+    let reference = format!("{orb_name}/{name}");
+    let arguments: Vec<String> = parameters
+        .iter()
+        .filter(|p| p.required)
+        .map(|p| format!("{}: {}", p.name, placeholder(p)))
+        .collect();
+    let (head, indent) = match kind {
+        Kind::Command => ("steps:\n  - ".to_string(), "      "),
+        Kind::Job => (
+            "workflows:\n  main:\n    jobs:\n      - ".to_string(),
+            "          ",
+        ),
+        Kind::Executor => {
+            let mut yaml = "jobs:\n  build:\n    executor:".to_string();
+            if arguments.is_empty() {
+                yaml.push_str(&format!(" {reference}\n"));
+            } else {
+                yaml.push_str(&format!("\n      name: {reference}\n"));
+                for argument in &arguments {
+                    yaml.push_str(&format!("      {argument}\n"));
+                }
+            }
+            yaml.push_str("    steps:\n      - checkout\n");
+            return yaml;
+        }
+    };
+    let mut yaml = format!("{head}{reference}");
+    if arguments.is_empty() {
+        yaml.push('\n');
+    } else {
+        yaml.push_str(":\n");
+        for argument in &arguments {
+            yaml.push_str(&format!("{indent}{argument}\n"));
+        }
+    }
+    yaml
+}
+
+/// Placeholder value for a required parameter in an example invocation.
+fn placeholder(parameter: &ParameterContext) -> String {
+    match &parameter.enum_values {
+        Some(values) => format!("<{}>", values.join("|")),
+        None => format!("<{}>", parameter.param_type),
+    }
+}
+
+/// `text` on one line with pipes escaped, for a Markdown table cell.
+fn table_cell(text: Option<&str>) -> String {
+    text.unwrap_or_default()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .replace('|', "\\|")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORB: &str = r#"
+version: 2.1
+description: Greeting toolkit
+commands:
+  greet:
+    description: |
+      Say hello.
+      Politely.
+    parameters:
+      name:
+        type: string
+        description: Who to greet | or wave at
+      style:
+        type: enum
+        enum: [formal, casual]
+        default: casual
+    steps:
+      - run: echo hello << parameters.name >>
+jobs:
+  welcome:
+    executor: default
+    steps:
+      - greet:
+          name: team
+executors:
+  default:
+    parameters:
+      tag:
+        type: string
+        default: "1.0"
+    docker:
+      - image: cimg/base:<< parameters.tag >>
+"#;
+
+    #[test]
+    fn test_render_lists_extension_points() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+version: 2.1
+jobs:
+  test:
+    parameters:
+      setup:
+        type: steps
+        default: []
+    steps:
+      - checkout
+      - steps: << parameters.setup >>
+      - run: cargo test
+"#,
+        )
+        .unwrap();
+        let files = render(&orb, "toolkit").unwrap();
+
+        let test = &files[&PathBuf::from("jobs/test.md")];
+        assert!(test.contains(
+            "## Extension points\n\nSteps passed to these parameters run inside the job:\n\n\
+             - `setup`: as step 2 of 3\n\n## Usage"
+        ));
+        assert!(!test.contains("pre-steps"));
+    }
+
+    #[test]
+    fn test_render_writes_index_and_pages() {
+        let orb: OrbDefinition = serde_yaml::from_str(ORB).unwrap();
+        let files = render(&orb, "toolkit").unwrap();
+        let paths: Vec<_> = files.keys().map(|p| p.to_str().unwrap()).collect();
+        assert_eq!(
+            paths,
+            [
+                "README.md",
+                "commands/greet.md",
+                "executors/default.md",
+                "jobs/welcome.md"
+            ]
+        );
+
+        let index = &files[&PathBuf::from("README.md")];
+        assert!(index.starts_with("# toolkit\n\nGreeting toolkit\n"));
+        assert!(index.contains("| [greet](commands/greet.md) | Say hello. |"));
+        assert!(index.contains("| [welcome](jobs/welcome.md) |  |"));
+
+        let greet = &files[&PathBuf::from("commands/greet.md")];
+        assert!(greet.starts_with("# toolkit/greet\n\n_Command_"));
+        assert!(greet.contains("| `name` | string | yes |  | Who to greet \\| or wave at |"));
+        assert!(greet.contains("| `style` | enum: formal, casual | no | `\"casual\"` |  |"));
+        assert!(greet.contains("```yaml\nsteps:\n  - toolkit/greet:\n      name: <string>\n```"));
+        assert!(greet.contains("`orb://commands/greet`"));
+
+        let welcome = &files[&PathBuf::from("jobs/welcome.md")];
+        assert!(welcome.contains("**Executor:** `default`"));
+        assert!(welcome.contains("This job takes no parameters."));
+        assert!(!welcome.contains("## Extension points"));
+        assert!(welcome.contains("jobs:\n      - toolkit/welcome\n```"));
+
+        let default = &files[&PathBuf::from("executors/default.md")];
+        assert!(default.contains("- `cimg/base:<< parameters.tag >>`"));
+        assert!(default.contains("    executor: toolkit/default\n    steps:"));
+    }
+}
@@ -0,0 +1,120 @@
+//! Failure categories and the process exit codes they map to.
+//!
+//! Commands fail with [`anyhow::Error`]. Errors raised at a known stage are
+//! tagged with a [`FailureKind`] through [`FailureKind::wrap`], which keeps
+//! their message, and [`exit_code`] turns any error into the exit code, so
+//! scripts can tell a broken orb from a broken toolchain:
+//!
+//! | Code | Meaning                                           |
+//! |------|---------------------------------------------------|
+//! | 0    | Success                                           |
+//! | 1    | Any other error (I/O, network, configuration)     |
+//! | 2    | Invalid command line                              |
+//! | 3    | The orb could not be parsed                       |
+//! | 4    | The orb failed validation or lint                 |
+//! | 5    | The server could not be generated or written      |
+//! | 6    | cargo failed to compile or package the server     |
+
+use std::fmt;
+
+/// Exit code of errors without a [`FailureKind`].
+pub const GENERAL_EXIT_CODE: u8 = 1;
+
+/// Exit code clap uses for an invalid command line.
+pub const USAGE_EXIT_CODE: u8 = 2;
+
+/// The stage a command failed at.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FailureKind {
+    /// The orb (or a prior version of it) could not be parsed
+    Parse,
+    /// The orb has unresolved references or lint errors, or warnings under
+    /// `--fail-on-warnings`
+    Validation,
+    /// Rendering or writing the server failed
+    Generation,
+    /// cargo failed to build or package the generated server
+    Compile,
+}
+
+impl FailureKind {
+    /// The process exit code for this kind of failure.
+    pub fn exit_code(self) -> u8 {
+        match self {
+            FailureKind::Parse => 3,
+            FailureKind::Validation => 4,
+            FailureKind::Generation => 5,
+            FailureKind::Compile => 6,
+        }
+    }
+
+    /// Tag `error` with this kind; its message is unchanged.
+    pub fn wrap(self, error: anyhow::Error) -> anyhow::Error {
+        anyhow::Error::new(Failure { kind: self, error })
+    }
+}
+
+/// An error tagged with the stage it was raised at.
+#[derive(Debug)]
+struct Failure {
+    kind: FailureKind,
+    error: anyhow::Error,
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.error, f)
+    }
+}
+
+impl std::error::Error for Failure {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        self.error.source()
+    }
+}
+
+/// The kind `error` was tagged with, if any.
+pub fn kind_of(error: &anyhow::Error) -> Option<FailureKind> {
+    error.downcast_ref::<Failure>().map(|failure| failure.kind)
+}
+
+/// The process exit code for `error`.
+pub fn exit_code(error: &anyhow::Error) -> u8 {
+    kind_of(error).map_or(GENERAL_EXIT_CODE, FailureKind::exit_code)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exit_codes_are_distinct() {
+        let kinds = [
+            FailureKind::Parse,
+            FailureKind::Validation,
+            FailureKind::Generation,
+            FailureKind::Compile,
+        ];
+        let mut codes: Vec<u8> = kinds.iter().map(|k| k.exit_code()).collect();
+        codes.extend([GENERAL_EXIT_CODE, USAGE_EXIT_CODE]);
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes, [1, 2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_wrap_keeps_message_and_sets_exit_code() {
+        let err = FailureKind::Validation.wrap(anyhow::anyhow!("2 lint error(s)"));
+        assert_eq!(err.to_string(), "2 lint error(s)");
+        assert_eq!(kind_of(&err), Some(FailureKind::Validation));
+        assert_eq!(exit_code(&err), 4);
+
+        let io = std::io::Error::new(std::io::ErrorKind::NotFound, "missing");
+        let err = FailureKind::Generation.wrap(anyhow::Error::new(io).context("Failed to write"));
+        assert_eq!(format!("{err:#}"), "Failed to write: missing");
+
+        let err = anyhow::anyhow!("no --orb-path given");
+        assert_eq!(kind_of(&err), None);
+        assert_eq!(exit_code(&err), GENERAL_EXIT_CODE);
+    }
+}
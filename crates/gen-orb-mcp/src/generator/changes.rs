@@ -0,0 +1,240 @@
+//! Change summaries between orb releases.
+//!
+//! Renders the Markdown served at `orb://changes` in generated servers, so an
+//! assistant can explain the upgrade impact of the current release. Breaking
+//! changes come from the semantic [`differ`](crate::differ); additions and
+//! removals are listed per definition category.
+//!
+//! Multi-version servers also serve `orb://compare`, a matrix of which
+//! definitions exist in each embedded version.
+
+use std::collections::{BTreeSet, HashMap};
+
+use crate::{differ, parser::OrbDefinition};
+
+/// Render a Markdown summary of what changed from `previous` to `current`.
+///
+/// # Arguments
+///
+/// * `orb_name` - The orb name used in the heading
+/// * `previous_label` - Label of the previous release (e.g. `"4.7.1"`)
+/// * `previous` - The previous orb definition
+/// * `current_version` - Version of the current release
+/// * `current` - The current orb definition
+pub fn render_changes(
+    orb_name: &str,
+    previous_label: &str,
+    previous: &OrbDefinition,
+    current_version: &str,
+    current: &OrbDefinition,
+) -> String {
+    let mut out = format!(
+        "# {orb_name} changes since {previous_label}\n\n\
+         Comparing {previous_label} with {current_version}.\n"
+    );
+
+    let sections = [
+        (
+            "Commands",
+            category_delta(&previous.commands, &current.commands),
+        ),
+        ("Jobs", category_delta(&previous.jobs, &current.jobs)),
+        (
+            "Executors",
+            category_delta(&previous.executors, &current.executors),
+        ),
+    ];
+
+    let rules = differ::diff(previous, current, current_version);
+    let any_delta = sections
+        .iter()
+        .any(|(_, (added, removed))| !added.is_empty() || !removed.is_empty());
+
+    if !any_delta && rules.is_empty() {
+        out.push_str("\nNo changes to commands, jobs, or executors.\n");
+        return out;
+    }
+
+    for (title, (added, removed)) in &sections {
+        if added.is_empty() && removed.is_empty() {
+            continue;
+        }
+        out.push_str(&format!("\n## {title}\n\n"));
+        for name in added {
+            out.push_str(&format!("- Added `{name}`\n"));
+        }
+        for name in removed {
+            out.push_str(&format!("- Removed `{name}`\n"));
+        }
+    }
+
+    if !rules.is_empty() {
+        out.push_str("\n## Breaking changes\n\n");
+        for rule in &rules {
+            out.push_str(&format!("- {}\n", rule.description()));
+        }
+    }
+
+    out
+}
+
+/// Lists the definition names of one category in an orb.
+type DefinitionNames = fn(&OrbDefinition) -> Vec<&String>;
+
+/// Render a Markdown matrix of which commands, jobs and executors exist in
+/// each embedded version.
+///
+/// Prior versions are ordered oldest first (semver when parseable), followed
+/// by the current version.
+pub fn render_comparison(
+    orb_name: &str,
+    current_version: &str,
+    current: &OrbDefinition,
+    prior_versions: &[(String, OrbDefinition)],
+) -> String {
+    let mut versions: Vec<(&str, &OrbDefinition)> = prior_versions
+        .iter()
+        .map(|(v, orb)| (v.as_str(), orb))
+        .collect();
+    versions.sort_by(|(a, _), (b, _)| cmp_versions(a, b));
+    versions.push((current_version, current));
+
+    let mut out = format!(
+        "# {orb_name} version comparison\n\n\
+         ✓ marks the versions that define each name. Prior versions are read at \
+         `orb://v<version>/...`; the current version ({current_version}) at `orb://...`.\n"
+    );
+
+    let header: String = versions.iter().map(|(v, _)| format!(" {v} |")).collect();
+    let rule: String = versions.iter().map(|_| " :-: |").collect();
+    let categories: [(&str, DefinitionNames); 3] = [
+        ("Commands", |o| o.commands.keys().collect()),
+        ("Jobs", |o| o.jobs.keys().collect()),
+        ("Executors", |o| o.executors.keys().collect()),
+    ];
+    for (title, keys) in categories {
+        let present: Vec<BTreeSet<&String>> = versions
+            .iter()
+            .map(|(_, o)| keys(o).into_iter().collect())
+            .collect();
+        let names: BTreeSet<&String> = present.iter().flatten().copied().collect();
+        if names.is_empty() {
+            continue;
+        }
+        out.push_str(&format!(
+            "\n## {title}\n\n| Name |{header}\n| --- |{rule}\n"
+        ));
+        for name in names {
+            let cells: String = present
+                .iter()
+                .map(|set| if set.contains(name) { " ✓ |" } else { "   |" })
+                .collect();
+            out.push_str(&format!("| `{name}` |{cells}\n"));
+        }
+    }
+    out
+}
+
+/// Order version labels by semver, falling back to string order when either
+/// is not a valid version.
+pub(crate) fn cmp_versions(a: &str, b: &str) -> std::cmp::Ordering {
+    match (semver::Version::parse(a), semver::Version::parse(b)) {
+        (Ok(a), Ok(b)) => a.cmp(&b),
+        _ => a.cmp(b),
+    }
+}
+
+/// Names added to and removed from one definition category, sorted.
+fn category_delta<T>(
+    previous: &HashMap<String, T>,
+    current: &HashMap<String, T>,
+) -> (BTreeSet<String>, BTreeSet<String>) {
+    let added = current
+        .keys()
+        .filter(|k| !previous.contains_key(*k))
+        .cloned()
+        .collect();
+    let removed = previous
+        .keys()
+        .filter(|k| !current.contains_key(*k))
+        .cloned()
+        .collect();
+    (added, removed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{Command, Job};
+
+    #[test]
+    fn test_render_changes_lists_added_and_removed() {
+        let mut previous = OrbDefinition::default();
+        previous
+            .commands
+            .insert("old-cmd".to_string(), Command::default());
+        previous.jobs.insert("build".to_string(), Job::default());
+
+        let mut current = OrbDefinition::default();
+        current
+            .commands
+            .insert("new-cmd".to_string(), Command::default());
+        current.jobs.insert("build".to_string(), Job::default());
+
+        let md = render_changes("toolkit", "1.0.0", &previous, "2.0.0", &current);
+
+        assert!(md.starts_with("# toolkit changes since 1.0.0"));
+        assert!(md.contains("## Commands"));
+        assert!(md.contains("- Added `new-cmd`"));
+        assert!(md.contains("- Removed `old-cmd`"));
+        assert!(!md.contains("## Jobs"), "unchanged category is omitted");
+    }
+
+    #[test]
+    fn test_render_changes_includes_breaking_rules() {
+        let mut previous = OrbDefinition::default();
+        previous.jobs.insert("deploy".to_string(), Job::default());
+        let current = OrbDefinition::default();
+
+        let md = render_changes("toolkit", "1.0.0", &previous, "2.0.0", &current);
+
+        assert!(md.contains("## Breaking changes"));
+        assert!(md.contains("Job `deploy` was removed in 2.0.0"));
+    }
+
+    #[test]
+    fn test_render_comparison_matrix() {
+        let mut v1 = OrbDefinition::default();
+        v1.commands.insert("old".to_string(), Command::default());
+        v1.commands.insert("keep".to_string(), Command::default());
+        let mut v10 = OrbDefinition::default();
+        v10.commands.insert("keep".to_string(), Command::default());
+        let mut current = OrbDefinition::default();
+        current
+            .commands
+            .insert("keep".to_string(), Command::default());
+        current
+            .commands
+            .insert("new".to_string(), Command::default());
+
+        let md = render_comparison(
+            "toolkit",
+            "2.0.0",
+            &current,
+            &[("1.10.0".to_string(), v10), ("1.2.0".to_string(), v1)],
+        );
+
+        assert!(md.contains("| Name | 1.2.0 | 1.10.0 | 2.0.0 |"));
+        assert!(md.contains("| `keep` | ✓ | ✓ | ✓ |"));
+        assert!(md.contains("| `new` |   |   | ✓ |"));
+        assert!(md.contains("| `old` | ✓ |   |   |"));
+        assert!(!md.contains("## Jobs"));
+    }
+
+    #[test]
+    fn test_render_changes_no_changes() {
+        let orb = OrbDefinition::default();
+        let md = render_changes("toolkit", "1.0.0", &orb, "1.0.1", &orb);
+        assert!(md.contains("No changes to commands, jobs, or executors."));
+    }
+}
@@ -0,0 +1,126 @@
+//! MCP client configuration for a generated server.
+//!
+//! `generate --print-client-config` prints the `mcpServers` blocks Claude
+//! Desktop and Cursor read, plus the command and arguments any other stdio
+//! client needs, pointing at where the server is built. HTTP servers are
+//! launched with `--stdio` by stdio-only clients, and Cursor connects to
+//! their URL instead.
+
+use std::fmt;
+
+use serde::Serialize;
+use serde_json::json;
+
+/// How a client starts a server over stdio.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StdioLaunch {
+    /// Program to run
+    pub command: String,
+    /// Arguments passed to `command`
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub args: Vec<String>,
+}
+
+/// Ready-to-paste client configuration for one server.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClientConfig {
+    /// Block for `claude_desktop_config.json`
+    pub claude_desktop: serde_json::Value,
+    /// Block for `.cursor/mcp.json` (or `~/.cursor/mcp.json`)
+    pub cursor: serde_json::Value,
+    /// Command line for any other stdio client
+    pub stdio: StdioLaunch,
+}
+
+impl ClientConfig {
+    /// Configuration for the server `name`, launched with `stdio` or, for
+    /// clients that speak streamable HTTP, reached at `http_url`.
+    pub fn new(name: &str, stdio: StdioLaunch, http_url: Option<&str>) -> Self {
+        let stdio_entry = serde_json::to_value(&stdio).unwrap_or_default();
+        let cursor_entry = match http_url {
+            Some(url) => json!({ "url": url }),
+            None => stdio_entry.clone(),
+        };
+        Self {
+            claude_desktop: json!({ "mcpServers": { name: stdio_entry } }),
+            cursor: json!({ "mcpServers": { name: cursor_entry } }),
+            stdio,
+        }
+    }
+}
+
+impl fmt::Display for ClientConfig {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pretty =
+            |value: &serde_json::Value| serde_json::to_string_pretty(value).map_err(|_| fmt::Error);
+        writeln!(f, "Claude Desktop (claude_desktop_config.json):")?;
+        writeln!(f, "{}", pretty(&self.claude_desktop)?)?;
+        writeln!(f)?;
+        writeln!(f, "Cursor (.cursor/mcp.json):")?;
+        writeln!(f, "{}", pretty(&self.cursor)?)?;
+        writeln!(f)?;
+        writeln!(f, "Other stdio clients:")?;
+        writeln!(f, "  Command: {}", self.stdio.command)?;
+        if !self.stdio.args.is_empty() {
+            writeln!(f, "  Args: {}", self.stdio.args.join(" "))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stdio_server_config() {
+        let config = ClientConfig::new(
+            "toolkit",
+            StdioLaunch {
+                command: "/work/dist/target/release/toolkit_mcp".to_string(),
+                args: vec![],
+            },
+            None,
+        );
+        let expected = json!({
+            "mcpServers": {
+                "toolkit": { "command": "/work/dist/target/release/toolkit_mcp" }
+            }
+        });
+        assert_eq!(config.claude_desktop, expected);
+        assert_eq!(config.cursor, expected);
+        assert_eq!(
+            config.to_string(),
+            "Claude Desktop (claude_desktop_config.json):\n\
+             {\n  \"mcpServers\": {\n    \"toolkit\": {\n      \
+             \"command\": \"/work/dist/target/release/toolkit_mcp\"\n    }\n  }\n}\n\
+             \n\
+             Cursor (.cursor/mcp.json):\n\
+             {\n  \"mcpServers\": {\n    \"toolkit\": {\n      \
+             \"command\": \"/work/dist/target/release/toolkit_mcp\"\n    }\n  }\n}\n\
+             \n\
+             Other stdio clients:\n  Command: /work/dist/target/release/toolkit_mcp\n"
+        );
+    }
+
+    #[test]
+    fn test_http_server_config() {
+        let config = ClientConfig::new(
+            "toolkit",
+            StdioLaunch {
+                command: "/work/toolkit_mcp".to_string(),
+                args: vec!["--stdio".to_string()],
+            },
+            Some("http://127.0.0.1:8080/mcp"),
+        );
+        assert_eq!(
+            config.claude_desktop["mcpServers"]["toolkit"],
+            json!({ "command": "/work/toolkit_mcp", "args": ["--stdio"] })
+        );
+        assert_eq!(
+            config.cursor["mcpServers"]["toolkit"],
+            json!({ "url": "http://127.0.0.1:8080/mcp" })
+        );
+        assert!(config.to_string().ends_with("  Args: --stdio\n"));
+    }
+}
@@ -3,10 +3,19 @@
 //! These structures are serialized and passed to Handlebars templates
 //! to generate the MCP server code.
 
-use serde::Serialize;
+use std::net::SocketAddr;
 
-use crate::parser::{
-    Command, Executor, ExecutorConfig, Job, OrbDefinition, Parameter, ParameterType,
+use serde::Serialize;
+use serde_json::json;
+
+use super::{BinstallMetadata, Language, Logo, Naming, PackageMetadata, Transport};
+use crate::{
+    graph,
+    parser::{
+        injection::{injection_points, Injection},
+        Command, Condition, Example, Executor, ExecutorConfig, Job, MachineConfig, OrbDefinition,
+        Parameter, ParameterType, Step, StructuredStep, Workflow, WorkflowJobOptions,
+    },
 };
 
 /// Root context passed to templates for generating the MCP server.
@@ -21,6 +30,13 @@ pub struct GeneratorContext {
     /// Struct name in PascalCase (e.g., "MyToolkitMcp")
     pub struct_name: String,
 
+    /// Package name in kebab-case, also the server name reported to MCP
+    /// clients (e.g., "my-toolkit-mcp")
+    pub package_name: String,
+
+    /// Scheme of every resource URI (e.g., "orb" for `orb://overview`)
+    pub uri_scheme: String,
+
     /// Server version string
     pub version: String,
 
@@ -40,6 +56,28 @@ pub struct GeneratorContext {
     /// Executor contexts for template rendering
     pub executors: Vec<ExecutorContext>,
 
+    /// Usage example contexts for template rendering
+    pub examples: Vec<ExampleContext>,
+
+    /// Workflow contexts for template rendering
+    pub workflows: Vec<WorkflowContext>,
+
+    /// YAML source of commands, jobs and executors, served at
+    /// `orb://source/...`
+    pub sources: Vec<SourceContext>,
+
+    /// JSON Schemas of command and job parameters
+    pub schemas: Vec<SchemaContext>,
+
+    /// Typed Rust enums of the `enum` parameters of commands and jobs
+    pub enum_types: Vec<EnumTypeContext>,
+
+    /// Pipeline parameters of a project config
+    pub pipeline_parameters: Vec<ParameterContext>,
+
+    /// Whether a project config is a dynamic-configuration setup config
+    pub setup: bool,
+
     /// Whether there are any resources to expose
     pub has_resources: bool,
 
@@ -55,6 +93,138 @@ pub struct GeneratorContext {
     /// Serialised JSON of `Vec<ConformanceRule>` to embed in the generated
     /// server. Empty string when `has_tools` is false.
     pub conformance_rules_json: String,
+
+    /// Friendly server title shown by MCP clients (escaped for a Rust string
+    /// literal). `None` leaves the `serverInfo` title unset.
+    pub server_title: Option<String>,
+
+    /// Usage guidance returned in the MCP `instructions` field (escaped for a
+    /// Rust string literal).
+    pub server_instructions: String,
+
+    /// Orb repository revision embedded as `SOURCE_REV` (escaped for a Rust
+    /// string literal).
+    pub source_rev: Option<String>,
+
+    /// Whether an `orb://changes` resource is emitted.
+    pub has_changes: bool,
+
+    /// Markdown summary of changes since the previous release. Empty when
+    /// `has_changes` is false.
+    pub changes_content: String,
+
+    /// Whether an `orb://compare` resource is emitted.
+    pub has_comparison: bool,
+
+    /// Markdown matrix of definitions per embedded version. Empty when
+    /// `has_comparison` is false.
+    pub comparison_content: String,
+
+    /// JSON dependency graph of the commands, jobs and executors, served at
+    /// `orb://graph`.
+    pub graph_content: String,
+
+    /// Whether `tests/properties.rs` and the `proptest` dev-dependency are
+    /// emitted.
+    pub property_tests: bool,
+
+    /// SPDX license expression for the `license` field of Cargo.toml.
+    pub license: Option<String>,
+
+    /// Whether a LICENSE file is emitted alongside Cargo.toml.
+    pub has_license_file: bool,
+
+    /// `[package.metadata.binstall]` values, escaped for TOML strings.
+    /// `None` omits the section.
+    pub binstall: Option<BinstallContext>,
+
+    /// Publishing fields of `[package]`, escaped for TOML strings. `None`
+    /// omits them.
+    pub package: Option<PackageContext>,
+
+    /// Whether the server must run without environment access (fixed log
+    /// filter instead of `RUST_LOG`).
+    pub sandboxed: bool,
+
+    /// Whether resources over `--max-resource-bytes` are split into parts,
+    /// which are listed as `text/plain` since no part parses on its own.
+    pub split_resources: bool,
+
+    /// Default bind address of a server using the HTTP transport. `None`
+    /// serves MCP over stdio.
+    pub http_bind: Option<String>,
+
+    /// Whether an HTTP server compresses its responses.
+    pub http_compression: bool,
+
+    /// Largest request body an HTTP server accepts, in bytes. `None` sets no
+    /// limit.
+    pub http_body_limit: Option<usize>,
+
+    /// Embedded logo served at `orb://logo`. `None` omits the resource and
+    /// the server icon.
+    pub logo: Option<LogoContext>,
+
+    /// Whether definitions are named `<orb>/<name>` and served under
+    /// `orb://<orb>/...` (a server merging several orbs).
+    pub namespaced: bool,
+
+    /// Aliases of imported orbs whose definitions are named `<alias>/<name>`
+    /// and served under `orb://<alias>/...`.
+    pub imports: Vec<String>,
+
+    /// Language of the generated server (`rust`, `typescript` or `python`).
+    pub language: Language,
+
+    /// Orb a dev-mode server reads at runtime (escaped for a Rust string
+    /// literal). `None` serves the embedded definitions only.
+    pub dev_orb_path: Option<String>,
+}
+
+/// Embedded logo details for the lib.rs and Cargo.toml templates.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogoContext {
+    /// File name under `assets/` (e.g. `logo.png`).
+    pub file_name: &'static str,
+    /// MIME type of the image.
+    pub mime_type: &'static str,
+    /// Icon size (`WxH` or `any`), if known.
+    pub size: Option<String>,
+}
+
+/// Escaped `[package.metadata.binstall]` values for the Cargo.toml template.
+#[derive(Debug, Clone, Serialize)]
+pub struct BinstallContext {
+    /// Download URL template.
+    pub pkg_url: String,
+    /// Package format (e.g. `bin`, `tgz`).
+    pub pkg_fmt: &'static str,
+    /// Binary path inside an archive, if set.
+    pub bin_dir: Option<String>,
+    /// Per-target download URL templates, ordered by target.
+    pub overrides: Vec<BinstallOverrideContext>,
+}
+
+/// An escaped `[package.metadata.binstall.overrides.<target>]` table.
+#[derive(Debug, Clone, Serialize)]
+pub struct BinstallOverrideContext {
+    /// Target triple.
+    pub target: String,
+    /// Download URL template for the target.
+    pub pkg_url: String,
+}
+
+/// Escaped publishing fields of `[package]` for the Cargo.toml template.
+#[derive(Debug, Clone, Serialize)]
+pub struct PackageContext {
+    /// Description replacing the orb's, if set.
+    pub description: Option<String>,
+    /// Repository URL, if set.
+    pub repository: Option<String>,
+    /// Crate keywords.
+    pub keywords: Vec<String>,
+    /// Registries the crate may be published to.
+    pub registries: Vec<String>,
 }
 
 /// A snapshot of one prior orb version's documentation, embedded alongside the
@@ -95,9 +265,15 @@ pub struct CommandContext {
     /// Description sanitized for use in Rust string literals
     pub description_escaped: Option<String>,
 
+    /// First line of the description, for the overview's Markdown tables
+    pub summary: Option<String>,
+
     /// Parameters accepted by this command
     pub parameters: Vec<ParameterContext>,
 
+    /// Where the steps passed to each `steps` parameter run
+    pub injected_steps: Vec<InjectedStepsContext>,
+
     /// MCP resource URI for this command
     pub uri: String,
 
@@ -117,9 +293,15 @@ pub struct JobContext {
     /// Description sanitized for use in Rust string literals
     pub description_escaped: Option<String>,
 
+    /// First line of the description, for the overview's Markdown tables
+    pub summary: Option<String>,
+
     /// Parameters accepted by this job
     pub parameters: Vec<ParameterContext>,
 
+    /// Where `pre-steps`, `post-steps` and each `steps` parameter run
+    pub injected_steps: Vec<InjectedStepsContext>,
+
     /// Executor reference if specified
     pub executor: Option<String>,
 
@@ -145,6 +327,9 @@ pub struct ExecutorContext {
     /// Description sanitized for use in Rust string literals
     pub description_escaped: Option<String>,
 
+    /// First line of the description, for the overview's Markdown tables
+    pub summary: Option<String>,
+
     /// Parameters accepted by this executor
     pub parameters: Vec<ParameterContext>,
 
@@ -158,12 +343,125 @@ pub struct ExecutorContext {
     pub json_content: String,
 }
 
+/// Context for a single usage example from the orb's `examples/`.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExampleContext {
+    /// Example name as defined in the orb
+    pub name: String,
+
+    /// Optional description (raw)
+    pub description: Option<String>,
+
+    /// Description sanitized for use in Rust string literals
+    pub description_escaped: Option<String>,
+
+    /// MCP resource URI for this example
+    pub uri: String,
+
+    /// JSON representation of the example for embedding
+    pub json_content: String,
+}
+
+/// Context for a single workflow.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkflowContext {
+    /// Workflow name as defined in the orb or config
+    pub name: String,
+
+    /// Number of job invocations in the workflow
+    pub job_count: usize,
+
+    /// MCP resource URI for this workflow
+    pub uri: String,
+
+    /// JSON representation of the workflow for embedding
+    pub json_content: String,
+}
+
+/// Context for the YAML source of a command, job or executor.
+#[derive(Debug, Clone, Serialize)]
+pub struct SourceContext {
+    /// Kind of definition (`Command`, `Job` or `Executor`)
+    pub label: &'static str,
+
+    /// Definition name as defined in the orb
+    pub name: String,
+
+    /// MCP resource URI of the source: the definition's URI under
+    /// `orb://source/`
+    pub uri: String,
+
+    /// YAML text as written in the orb
+    pub yaml: String,
+}
+
+/// Context for the parameter JSON Schema of a command or job.
+#[derive(Debug, Clone, Serialize)]
+pub struct SchemaContext {
+    /// Kind of definition (`Command` or `Job`)
+    pub label: &'static str,
+
+    /// Kind as `check_parameters` takes it (`command` or `job`)
+    pub kind: &'static str,
+
+    /// Definition name as defined in the orb
+    pub name: String,
+
+    /// MCP resource URI of the schema: the definition's URI under
+    /// `orb://schemas/`
+    pub uri: String,
+
+    /// JSON Schema of the invocation's parameters
+    pub json_content: String,
+}
+
+/// Context for the Rust enum generated for an `enum` parameter.
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumTypeContext {
+    /// Rust type name, e.g. `CommandLogLevel`
+    pub type_name: String,
+
+    /// Kind of definition (`command` or `job`)
+    pub kind: &'static str,
+
+    /// Definition name, escaped for a Rust string literal
+    pub definition: String,
+
+    /// Parameter name, escaped for a Rust string literal
+    pub parameter: String,
+
+    /// One variant per allowed value, in declaration order
+    pub variants: Vec<EnumVariantContext>,
+}
+
+/// Context for one variant of an [`EnumTypeContext`].
+#[derive(Debug, Clone, Serialize)]
+pub struct EnumVariantContext {
+    /// Rust identifier of the variant
+    pub ident: String,
+
+    /// Allowed value, escaped for a Rust string literal
+    pub value: String,
+}
+
 /// Context for executor configuration.
 #[derive(Debug, Clone, Serialize, Default)]
 pub struct ExecutorConfigContext {
     /// Docker images (as strings)
     pub docker_images: Vec<String>,
 
+    /// Whether the job runs on a `machine` executor
+    pub machine: bool,
+
+    /// Machine image, when one is named (`machine: true` uses the default)
+    pub machine_image: Option<String>,
+
+    /// Whether the machine executor enables Docker layer caching
+    pub docker_layer_caching: bool,
+
+    /// Xcode version of a `macos` executor
+    pub xcode: Option<String>,
+
     /// Resource class
     pub resource_class: Option<String>,
 
@@ -209,28 +507,53 @@ impl GeneratorContext {
     ///   filename)
     /// * `version` - The semantic version for the generated MCP server crate
     pub fn from_orb(orb: &OrbDefinition, orb_name: &str, version: &str) -> Self {
-        let crate_name = to_snake_case(orb_name).replace('-', "_") + "_mcp";
-        let struct_name = to_pascal_case(orb_name) + "Mcp";
+        let naming = Naming::default();
+        let package_name = naming.package_name(orb_name);
 
-        let commands: Vec<CommandContext> = orb
-            .commands
-            .iter()
-            .map(|(name, cmd)| CommandContext::from_command(name, cmd))
+        let command_slugs = unique_slugs(orb.commands.keys());
+        let commands: Vec<CommandContext> = sorted(&orb.commands)
+            .into_iter()
+            .map(|(name, cmd)| CommandContext::from_command(name, &command_slugs[name], cmd))
             .collect();
 
-        let jobs: Vec<JobContext> = orb
-            .jobs
-            .iter()
-            .map(|(name, job)| JobContext::from_job(name, job))
+        let job_slugs = unique_slugs(orb.jobs.keys());
+        let jobs: Vec<JobContext> = sorted(&orb.jobs)
+            .into_iter()
+            .map(|(name, job)| JobContext::from_job(name, &job_slugs[name], job, &orb.workflows))
             .collect();
 
-        let executors: Vec<ExecutorContext> = orb
-            .executors
-            .iter()
-            .map(|(name, exec)| ExecutorContext::from_executor(name, exec))
+        let executor_slugs = unique_slugs(orb.executors.keys());
+        let executors: Vec<ExecutorContext> = sorted(&orb.executors)
+            .into_iter()
+            .map(|(name, exec)| ExecutorContext::from_executor(name, &executor_slugs[name], exec))
             .collect();
 
-        let has_resources = !commands.is_empty() || !jobs.is_empty() || !executors.is_empty();
+        let example_slugs = unique_slugs(orb.examples.keys());
+        let examples: Vec<ExampleContext> = sorted(&orb.examples)
+            .into_iter()
+            .map(|(name, example)| {
+                ExampleContext::from_example(name, &example_slugs[name], example)
+            })
+            .collect();
+
+        let workflow_slugs = unique_slugs(orb.workflows.keys());
+        let workflows: Vec<WorkflowContext> = sorted(&orb.workflows)
+            .into_iter()
+            .map(|(name, workflow)| {
+                WorkflowContext::from_workflow(name, &workflow_slugs[name], workflow)
+            })
+            .collect();
+
+        let pipeline_parameters: Vec<ParameterContext> = sorted(&orb.parameters)
+            .into_iter()
+            .map(|(pname, param)| ParameterContext::from_parameter(pname, param))
+            .collect();
+
+        let has_resources = !commands.is_empty()
+            || !jobs.is_empty()
+            || !executors.is_empty()
+            || !examples.is_empty()
+            || !workflows.is_empty();
 
         // Format description for doc comments (prefix each line with //!)
         let description_doc = orb.description.as_ref().map(|d| {
@@ -242,19 +565,52 @@ impl GeneratorContext {
 
         Self {
             orb_name: orb_name.to_string(),
-            crate_name,
-            struct_name,
+            crate_name: to_snake_case(&package_name).replace('-', "_"),
+            struct_name: to_pascal_case(&package_name),
+            package_name,
+            uri_scheme: naming.uri_scheme,
             version: version.to_string(),
             description: orb.description.clone(),
             description_doc,
             commands,
             jobs,
             executors,
+            examples,
+            workflows,
+            sources: vec![],
+            schemas: vec![],
+            enum_types: vec![],
+            pipeline_parameters,
+            setup: orb.setup,
             has_resources,
             prior_versions: vec![],
             has_prior_versions: false,
             has_tools: false,
             conformance_rules_json: String::new(),
+            server_title: None,
+            server_instructions: escape_multiline_literal(&default_instructions(orb_name)),
+            source_rev: None,
+            has_changes: false,
+            changes_content: String::new(),
+            has_comparison: false,
+            comparison_content: String::new(),
+            graph_content: serde_json::to_string_pretty(&graph::build(orb))
+                .unwrap_or_else(|_| "{}".to_string()),
+            property_tests: false,
+            license: None,
+            has_license_file: false,
+            binstall: None,
+            package: None,
+            sandboxed: false,
+            split_resources: false,
+            http_bind: None,
+            http_compression: false,
+            http_body_limit: None,
+            logo: None,
+            namespaced: false,
+            imports: vec![],
+            language: Language::default(),
+            dev_orb_path: None,
         }
     }
 
@@ -281,41 +637,408 @@ impl GeneratorContext {
         ctx.prior_versions = prior_versions;
         ctx
     }
+
+    /// Serve definitions named `<orb>/<name>` at `orb://<orb>/commands/...`,
+    /// `orb://<orb>/jobs/...`, `orb://<orb>/executors/...`,
+    /// `orb://<orb>/examples/...` and `orb://<orb>/workflows/...` when
+    /// `enabled`.
+    pub fn with_namespaces(mut self, enabled: bool) -> Self {
+        if !enabled {
+            return self;
+        }
+        let uris = namespaced_uris(self.commands.iter().map(|c| &c.name), "commands");
+        for command in &mut self.commands {
+            command.uri = uris[&command.name].clone();
+        }
+        let uris = namespaced_uris(self.jobs.iter().map(|j| &j.name), "jobs");
+        for job in &mut self.jobs {
+            job.uri = uris[&job.name].clone();
+        }
+        let uris = namespaced_uris(self.executors.iter().map(|e| &e.name), "executors");
+        for executor in &mut self.executors {
+            executor.uri = uris[&executor.name].clone();
+        }
+        let uris = namespaced_uris(self.examples.iter().map(|e| &e.name), "examples");
+        for example in &mut self.examples {
+            example.uri = uris[&example.name].clone();
+        }
+        let uris = namespaced_uris(self.workflows.iter().map(|w| &w.name), "workflows");
+        for workflow in &mut self.workflows {
+            workflow.uri = uris[&workflow.name].clone();
+        }
+        self.namespaced = true;
+        self
+    }
+
+    /// Serve the definitions of imported orbs, named `<alias>/<name>`, at
+    /// `orb://<alias>/commands/...`, `orb://<alias>/jobs/...` and
+    /// `orb://<alias>/executors/...`. The orb's own definitions keep their
+    /// URIs.
+    pub fn with_imports<'a>(mut self, aliases: impl Iterator<Item = &'a String>) -> Self {
+        let aliases: Vec<String> = aliases.cloned().collect();
+        if aliases.is_empty() || self.namespaced {
+            return self;
+        }
+        let imported = |name: &String| {
+            name.split_once('/')
+                .is_some_and(|(alias, _)| aliases.iter().any(|a| a == alias))
+        };
+        let uris = namespaced_uris(
+            self.commands
+                .iter()
+                .map(|c| &c.name)
+                .filter(|&n| imported(n)),
+            "commands",
+        );
+        for command in &mut self.commands {
+            if let Some(uri) = uris.get(&command.name) {
+                command.uri = uri.clone();
+            }
+        }
+        let uris = namespaced_uris(
+            self.jobs.iter().map(|j| &j.name).filter(|&n| imported(n)),
+            "jobs",
+        );
+        for job in &mut self.jobs {
+            if let Some(uri) = uris.get(&job.name) {
+                job.uri = uri.clone();
+            }
+        }
+        let uris = namespaced_uris(
+            self.executors
+                .iter()
+                .map(|e| &e.name)
+                .filter(|&n| imported(n)),
+            "executors",
+        );
+        for executor in &mut self.executors {
+            if let Some(uri) = uris.get(&executor.name) {
+                executor.uri = uri.clone();
+            }
+        }
+        self.imports = aliases;
+        self
+    }
+
+    /// Serve the YAML text of each command, job and executor found in
+    /// `sources` (keyed by `commands/<name>` and so on) at the definition's
+    /// URI under `orb://source/`, e.g. `orb://source/commands/greet`.
+    pub fn with_sources(mut self, sources: &std::collections::HashMap<String, String>) -> Self {
+        let definitions = self
+            .commands
+            .iter()
+            .map(|c| ("Command", "commands", &c.name, &c.uri))
+            .chain(self.jobs.iter().map(|j| ("Job", "jobs", &j.name, &j.uri)))
+            .chain(
+                self.executors
+                    .iter()
+                    .map(|e| ("Executor", "executors", &e.name, &e.uri)),
+            );
+        self.sources = definitions
+            .filter_map(|(label, category, name, uri)| {
+                let yaml = sources.get(&format!("{category}/{name}"))?;
+                Some(SourceContext {
+                    label,
+                    name: name.clone(),
+                    uri: format!("orb://source/{}", uri.trim_start_matches("orb://")),
+                    yaml: yaml.clone(),
+                })
+            })
+            .collect();
+        self
+    }
+
+    /// Name the crate, package and server struct after `naming`, and serve
+    /// every resource under its URI scheme.
+    ///
+    /// Call last: URIs set by the other `with_*` methods are rewritten.
+    pub fn with_naming(mut self, naming: &Naming) -> Self {
+        self.package_name = naming.package_name(&self.orb_name);
+        self.crate_name = to_snake_case(&self.package_name).replace('-', "_");
+        self.struct_name = to_pascal_case(&self.package_name);
+        if naming.is_default_scheme() {
+            return self;
+        }
+        self.uri_scheme = naming.uri_scheme.clone();
+        let rescheme = |uri: &mut String| *uri = naming.uri(uri);
+        let snapshots = &mut self.prior_versions;
+        for command in self
+            .commands
+            .iter_mut()
+            .chain(snapshots.iter_mut().flat_map(|s| s.commands.iter_mut()))
+        {
+            rescheme(&mut command.uri);
+        }
+        for job in self
+            .jobs
+            .iter_mut()
+            .chain(snapshots.iter_mut().flat_map(|s| s.jobs.iter_mut()))
+        {
+            rescheme(&mut job.uri);
+        }
+        for executor in self
+            .executors
+            .iter_mut()
+            .chain(snapshots.iter_mut().flat_map(|s| s.executors.iter_mut()))
+        {
+            rescheme(&mut executor.uri);
+        }
+        self.examples.iter_mut().for_each(|e| rescheme(&mut e.uri));
+        self.workflows.iter_mut().for_each(|w| rescheme(&mut w.uri));
+        self.sources.iter_mut().for_each(|s| rescheme(&mut s.uri));
+        self.schemas.iter_mut().for_each(|s| rescheme(&mut s.uri));
+        let scheme = format!("{}://", naming.uri_scheme);
+        self.changes_content = self.changes_content.replace("orb://", &scheme);
+        self.comparison_content = self.comparison_content.replace("orb://", &scheme);
+        self
+    }
+
+    /// Describe the parameters of each command and job as a JSON Schema,
+    /// served at the definition's URI under `orb://schemas/`, e.g.
+    /// `orb://schemas/commands/greet`.
+    pub fn with_parameter_schemas(mut self) -> Self {
+        let definitions = self
+            .commands
+            .iter()
+            .map(|c| ("Command", "command", &c.name, &c.uri, &c.parameters))
+            .chain(
+                self.jobs
+                    .iter()
+                    .map(|j| ("Job", "job", &j.name, &j.uri, &j.parameters)),
+            );
+        self.schemas = definitions
+            .map(|(label, kind, name, uri, parameters)| SchemaContext {
+                label,
+                kind,
+                name: name.clone(),
+                uri: format!("orb://schemas/{}", uri.trim_start_matches("orb://")),
+                json_content: parameter_schema(&format!("the {name} {kind}"), parameters),
+            })
+            .collect();
+        self
+    }
+
+    /// Generate a Rust enum for each `enum` parameter of a command or job,
+    /// so tool arguments outside the allowed values fail to deserialize.
+    ///
+    /// Type names join the kind, definition and parameter names
+    /// (`CommandLogLevel`); colliding names and variants get numeric
+    /// suffixes. Call after [`with_dev_orb_path`](Self::with_dev_orb_path):
+    /// dev-mode servers get none.
+    pub fn with_enum_types(mut self) -> Self {
+        // Dev-mode servers read their definitions at runtime
+        if self.dev_orb_path.is_some() {
+            return self;
+        }
+        let definitions = self
+            .commands
+            .iter()
+            .map(|c| ("command", &c.name, &c.parameters))
+            .chain(self.jobs.iter().map(|j| ("job", &j.name, &j.parameters)));
+        let mut type_names = Vec::new();
+        let mut enum_types = Vec::new();
+        for (kind, name, parameters) in definitions {
+            for parameter in parameters {
+                let Some(values) = parameter.enum_values.as_ref().filter(|v| !v.is_empty()) else {
+                    continue;
+                };
+                let type_name = unique_identifier(
+                    rust_identifier(&format!("{kind} {name} {}", parameter.name)),
+                    &mut type_names,
+                );
+                let mut idents = Vec::new();
+                let variants = values
+                    .iter()
+                    .map(|value| EnumVariantContext {
+                        ident: unique_identifier(rust_identifier(value), &mut idents),
+                        value: escape_multiline_literal(value),
+                    })
+                    .collect();
+                enum_types.push(EnumTypeContext {
+                    type_name,
+                    kind,
+                    definition: escape_multiline_literal(name),
+                    parameter: escape_multiline_literal(&parameter.name),
+                    variants,
+                });
+            }
+        }
+        self.enum_types = enum_types;
+        self
+    }
+
+    /// Record the language the server is generated in.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Read the orb at `orb_path` at runtime (dev mode); `None` keeps the
+    /// embedded definitions.
+    pub fn with_dev_orb_path(mut self, orb_path: Option<&std::path::Path>) -> Self {
+        self.dev_orb_path = orb_path.map(|p| escape_multiline_literal(&p.to_string_lossy()));
+        self
+    }
+
+    /// Override the `serverInfo` title and `instructions` advertised by the
+    /// generated server.
+    ///
+    /// `None` keeps the current value (no title, default instructions).
+    pub fn with_server_info(mut self, title: Option<&str>, instructions: Option<&str>) -> Self {
+        if let Some(title) = title {
+            self.server_title = Some(escape_multiline_literal(title));
+        }
+        if let Some(instructions) = instructions {
+            self.server_instructions = escape_multiline_literal(instructions);
+        }
+        self
+    }
+
+    /// Set the Markdown served at `orb://compare`; `None` omits the resource.
+    pub fn with_comparison(mut self, comparison: Option<String>) -> Self {
+        self.has_comparison = comparison.is_some();
+        self.comparison_content = comparison.unwrap_or_default();
+        self
+    }
+
+    /// Set the orb source revision embedded in the generated server.
+    pub fn with_source_rev(mut self, rev: Option<&str>) -> Self {
+        self.source_rev = rev.map(escape_multiline_literal);
+        self
+    }
+
+    /// Emit a proptest suite in the generated crate.
+    pub fn with_property_tests(mut self, enabled: bool) -> Self {
+        self.property_tests = enabled;
+        self
+    }
+
+    /// Set the crate license; `has_file` records that a LICENSE file is
+    /// emitted.
+    pub fn with_license(mut self, license: Option<&str>, has_file: bool) -> Self {
+        self.license = license.map(str::to_string);
+        self.has_license_file = has_file;
+        self
+    }
+
+    /// Set the `[package.metadata.binstall]` section; `None` omits it.
+    pub fn with_binstall(mut self, binstall: Option<&BinstallMetadata>) -> Self {
+        self.binstall = binstall.map(|b| BinstallContext {
+            pkg_url: escape_multiline_literal(&b.pkg_url),
+            pkg_fmt: b.pkg_fmt.as_str(),
+            bin_dir: b.bin_dir.as_deref().map(escape_multiline_literal),
+            overrides: b
+                .overrides
+                .iter()
+                .map(|(target, pkg_url)| BinstallOverrideContext {
+                    target: escape_multiline_literal(target),
+                    pkg_url: escape_multiline_literal(pkg_url),
+                })
+                .collect(),
+        });
+        self
+    }
+
+    /// Set the publishing fields of `[package]`; `None` omits them.
+    pub fn with_package(mut self, package: Option<&PackageMetadata>) -> Self {
+        let escape = |values: &[String]| -> Vec<String> {
+            values.iter().map(|v| escape_multiline_literal(v)).collect()
+        };
+        self.package = package.map(|p| PackageContext {
+            description: p.description.as_deref().map(escape_multiline_literal),
+            repository: p.repository.as_deref().map(escape_multiline_literal),
+            keywords: escape(&p.keywords),
+            registries: escape(&p.registries),
+        });
+        self
+    }
+
+    /// Mark the server as sandboxed.
+    pub fn with_sandboxed(mut self, sandboxed: bool) -> Self {
+        self.sandboxed = sandboxed;
+        self
+    }
+
+    /// Mark resources over the size limit as split into parts.
+    pub fn with_split_resources(mut self, split: bool) -> Self {
+        self.split_resources = split;
+        self
+    }
+
+    /// Serve MCP over `transport`, binding HTTP servers to `http_bind` by
+    /// default.
+    pub fn with_transport(mut self, transport: Transport, http_bind: SocketAddr) -> Self {
+        self.http_bind = (transport == Transport::Http).then(|| http_bind.to_string());
+        self
+    }
+
+    /// Compress the responses of an HTTP server and limit the size of its
+    /// request bodies; `None` sets no limit.
+    pub fn with_http_layers(mut self, compression: bool, body_limit: Option<usize>) -> Self {
+        self.http_compression = compression;
+        self.http_body_limit = body_limit;
+        self
+    }
+
+    /// Set the embedded logo; `None` omits it.
+    pub fn with_logo(mut self, logo: Option<&Logo>) -> Self {
+        self.logo = logo.map(|l| LogoContext {
+            file_name: l.file_name(),
+            mime_type: l.mime_type(),
+            size: l.size(),
+        });
+        self
+    }
+
+    /// Set the Markdown served at `orb://changes`; `None` omits the resource.
+    pub fn with_changes(mut self, changes: Option<String>) -> Self {
+        self.has_changes = changes.is_some();
+        self.changes_content = changes.unwrap_or_default();
+        self
+    }
+}
+
+/// Default MCP `instructions` text for a generated server.
+pub(crate) fn default_instructions(orb_name: &str) -> String {
+    format!("MCP server for the {orb_name} CircleCI orb")
 }
 
 impl VersionSnapshot {
     /// Build a snapshot for a prior version with version-prefixed resource
     /// URIs.
     pub fn build(version: &str, orb: &OrbDefinition, orb_name: &str) -> Self {
-        let version_ident = version.replace(['.', '-'], "_");
+        let version_ident = version.replace(['.', '-', '+'], "_");
         let prefix = format!("orb://v{version}");
 
-        let commands: Vec<CommandContext> = orb
-            .commands
-            .iter()
+        let command_slugs = unique_slugs(orb.commands.keys());
+        let commands: Vec<CommandContext> = sorted(&orb.commands)
+            .into_iter()
             .map(|(name, cmd)| {
-                let mut ctx = CommandContext::from_command(name, cmd);
-                ctx.uri = format!("{}/commands/{}", prefix, name);
+                let slug = &command_slugs[name];
+                let mut ctx = CommandContext::from_command(name, slug, cmd);
+                ctx.uri = format!("{}/commands/{}", prefix, slug);
                 ctx
             })
             .collect();
 
-        let jobs: Vec<JobContext> = orb
-            .jobs
-            .iter()
+        let job_slugs = unique_slugs(orb.jobs.keys());
+        let jobs: Vec<JobContext> = sorted(&orb.jobs)
+            .into_iter()
             .map(|(name, job)| {
-                let mut ctx = JobContext::from_job(name, job);
-                ctx.uri = format!("{}/jobs/{}", prefix, name);
+                let slug = &job_slugs[name];
+                let mut ctx = JobContext::from_job(name, slug, job, &orb.workflows);
+                ctx.uri = format!("{}/jobs/{}", prefix, slug);
                 ctx
             })
             .collect();
 
-        let executors: Vec<ExecutorContext> = orb
-            .executors
-            .iter()
+        let executor_slugs = unique_slugs(orb.executors.keys());
+        let executors: Vec<ExecutorContext> = sorted(&orb.executors)
+            .into_iter()
             .map(|(name, exec)| {
-                let mut ctx = ExecutorContext::from_executor(name, exec);
-                ctx.uri = format!("{}/executors/{}", prefix, name);
+                let slug = &executor_slugs[name];
+                let mut ctx = ExecutorContext::from_executor(name, slug, exec);
+                ctx.uri = format!("{}/executors/{}", prefix, slug);
                 ctx
             })
             .collect();
@@ -335,10 +1058,9 @@ impl VersionSnapshot {
 }
 
 impl CommandContext {
-    fn from_command(name: &str, cmd: &Command) -> Self {
-        let parameters: Vec<ParameterContext> = cmd
-            .parameters
-            .iter()
+    fn from_command(name: &str, slug: &str, cmd: &Command) -> Self {
+        let parameters: Vec<ParameterContext> = sorted(&cmd.parameters)
+            .into_iter()
             .map(|(pname, param)| ParameterContext::from_parameter(pname, param))
             .collect();
 
@@ -352,18 +1074,24 @@ impl CommandContext {
                 .description
                 .as_ref()
                 .map(|s| escape_for_string_literal(s)),
+            summary: cmd.description.as_deref().and_then(summary_line),
             parameters,
-            uri: format!("orb://commands/{}", name),
+            injected_steps: injected_steps(&cmd.steps, &cmd.parameters, false),
+            uri: format!("orb://commands/{}", slug),
             json_content,
         }
     }
 }
 
 impl JobContext {
-    fn from_job(name: &str, job: &Job) -> Self {
-        let parameters: Vec<ParameterContext> = job
-            .parameters
-            .iter()
+    fn from_job(
+        name: &str,
+        slug: &str,
+        job: &Job,
+        workflows: &std::collections::HashMap<String, Workflow>,
+    ) -> Self {
+        let parameters: Vec<ParameterContext> = sorted(&job.parameters)
+            .into_iter()
             .map(|(pname, param)| ParameterContext::from_parameter(pname, param))
             .collect();
 
@@ -372,7 +1100,7 @@ impl JobContext {
             crate::parser::ExecutorRef::WithParams { name, .. } => name.clone(),
         });
 
-        let json_content = create_job_json(name, job);
+        let json_content = create_job_json(name, job, workflows);
 
         Self {
             name: name.to_string(),
@@ -381,20 +1109,21 @@ impl JobContext {
                 .description
                 .as_ref()
                 .map(|s| escape_for_string_literal(s)),
+            summary: job.description.as_deref().and_then(summary_line),
             parameters,
+            injected_steps: injected_steps(&job.steps, &job.parameters, true),
             executor,
             config: ExecutorConfigContext::from_config(&job.config),
-            uri: format!("orb://jobs/{}", name),
+            uri: format!("orb://jobs/{}", slug),
             json_content,
         }
     }
 }
 
 impl ExecutorContext {
-    fn from_executor(name: &str, exec: &Executor) -> Self {
-        let parameters: Vec<ParameterContext> = exec
-            .parameters
-            .iter()
+    fn from_executor(name: &str, slug: &str, exec: &Executor) -> Self {
+        let parameters: Vec<ParameterContext> = sorted(&exec.parameters)
+            .into_iter()
             .map(|(pname, param)| ParameterContext::from_parameter(pname, param))
             .collect();
 
@@ -407,14 +1136,41 @@ impl ExecutorContext {
                 .description
                 .as_ref()
                 .map(|s| escape_for_string_literal(s)),
+            summary: exec.description.as_deref().and_then(summary_line),
             parameters,
             config: ExecutorConfigContext::from_config(&exec.config),
-            uri: format!("orb://executors/{}", name),
+            uri: format!("orb://executors/{}", slug),
             json_content,
         }
     }
 }
 
+impl ExampleContext {
+    fn from_example(name: &str, slug: &str, example: &Example) -> Self {
+        Self {
+            name: name.to_string(),
+            description: example.description.clone(),
+            description_escaped: example
+                .description
+                .as_ref()
+                .map(|s| escape_for_string_literal(s)),
+            uri: format!("orb://examples/{}", slug),
+            json_content: create_example_json(name, example),
+        }
+    }
+}
+
+impl WorkflowContext {
+    fn from_workflow(name: &str, slug: &str, workflow: &Workflow) -> Self {
+        Self {
+            name: name.to_string(),
+            job_count: workflow.jobs.len(),
+            uri: format!("orb://workflows/{}", slug),
+            json_content: create_workflow_json(name, workflow),
+        }
+    }
+}
+
 impl ExecutorConfigContext {
     fn from_config(config: &ExecutorConfig) -> Self {
         let environment: Vec<(String, String)> = config
@@ -423,8 +1179,14 @@ impl ExecutorConfigContext {
             .map(|(k, v)| (k.clone(), v.clone()))
             .collect();
 
+        let machine = machine_json(config);
+
         Self {
             docker_images: extract_docker_images(config),
+            machine: machine.is_some(),
+            machine_image: machine.and_then(|m| m.image).map(str::to_string),
+            docker_layer_caching: machine.and_then(|m| m.docker_layer_caching) == Some(true),
+            xcode: config.macos.as_ref().map(|macos| macos.xcode.clone()),
             resource_class: config.resource_class.clone(),
             working_directory: config.working_directory.clone(),
             environment,
@@ -435,7 +1197,7 @@ impl ExecutorConfigContext {
 
 impl ParameterContext {
     fn from_parameter(name: &str, param: &Parameter) -> Self {
-        let param_type = param_type_to_str(&param.param_type).to_string();
+        let param_type = param.param_type.as_str().to_string();
 
         let default = param
             .default
@@ -453,17 +1215,55 @@ impl ParameterContext {
     }
 }
 
-/// Convert ParameterType to string representation.
-fn param_type_to_str(pt: &ParameterType) -> &'static str {
-    match pt {
-        ParameterType::String => "string",
-        ParameterType::Boolean => "boolean",
-        ParameterType::Integer => "integer",
-        ParameterType::Enum => "enum",
-        ParameterType::EnvVarName => "env_var_name",
-        ParameterType::Steps => "steps",
-        ParameterType::Executor => "executor",
-    }
+/// JSON Schema of the parameters an invocation of `definition` passes: one
+/// property per parameter, with those lacking a default required.
+fn parameter_schema(definition: &str, parameters: &[ParameterContext]) -> String {
+    let properties: serde_json::Map<String, serde_json::Value> = parameters
+        .iter()
+        .map(|p| {
+            let mut property = match p.param_type.as_str() {
+                "boolean" => json!({ "type": "boolean" }),
+                "integer" => json!({ "type": "integer" }),
+                "enum" => json!({
+                    "type": "string",
+                    "enum": p.enum_values.clone().unwrap_or_default(),
+                }),
+                "env_var_name" => json!({
+                    "type": "string",
+                    "pattern": "^[A-Za-z_][A-Za-z0-9_]*$",
+                }),
+                "steps" => json!({ "type": "array" }),
+                // An executor name or an inline executor invocation.
+                "executor" => json!({ "type": ["string", "object"] }),
+                _ => json!({ "type": "string" }),
+            };
+            if let Some(description) = &p.description {
+                property["description"] = json!(description);
+            }
+            if let Some(default) = p
+                .default
+                .as_deref()
+                .and_then(|d| serde_json::from_str::<serde_json::Value>(d).ok())
+            {
+                property["default"] = default;
+            }
+            (p.name.clone(), property)
+        })
+        .collect();
+    let required: Vec<&str> = parameters
+        .iter()
+        .filter(|p| p.required)
+        .map(|p| p.name.as_str())
+        .collect();
+    let schema = json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": format!("Parameters of {definition}"),
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    });
+    serde_json::to_string_pretty(&schema).unwrap_or_else(|_| "{}".to_string())
 }
 
 /// Extract docker image names from ExecutorConfig.
@@ -483,11 +1283,153 @@ fn extract_docker_images(config: &ExecutorConfig) -> Vec<String> {
         .unwrap_or_default()
 }
 
+/// A `machine` executor's image and Docker layer caching flag.
+#[derive(Debug, Clone, Copy, Serialize)]
+struct MachineJson<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    image: Option<&'a str>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    docker_layer_caching: Option<bool>,
+}
+
+/// The `machine` executor of `config`; `None` for docker, macOS and
+/// `machine: false`.
+fn machine_json(config: &ExecutorConfig) -> Option<MachineJson<'_>> {
+    match config.machine.as_ref()? {
+        MachineConfig::Enabled(false) => None,
+        MachineConfig::Enabled(true) => Some(MachineJson {
+            image: None,
+            docker_layer_caching: None,
+        }),
+        MachineConfig::Image {
+            image,
+            docker_layer_caching,
+        } => Some(MachineJson {
+            image: Some(image.as_str()),
+            docker_layer_caching: *docker_layer_caching,
+        }),
+    }
+}
+
+/// Convert a definition name into a URI-safe slug.
+///
+/// Lowercases ASCII letters, keeps digits, `-` and `_`, and replaces every
+/// other character (dots, slashes, spaces, ...) with `-`. Runs of `-` are
+/// collapsed and leading/trailing `-` trimmed, so `deploy/prod.blue-green`
+/// becomes `deploy-prod-blue-green`. An empty result falls back to `unnamed`.
+///
+/// Generated servers carry a copy of this function to map tool arguments to
+/// resource URIs; keep the two in sync.
+pub fn slugify(name: &str) -> String {
+    let mut slug = String::with_capacity(name.len());
+    for c in name.chars() {
+        let c = c.to_ascii_lowercase();
+        if c.is_ascii_alphanumeric() || c == '_' {
+            slug.push(c);
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    let slug = slug.trim_matches('-');
+    if slug.is_empty() {
+        "unnamed".to_string()
+    } else {
+        slug.to_string()
+    }
+}
+
+/// Entries of `map` in key order, so generated files do not depend on
+/// `HashMap` iteration order.
+fn sorted<T>(map: &std::collections::HashMap<String, T>) -> Vec<(&String, &T)> {
+    let mut entries: Vec<_> = map.iter().collect();
+    entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+    entries
+}
+
+/// Assign a unique slug to every name in one definition category.
+///
+/// Names are processed in sorted order so the result is deterministic. When
+/// two names collide on the same slug, later names receive a numeric suffix
+/// (`-2`, `-3`, ...) and a warning is logged; the original name is still
+/// carried in the resource JSON.
+fn unique_slugs<'a>(
+    names: impl Iterator<Item = &'a String>,
+) -> std::collections::HashMap<String, String> {
+    let mut sorted: Vec<&String> = names.collect();
+    sorted.sort();
+
+    let mut taken = std::collections::HashSet::new();
+    let mut slugs = std::collections::HashMap::new();
+    for name in sorted {
+        let base = slugify(name);
+        let mut slug = base.clone();
+        let mut n = 2;
+        while !taken.insert(slug.clone()) {
+            slug = format!("{base}-{n}");
+            n += 1;
+        }
+        if slug != base {
+            tracing::warn!(name = %name, slug = %slug, "URI slug collision; added suffix");
+        }
+        slugs.insert(name.clone(), slug);
+    }
+    slugs
+}
+
+/// URIs of the `<orb>/<name>` definitions in `category`, with slugs made
+/// unique within each orb.
+fn namespaced_uris<'a>(
+    names: impl Iterator<Item = &'a String>,
+    category: &str,
+) -> std::collections::HashMap<String, String> {
+    let mut by_orb: std::collections::HashMap<&str, Vec<String>> = std::collections::HashMap::new();
+    for name in names {
+        let (orb, local) = name.split_once('/').unwrap_or(("", name));
+        by_orb.entry(orb).or_default().push(local.to_string());
+    }
+    let mut uris = std::collections::HashMap::new();
+    for (orb, locals) in by_orb {
+        for (local, slug) in unique_slugs(locals.iter()) {
+            let name = if orb.is_empty() {
+                local
+            } else {
+                format!("{orb}/{local}")
+            };
+            uris.insert(name, format!("orb://{orb}/{category}/{slug}"));
+        }
+    }
+    uris
+}
+
 /// Escape a string for use in a Rust string literal.
 ///
-/// Replaces newlines with spaces and escapes double quotes.
+/// Replaces newlines with spaces and escapes backslashes and double quotes.
 fn escape_for_string_literal(s: &str) -> String {
-    s.replace('\n', " ").replace('\r', "").replace('"', "\\\"")
+    s.replace('\\', "\\\\")
+        .replace('\n', " ")
+        .replace('\r', "")
+        .replace('"', "\\\"")
+}
+
+/// First non-blank line of `description`, trimmed, with `|` escaped so it
+/// stays within one Markdown table cell.
+fn summary_line(description: &str) -> Option<String> {
+    description
+        .lines()
+        .map(str::trim)
+        .find(|line| !line.is_empty())
+        .map(|line| line.replace('|', "\\|"))
+}
+
+/// Escape a string for use in a Rust string literal, preserving line breaks.
+///
+/// Unlike [`escape_for_string_literal`], newlines are kept as `\n` escapes so
+/// multi-paragraph text (e.g. server instructions) survives intact.
+fn escape_multiline_literal(s: &str) -> String {
+    s.replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\r', "")
+        .replace('\n', "\\n")
 }
 
 /// Convert a string to snake_case.
@@ -533,6 +1475,43 @@ fn to_pascal_case(s: &str) -> String {
     result
 }
 
+/// `s` as a PascalCase Rust identifier: runs of ASCII letters and digits
+/// are capitalized and joined, with a `V` prefix when the result would be
+/// empty or start with a digit.
+fn rust_identifier(s: &str) -> String {
+    let mut ident: String = s
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            let mut chars = part.chars();
+            chars
+                .next()
+                .map(|first| first.to_ascii_uppercase().to_string() + chars.as_str())
+                .unwrap_or_default()
+        })
+        .collect();
+    if !ident.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        ident.insert(0, 'V');
+    }
+    if ident == "Self" {
+        ident.push('_');
+    }
+    ident
+}
+
+/// `ident`, suffixed with `2`, `3`, ... until it is not in `used`, which it
+/// is then added to.
+fn unique_identifier(ident: String, used: &mut Vec<String>) -> String {
+    let mut unique = ident.clone();
+    let mut n = 1;
+    while used.contains(&unique) {
+        n += 1;
+        unique = format!("{ident}{n}");
+    }
+    used.push(unique.clone());
+    unique
+}
+
 /// JSON representation of a parameter for embedding in resources.
 #[derive(Serialize)]
 struct ParameterJson<'a> {
@@ -548,11 +1527,11 @@ struct ParameterJson<'a> {
 
 /// Convert parameters map to JSON-serializable format.
 fn params_to_json(params: &std::collections::HashMap<String, Parameter>) -> Vec<ParameterJson<'_>> {
-    params
-        .iter()
+    sorted(params)
+        .into_iter()
         .map(|(pname, param)| ParameterJson {
             name: pname,
-            param_type: param_type_to_str(&param.param_type),
+            param_type: param.param_type.as_str(),
             description: param.description.as_deref(),
             default: param.default.as_ref(),
             required: param.default.is_none(),
@@ -561,6 +1540,118 @@ fn params_to_json(params: &std::collections::HashMap<String, Parameter>) -> Vec<
         .collect()
 }
 
+/// JSON of `steps` as written in the orb (run commands, cache keys,
+/// conditionals, ...), without the unset optional fields of parsed steps.
+/// Each `when`/`unless` step also gets a `condition_description` reading its
+/// condition in words.
+fn steps_to_json(steps: &[Step]) -> serde_json::Value {
+    fn prune(value: &mut serde_json::Value) {
+        match value {
+            serde_json::Value::Object(fields) => {
+                fields.retain(|_, v| !v.is_null() && v.as_object().is_none_or(|o| !o.is_empty()));
+                fields.values_mut().for_each(prune);
+            }
+            serde_json::Value::Array(items) => items.iter_mut().for_each(prune),
+            _ => {}
+        }
+    }
+
+    fn describe_conditions(steps: &[Step], json: &mut serde_json::Value) {
+        let Some(items) = json.as_array_mut() else {
+            return;
+        };
+        for (step, item) in steps.iter().zip(items) {
+            let (key, conditional) = match step {
+                Step::Structured(StructuredStep::When(c)) => ("when", c),
+                Step::Structured(StructuredStep::Unless(c)) => ("unless", c),
+                _ => continue,
+            };
+            if let Some(body) = item.get_mut(key).and_then(|b| b.as_object_mut()) {
+                body.insert(
+                    "condition_description".to_string(),
+                    conditional.condition.describe().into(),
+                );
+                if let Some(nested) = body.get_mut("steps") {
+                    describe_conditions(&conditional.steps, nested);
+                }
+            }
+        }
+    }
+
+    let mut json = serde_json::to_value(steps).unwrap_or_default();
+    prune(&mut json);
+    describe_conditions(steps, &mut json);
+    json
+}
+
+/// Where one set of injected steps runs.
+#[derive(Debug, Clone, Serialize)]
+pub struct InjectedStepsContext {
+    /// `pre-steps`, `post-steps` or the name of a `steps` parameter
+    pub name: String,
+
+    /// Where the steps run, e.g. "as step 2 of 4"
+    pub runs: String,
+}
+
+/// Where the steps injected into a command or job run: a job's `pre-steps`
+/// and `post-steps`, then each `steps` parameter by the injection points
+/// splicing it in or forwarding it to another command.
+fn injected_steps(
+    steps: &[Step],
+    params: &std::collections::HashMap<String, Parameter>,
+    is_job: bool,
+) -> Vec<InjectedStepsContext> {
+    let mut injected = Vec::new();
+    if is_job {
+        injected.push(InjectedStepsContext {
+            name: "pre-steps".to_string(),
+            runs: "before the job's first step, ahead of checkout; passed by the workflow that \
+                   invokes the job"
+                .to_string(),
+        });
+        injected.push(InjectedStepsContext {
+            name: "post-steps".to_string(),
+            runs: "after the job's last step; passed by the workflow that invokes the job"
+                .to_string(),
+        });
+    }
+    let points = injection_points(steps);
+    let total = steps.len();
+    for (name, _) in sorted(params)
+        .into_iter()
+        .filter(|(_, p)| p.param_type == ParameterType::Steps)
+    {
+        let mut places: Vec<String> = points
+            .iter()
+            .filter(|point| &point.parameter == name)
+            .map(|point| {
+                let step = point.path[0] + 1;
+                let position = if point.is_nested() { "within" } else { "in" };
+                match &point.via {
+                    Injection::Spliced if point.is_nested() => {
+                        format!("within step {step} of {total}")
+                    }
+                    Injection::Spliced => format!("as step {step} of {total}"),
+                    Injection::Forwarded { command, argument } => format!(
+                        "passed to `{command}` as `{argument}` {position} step {step} of {total}"
+                    ),
+                }
+            })
+            .collect();
+        places.dedup();
+        injected.push(InjectedStepsContext {
+            name: name.clone(),
+            runs: if places.is_empty() {
+                "nowhere: no step runs this parameter".to_string()
+            } else {
+                places.join(" and ")
+            },
+        });
+    }
+    injected
+}
+
 /// Create JSON representation of a command for embedding in resources.
 fn create_command_json(name: &str, cmd: &Command) -> String {
     #[derive(Serialize)]
@@ -569,6 +1660,9 @@ fn create_command_json(name: &str, cmd: &Command) -> String {
         description: Option<&'a str>,
         parameters: Vec<ParameterJson<'a>>,
         steps_count: usize,
+        steps: serde_json::Value,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        injected_steps: Vec<InjectedStepsContext>,
     }
 
     let json = CommandJson {
@@ -576,13 +1670,22 @@ fn create_command_json(name: &str, cmd: &Command) -> String {
         description: cmd.description.as_deref(),
         parameters: params_to_json(&cmd.parameters),
         steps_count: cmd.steps.len(),
+        steps: steps_to_json(&cmd.steps),
+        injected_steps: injected_steps(&cmd.steps, &cmd.parameters, false),
     };
 
     serde_json::to_string_pretty(&json).unwrap_or_else(|_| "{}".to_string())
 }
 
 /// Create JSON representation of a job for embedding in resources.
-fn create_job_json(name: &str, job: &Job) -> String {
+///
+/// `invocations` lists the workflows of `workflows` that run the job, with
+/// the branches and tags each runs it for.
+fn create_job_json(
+    name: &str,
+    job: &Job,
+    workflows: &std::collections::HashMap<String, Workflow>,
+) -> String {
     #[derive(Serialize)]
     struct JobJson<'a> {
         name: &'a str,
@@ -590,10 +1693,43 @@ fn create_job_json(name: &str, job: &Job) -> String {
         executor: Option<String>,
         parameters: Vec<ParameterJson<'a>>,
         steps_count: usize,
+        steps: serde_json::Value,
+        injected_steps: Vec<InjectedStepsContext>,
         docker_images: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        machine: Option<MachineJson<'a>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        xcode: Option<&'a str>,
         resource_class: Option<&'a str>,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        invocations: Vec<InvocationJson<'a>>,
     }
 
+    #[derive(Serialize)]
+    struct InvocationJson<'a> {
+        workflow: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        name: Option<&'a str>,
+        runs_on: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        matrix_jobs: Vec<String>,
+    }
+
+    let invocations = sorted(workflows)
+        .into_iter()
+        .flat_map(|(workflow, w)| {
+            w.jobs
+                .iter()
+                .filter(|invocation| invocation.job == name)
+                .map(|invocation| InvocationJson {
+                    workflow,
+                    name: invocation.options.name.as_deref(),
+                    runs_on: invocation.options.runs_on(),
+                    matrix_jobs: matrix_jobs(&invocation.job, &invocation.options),
+                })
+        })
+        .collect();
+
     let executor = job.executor.as_ref().map(|e| match e {
         crate::parser::ExecutorRef::Name(n) => n.clone(),
         crate::parser::ExecutorRef::WithParams { name, .. } => name.clone(),
@@ -605,13 +1741,26 @@ fn create_job_json(name: &str, job: &Job) -> String {
         executor,
         parameters: params_to_json(&job.parameters),
         steps_count: job.steps.len(),
+        steps: steps_to_json(&job.steps),
+        injected_steps: injected_steps(&job.steps, &job.parameters, true),
         docker_images: extract_docker_images(&job.config),
+        machine: machine_json(&job.config),
+        xcode: job.config.macos.as_ref().map(|macos| macos.xcode.as_str()),
         resource_class: job.config.resource_class.as_deref(),
+        invocations,
     };
 
     serde_json::to_string_pretty(&json).unwrap_or_else(|_| "{}".to_string())
 }
 
+/// Names of the jobs a workflow job invocation's `matrix` expands into;
+/// empty without a matrix.
+fn matrix_jobs(job: &str, options: &WorkflowJobOptions) -> Vec<String> {
+    options.matrix.as_ref().map_or_else(Vec::new, |matrix| {
+        matrix.job_names(options.name.as_deref().unwrap_or(job))
+    })
+}
+
 /// Create JSON representation of an executor for embedding in resources.
 fn create_executor_json(name: &str, exec: &Executor) -> String {
     #[derive(Serialize)]
@@ -620,6 +1769,10 @@ fn create_executor_json(name: &str, exec: &Executor) -> String {
         description: Option<&'a str>,
         parameters: Vec<ParameterJson<'a>>,
         docker_images: Vec<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        machine: Option<MachineJson<'a>>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        xcode: Option<&'a str>,
         resource_class: Option<&'a str>,
         working_directory: Option<&'a str>,
     }
@@ -629,6 +1782,8 @@ fn create_executor_json(name: &str, exec: &Executor) -> String {
         description: exec.description.as_deref(),
         parameters: params_to_json(&exec.parameters),
         docker_images: extract_docker_images(&exec.config),
+        machine: machine_json(&exec.config),
+        xcode: exec.config.macos.as_ref().map(|macos| macos.xcode.as_str()),
         resource_class: exec.config.resource_class.as_deref(),
         working_directory: exec.config.working_directory.as_deref(),
     };
@@ -636,6 +1791,85 @@ fn create_executor_json(name: &str, exec: &Executor) -> String {
     serde_json::to_string_pretty(&json).unwrap_or_else(|_| "{}".to_string())
 }
 
+/// Create JSON representation of a usage example for embedding in resources.
+///
+/// `usage` and `result` are rendered back to YAML so assistants can quote
+/// them as config snippets.
+fn create_example_json(name: &str, example: &Example) -> String {
+    #[derive(Serialize)]
+    struct ExampleJson<'a> {
+        name: &'a str,
+        description: Option<&'a str>,
+        usage: String,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        result: Option<String>,
+    }
+
+    let to_yaml = |value: &serde_yaml::Value| serde_yaml::to_string(value).unwrap_or_default();
+    let json = ExampleJson {
+        name,
+        description: example.description.as_deref(),
+        usage: to_yaml(&example.usage),
+        result: example.result.as_ref().map(to_yaml),
+    };
+
+    serde_json::to_string_pretty(&json).unwrap_or_else(|_| "{}".to_string())
+}
+
+/// Create JSON representation of a workflow for embedding in resources.
+///
+/// Every job invocation is spelled out as an object, whether the config
+/// wrote it as a bare name or a mapping, with `runs_on` reading its filters
+/// and `matrix_jobs` naming the jobs its matrix expands into.
+fn create_workflow_json(name: &str, workflow: &Workflow) -> String {
+    #[derive(Serialize)]
+    struct WorkflowJson<'a> {
+        name: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        when: Option<&'a Condition>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        when_description: Option<String>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        unless: Option<&'a Condition>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        unless_description: Option<String>,
+        #[serde(skip_serializing_if = "<[_]>::is_empty")]
+        triggers: &'a [serde_yaml::Value],
+        jobs: Vec<WorkflowJobJson<'a>>,
+    }
+
+    #[derive(Serialize)]
+    struct WorkflowJobJson<'a> {
+        job: &'a str,
+        #[serde(flatten)]
+        options: &'a WorkflowJobOptions,
+        runs_on: String,
+        #[serde(skip_serializing_if = "Vec::is_empty")]
+        matrix_jobs: Vec<String>,
+    }
+
+    let json = WorkflowJson {
+        name,
+        when: workflow.when.as_ref(),
+        when_description: workflow.when.as_ref().map(Condition::describe),
+        unless: workflow.unless.as_ref(),
+        unless_description: workflow.unless.as_ref().map(Condition::describe),
+        triggers: &workflow.triggers,
+        jobs: workflow
+            .jobs
+            .iter()
+            .map(|job| WorkflowJobJson {
+                job: &job.job,
+                options: &job.options,
+                runs_on: job.options.runs_on(),
+                matrix_jobs: matrix_jobs(&job.job, &job.options),
+            })
+            .collect(),
+    };
+
+    serde_json::to_string_pretty(&json).unwrap_or_else(|_| "{}".to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use std::collections::HashMap;
@@ -652,6 +1886,71 @@ mod tests {
         assert_eq!(to_snake_case("my orb"), "my_orb");
     }
 
+    #[test]
+    fn test_summary_line() {
+        assert_eq!(
+            summary_line("\n  Build the crate\nwith cargo.\n").as_deref(),
+            Some("Build the crate")
+        );
+        assert_eq!(summary_line("a | b").as_deref(), Some("a \\| b"));
+        assert_eq!(summary_line(" \n"), None);
+    }
+
+    #[test]
+    fn test_rust_identifier() {
+        assert_eq!(rust_identifier("command log level"), "CommandLogLevel");
+        assert_eq!(rust_identifier("linux/amd64"), "LinuxAmd64");
+        assert_eq!(rust_identifier("2.1"), "V21");
+        assert_eq!(rust_identifier(""), "V");
+        assert_eq!(rust_identifier("self"), "Self_");
+        let mut used = vec![];
+        assert_eq!(unique_identifier("V21".into(), &mut used), "V21");
+        assert_eq!(unique_identifier("V21".into(), &mut used), "V212");
+    }
+
+    #[test]
+    fn test_enum_types_for_enum_parameters() {
+        let mut orb = OrbDefinition::default();
+        orb.commands.insert(
+            "log".to_string(),
+            Command {
+                parameters: HashMap::from([
+                    (
+                        "level".to_string(),
+                        Parameter {
+                            param_type: ParameterType::Enum,
+                            enum_values: Some(vec!["info".into(), "warn-only".into()]),
+                            ..Default::default()
+                        },
+                    ),
+                    (
+                        "message".to_string(),
+                        Parameter {
+                            param_type: ParameterType::String,
+                            ..Default::default()
+                        },
+                    ),
+                ]),
+                ..Default::default()
+            },
+        );
+
+        let ctx = GeneratorContext::from_orb(&orb, "test-orb", "1.0.0").with_enum_types();
+        assert_eq!(ctx.enum_types.len(), 1);
+        let enum_type = &ctx.enum_types[0];
+        assert_eq!(enum_type.type_name, "CommandLogLevel");
+        assert_eq!(
+            (enum_type.kind, enum_type.parameter.as_str()),
+            ("command", "level")
+        );
+        let variants: Vec<(&str, &str)> = enum_type
+            .variants
+            .iter()
+            .map(|v| (v.ident.as_str(), v.value.as_str()))
+            .collect();
+        assert_eq!(variants, vec![("Info", "info"), ("WarnOnly", "warn-only")]);
+    }
+
     #[test]
     fn test_to_pascal_case() {
         assert_eq!(to_pascal_case("my-orb"), "MyOrb");
@@ -703,6 +2002,374 @@ mod tests {
         assert_eq!(cmd.uri, "orb://commands/greet");
     }
 
+    #[test]
+    fn test_with_naming_renames_server_and_reschemes_uris() {
+        let mut orb = OrbDefinition::default();
+        orb.commands.insert("greet".to_string(), Command::default());
+        let sources = HashMap::from([("commands/greet".to_string(), "steps: []\n".to_string())]);
+
+        let ctx = GeneratorContext::from_orb_with_extras(
+            &orb,
+            "my-toolkit",
+            "1.5.0",
+            vec![("1.0.0".to_string(), orb.clone())],
+            None,
+        )
+        .with_sources(&sources)
+        .with_parameter_schemas()
+        .with_naming(
+            &Naming::default()
+                .with_prefix("acme")
+                .with_suffix("")
+                .with_uri_scheme("acme"),
+        );
+
+        assert_eq!(ctx.package_name, "acme-my-toolkit");
+        assert_eq!(ctx.crate_name, "acme_my_toolkit");
+        assert_eq!(ctx.struct_name, "AcmeMyToolkit");
+        assert_eq!(ctx.uri_scheme, "acme");
+        assert_eq!(ctx.commands[0].uri, "acme://commands/greet");
+        assert_eq!(ctx.sources[0].uri, "acme://source/commands/greet");
+        assert_eq!(ctx.schemas[0].uri, "acme://schemas/commands/greet");
+        assert_eq!(
+            ctx.prior_versions[0].commands[0].uri,
+            "acme://v1.0.0/commands/greet"
+        );
+    }
+
+    #[test]
+    fn test_examples_become_resources() {
+        let mut orb = OrbDefinition::default();
+        orb.examples.insert(
+            "Basic Usage".to_string(),
+            crate::parser::Example {
+                description: Some("Run the\nbuild".to_string()),
+                usage: serde_yaml::from_str("version: 2.1\nworkflows: {}\n").unwrap(),
+                result: None,
+            },
+        );
+
+        let ctx = GeneratorContext::from_orb(&orb, "my-toolkit", "1.0.0");
+        assert!(ctx.has_resources);
+        let example = &ctx.examples[0];
+        assert_eq!(example.uri, "orb://examples/basic-usage");
+        assert_eq!(
+            example.description_escaped.as_deref(),
+            Some("Run the build")
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&example.json_content).unwrap();
+        assert_eq!(json["name"], "Basic Usage");
+        assert_eq!(json["usage"], "version: 2.1\nworkflows: {}\n");
+        assert!(json.get("result").is_none());
+    }
+
+    #[test]
+    fn test_workflows_become_resources() {
+        let mut orb = OrbDefinition::default();
+        orb.workflows.insert(
+            "Build and Deploy".to_string(),
+            serde_yaml::from_str(
+                r#"
+jobs:
+  - build
+  - deploy:
+      requires: [build]
+      context: org-global
+      filters:
+        branches:
+          only: main
+"#,
+            )
+            .unwrap(),
+        );
+
+        let ctx = GeneratorContext::from_orb(&orb, "my-toolkit", "1.0.0");
+        assert!(ctx.has_resources);
+        let workflow = &ctx.workflows[0];
+        assert_eq!(workflow.uri, "orb://workflows/build-and-deploy");
+        assert_eq!(workflow.job_count, 2);
+
+        let json: serde_json::Value = serde_json::from_str(&workflow.json_content).unwrap();
+        assert_eq!(json["name"], "Build and Deploy");
+        assert_eq!(
+            json["jobs"][0],
+            serde_json::json!({"job": "build", "runs_on": "all branches; no tags"})
+        );
+        assert_eq!(
+            json["jobs"][1],
+            serde_json::json!({
+                "job": "deploy",
+                "requires": ["build"],
+                "context": ["org-global"],
+                "filters": {"branches": {"only": ["main"]}},
+                "runs_on": "branches main; no tags"
+            })
+        );
+        assert!(json.get("when").is_none());
+
+        orb.workflows.insert(
+            "nightly".to_string(),
+            serde_yaml::from_str(
+                "unless:\n  equal: [main, << pipeline.git.branch >>]\njobs: [build]\n",
+            )
+            .unwrap(),
+        );
+        let ctx = GeneratorContext::from_orb(&orb, "my-toolkit", "1.0.0");
+        let nightly = ctx.workflows.iter().find(|w| w.name == "nightly").unwrap();
+        let json: serde_json::Value = serde_json::from_str(&nightly.json_content).unwrap();
+        assert_eq!(
+            json["unless"],
+            serde_json::json!({"equal": ["main", "<< pipeline.git.branch >>"]})
+        );
+        assert_eq!(
+            json["unless_description"],
+            "\"main\" equals pipeline.git.branch"
+        );
+    }
+
+    #[test]
+    fn test_job_json_lists_workflow_invocations() {
+        let mut orb = OrbDefinition::default();
+        orb.jobs.insert("test".to_string(), Job::default());
+        orb.workflows.insert(
+            "ci".to_string(),
+            serde_yaml::from_str(
+                r#"
+jobs:
+  - test:
+      matrix:
+        parameters:
+          python: ["3.11", "3.12"]
+  - test:
+      name: release-test
+      filters:
+        branches:
+          ignore: /.*/
+        tags:
+          only: /^v.*/
+  - build
+"#,
+            )
+            .unwrap(),
+        );
+
+        let ctx = GeneratorContext::from_orb(&orb, "my-toolkit", "1.0.0");
+        let json: serde_json::Value = serde_json::from_str(&ctx.jobs[0].json_content).unwrap();
+        assert_eq!(
+            json["invocations"],
+            serde_json::json!([
+                {
+                    "workflow": "ci",
+                    "runs_on": "all branches; no tags",
+                    "matrix_jobs": ["test-3.11", "test-3.12"]
+                },
+                {
+                    "workflow": "ci",
+                    "name": "release-test",
+                    "runs_on": "all branches except /.*/; tags /^v.*/"
+                }
+            ])
+        );
+
+        let workflow: serde_json::Value =
+            serde_json::from_str(&ctx.workflows[0].json_content).unwrap();
+        assert_eq!(
+            workflow["jobs"][0]["matrix"],
+            serde_json::json!({"parameters": {"python": ["3.11", "3.12"]}})
+        );
+        assert_eq!(
+            workflow["jobs"][0]["matrix_jobs"],
+            serde_json::json!(["test-3.11", "test-3.12"])
+        );
+
+        orb.workflows.clear();
+        let ctx = GeneratorContext::from_orb(&orb, "my-toolkit", "1.0.0");
+        let json: serde_json::Value = serde_json::from_str(&ctx.jobs[0].json_content).unwrap();
+        assert!(json.get("invocations").is_none());
+    }
+
+    #[test]
+    fn test_machine_and_macos_executors_are_described() {
+        let mut orb = OrbDefinition::default();
+        orb.executors.insert(
+            "vm".to_string(),
+            serde_yaml::from_str(
+                "machine:\n  image: ubuntu-2204:current\n  docker_layer_caching: true\n",
+            )
+            .unwrap(),
+        );
+        orb.executors.insert(
+            "mac".to_string(),
+            serde_yaml::from_str("macos:\n  xcode: 15.4.0\n").unwrap(),
+        );
+        orb.jobs.insert(
+            "build".to_string(),
+            serde_yaml::from_str("machine: true\nsteps: [checkout]\n").unwrap(),
+        );
+
+        let ctx = GeneratorContext::from_orb(&orb, "my-toolkit", "1.0.0");
+        let mac = &ctx.executors[0].config;
+        assert!(!mac.machine);
+        assert_eq!(mac.xcode.as_deref(), Some("15.4.0"));
+        let vm = &ctx.executors[1].config;
+        assert!(vm.machine && vm.docker_layer_caching);
+        assert_eq!(vm.machine_image.as_deref(), Some("ubuntu-2204:current"));
+        let build = &ctx.jobs[0].config;
+        assert!(build.machine);
+        assert_eq!(build.machine_image, None);
+
+        let json: serde_json::Value = serde_json::from_str(&ctx.executors[1].json_content).unwrap();
+        assert_eq!(
+            json["machine"],
+            serde_json::json!({"image": "ubuntu-2204:current", "docker_layer_caching": true})
+        );
+        assert!(json.get("xcode").is_none());
+        let json: serde_json::Value = serde_json::from_str(&ctx.executors[0].json_content).unwrap();
+        assert_eq!(json["xcode"], "15.4.0");
+        assert!(json.get("machine").is_none());
+        let json: serde_json::Value = serde_json::from_str(&ctx.jobs[0].json_content).unwrap();
+        assert_eq!(json["machine"], serde_json::json!({}));
+    }
+
+    #[test]
+    fn test_command_json_includes_full_steps() {
+        let steps: Vec<Step> = serde_yaml::from_str(
+            r#"
+- checkout
+- restore_cache:
+    keys:
+      - deps-{{ checksum "Cargo.lock" }}
+- run:
+    name: Test
+    command: cargo test
+- when:
+    condition: << parameters.publish >>
+    steps:
+      - run: cargo publish
+"#,
+        )
+        .unwrap();
+        let cmd = Command {
+            description: None,
+            parameters: HashMap::new(),
+            steps,
+        };
+
+        let json: serde_json::Value =
+            serde_json::from_str(&create_command_json("release", &cmd)).unwrap();
+        assert_eq!(json["steps_count"], 4);
+        assert_eq!(
+            json["steps"],
+            serde_json::json!([
+                "checkout",
+                {"restore_cache": {"keys": ["deps-{{ checksum \"Cargo.lock\" }}"]}},
+                {"run": {"name": "Test", "command": "cargo test"}},
+                {"when": {
+                    "condition": "<< parameters.publish >>",
+                    "steps": [{"run": "cargo publish"}],
+                    "condition_description": "parameters.publish"
+                }},
+            ])
+        );
+        assert!(json.get("injected_steps").is_none());
+    }
+
+    #[test]
+    fn test_job_json_explains_injected_steps() {
+        let job: Job = serde_yaml::from_str(
+            r#"
+parameters:
+  setup:
+    type: steps
+    default: []
+  after-test:
+    type: steps
+    default: []
+  unused:
+    type: steps
+    default: []
+steps:
+  - checkout
+  - steps: << parameters.setup >>
+  - run: cargo test
+  - when:
+      condition: true
+      steps:
+        - steps: << parameters.after-test >>
+"#,
+        )
+        .unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&create_job_json("test", &job, &HashMap::new())).unwrap();
+        assert_eq!(
+            json["steps"][1],
+            serde_json::json!({"steps": "<< parameters.setup >>"})
+        );
+        let runs: Vec<(&str, &str)> = json["injected_steps"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|i| (i["name"].as_str().unwrap(), i["runs"].as_str().unwrap()))
+            .collect();
+        assert_eq!(runs[0].0, "pre-steps");
+        assert!(runs[0].1.starts_with("before the job's first step"));
+        assert_eq!(runs[1].0, "post-steps");
+        assert_eq!(
+            runs[2..],
+            [
+                ("after-test", "within step 4 of 4"),
+                ("setup", "as step 2 of 4"),
+                ("unused", "nowhere: no step runs this parameter"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_command_context_explains_forwarded_steps() {
+        let cmd: Command = serde_yaml::from_str(
+            r#"
+parameters:
+  after-build:
+    type: steps
+    default: []
+  post-install:
+    type: steps
+    default: []
+steps:
+  - build:
+      after-build: << parameters.after-build >>
+  - when:
+      condition: true
+      steps:
+        - node/install:
+            post-install: << parameters.post-install >>
+"#,
+        )
+        .unwrap();
+
+        let context = CommandContext::from_command("release", "release", &cmd);
+        let runs: Vec<(&str, &str)> = context
+            .injected_steps
+            .iter()
+            .map(|i| (i.name.as_str(), i.runs.as_str()))
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                (
+                    "after-build",
+                    "passed to `build` as `after-build` in step 1 of 2"
+                ),
+                (
+                    "post-install",
+                    "passed to `node/install` as `post-install` within step 2 of 2"
+                ),
+            ]
+        );
+    }
+
     #[test]
     fn test_parameter_context() {
         let param = Parameter {
@@ -852,4 +2519,132 @@ mod tests {
         let snap2 = VersionSnapshot::build("1.0.0", &with_cmd, "test-orb");
         assert!(snap2.has_resources);
     }
+
+    #[test]
+    fn test_with_namespaces_prefixes_uris_with_orb() {
+        let orb = OrbDefinition {
+            commands: HashMap::from([
+                ("build/greet".to_string(), Command::default()),
+                ("deploy/greet".to_string(), Command::default()),
+                ("deploy/Greet".to_string(), Command::default()),
+            ]),
+            examples: HashMap::from([("deploy/basic".to_string(), Default::default())]),
+            workflows: HashMap::from([("deploy/main".to_string(), Default::default())]),
+            ..Default::default()
+        };
+        let ctx = GeneratorContext::from_orb(&orb, "platform", "1.0.0").with_namespaces(true);
+        assert!(ctx.namespaced);
+        let uri_of = |name: &str| {
+            ctx.commands
+                .iter()
+                .find(|c| c.name == name)
+                .map(|c| c.uri.clone())
+                .unwrap()
+        };
+        assert_eq!(uri_of("build/greet"), "orb://build/commands/greet");
+        assert_eq!(uri_of("deploy/Greet"), "orb://deploy/commands/greet");
+        assert_eq!(uri_of("deploy/greet"), "orb://deploy/commands/greet-2");
+        assert_eq!(ctx.examples[0].uri, "orb://deploy/examples/basic");
+        assert_eq!(ctx.workflows[0].uri, "orb://deploy/workflows/main");
+    }
+
+    #[test]
+    fn test_parameter_schemas_describe_types_defaults_and_required() {
+        let parameter = |param_type, default: Option<serde_yaml::Value>, enum_values| Parameter {
+            param_type,
+            description: None,
+            default,
+            enum_values,
+        };
+        let command = Command {
+            parameters: HashMap::from([
+                (
+                    "who".to_string(),
+                    parameter(ParameterType::String, None, None),
+                ),
+                (
+                    "loud".to_string(),
+                    parameter(ParameterType::Boolean, Some(false.into()), None),
+                ),
+                (
+                    "mood".to_string(),
+                    parameter(
+                        ParameterType::Enum,
+                        Some("calm".into()),
+                        Some(vec!["calm".to_string(), "excited".to_string()]),
+                    ),
+                ),
+            ]),
+            ..Default::default()
+        };
+        let orb = OrbDefinition {
+            commands: HashMap::from([("greet".to_string(), command)]),
+            ..Default::default()
+        };
+        let ctx = GeneratorContext::from_orb(&orb, "test-orb", "1.0.0").with_parameter_schemas();
+        assert_eq!(ctx.schemas.len(), 1);
+        let schema = &ctx.schemas[0];
+        assert_eq!(schema.uri, "orb://schemas/commands/greet");
+        assert_eq!(schema.kind, "command");
+        let schema: serde_json::Value = serde_json::from_str(&schema.json_content).unwrap();
+        assert_eq!(schema["title"], "Parameters of the greet command");
+        assert_eq!(schema["required"], json!(["who"]));
+        assert_eq!(schema["additionalProperties"], false);
+        assert_eq!(schema["properties"]["who"], json!({ "type": "string" }));
+        assert_eq!(
+            schema["properties"]["loud"],
+            json!({ "type": "boolean", "default": false })
+        );
+        assert_eq!(
+            schema["properties"]["mood"],
+            json!({ "type": "string", "enum": ["calm", "excited"], "default": "calm" })
+        );
+    }
+
+    #[test]
+    fn test_slugify() {
+        assert_eq!(slugify("greet"), "greet");
+        assert_eq!(slugify("deploy/prod.blue-green"), "deploy-prod-blue-green");
+        assert_eq!(slugify("RunTests"), "runtests");
+        assert_eq!(slugify("my_cmd"), "my_cmd");
+        assert_eq!(slugify("--weird..name--"), "weird-name");
+        assert_eq!(slugify("..."), "unnamed");
+    }
+
+    #[test]
+    fn test_unusual_names_produce_slugged_uris_and_keep_original_name() {
+        let mut orb = OrbDefinition::default();
+        orb.commands
+            .insert("deploy/prod.blue-green".to_string(), Command::default());
+
+        let ctx = GeneratorContext::from_orb(&orb, "test-orb", "1.0.0");
+        let cmd = &ctx.commands[0];
+
+        assert_eq!(cmd.uri, "orb://commands/deploy-prod-blue-green");
+        assert_eq!(cmd.name, "deploy/prod.blue-green");
+        assert!(cmd.json_content.contains("\"deploy/prod.blue-green\""));
+    }
+
+    #[test]
+    fn test_slug_collisions_are_disambiguated_deterministically() {
+        let mut orb = OrbDefinition::default();
+        for name in ["Build", "build", "BUILD"] {
+            orb.jobs
+                .insert(name.to_string(), crate::parser::Job::default());
+        }
+
+        let ctx = GeneratorContext::from_orb(&orb, "test-orb", "1.0.0");
+        let uri_of = |name: &str| {
+            ctx.jobs
+                .iter()
+                .find(|j| j.name == name)
+                .map(|j| j.uri.clone())
+                .unwrap()
+        };
+
+        // Sorted order: "BUILD" < "Build" < "build"
+        assert_eq!(uri_of("BUILD"), "orb://jobs/build");
+        assert_eq!(uri_of("Build"), "orb://jobs/build-2");
+        assert_eq!(uri_of("build"), "orb://jobs/build-3");
+    }
 }
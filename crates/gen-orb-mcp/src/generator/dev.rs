@@ -0,0 +1,270 @@
+//! Runtime orb loading for dev-mode servers.
+//!
+//! A server generated with a dev orb path (`generate --dev`) depends on this
+//! crate and calls [`DevOrb::load`] at startup, and again whenever
+//! [`last_modified`] changes when started with `--watch`. The definitions it
+//! reads replace those embedded at generation time, so orb authors see their
+//! edits without regenerating and recompiling the server.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+    time::SystemTime,
+};
+
+use super::{current_entries, CodeGenerator, GeneratorContext, GeneratorError};
+use crate::{lint::source::definition_sources, parser::OrbParser};
+
+/// URI prefixes of the current-version resources a [`DevOrb`] serves.
+const SERVED_PREFIXES: &[&str] = &[
+    "orb://commands/",
+    "orb://jobs/",
+    "orb://executors/",
+    "orb://examples/",
+    "orb://workflows/",
+    "orb://source/",
+    "orb://schemas/",
+];
+
+/// A resource listed by a dev-mode server.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevResource {
+    /// Resource URI, e.g. `orb://commands/greet`
+    pub uri: String,
+    /// Definition name
+    pub name: String,
+    /// Description shown in `resources/list`
+    pub description: String,
+    /// MIME type of the content
+    pub mime_type: &'static str,
+}
+
+/// The orb as read at runtime: the overview, the dependency graph, the
+/// definition resources and their content.
+#[derive(Debug, Clone, Default)]
+pub struct DevOrb {
+    overview: String,
+    graph: String,
+    resources: Vec<DevResource>,
+    contents: HashMap<String, String>,
+}
+
+impl DevOrb {
+    /// Parse the orb at `orb_path` and render its resources as a server for
+    /// `orb_name` at `version` serves them.
+    pub fn load(orb_path: &Path, orb_name: &str, version: &str) -> Result<Self, GeneratorError> {
+        let orb = OrbParser::parse(orb_path).map_err(|source| GeneratorError::OrbParse {
+            path: orb_path.to_path_buf(),
+            source,
+        })?;
+        let sources: HashMap<String, String> = definition_sources(orb_path).into_iter().collect();
+        let context = GeneratorContext::from_orb(&orb, orb_name, version)
+            .with_sources(&sources)
+            .with_parameter_schemas();
+        let ctx_json = serde_json::to_value(&context)
+            .map_err(|e| GeneratorError::Serialization { source: e })?;
+        let overview = CodeGenerator::new()?
+            .handlebars
+            .render("overview.md", &ctx_json)
+            .map_err(|e| GeneratorError::TemplateRender {
+                name: "overview.md".to_string(),
+                source: e,
+            })?;
+
+        let described = |label: &str, name: &str, description: &Option<String>| match description {
+            Some(description) => format!("{label}: {name} - {description}"),
+            None => format!("{label}: {name}"),
+        };
+        let mut resources = Vec::new();
+        for c in &context.commands {
+            resources.push((
+                &c.uri,
+                &c.name,
+                described("Command", &c.name, &c.description),
+            ));
+        }
+        for j in &context.jobs {
+            resources.push((&j.uri, &j.name, described("Job", &j.name, &j.description)));
+        }
+        for e in &context.executors {
+            resources.push((
+                &e.uri,
+                &e.name,
+                described("Executor", &e.name, &e.description),
+            ));
+        }
+        for e in &context.examples {
+            resources.push((
+                &e.uri,
+                &e.name,
+                described("Example", &e.name, &e.description),
+            ));
+        }
+        for w in &context.workflows {
+            resources.push((&w.uri, &w.name, described("Workflow", &w.name, &None)));
+        }
+        let mut resources: Vec<DevResource> = resources
+            .into_iter()
+            .map(|(uri, name, description)| DevResource {
+                uri: uri.clone(),
+                name: name.clone(),
+                description,
+                mime_type: "application/json",
+            })
+            .collect();
+        resources.extend(context.sources.iter().map(|s| DevResource {
+            uri: s.uri.clone(),
+            name: s.name.clone(),
+            description: format!(
+                "{} source: {} - YAML as written in the orb",
+                s.label, s.name
+            ),
+            mime_type: "application/yaml",
+        }));
+        resources.extend(context.schemas.iter().map(|s| DevResource {
+            uri: s.uri.clone(),
+            name: s.name.clone(),
+            description: format!(
+                "{} parameter schema: {} - JSON Schema of the parameters an invocation passes",
+                s.label, s.name
+            ),
+            mime_type: "application/schema+json",
+        }));
+
+        let contents = current_entries(&context)
+            .into_iter()
+            .map(|(uri, content)| (uri.to_string(), content.to_string()))
+            .collect();
+        Ok(Self {
+            overview,
+            graph: context.graph_content,
+            resources,
+            contents,
+        })
+    }
+
+    /// Markdown served at `orb://overview`.
+    pub fn overview(&self) -> &str {
+        &self.overview
+    }
+
+    /// JSON served at `orb://graph`.
+    pub fn graph(&self) -> &str {
+        &self.graph
+    }
+
+    /// The definition resources, in `resources/list` order.
+    pub fn resources(&self) -> &[DevResource] {
+        &self.resources
+    }
+
+    /// Content of the resource at `uri`, if the orb defines it.
+    pub fn get(&self, uri: &str) -> Option<&str> {
+        self.contents.get(uri).map(String::as_str)
+    }
+
+    /// Whether `uri` is a current-version definition resource, which a
+    /// loaded dev orb serves in place of the embedded data (so definitions
+    /// removed from the orb disappear).
+    pub fn serves(uri: &str) -> bool {
+        SERVED_PREFIXES.iter().any(|prefix| uri.starts_with(prefix))
+    }
+}
+
+/// Latest modification time of the files making up the orb at `orb_path`:
+/// the file itself for a packed orb, every file under the orb directory for
+/// an unpacked one. `None` when nothing can be read.
+pub fn last_modified(orb_path: &Path) -> Option<SystemTime> {
+    let orb_dir = if orb_path.is_dir() {
+        orb_path
+    } else if orb_path.file_name().is_some_and(|f| f == "@orb.yml") {
+        orb_path.parent().unwrap_or(orb_path)
+    } else {
+        return fs::metadata(orb_path).and_then(|m| m.modified()).ok();
+    };
+    let mut latest = None;
+    let mut dirs: Vec<PathBuf> = vec![orb_dir.to_path_buf()];
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.filter_map(Result::ok) {
+            let Ok(metadata) = entry.metadata() else {
+                continue;
+            };
+            if metadata.is_dir() {
+                dirs.push(entry.path());
+            } else if let Ok(modified) = metadata.modified() {
+                latest = latest.max(Some(modified));
+            }
+        }
+    }
+    latest
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_dev_orb_loads_definitions_and_tracks_edits() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("orb.yml");
+        fs::write(
+            &path,
+            "version: 2.1\ndescription: Greeter\ncommands:\n  greet:\n    description: Say hello\n    parameters:\n      to:\n        type: string\n    steps: []\n",
+        )
+        .unwrap();
+
+        let orb = DevOrb::load(&path, "test-orb", "1.0.0").unwrap();
+        assert!(orb.overview().contains("greet"));
+        assert!(orb.graph().contains("\"commands/greet\""));
+        assert!(orb
+            .get("orb://commands/greet")
+            .unwrap()
+            .contains("Say hello"));
+        assert!(orb.get("orb://schemas/commands/greet").is_some());
+        assert!(orb.get("orb://commands/farewell").is_none());
+        let greet = &orb.resources()[0];
+        assert_eq!(greet.uri, "orb://commands/greet");
+        assert_eq!(greet.description, "Command: greet - Say hello");
+        assert!(orb
+            .resources()
+            .iter()
+            .all(|resource| DevOrb::serves(&resource.uri)));
+        assert!(!DevOrb::serves("orb://overview"));
+        assert!(!DevOrb::serves("orb://v1.0.0/commands/greet"));
+
+        let before = last_modified(&path).unwrap();
+        let file = fs::File::options().append(true).open(&path).unwrap();
+        file.set_modified(before + Duration::from_secs(5)).unwrap();
+        assert!(last_modified(&path).unwrap() > before);
+
+        fs::write(&path, "version: 2.1\ncommands: [").unwrap();
+        let err = DevOrb::load(&path, "test-orb", "1.0.0").unwrap_err();
+        assert!(matches!(err, GeneratorError::OrbParse { .. }));
+    }
+
+    #[test]
+    fn test_last_modified_covers_unpacked_orb_files() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir(dir.path().join("commands")).unwrap();
+        fs::write(dir.path().join("@orb.yml"), "version: 2.1\n").unwrap();
+        let command = dir.path().join("commands/greet.yml");
+        fs::write(&command, "steps: []\n").unwrap();
+
+        let before = last_modified(&dir.path().join("@orb.yml")).unwrap();
+        let file = fs::File::options().append(true).open(&command).unwrap();
+        file.set_modified(before + Duration::from_secs(5)).unwrap();
+        assert_eq!(
+            last_modified(dir.path()),
+            Some(before + Duration::from_secs(5))
+        );
+        assert_eq!(last_modified(&dir.path().join("missing.yml")), None);
+    }
+}
@@ -0,0 +1,335 @@
+//! Embedded data of servers generated in languages other than Rust.
+//!
+//! The TypeScript and Python targets serve the same resources as the Rust
+//! server from a single data file: `serverInfo` values, every resource with
+//! its content, and a name-to-URI index of the definitions. This module
+//! builds that data from the [`GeneratorContext`]; each target serializes it
+//! as JSON in its own wrapper.
+
+use std::borrow::Cow;
+
+use serde::Serialize;
+use serde_json::json;
+
+use super::{
+    context::{self, CommandContext, ExampleContext, ExecutorContext, JobContext, WorkflowContext},
+    CodeGenerator, GeneratorContext, GeneratorError,
+};
+
+/// One embedded resource.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct EmbeddedResource<'a> {
+    uri: String,
+    name: String,
+    description: String,
+    mime_type: &'static str,
+    text: Cow<'a, str>,
+    /// Prior-version resources are readable but not listed.
+    listed: bool,
+}
+
+/// Name-to-URI entry of the definition index; the version is empty for the
+/// current release.
+#[derive(Serialize)]
+pub(super) struct Definition<'a> {
+    version: &'a str,
+    kind: &'static str,
+    name: &'a str,
+    uri: &'a str,
+}
+
+/// `serverInfo` values.
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ServerData<'a> {
+    name: String,
+    title: Option<&'a str>,
+    version: &'a str,
+    orb: &'a str,
+    instructions: String,
+    source_rev: Option<&'a str>,
+}
+
+/// Everything a server embeds.
+#[derive(Serialize)]
+pub(super) struct EmbeddedData<'a> {
+    pub(super) server: ServerData<'a>,
+    pub(super) resources: Vec<EmbeddedResource<'a>>,
+    pub(super) definitions: Vec<Definition<'a>>,
+}
+
+/// Build the embedded data for `context`, with `overview` served at
+/// `orb://overview`.
+pub(super) fn data<'a>(
+    generator: &'a CodeGenerator<'_>,
+    context: &'a GeneratorContext,
+    overview: String,
+) -> Result<EmbeddedData<'a>, GeneratorError> {
+    let orb_name = context.orb_name.as_str();
+    let scheme = context.uri_scheme.as_str();
+    let server_info = to_json(&json!({
+        "name": context.package_name,
+        "orb": orb_name,
+        "version": context.version,
+        "source_rev": generator.source_rev,
+    }))?;
+
+    let resource = |uri: &str,
+                    name: String,
+                    description: String,
+                    mime_type: &'static str,
+                    text: Cow<'a, str>| EmbeddedResource {
+        uri: uri.to_string(),
+        name,
+        description,
+        mime_type,
+        text,
+        listed: true,
+    };
+    let mut resources = vec![
+        resource(
+            &format!("{scheme}://overview"),
+            format!("{orb_name} Overview"),
+            format!("Complete overview of the {orb_name} CircleCI orb"),
+            "text/markdown",
+            Cow::Owned(overview),
+        ),
+        resource(
+            &format!("{scheme}://server-info"),
+            format!("{orb_name} Server Info"),
+            "Server name, version and the orb source revision it was generated from".to_string(),
+            "application/json",
+            Cow::Owned(server_info),
+        ),
+        resource(
+            &format!("{scheme}://graph"),
+            format!("{orb_name} Dependency Graph"),
+            "Which commands each job and command invokes and which executor each job runs on"
+                .to_string(),
+            "application/json",
+            Cow::Borrowed(context.graph_content.as_str()),
+        ),
+    ];
+    let mut definitions = Vec::new();
+    add_definitions(
+        &mut resources,
+        &mut definitions,
+        "",
+        entries(
+            &context.commands,
+            &context.jobs,
+            &context.executors,
+            &context.examples,
+            &context.workflows,
+        ),
+        true,
+    );
+    for source in &context.sources {
+        resources.push(resource(
+            &source.uri,
+            source.name.clone(),
+            format!(
+                "{} source: {} - YAML as written in the orb",
+                source.label, source.name
+            ),
+            "application/yaml",
+            Cow::Borrowed(source.yaml.as_str()),
+        ));
+    }
+    for schema in &context.schemas {
+        resources.push(resource(
+            &schema.uri,
+            schema.name.clone(),
+            format!(
+                "{} parameter schema: {} - JSON Schema of the parameters an invocation passes",
+                schema.label, schema.name
+            ),
+            "application/schema+json",
+            Cow::Borrowed(schema.json_content.as_str()),
+        ));
+    }
+    if context.has_changes {
+        resources.push(resource(
+            &format!("{scheme}://changes"),
+            format!("{orb_name} Changes"),
+            format!(
+                "What changed in this release of the {orb_name} orb compared with the previous one"
+            ),
+            "text/markdown",
+            Cow::Borrowed(context.changes_content.as_str()),
+        ));
+    }
+    if context.has_comparison {
+        resources.push(resource(
+            &format!("{scheme}://compare"),
+            format!("{orb_name} Version Comparison"),
+            "Matrix of the commands, jobs and executors defined in each embedded version"
+                .to_string(),
+            "text/markdown",
+            Cow::Borrowed(context.comparison_content.as_str()),
+        ));
+    }
+    if context.has_prior_versions {
+        resources.push(resource(
+            &format!("{scheme}://versions"),
+            "Orb Versions".to_string(),
+            "List of all embedded orb versions with their resource URIs".to_string(),
+            "text/plain",
+            Cow::Owned(versions_content(context)),
+        ));
+    }
+    for snapshot in &context.prior_versions {
+        add_definitions(
+            &mut resources,
+            &mut definitions,
+            &snapshot.version,
+            entries(
+                &snapshot.commands,
+                &snapshot.jobs,
+                &snapshot.executors,
+                &[],
+                &[],
+            ),
+            false,
+        );
+    }
+
+    let server = ServerData {
+        name: context.package_name.clone(),
+        title: generator.server_title.as_deref(),
+        version: &context.version,
+        orb: orb_name,
+        instructions: generator
+            .server_instructions
+            .clone()
+            .unwrap_or_else(|| context::default_instructions(orb_name)),
+        source_rev: generator.source_rev.as_deref(),
+    };
+    Ok(EmbeddedData {
+        server,
+        resources,
+        definitions,
+    })
+}
+
+/// A command, job, executor, example or workflow to embed.
+struct Entry<'a> {
+    kind: &'static str,
+    name: &'a str,
+    description: Option<&'a str>,
+    uri: &'a str,
+    json_content: &'a str,
+}
+
+/// Every definition of one version, in kind order.
+fn entries<'a>(
+    commands: &'a [CommandContext],
+    jobs: &'a [JobContext],
+    executors: &'a [ExecutorContext],
+    examples: &'a [ExampleContext],
+    workflows: &'a [WorkflowContext],
+) -> impl Iterator<Item = Entry<'a>> {
+    let commands = commands.iter().map(|c| Entry {
+        kind: "command",
+        name: &c.name,
+        description: c.description.as_deref(),
+        uri: &c.uri,
+        json_content: &c.json_content,
+    });
+    let jobs = jobs.iter().map(|j| Entry {
+        kind: "job",
+        name: &j.name,
+        description: j.description.as_deref(),
+        uri: &j.uri,
+        json_content: &j.json_content,
+    });
+    let executors = executors.iter().map(|e| Entry {
+        kind: "executor",
+        name: &e.name,
+        description: e.description.as_deref(),
+        uri: &e.uri,
+        json_content: &e.json_content,
+    });
+    let examples = examples.iter().map(|e| Entry {
+        kind: "example",
+        name: &e.name,
+        description: e.description.as_deref(),
+        uri: &e.uri,
+        json_content: &e.json_content,
+    });
+    let workflows = workflows.iter().map(|w| Entry {
+        kind: "workflow",
+        name: &w.name,
+        description: None,
+        uri: &w.uri,
+        json_content: &w.json_content,
+    });
+    commands
+        .chain(jobs)
+        .chain(executors)
+        .chain(examples)
+        .chain(workflows)
+}
+
+fn add_definitions<'a>(
+    resources: &mut Vec<EmbeddedResource<'a>>,
+    definitions: &mut Vec<Definition<'a>>,
+    version: &'a str,
+    entries: impl Iterator<Item = Entry<'a>>,
+    listed: bool,
+) {
+    for entry in entries {
+        let label = match entry.kind {
+            "command" => "Command",
+            "job" => "Job",
+            "executor" => "Executor",
+            "example" => "Example",
+            _ => "Workflow",
+        };
+        // Same single-line description as the Rust server's resource list.
+        let description = match entry.description {
+            Some(d) => format!(
+                "{label}: {} - {}",
+                entry.name,
+                d.replace('\n', " ").replace('\r', "")
+            ),
+            None => format!("{label}: {}", entry.name),
+        };
+        resources.push(EmbeddedResource {
+            uri: entry.uri.to_string(),
+            name: entry.name.to_string(),
+            description,
+            mime_type: "application/json",
+            text: Cow::Borrowed(entry.json_content),
+            listed,
+        });
+        definitions.push(Definition {
+            version,
+            kind: entry.kind,
+            name: entry.name,
+            uri: entry.uri,
+        });
+    }
+}
+
+/// Text served at `orb://versions`, matching the Rust server.
+fn versions_content(context: &GeneratorContext) -> String {
+    let s = &context.uri_scheme;
+    let mut text = format!(
+        "{} embedded versions.\n\nTo read a resource: request its URI via resources/read.\n\n\
+         Versions:\n- current: {s}://commands/<name>, {s}://jobs/<name>, {s}://executors/<name>\n",
+        context.orb_name
+    );
+    for snapshot in &context.prior_versions {
+        let v = &snapshot.version;
+        text.push_str(&format!(
+            "- {v}: {s}://v{v}/commands/<name>, {s}://v{v}/jobs/<name>, {s}://v{v}/executors/<name>\n"
+        ));
+    }
+    text
+}
+
+pub(super) fn to_json(value: &impl Serialize) -> Result<String, GeneratorError> {
+    serde_json::to_string_pretty(value).map_err(|e| GeneratorError::Serialization { source: e })
+}
@@ -42,6 +42,14 @@ pub enum GeneratorError {
         source: std::io::Error,
     },
 
+    /// Failed to read an existing output file.
+    #[error("failed to read file '{path}': {source}")]
+    FileRead {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
     /// Failed to create output directory.
     #[error("failed to create directory '{path}': {source}")]
     DirectoryCreate {
@@ -58,7 +66,60 @@ pub enum GeneratorError {
     #[error("clippy failed: {message}")]
     ClippyFailed { message: String },
 
+    /// A sandboxed server would need filesystem, network or environment
+    /// access.
+    #[error("generated server is not sandbox-safe: {reason}")]
+    SandboxViolation { reason: String },
+
+    /// The logo is not a supported image.
+    #[error("invalid logo: {reason}")]
+    InvalidLogo { reason: String },
+
+    /// An option that only applies to another target language.
+    #[error("{option} is not supported when generating {language}")]
+    UnsupportedOption {
+        language: super::Language,
+        option: String,
+    },
+
+    /// An option that only applies to a single-orb server.
+    #[error("{option} cannot be combined with multiple orbs")]
+    MultiOrbOption { option: String },
+
+    /// A template override for a template that cannot be overridden.
+    #[error("template '{name}' cannot be overridden; only main.rs.hbs, lib.rs.hbs and Cargo.toml.hbs can")]
+    UnknownTemplate { name: String },
+
+    /// Invalid definition filter pattern.
+    #[error("invalid pattern '{pattern}': {source}")]
+    InvalidPattern {
+        pattern: String,
+        #[source]
+        source: glob::PatternError,
+    },
+
     /// Invalid orb name.
     #[error("invalid orb name '{name}': {reason}")]
     InvalidOrbName { name: String, reason: String },
+
+    /// Invalid name prefix, crate suffix or URI scheme.
+    #[error("invalid {option} '{value}': {reason}")]
+    InvalidNaming {
+        option: String,
+        value: String,
+        reason: String,
+    },
+
+    /// An option a dev-mode server cannot honour, as it serves the whole
+    /// orb read at runtime.
+    #[error("{option} cannot be combined with dev mode")]
+    DevModeOption { option: String },
+
+    /// Failed to parse the orb a dev-mode server reads at runtime.
+    #[error("failed to parse orb '{path}': {source}")]
+    OrbParse {
+        path: PathBuf,
+        #[source]
+        source: crate::parser::ParseError,
+    },
 }
@@ -0,0 +1,86 @@
+//! Target languages for generated MCP servers.
+//!
+//! Each language brings its own templates, manifest and file layout; the
+//! [`GeneratorContext`](super::GeneratorContext) built from the orb is shared.
+//! Rust is the reference target. TypeScript servers use the official
+//! `@modelcontextprotocol/sdk` package for teams whose MCP tooling is
+//! Node-based, and Python servers the official `mcp` package for teams
+//! standardized on Python; both support a subset of the generator options.
+
+use std::fmt;
+
+/// Language of the generated MCP server.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    clap::ValueEnum,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Language {
+    /// Rust crate using `rmcp` (can be compiled with `--format binary`)
+    #[default]
+    Rust,
+    /// Node package using the TypeScript MCP SDK
+    #[value(name = "typescript")]
+    #[serde(rename = "typescript")]
+    TypeScript,
+    /// Python package using the Python MCP SDK
+    Python,
+}
+
+impl Language {
+    /// Manifest file at the root of the generated output.
+    pub fn manifest_file(self) -> &'static str {
+        match self {
+            Language::Rust => "Cargo.toml",
+            Language::TypeScript => "package.json",
+            Language::Python => "pyproject.toml",
+        }
+    }
+
+    /// Command that builds the generated output, run from its root.
+    pub fn build_command(self) -> &'static str {
+        match self {
+            Language::Rust => "cargo build --release",
+            Language::TypeScript => "npm install && npm run build",
+            Language::Python => "pip install .",
+        }
+    }
+}
+
+impl fmt::Display for Language {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Language::Rust => "rust",
+            Language::TypeScript => "typescript",
+            Language::Python => "python",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::*;
+
+    #[test]
+    fn test_language_names_match_cli_values() {
+        for language in Language::value_variants() {
+            let value = language.to_possible_value().unwrap();
+            assert_eq!(value.get_name(), language.to_string());
+            assert_eq!(
+                serde_json::to_string(language).unwrap(),
+                format!("\"{language}\"")
+            );
+        }
+        assert_eq!(Language::TypeScript.manifest_file(), "package.json");
+        assert_eq!(Language::Python.manifest_file(), "pyproject.toml");
+    }
+}
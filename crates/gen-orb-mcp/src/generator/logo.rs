@@ -0,0 +1,169 @@
+//! Orb logos embedded in generated servers.
+//!
+//! A logo is served as the binary resource `orb://logo` and advertised as the
+//! server icon, so MCP clients that render icons can show a branded server.
+//! The image format is detected from the file contents rather than its name,
+//! since logos downloaded from a URL often have no useful extension.
+
+use super::GeneratorError;
+
+/// Largest logo that will be embedded. Icons are sent inline as data URIs,
+/// so anything bigger bloats every `initialize` response.
+pub const MAX_LOGO_BYTES: usize = 1024 * 1024;
+
+/// An image to embed as the orb logo.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Logo {
+    bytes: Vec<u8>,
+    format: LogoFormat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogoFormat {
+    Png,
+    Jpeg,
+    Gif,
+    Webp,
+    Ico,
+    Svg,
+}
+
+impl Logo {
+    /// Detect the image format of `bytes`.
+    ///
+    /// PNG, JPEG, GIF, WebP, ICO and SVG are accepted.
+    pub fn from_bytes(bytes: Vec<u8>) -> Result<Self, GeneratorError> {
+        if bytes.len() > MAX_LOGO_BYTES {
+            return Err(GeneratorError::InvalidLogo {
+                reason: format!(
+                    "{} bytes exceeds the {MAX_LOGO_BYTES} byte limit",
+                    bytes.len()
+                ),
+            });
+        }
+        let format = sniff(&bytes).ok_or_else(|| GeneratorError::InvalidLogo {
+            reason: "not a PNG, JPEG, GIF, WebP, ICO or SVG image".to_string(),
+        })?;
+        Ok(Self { bytes, format })
+    }
+
+    /// The image data.
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    /// MIME type of the image (e.g. `image/png`).
+    pub fn mime_type(&self) -> &'static str {
+        match self.format {
+            LogoFormat::Png => "image/png",
+            LogoFormat::Jpeg => "image/jpeg",
+            LogoFormat::Gif => "image/gif",
+            LogoFormat::Webp => "image/webp",
+            LogoFormat::Ico => "image/x-icon",
+            LogoFormat::Svg => "image/svg+xml",
+        }
+    }
+
+    /// File name used inside the generated crate (e.g. `logo.png`).
+    pub fn file_name(&self) -> &'static str {
+        match self.format {
+            LogoFormat::Png => "logo.png",
+            LogoFormat::Jpeg => "logo.jpg",
+            LogoFormat::Gif => "logo.gif",
+            LogoFormat::Webp => "logo.webp",
+            LogoFormat::Ico => "logo.ico",
+            LogoFormat::Svg => "logo.svg",
+        }
+    }
+
+    /// Icon size in MCP `WxH` form: `any` for SVG, the header dimensions for
+    /// PNG, `None` when unknown.
+    pub fn size(&self) -> Option<String> {
+        match self.format {
+            LogoFormat::Svg => Some("any".to_string()),
+            LogoFormat::Png => {
+                let dimension = |at: usize| {
+                    self.bytes
+                        .get(at..at + 4)
+                        .map(|b| u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+                };
+                Some(format!("{}x{}", dimension(16)?, dimension(20)?))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn sniff(bytes: &[u8]) -> Option<LogoFormat> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some(LogoFormat::Png)
+    } else if bytes.starts_with(&[0xff, 0xd8, 0xff]) {
+        Some(LogoFormat::Jpeg)
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        Some(LogoFormat::Gif)
+    } else if bytes.starts_with(b"RIFF") && bytes.get(8..12) == Some(b"WEBP") {
+        Some(LogoFormat::Webp)
+    } else if bytes.starts_with(&[0, 0, 1, 0]) {
+        Some(LogoFormat::Ico)
+    } else if is_svg(bytes) {
+        Some(LogoFormat::Svg)
+    } else {
+        None
+    }
+}
+
+/// Whether `bytes` is UTF-8 text whose root element is `<svg`.
+fn is_svg(bytes: &[u8]) -> bool {
+    let Ok(text) = std::str::from_utf8(bytes) else {
+        return false;
+    };
+    let mut rest = text.trim_start_matches('\u{feff}').trim_start();
+    // Skip the XML declaration, comments and doctype before the root element.
+    while let Some(tail) = rest.strip_prefix("<?").or_else(|| rest.strip_prefix("<!")) {
+        let end = if tail.starts_with("--") { "-->" } else { ">" };
+        let Some(close) = tail.find(end) else {
+            return false;
+        };
+        rest = tail[close + end.len()..].trim_start();
+    }
+    rest.starts_with("<svg")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A 2x3 PNG header (contents beyond IHDR are irrelevant to sniffing).
+    const PNG: &[u8] = b"\x89PNG\r\n\x1a\n\0\0\0\rIHDR\0\0\0\x02\0\0\0\x03\x08\x06\0\0\0";
+
+    #[test]
+    fn test_from_bytes_detects_format_and_size() {
+        let logo = Logo::from_bytes(PNG.to_vec()).unwrap();
+        assert_eq!(logo.mime_type(), "image/png");
+        assert_eq!(logo.file_name(), "logo.png");
+        assert_eq!(logo.size().as_deref(), Some("2x3"));
+
+        let svg =
+            b"<?xml version=\"1.0\"?>\n<!-- mark -->\n<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+        let logo = Logo::from_bytes(svg.to_vec()).unwrap();
+        assert_eq!(logo.mime_type(), "image/svg+xml");
+        assert_eq!(logo.size().as_deref(), Some("any"));
+
+        let logo = Logo::from_bytes(b"RIFF\0\0\0\0WEBPVP8 ".to_vec()).unwrap();
+        assert_eq!(logo.mime_type(), "image/webp");
+        assert_eq!(logo.size(), None);
+    }
+
+    #[test]
+    fn test_from_bytes_rejects_unknown_and_oversized() {
+        let err = Logo::from_bytes(b"<html></html>".to_vec()).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid logo: not a PNG, JPEG, GIF, WebP, ICO or SVG image"
+        );
+
+        let mut big = PNG.to_vec();
+        big.resize(MAX_LOGO_BYTES + 1, 0);
+        assert!(Logo::from_bytes(big).is_err());
+    }
+}
@@ -1,30 +1,47 @@
 //! Code generator module for creating MCP servers from orb definitions.
 //!
 //! This module transforms a parsed `OrbDefinition` into a working MCP server
-//! by rendering Handlebars templates to produce Rust source code.
+//! by rendering Handlebars templates to produce Rust source code, or a
+//! TypeScript or Python package (see [`Language`]).
 //!
 //! # Example
 //!
 //! ```no_run
 //! use std::path::Path;
 //!
-//! use gen_orb_mcp::{generator::CodeGenerator, parser::OrbParser};
+//! use gen_orb_mcp::{
+//!     generator::{generate_in_memory, GenerateOptions},
+//!     parser::OrbParser,
+//! };
 //!
 //! let orb = OrbParser::parse(Path::new("./src/@orb.yml")).unwrap();
-//! let generator = CodeGenerator::new().unwrap();
-//! let server = generator.generate(&orb, "my-orb", "1.0.0").unwrap();
+//! let server = generate_in_memory(&orb, &GenerateOptions::new("my-orb", "1.0.0")).unwrap();
 //!
 //! // Write to output directory
 //! server.write_to(Path::new("./dist")).unwrap();
 //! ```
 
+pub mod changes;
+pub mod clients;
 pub mod context;
+pub mod dev;
+mod embedded;
 pub mod error;
+pub mod language;
+pub mod logo;
+pub mod naming;
+pub mod options;
+mod python;
+mod sandbox;
+mod tarball;
 pub mod templates;
+pub mod transport;
+mod typescript;
 
 use std::{
-    collections::HashMap,
+    collections::{BTreeMap, HashMap},
     fs,
+    net::SocketAddr,
     path::{Path, PathBuf},
     process::Command,
 };
@@ -32,8 +49,148 @@ use std::{
 pub use context::GeneratorContext;
 pub use error::GeneratorError;
 use handlebars::Handlebars;
+pub use language::Language;
+pub use logo::Logo;
+pub use naming::Naming;
+pub use options::{generate_in_memory, GenerateOptions};
+pub use transport::Transport;
+
+use crate::parser::{
+    ExecutorRef, OrbDefinition, Parameter, ParameterType, Step, StepList, StructuredStep,
+};
+
+/// A category of orb definitions that can be exposed by a generated server.
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum, serde::Serialize, serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum DefinitionCategory {
+    /// Reusable commands
+    Commands,
+    /// Jobs
+    Jobs,
+    /// Executors
+    Executors,
+    /// Usage examples
+    Examples,
+    /// Workflows
+    Workflows,
+}
+
+impl DefinitionCategory {
+    /// The category as written in `--only` and in resource URIs.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            DefinitionCategory::Commands => "commands",
+            DefinitionCategory::Jobs => "jobs",
+            DefinitionCategory::Executors => "executors",
+            DefinitionCategory::Examples => "examples",
+            DefinitionCategory::Workflows => "workflows",
+        }
+    }
+}
+
+/// Package format of a binstall download (`pkg-fmt`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BinstallPkgFmt {
+    /// Gzipped tarball
+    Tgz,
+    /// Uncompressed tarball
+    Tar,
+    /// Bzip2 tarball
+    Tbz2,
+    /// Xz tarball
+    Txz,
+    /// Zstd tarball
+    Tzstd,
+    /// Zip archive
+    Zip,
+    /// Bare executable, as uploaded by `publish`
+    #[default]
+    Bin,
+}
+
+impl BinstallPkgFmt {
+    /// The value cargo-binstall expects for `pkg-fmt`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BinstallPkgFmt::Tgz => "tgz",
+            BinstallPkgFmt::Tar => "tar",
+            BinstallPkgFmt::Tbz2 => "tbz2",
+            BinstallPkgFmt::Txz => "txz",
+            BinstallPkgFmt::Tzstd => "tzstd",
+            BinstallPkgFmt::Zip => "zip",
+            BinstallPkgFmt::Bin => "bin",
+        }
+    }
+}
+
+/// `[package.metadata.binstall]` settings for the generated Cargo.toml.
+///
+/// URL and directory values are cargo-binstall templates such as
+/// `https://artifacts.example.com/{ name }/{ version }/{ name }-{ target }`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BinstallMetadata {
+    /// Download URL template (`pkg-url`).
+    pub pkg_url: String,
+    /// Package format (`pkg-fmt`).
+    pub pkg_fmt: BinstallPkgFmt,
+    /// Path of the binary inside an archive (`bin-dir`).
+    pub bin_dir: Option<String>,
+    /// Download URL templates for particular target triples
+    /// (`overrides.<target>.pkg-url`).
+    pub overrides: BTreeMap<String, String>,
+}
 
-use crate::parser::OrbDefinition;
+/// Targets `publish` names release assets for, as the target triple and the
+/// asset suffix (`<os>-<arch>`) of a binary built on it.
+const RELEASE_TARGETS: [(&str, &str); 5] = [
+    ("x86_64-unknown-linux-gnu", "linux-x86_64"),
+    ("aarch64-unknown-linux-gnu", "linux-aarch64"),
+    ("x86_64-apple-darwin", "macos-x86_64"),
+    ("aarch64-apple-darwin", "macos-aarch64"),
+    ("x86_64-pc-windows-msvc", "windows-x86_64.exe"),
+];
+
+impl BinstallMetadata {
+    /// Metadata finding the bare executables `publish` attaches to the
+    /// GitHub release `<tag_prefix><version>` of `repo`.
+    ///
+    /// `publish` names assets `<crate>-<os>-<arch>`, so each target it
+    /// builds for gets an override; other targets look for
+    /// `<crate>-<target>`.
+    pub fn github_release(repo: &str, tag_prefix: &str) -> Self {
+        let base = format!(
+            "{}/releases/download/{tag_prefix}{{ version }}",
+            repo.trim_end_matches('/')
+        );
+        Self {
+            pkg_url: format!("{base}/{{ name }}-{{ target }}{{ binary-ext }}"),
+            pkg_fmt: BinstallPkgFmt::Bin,
+            bin_dir: None,
+            overrides: RELEASE_TARGETS
+                .iter()
+                .map(|(target, suffix)| (target.to_string(), format!("{base}/{{ name }}-{suffix}")))
+                .collect(),
+        }
+    }
+}
+
+/// `[package]` fields that make the generated crate publishable to a
+/// registry, in addition to its license.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageMetadata {
+    /// `description`, replacing the orb's description.
+    pub description: Option<String>,
+    /// `repository` URL.
+    pub repository: Option<String>,
+    /// `keywords`; crates.io accepts at most five.
+    pub keywords: Vec<String>,
+    /// Registries the crate may be published to (`publish`); empty allows
+    /// any.
+    pub registries: Vec<String>,
+}
 
 /// Generated MCP server output containing all source files.
 #[derive(Debug, Clone)]
@@ -54,6 +211,51 @@ pub struct GeneratedServer {
 }
 
 impl GeneratedServer {
+    /// Pack every generated file into a gzip-compressed tar archive, under a
+    /// `<crate_name>/` directory and in path order.
+    ///
+    /// Entries carry fixed modes and timestamps, so identical output packs
+    /// into identical archives.
+    pub fn into_tarball(self) -> Vec<u8> {
+        let mut entries: Vec<(PathBuf, Vec<u8>)> = self
+            .files
+            .into_iter()
+            .map(|(path, content)| (path, content.into_bytes()))
+            .chain(self.binary_files)
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(&b.0));
+        tarball::tar_gz(entries.iter().map(|(path, content)| {
+            let path = path.to_string_lossy().replace('\\', "/");
+            (format!("{}/{path}", self.crate_name), content.as_slice())
+        }))
+    }
+
+    /// SHA-256 over every generated file (paths and contents, in path
+    /// order), as a hex string.
+    ///
+    /// Identical output hashes identically, which lets `generate
+    /// --binary-cache` skip rebuilding unchanged servers.
+    pub fn content_hash(&self) -> String {
+        use sha2::{Digest, Sha256};
+
+        let mut entries: Vec<(&PathBuf, &[u8])> = self
+            .files
+            .iter()
+            .map(|(path, content)| (path, content.as_bytes()))
+            .chain(self.binary_files.iter().map(|(p, c)| (p, c.as_slice())))
+            .collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+
+        let mut hasher = Sha256::new();
+        for (path, content) in entries {
+            hasher.update(path.to_string_lossy().as_bytes());
+            hasher.update([0]);
+            hasher.update((content.len() as u64).to_le_bytes());
+            hasher.update(content);
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
     /// Write all generated files to the specified output directory.
     ///
     /// Creates the directory structure if it doesn't exist.
@@ -109,38 +311,144 @@ impl GeneratedServer {
         Ok(())
     }
 
-    /// Format the generated Rust files using rustfmt.
+    /// Unified diff of `output_dir` against the generated files, as
+    /// `write_to` would change it.
     ///
-    /// This modifies the files in-place within the GeneratedServer.
-    pub fn format(&mut self, output_dir: &Path) -> Result<(), GeneratorError> {
-        // Write files first so rustfmt can process them
-        self.write_to(output_dir)?;
+    /// Files that do not exist yet are diffed against `/dev/null`; changed
+    /// binary files are reported by name only. Files `write_to` would leave
+    /// alone are not listed. Returns an empty string when writing would
+    /// change nothing.
+    pub fn diff_against(&self, output_dir: &Path) -> Result<String, GeneratorError> {
+        let read = |rel_path: &Path| {
+            let full_path = output_dir.join(rel_path);
+            match fs::read(&full_path) {
+                Ok(bytes) => Ok(Some(bytes)),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+                Err(e) => Err(GeneratorError::FileRead {
+                    path: full_path,
+                    source: e,
+                }),
+            }
+        };
 
-        // Collect Rust file paths first to avoid borrow issues
-        let rs_files: Vec<PathBuf> = self
-            .files
-            .keys()
-            .filter(|p| p.extension().is_some_and(|ext| ext == "rs"))
-            .cloned()
-            .collect();
+        let mut paths: Vec<&PathBuf> = self.files.keys().chain(self.binary_files.keys()).collect();
+        paths.sort();
+        let mut diff = String::new();
+        for rel_path in paths {
+            let existing = read(rel_path)?;
+            let old_name = match existing {
+                Some(_) => format!("a/{}", rel_path.display()),
+                None => "/dev/null".to_string(),
+            };
+            let new_name = format!("b/{}", rel_path.display());
+            if let Some(content) = self.files.get(rel_path) {
+                let old = existing
+                    .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+                    .unwrap_or_default();
+                if old == *content && old_name != "/dev/null" {
+                    continue;
+                }
+                diff.push_str(
+                    &similar::TextDiff::from_lines(old.as_str(), content.as_str())
+                        .unified_diff()
+                        .header(&old_name, &new_name)
+                        .to_string(),
+                );
+            } else if existing.as_deref() != Some(self.binary_files[rel_path].as_slice()) {
+                diff.push_str(&format!("Binary files {old_name} and {new_name} differ\n"));
+            }
+        }
+        Ok(diff)
+    }
 
-        // Run rustfmt on each Rust file
-        for rel_path in rs_files {
-            let full_path = output_dir.join(&rel_path);
-            run_rustfmt(&full_path)?;
+    /// Format the generated Rust files using rustfmt, in memory.
+    ///
+    /// Each `.rs` file is piped through `rustfmt` and replaced by its output.
+    /// Files rustfmt rejects are kept as rendered, with a warning, and when
+    /// rustfmt is not installed nothing is formatted.
+    pub fn format_sources(&mut self) -> Result<(), GeneratorError> {
+        for (rel_path, content) in &mut self.files {
+            if rel_path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            match run_rustfmt(rel_path, content)? {
+                Rustfmt::Formatted(formatted) => *content = formatted,
+                Rustfmt::Rejected => {}
+                Rustfmt::Missing => return Ok(()),
+            }
+        }
+        Ok(())
+    }
 
-            // Read back the formatted content
-            let formatted =
-                fs::read_to_string(&full_path).map_err(|e| GeneratorError::FileWrite {
-                    path: full_path.clone(),
-                    source: e,
-                })?;
+    /// Write all files to `output_dir`, apply `cargo clippy --fix` there and
+    /// read the fixed Rust files back.
+    ///
+    /// `cargo` is the cargo command to run in `output_dir`, with any
+    /// environment (target directory, compiler wrapper) already applied.
+    /// Clippy failures, such as a crate that does not compile, are logged and
+    /// leave the files unfixed.
+    pub fn clippy_fix(&mut self, output_dir: &Path, cargo: Command) -> Result<(), GeneratorError> {
+        self.write_to(output_dir)?;
+        run_clippy_fix(cargo)?;
 
-            self.files.insert(rel_path, formatted);
+        for (rel_path, content) in &mut self.files {
+            if rel_path.extension().is_none_or(|ext| ext != "rs") {
+                continue;
+            }
+            let full_path = output_dir.join(rel_path);
+            *content = fs::read_to_string(&full_path).map_err(|e| GeneratorError::FileRead {
+                path: full_path.clone(),
+                source: e,
+            })?;
         }
-
         Ok(())
     }
+
+    /// Format the generated Rust files using rustfmt and write all files to
+    /// `output_dir`.
+    ///
+    /// This modifies the files in-place within the GeneratedServer.
+    pub fn format(&mut self, output_dir: &Path) -> Result<(), GeneratorError> {
+        self.format_sources()?;
+        self.write_to(output_dir)
+    }
+}
+
+/// Read the overridable templates present in `dir`, as `(file name, path,
+/// source)`, warning about other templates there.
+fn read_template_overrides(
+    dir: &Path,
+) -> Result<Vec<(&'static str, PathBuf, String)>, GeneratorError> {
+    let entries = fs::read_dir(dir).map_err(|e| GeneratorError::FileRead {
+        path: dir.to_path_buf(),
+        source: e,
+    })?;
+    for entry in entries.flatten() {
+        let file_name = entry.file_name();
+        let file_name = file_name.to_string_lossy();
+        let overridable = templates::OVERRIDABLE
+            .iter()
+            .any(|(file, _)| *file == file_name);
+        if file_name.ends_with(".hbs") && !overridable {
+            tracing::warn!(
+                template = %entry.path().display(),
+                "Only main.rs.hbs, lib.rs.hbs and Cargo.toml.hbs can be overridden; ignoring"
+            );
+        }
+    }
+    let mut overrides = Vec::new();
+    for (file, _) in templates::OVERRIDABLE {
+        let path = dir.join(file);
+        if !path.is_file() {
+            continue;
+        }
+        let source = fs::read_to_string(&path).map_err(|e| GeneratorError::FileRead {
+            path: path.clone(),
+            source: e,
+        })?;
+        overrides.push((*file, path, source));
+    }
+    Ok(overrides)
 }
 
 /// Code generator that transforms orb definitions into MCP server source code.
@@ -149,6 +457,32 @@ pub struct CodeGenerator<'a> {
     handlebars: Handlebars<'a>,
     prior_versions: Vec<(String, OrbDefinition)>,
     conformance_rules_json: Option<String>,
+    server_title: Option<String>,
+    server_instructions: Option<String>,
+    previous_orb: Option<(String, OrbDefinition)>,
+    imported_orbs: Vec<(String, OrbDefinition)>,
+    sources: HashMap<String, String>,
+    template_overrides: Vec<PathBuf>,
+    categories: Vec<DefinitionCategory>,
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+    strict_names: bool,
+    source_rev: Option<String>,
+    property_tests: bool,
+    license: Option<String>,
+    license_text: Option<String>,
+    binstall: Option<BinstallMetadata>,
+    package: Option<PackageMetadata>,
+    max_resource_bytes: Option<usize>,
+    sandboxed: bool,
+    logo: Option<Logo>,
+    language: Language,
+    transport: Transport,
+    http_bind: SocketAddr,
+    http_compression: bool,
+    http_body_limit: Option<usize>,
+    dev_orb_path: Option<PathBuf>,
+    naming: Naming,
 }
 
 impl<'a> CodeGenerator<'a> {
@@ -171,6 +505,260 @@ impl<'a> CodeGenerator<'a> {
         self
     }
 
+    /// Set the friendly title advertised in the generated server's
+    /// `serverInfo` (e.g. "Acme CI Toolkit").
+    pub fn with_server_title(mut self, title: impl Into<String>) -> Self {
+        self.server_title = Some(title.into());
+        self
+    }
+
+    /// Set the usage guidance returned in the generated server's
+    /// `instructions` field.
+    pub fn with_server_instructions(mut self, instructions: impl Into<String>) -> Self {
+        self.server_instructions = Some(instructions.into());
+        self
+    }
+
+    /// Set the previous orb release to summarise in the `orb://changes`
+    /// resource.
+    ///
+    /// When unset, the newest prior-version snapshot (if any) is used instead.
+    pub fn with_previous_orb(mut self, label: impl Into<String>, orb: OrbDefinition) -> Self {
+        self.previous_orb = Some((label.into(), orb));
+        self
+    }
+
+    /// Set the orbs the orb imports, keyed by their `orbs:` alias.
+    ///
+    /// Their commands, jobs and executors are named `<alias>/<name>`, as the
+    /// orb invokes them, and served at `orb://<alias>/commands/<name>` and so
+    /// on alongside the orb's own definitions.
+    pub fn with_imported_orbs(mut self, orbs: Vec<(String, OrbDefinition)>) -> Self {
+        self.imported_orbs = orbs;
+        self
+    }
+
+    /// Set the YAML text of the orb's commands, jobs and executors, keyed by
+    /// `commands/<name>`, `jobs/<name>` and `executors/<name>`.
+    ///
+    /// Each is served verbatim at `orb://source/commands/<name>` and so on,
+    /// next to its JSON summary.
+    pub fn with_sources(mut self, sources: HashMap<String, String>) -> Self {
+        self.sources = sources;
+        self
+    }
+
+    /// Restrict the generated server to the given definition categories.
+    ///
+    /// An empty list (the default) exposes every category.
+    pub fn with_categories(mut self, categories: &[DefinitionCategory]) -> Self {
+        self.categories = categories.to_vec();
+        self
+    }
+
+    /// Expose only the definitions whose `<category>/<name>` (e.g.
+    /// `commands/install` or `jobs/deploy-staging`) matches one of the
+    /// `include` globs, when any are given, and none of the `exclude` globs.
+    pub fn with_filters(
+        mut self,
+        include: &[String],
+        exclude: &[String],
+    ) -> Result<Self, GeneratorError> {
+        let compile = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|pattern| {
+                    glob::Pattern::new(pattern).map_err(|e| GeneratorError::InvalidPattern {
+                        pattern: pattern.clone(),
+                        source: e,
+                    })
+                })
+                .collect::<Result<Vec<_>, _>>()
+        };
+        self.include = compile(include)?;
+        self.exclude = compile(exclude)?;
+        Ok(self)
+    }
+
+    /// Reject invalid orb names instead of sanitizing them.
+    pub fn with_strict_names(mut self, strict: bool) -> Self {
+        self.strict_names = strict;
+        self
+    }
+
+    /// Record the orb repository revision (e.g. a commit SHA) the server is
+    /// generated from.
+    pub fn with_source_rev(mut self, rev: impl Into<String>) -> Self {
+        self.source_rev = Some(rev.into());
+        self
+    }
+
+    /// Emit `tests/properties.rs`, a proptest suite that fuzzes resource
+    /// reads and tool inputs, plus the `proptest` dev-dependency.
+    pub fn with_property_tests(mut self, enabled: bool) -> Self {
+        self.property_tests = enabled;
+        self
+    }
+
+    /// Set the SPDX license expression written to the generated Cargo.toml
+    /// (e.g. `MIT OR Apache-2.0`).
+    pub fn with_license(mut self, spdx: impl Into<String>) -> Self {
+        self.license = Some(spdx.into());
+        self
+    }
+
+    /// Write `text` to a LICENSE file in the generated crate.
+    ///
+    /// Without [`with_license`](Self::with_license) the manifest points at
+    /// the file through `license-file`.
+    pub fn with_license_text(mut self, text: impl Into<String>) -> Self {
+        self.license_text = Some(text.into());
+        self
+    }
+
+    /// Emit `[package.metadata.binstall]` so the generated server can be
+    /// installed with `cargo binstall`.
+    pub fn with_binstall(mut self, binstall: BinstallMetadata) -> Self {
+        self.binstall = Some(binstall);
+        self
+    }
+
+    /// Fill in the `[package]` fields a registry needs to publish the
+    /// generated crate, and point `readme` at the generated README.
+    pub fn with_package(mut self, package: PackageMetadata) -> Self {
+        self.package = Some(package);
+        self
+    }
+
+    /// Split embedded resources larger than `max_bytes` into continuation
+    /// resources (`{uri}/part/2`, ...).
+    pub fn with_max_resource_bytes(mut self, max_bytes: usize) -> Self {
+        self.max_resource_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Embed `logo` as the `orb://logo` resource and the server icon.
+    pub fn with_logo(mut self, logo: Logo) -> Self {
+        self.logo = Some(logo);
+        self
+    }
+
+    /// Generate a server that serves embedded data only, with no filesystem,
+    /// network or environment access at runtime.
+    ///
+    /// Conformance rules are rejected, since the migration tools read and
+    /// write consumer CI directories, and the rendered sources are audited
+    /// for such APIs.
+    pub fn with_sandboxed(mut self, sandboxed: bool) -> Self {
+        self.sandboxed = sandboxed;
+        self
+    }
+
+    /// Generate a dev-mode server that reads the orb at `orb_path` when it
+    /// starts (and whenever it changes, when started with `--watch`) instead
+    /// of serving the definitions embedded at generation time.
+    ///
+    /// The server depends on this crate to parse the orb, so sandboxing,
+    /// definition filters, imported orbs and a custom URI scheme are
+    /// rejected.
+    pub fn with_dev_orb_path(mut self, orb_path: impl Into<PathBuf>) -> Self {
+        self.dev_orb_path = Some(orb_path.into());
+        self
+    }
+
+    /// Serve MCP over `transport` (default: stdio).
+    ///
+    /// The HTTP transport listens on the network, so it is rejected for
+    /// sandboxed servers.
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Set the address an HTTP server binds to when started without
+    /// `--bind` (default: [`DEFAULT_HTTP_BIND`](transport::DEFAULT_HTTP_BIND)).
+    pub fn with_http_bind(mut self, addr: SocketAddr) -> Self {
+        self.http_bind = addr;
+        self
+    }
+
+    /// Name the crate, package and server struct, and the scheme of resource
+    /// URIs, after `naming` (default: `<orb>_mcp` serving `orb://...`).
+    ///
+    /// A dev-mode server reads its resources at runtime at `orb://` URIs, so
+    /// another scheme is rejected for it.
+    pub fn with_naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Render with the `main.rs.hbs`, `lib.rs.hbs` and `Cargo.toml.hbs`
+    /// found in `dir` instead of the built-in templates.
+    ///
+    /// Missing files keep the built-in template. Other `.hbs` files are
+    /// ignored with a warning, since the remaining templates are tied to the
+    /// generator's data layout. Overrides only shape Rust output.
+    pub fn with_templates_dir(mut self, dir: &Path) -> Result<Self, GeneratorError> {
+        for (file, path, source) in read_template_overrides(dir)? {
+            self.register_override(file, &source, path)?;
+        }
+        Ok(self)
+    }
+
+    /// Replace the template `file` (`main.rs.hbs`, `lib.rs.hbs` or
+    /// `Cargo.toml.hbs`) with `source`, as a file in `--templates-dir` would.
+    pub fn with_template(mut self, file: &str, source: &str) -> Result<Self, GeneratorError> {
+        self.register_override(file, source, PathBuf::from(file))?;
+        Ok(self)
+    }
+
+    /// Register `source` in place of the template `file`, read from `origin`.
+    fn register_override(
+        &mut self,
+        file: &str,
+        source: &str,
+        origin: PathBuf,
+    ) -> Result<(), GeneratorError> {
+        let Some((_, name)) = templates::OVERRIDABLE.iter().find(|(f, _)| *f == file) else {
+            return Err(GeneratorError::UnknownTemplate {
+                name: file.to_string(),
+            });
+        };
+        self.handlebars
+            .register_template_string(name, source)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: origin.display().to_string(),
+                source: e,
+            })?;
+        tracing::info!(template = %origin.display(), "Using template override");
+        self.template_overrides.push(origin);
+        Ok(())
+    }
+
+    /// Generate a server in `language` (default: Rust).
+    ///
+    /// Options that only shape Rust output (conformance rules, property
+    /// tests, binstall metadata, resource splitting, sandboxing, the HTTP
+    /// transport and template overrides) are rejected for other languages,
+    /// as is a server title for Python.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Compress HTTP responses with gzip or deflate, as the client accepts.
+    pub fn with_http_compression(mut self, compression: bool) -> Self {
+        self.http_compression = compression;
+        self
+    }
+
+    /// Reject HTTP request bodies larger than `max_bytes` with
+    /// `413 Payload Too Large`.
+    pub fn with_http_body_limit(mut self, max_bytes: usize) -> Self {
+        self.http_body_limit = Some(max_bytes);
+        self
+    }
+
     /// Create a new code generator with registered templates.
     pub fn new() -> Result<Self, GeneratorError> {
         let mut handlebars = Handlebars::new();
@@ -221,6 +809,62 @@ impl<'a> CodeGenerator<'a> {
                 source: e,
             })?;
 
+        handlebars
+            .register_template_string("resources.rs", templates::RESOURCES_RS)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: "resources.rs".to_string(),
+                source: e,
+            })?;
+
+        handlebars
+            .register_template_string("properties.rs", templates::PROPERTIES_RS)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: "properties.rs".to_string(),
+                source: e,
+            })?;
+
+        handlebars
+            .register_template_string("overview.md", templates::OVERVIEW_MD)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: "overview.md".to_string(),
+                source: e,
+            })?;
+
+        handlebars
+            .register_partial("overview", templates::OVERVIEW_MD)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: "overview".to_string(),
+                source: e,
+            })?;
+
+        handlebars
+            .register_template_string("README.md", templates::README_MD)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: "README.md".to_string(),
+                source: e,
+            })?;
+
+        handlebars
+            .register_template_string("index.ts", templates::TS_INDEX)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: "index.ts".to_string(),
+                source: e,
+            })?;
+
+        handlebars
+            .register_template_string("tsconfig.json", templates::TS_TSCONFIG)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: "tsconfig.json".to_string(),
+                source: e,
+            })?;
+
+        handlebars
+            .register_template_string("server.py", templates::PY_SERVER)
+            .map_err(|e| GeneratorError::TemplateRegister {
+                name: "server.py".to_string(),
+                source: e,
+            })?;
+
         // Register custom helpers
         register_helpers(&mut handlebars);
 
@@ -228,6 +872,32 @@ impl<'a> CodeGenerator<'a> {
             handlebars,
             prior_versions: vec![],
             conformance_rules_json: None,
+            server_title: None,
+            server_instructions: None,
+            previous_orb: None,
+            imported_orbs: vec![],
+            sources: HashMap::new(),
+            template_overrides: vec![],
+            categories: vec![],
+            include: vec![],
+            exclude: vec![],
+            strict_names: false,
+            source_rev: None,
+            property_tests: false,
+            license: None,
+            license_text: None,
+            binstall: None,
+            package: None,
+            max_resource_bytes: None,
+            sandboxed: false,
+            logo: None,
+            language: Language::default(),
+            transport: Transport::default(),
+            http_bind: transport::DEFAULT_HTTP_BIND,
+            http_compression: false,
+            http_body_limit: None,
+            dev_orb_path: None,
+            naming: Naming::default(),
         })
     }
 
@@ -243,43 +913,292 @@ impl<'a> CodeGenerator<'a> {
     /// # Returns
     ///
     /// A `GeneratedServer` containing all source files ready to be written.
+    #[deprecated(
+        since = "0.3.0",
+        note = "describe the server with `GenerateOptions` and call `generate_in_memory`"
+    )]
     pub fn generate(
         &self,
         orb: &OrbDefinition,
         orb_name: &str,
         version: &str,
     ) -> Result<GeneratedServer, GeneratorError> {
-        // Validate orb name
-        validate_orb_name(orb_name)?;
+        self.generate_single(orb, orb_name, version)
+    }
+
+    fn generate_single(
+        &self,
+        orb: &OrbDefinition,
+        orb_name: &str,
+        version: &str,
+    ) -> Result<GeneratedServer, GeneratorError> {
+        let orb_name = self.checked_orb_name(orb_name)?;
+        self.generate_server(orb, &orb_name, version, false)
+    }
+
+    /// Generate one MCP server exposing several orbs.
+    ///
+    /// Definitions are named `<orb>/<name>`, as consumers reference them, and
+    /// served at `orb://<orb>/commands/<name>`, `orb://<orb>/jobs/<name>`,
+    /// `orb://<orb>/executors/<name>`, `orb://<orb>/examples/<name>` and
+    /// `orb://<orb>/workflows/<name>`. Prior versions, the previous release
+    /// and conformance rules describe a single orb's history, so they are
+    /// rejected.
+    pub fn generate_multi(
+        &self,
+        orbs: &[(String, OrbDefinition)],
+        server_name: &str,
+        version: &str,
+    ) -> Result<GeneratedServer, GeneratorError> {
+        let unsupported = [
+            ("prior versions", !self.prior_versions.is_empty()),
+            ("a previous orb", self.previous_orb.is_some()),
+            ("conformance rules", self.conformance_rules_json.is_some()),
+            ("imported orbs", !self.imported_orbs.is_empty()),
+            ("definition sources", !self.sources.is_empty()),
+            ("dev mode", self.dev_orb_path.is_some()),
+        ];
+        if let Some((option, _)) = unsupported.iter().find(|(_, set)| *set) {
+            return Err(GeneratorError::MultiOrbOption {
+                option: option.to_string(),
+            });
+        }
+
+        let server_name = self.checked_orb_name(server_name)?;
+        let mut named = Vec::with_capacity(orbs.len());
+        for (name, orb) in orbs {
+            let name = self.checked_orb_name(name)?;
+            if named.iter().any(|(other, _)| *other == name) {
+                return Err(GeneratorError::InvalidOrbName {
+                    name,
+                    reason: "more than one orb has this name".to_string(),
+                });
+            }
+            named.push((name, orb));
+        }
+        let merged = merge_orbs(&named);
+        self.generate_server(&merged, &server_name, version, true)
+    }
+
+    /// Validate `orb_name`, sanitizing it unless strict names are required.
+    fn checked_orb_name(&self, orb_name: &str) -> Result<String, GeneratorError> {
+        match validate_orb_name(orb_name) {
+            Ok(()) => Ok(orb_name.to_string()),
+            Err(e) if self.strict_names => Err(e),
+            Err(e) => {
+                let sanitized = sanitize_orb_name(orb_name);
+                tracing::warn!(%e, sanitized = %sanitized, "Using sanitized orb name");
+                Ok(sanitized)
+            }
+        }
+    }
+
+    /// Generate the server for `orb`, whose definition names are
+    /// `<orb>/<name>` when `namespaced`.
+    fn generate_server(
+        &self,
+        orb: &OrbDefinition,
+        orb_name: &str,
+        version: &str,
+        namespaced: bool,
+    ) -> Result<GeneratedServer, GeneratorError> {
+        if self.language != Language::Rust {
+            self.check_language_options()?;
+        }
+        self.naming.validate()?;
+
+        if self.sandboxed && self.conformance_rules_json.is_some() {
+            return Err(GeneratorError::SandboxViolation {
+                reason: "migration tools read and write consumer CI directories".to_string(),
+            });
+        }
+        if self.sandboxed && self.transport == Transport::Http {
+            return Err(GeneratorError::SandboxViolation {
+                reason: "the HTTP transport listens on the network".to_string(),
+            });
+        }
+        if self.dev_orb_path.is_some() {
+            self.check_dev_options()?;
+        }
+
+        let with_imports;
+        let orb = if self.imported_orbs.is_empty() {
+            orb
+        } else {
+            with_imports = merge_imports(orb, &self.imported_orbs);
+            &with_imports
+        };
+        for pattern in self.unmatched_includes(orb) {
+            tracing::warn!(pattern, "Include pattern matches no definition");
+        }
+        // Drop definition categories and definitions that were not selected
+        let orb = &self.restrict(orb);
+        // Oldest first, whatever order the snapshots were loaded in
+        let mut prior_versions: Vec<(String, OrbDefinition)> = self
+            .prior_versions
+            .iter()
+            .map(|(v, prior)| (v.clone(), self.restrict(prior)))
+            .collect();
+        prior_versions.sort_by(|(a, _), (b, _)| changes::cmp_versions(a, b));
+        let comparison = (!prior_versions.is_empty())
+            .then(|| changes::render_comparison(orb_name, version, orb, &prior_versions));
 
         // Build template context
         let context = GeneratorContext::from_orb_with_extras(
             orb,
             orb_name,
             version,
-            self.prior_versions.clone(),
+            prior_versions,
             self.conformance_rules_json.clone(),
-        );
+        )
+        .with_server_info(
+            self.server_title.as_deref(),
+            self.server_instructions.as_deref(),
+        )
+        .with_source_rev(self.source_rev.as_deref())
+        .with_property_tests(self.property_tests)
+        .with_license(self.license.as_deref(), self.license_text.is_some())
+        .with_binstall(self.binstall.as_ref())
+        .with_package(self.package.as_ref())
+        .with_sandboxed(self.sandboxed)
+        .with_split_resources(self.max_resource_bytes.is_some())
+        .with_transport(self.transport, self.http_bind)
+        .with_http_layers(self.http_compression, self.http_body_limit)
+        .with_logo(self.logo.as_ref())
+        .with_comparison(comparison)
+        .with_changes(self.previous_release().map(|(label, previous)| {
+            changes::render_changes(orb_name, label, &self.restrict(previous), version, orb)
+        }))
+        .with_namespaces(namespaced)
+        .with_imports(self.imported_orbs.iter().map(|(alias, _)| alias))
+        .with_sources(&self.sources)
+        .with_parameter_schemas()
+        .with_language(self.language)
+        .with_dev_orb_path(self.dev_orb_path.as_deref())
+        .with_enum_types()
+        .with_naming(&self.naming);
 
         // Serialize context for templates
         let ctx_json = serde_json::to_value(&context)
             .map_err(|e| GeneratorError::Serialization { source: e })?;
 
-        // Render templates
-        let mut files = HashMap::new();
         let mut binary_files: HashMap<PathBuf, Vec<u8>> = HashMap::new();
+        if let Some(logo) = &self.logo {
+            // A Python server reads the logo from its installed package
+            let assets = match self.language {
+                Language::Python => python::package_dir(&context).join("assets"),
+                _ => PathBuf::from("assets"),
+            };
+            binary_files.insert(assets.join(logo.file_name()), logo.bytes().to_vec());
+        }
 
-        // main.rs
-        let main_rs = self.handlebars.render("main.rs", &ctx_json).map_err(|e| {
-            GeneratorError::TemplateRender {
-                name: "main.rs".to_string(),
-                source: e,
+        let (mut files, crate_name) = match self.language {
+            Language::Rust => {
+                let files = self.render_rust(&context, &ctx_json, &mut binary_files)?;
+                (files, context.crate_name)
+            }
+            Language::TypeScript => (
+                typescript::render(self, &context, &ctx_json)?,
+                context.package_name,
+            ),
+            Language::Python => (
+                python::render(self, &context, &ctx_json)?,
+                context.package_name,
+            ),
+        };
+
+        let readme = self
+            .handlebars
+            .render("README.md", &ctx_json)
+            .map_err(|e| GeneratorError::TemplateRender {
+                name: "README.md".to_string(),
+                source: e,
+            })?;
+        files.insert(PathBuf::from("README.md"), readme);
+
+        if let Some(text) = &self.license_text {
+            files.insert(PathBuf::from("LICENSE"), text.clone());
+        }
+
+        Ok(GeneratedServer {
+            files,
+            binary_files,
+            crate_name,
+            orb_name: orb_name.to_string(),
+        })
+    }
+
+    /// Fail on options that only shape Rust output.
+    fn check_language_options(&self) -> Result<(), GeneratorError> {
+        let unsupported = [
+            ("conformance rules", self.conformance_rules_json.is_some()),
+            ("property tests", self.property_tests),
+            ("binstall metadata", self.binstall.is_some()),
+            ("package metadata", self.package.is_some()),
+            ("resource splitting", self.max_resource_bytes.is_some()),
+            ("sandboxing", self.sandboxed),
+            ("the HTTP transport", self.transport == Transport::Http),
+            ("template overrides", !self.template_overrides.is_empty()),
+            ("dev mode", self.dev_orb_path.is_some()),
+            (
+                "a server title",
+                self.language == Language::Python && self.server_title.is_some(),
+            ),
+        ];
+        match unsupported.iter().find(|(_, set)| *set) {
+            Some((option, _)) => Err(GeneratorError::UnsupportedOption {
+                language: self.language,
+                option: option.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Reject options a dev-mode server cannot honour: it serves every
+    /// definition of the orb it reads, parsed by this crate at runtime.
+    fn check_dev_options(&self) -> Result<(), GeneratorError> {
+        if self.sandboxed {
+            return Err(GeneratorError::SandboxViolation {
+                reason: "dev mode reads the orb from the filesystem".to_string(),
+            });
+        }
+        let unsupported = [
+            (
+                "definition filters",
+                !self.categories.is_empty() || !self.include.is_empty() || !self.exclude.is_empty(),
+            ),
+            ("imported orbs", !self.imported_orbs.is_empty()),
+            ("a custom URI scheme", !self.naming.is_default_scheme()),
+        ];
+        match unsupported.iter().find(|(_, set)| *set) {
+            Some((option, _)) => Err(GeneratorError::DevModeOption {
+                option: option.to_string(),
+            }),
+            None => Ok(()),
+        }
+    }
+
+    /// Render the Rust crate sources, adding its data blobs to
+    /// `binary_files`.
+    fn render_rust(
+        &self,
+        context: &GeneratorContext,
+        ctx_json: &serde_json::Value,
+        binary_files: &mut HashMap<PathBuf, Vec<u8>>,
+    ) -> Result<HashMap<PathBuf, String>, GeneratorError> {
+        let mut files = HashMap::new();
+
+        // main.rs
+        let main_rs = self.handlebars.render("main.rs", ctx_json).map_err(|e| {
+            GeneratorError::TemplateRender {
+                name: "main.rs".to_string(),
+                source: e,
             }
         })?;
         files.insert(PathBuf::from("src/main.rs"), main_rs);
 
         // lib.rs
-        let lib_rs = self.handlebars.render("lib.rs", &ctx_json).map_err(|e| {
+        let lib_rs = self.handlebars.render("lib.rs", ctx_json).map_err(|e| {
             GeneratorError::TemplateRender {
                 name: "lib.rs".to_string(),
                 source: e,
@@ -290,13 +1209,35 @@ impl<'a> CodeGenerator<'a> {
         // Cargo.toml
         let cargo_toml = self
             .handlebars
-            .render("Cargo.toml", &ctx_json)
+            .render("Cargo.toml", ctx_json)
             .map_err(|e| GeneratorError::TemplateRender {
                 name: "Cargo.toml".to_string(),
                 source: e,
             })?;
         files.insert(PathBuf::from("Cargo.toml"), cargo_toml);
 
+        // tests/resources.rs: starts the server and reads every resource
+        let resources_rs = self
+            .handlebars
+            .render("resources.rs", ctx_json)
+            .map_err(|e| GeneratorError::TemplateRender {
+                name: "resources.rs".to_string(),
+                source: e,
+            })?;
+        files.insert(PathBuf::from("tests/resources.rs"), resources_rs);
+
+        // tests/properties.rs (opt-in)
+        if context.property_tests {
+            let properties_rs = self
+                .handlebars
+                .render("properties.rs", ctx_json)
+                .map_err(|e| GeneratorError::TemplateRender {
+                    name: "properties.rs".to_string(),
+                    source: e,
+                })?;
+            files.insert(PathBuf::from("tests/properties.rs"), properties_rs);
+        }
+
         // Current-version resource data
         //
         // Instead of embedding json_content inline in the read_resource match
@@ -304,13 +1245,12 @@ impl<'a> CodeGenerator<'a> {
         // version resource content is packed into data/current.bin and looked
         // up at runtime via include_bytes! in src/current/mod.rs.
         if context.has_resources {
-            let current_bin =
-                build_current_bin(&context.commands, &context.jobs, &context.executors);
+            let current_bin = build_current_bin(&context, self.max_resource_bytes);
             binary_files.insert(PathBuf::from("data/current.bin"), current_bin);
 
             let current_mod = self
                 .handlebars
-                .render("current_mod.rs", &ctx_json)
+                .render("current_mod.rs", ctx_json)
                 .map_err(|e| GeneratorError::TemplateRender {
                     name: "current_mod.rs".to_string(),
                     source: e,
@@ -327,13 +1267,13 @@ impl<'a> CodeGenerator<'a> {
         // shim that looks up entries via `include_bytes!`.
         if context.has_prior_versions {
             // data/versions.bin — compact binary lookup table
-            let versions_bin = build_versions_bin(&context.prior_versions);
+            let versions_bin = build_versions_bin(&context.prior_versions, self.max_resource_bytes);
             binary_files.insert(PathBuf::from("data/versions.bin"), versions_bin);
 
             // src/versions/mod.rs — include_bytes! shim + sequential lookup fn
             let versions_mod = self
                 .handlebars
-                .render("versions_mod.rs", &ctx_json)
+                .render("versions_mod.rs", ctx_json)
                 .map_err(|e| GeneratorError::TemplateRender {
                     name: "versions_mod.rs".to_string(),
                     source: e,
@@ -341,12 +1281,98 @@ impl<'a> CodeGenerator<'a> {
             files.insert(PathBuf::from("src/versions/mod.rs"), versions_mod);
         }
 
-        Ok(GeneratedServer {
-            files,
-            binary_files,
-            crate_name: context.crate_name,
-            orb_name: orb_name.to_string(),
+        if self.sandboxed {
+            sandbox::audit(&files)?;
+        }
+
+        Ok(files)
+    }
+
+    /// Copy of `orb` containing only the selected definition categories.
+    fn restrict(&self, orb: &OrbDefinition) -> OrbDefinition {
+        let mut orb = orb.clone();
+        if !self.categories.is_empty() {
+            if !self.categories.contains(&DefinitionCategory::Commands) {
+                orb.commands.clear();
+            }
+            if !self.categories.contains(&DefinitionCategory::Jobs) {
+                orb.jobs.clear();
+            }
+            if !self.categories.contains(&DefinitionCategory::Executors) {
+                orb.executors.clear();
+            }
+            if !self.categories.contains(&DefinitionCategory::Examples) {
+                orb.examples.clear();
+            }
+            if !self.categories.contains(&DefinitionCategory::Workflows) {
+                orb.workflows.clear();
+            }
+        }
+        if !self.include.is_empty() || !self.exclude.is_empty() {
+            orb.commands
+                .retain(|name, _| self.selected(DefinitionCategory::Commands, name));
+            orb.jobs
+                .retain(|name, _| self.selected(DefinitionCategory::Jobs, name));
+            orb.executors
+                .retain(|name, _| self.selected(DefinitionCategory::Executors, name));
+            orb.examples
+                .retain(|name, _| self.selected(DefinitionCategory::Examples, name));
+            orb.workflows
+                .retain(|name, _| self.selected(DefinitionCategory::Workflows, name));
+        }
+        orb
+    }
+
+    /// Whether the `--include` / `--exclude` globs keep the definition
+    /// `name` of `category`.
+    fn selected(&self, category: DefinitionCategory, name: &str) -> bool {
+        let path = format!("{}/{name}", category.as_str());
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches(&path)))
+            && !self.exclude.iter().any(|p| p.matches(&path))
+    }
+
+    /// Include globs that match no definition of `orb`, usually typos.
+    fn unmatched_includes(&self, orb: &OrbDefinition) -> Vec<&str> {
+        let paths: Vec<String> = [
+            (
+                DefinitionCategory::Commands,
+                orb.commands.keys().collect::<Vec<_>>(),
+            ),
+            (DefinitionCategory::Jobs, orb.jobs.keys().collect()),
+            (
+                DefinitionCategory::Executors,
+                orb.executors.keys().collect(),
+            ),
+            (DefinitionCategory::Examples, orb.examples.keys().collect()),
+            (
+                DefinitionCategory::Workflows,
+                orb.workflows.keys().collect(),
+            ),
+        ]
+        .into_iter()
+        .flat_map(|(category, names)| {
+            names
+                .into_iter()
+                .map(move |name| format!("{}/{name}", category.as_str()))
         })
+        .collect();
+        self.include
+            .iter()
+            .filter(|p| !paths.iter().any(|path| p.matches(path)))
+            .map(glob::Pattern::as_str)
+            .collect()
+    }
+
+    /// The release to compare against for `orb://changes`: the explicit
+    /// previous orb, else the highest-versioned prior snapshot.
+    fn previous_release(&self) -> Option<(&str, &OrbDefinition)> {
+        if let Some((label, orb)) = &self.previous_orb {
+            return Some((label.as_str(), orb));
+        }
+        self.prior_versions
+            .iter()
+            .max_by(|(a, _), (b, _)| changes::cmp_versions(a, b))
+            .map(|(label, orb)| (label.as_str(), orb))
     }
 
     /// Generate an MCP server and format the output.
@@ -359,7 +1385,7 @@ impl<'a> CodeGenerator<'a> {
         version: &str,
         output_dir: &Path,
     ) -> Result<GeneratedServer, GeneratorError> {
-        let mut server = self.generate(orb, orb_name, version)?;
+        let mut server = self.generate_single(orb, orb_name, version)?;
         server.format(output_dir)?;
         Ok(server)
     }
@@ -395,6 +1421,263 @@ fn register_helpers(handlebars: &mut Handlebars) {
     );
 }
 
+/// Rewrite `name` into a valid orb name.
+///
+/// Characters other than ASCII letters, digits, `-` and `_` become `-`, runs of
+/// `-` collapse, and `orb-` is prefixed when the result does not start with a
+/// letter (e.g. `my.orb@2` → `my-orb-2`, `2fa` → `orb-2fa`).
+pub fn sanitize_orb_name(name: &str) -> String {
+    let mut out = String::with_capacity(name.len());
+    for c in name.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '_' {
+            c
+        } else {
+            '-'
+        };
+        if c == '-' && (out.is_empty() || out.ends_with('-')) {
+            continue;
+        }
+        out.push(c);
+    }
+    let out = out.trim_end_matches('-');
+    if out.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        out.to_string()
+    } else if out.is_empty() {
+        "orb".to_string()
+    } else {
+        format!("orb-{out}")
+    }
+}
+
+/// Merge `orbs` into one definition whose commands, jobs, executors,
+/// examples and workflows are named `<orb>/<name>`, as are the references
+/// between them.
+///
+/// The merged definition has no `orbs:` imports and no pipeline
+/// `parameters`: imports are rejected by [`CodeGenerator::generate_multi`]
+/// and pipeline parameters belong to project configs, not orbs.
+fn merge_orbs(orbs: &[(String, &OrbDefinition)]) -> OrbDefinition {
+    let names: Vec<&str> = orbs.iter().map(|(name, _)| name.as_str()).collect();
+    let mut description = format!("Combines the {} CircleCI orbs.\n", names.join(", "));
+    let mut merged = OrbDefinition {
+        version: "2.1".to_string(),
+        ..Default::default()
+    };
+    for (name, orb) in orbs {
+        let orb = &qualify_references(name, orb);
+        if let Some(text) = &orb.description {
+            description.push_str(&format!("\n- {name}: {}", text.trim()));
+        }
+        let qualified = |key: &String| format!("{name}/{key}");
+        merged.commands.extend(
+            orb.commands
+                .iter()
+                .map(|(key, command)| (qualified(key), command.clone())),
+        );
+        merged.jobs.extend(
+            orb.jobs
+                .iter()
+                .map(|(key, job)| (qualified(key), job.clone())),
+        );
+        merged.executors.extend(
+            orb.executors
+                .iter()
+                .map(|(key, executor)| (qualified(key), executor.clone())),
+        );
+        merged.examples.extend(
+            orb.examples
+                .iter()
+                .map(|(key, example)| (qualified(key), example.clone())),
+        );
+        merged.workflows.extend(
+            orb.workflows
+                .iter()
+                .map(|(key, workflow)| (qualified(key), workflow.clone())),
+        );
+    }
+    merged.description = Some(description);
+    merged
+}
+
+/// Add the commands, jobs and executors of the `imported` orbs to `orb`,
+/// named `<alias>/<name>`, as are the references between them.
+fn merge_imports(orb: &OrbDefinition, imported: &[(String, OrbDefinition)]) -> OrbDefinition {
+    let mut merged = orb.clone();
+    for (alias, import) in imported {
+        let import = &qualify_references(alias, import);
+        let qualified = |key: &String| format!("{alias}/{key}");
+        merged.commands.extend(
+            import
+                .commands
+                .iter()
+                .map(|(key, command)| (qualified(key), command.clone())),
+        );
+        merged.jobs.extend(
+            import
+                .jobs
+                .iter()
+                .map(|(key, job)| (qualified(key), job.clone())),
+        );
+        merged.executors.extend(
+            import
+                .executors
+                .iter()
+                .map(|(key, executor)| (qualified(key), executor.clone())),
+        );
+    }
+    merged
+}
+
+/// A copy of `orb` whose references to its own commands, executors and jobs
+/// are named `<name>/<reference>`, to match its definitions once merged
+/// under `name`.
+///
+/// Covers step invocations (including `when`/`unless` bodies and steps
+/// passed to `steps` parameters), job executors, `executor` and `steps`
+/// parameter defaults and arguments, and workflow jobs with their
+/// `requires` and `pre-steps`/`post-steps`.
+fn qualify_references(name: &str, orb: &OrbDefinition) -> OrbDefinition {
+    let qualifier = Qualifier { name, orb };
+    let mut qualified = orb.clone();
+    for command in qualified.commands.values_mut() {
+        qualifier.parameters(&mut command.parameters);
+        qualifier.steps(&mut command.steps);
+    }
+    for job in qualified.jobs.values_mut() {
+        qualifier.parameters(&mut job.parameters);
+        qualifier.steps(&mut job.steps);
+        if let Some(ExecutorRef::Name(executor) | ExecutorRef::WithParams { name: executor, .. }) =
+            &mut job.executor
+        {
+            qualifier.reference(&orb.executors, executor);
+        }
+    }
+    for workflow in qualified.workflows.values_mut() {
+        // `requires` names an invocation, which is its job unless renamed.
+        let renamed: Vec<String> = workflow
+            .jobs
+            .iter()
+            .filter(|invocation| invocation.options.name.is_none())
+            .filter(|invocation| orb.jobs.contains_key(&invocation.job))
+            .map(|invocation| invocation.job.clone())
+            .collect();
+        for invocation in &mut workflow.jobs {
+            let options = &mut invocation.options;
+            for required in &mut options.requires {
+                if renamed.contains(required) {
+                    *required = format!("{name}/{required}");
+                }
+            }
+            for list in [&mut options.pre_steps, &mut options.post_steps]
+                .into_iter()
+                .flatten()
+            {
+                if let StepList::Steps(steps) = list {
+                    qualifier.steps(steps);
+                }
+            }
+            if let Some(job) = orb.jobs.get(&invocation.job) {
+                let args = options.parameters.iter_mut();
+                qualifier.arguments(&job.parameters, args.map(|(k, v)| (k.as_str(), v)));
+                invocation.job = format!("{name}/{}", invocation.job);
+            }
+        }
+    }
+    qualified
+}
+
+/// Rewrites the references of `orb` into its namespace `name`.
+struct Qualifier<'a> {
+    name: &'a str,
+    orb: &'a OrbDefinition,
+}
+
+impl Qualifier<'_> {
+    /// Prefix `reference` when it names one of the `defined` definitions.
+    fn reference<T>(&self, defined: &HashMap<String, T>, reference: &mut String) {
+        if defined.contains_key(reference.as_str()) {
+            *reference = format!("{}/{reference}", self.name);
+        }
+    }
+
+    fn steps(&self, steps: &mut [Step]) {
+        for step in steps {
+            match step {
+                Step::Simple(command) => self.reference(&self.orb.commands, command),
+                Step::Structured(StructuredStep::When(c) | StructuredStep::Unless(c)) => {
+                    self.steps(&mut c.steps);
+                }
+                Step::Structured(StructuredStep::Steps(StepList::Steps(steps))) => {
+                    self.steps(steps);
+                }
+                Step::Structured(StructuredStep::CommandInvocation(invocation)) => {
+                    *invocation = std::mem::take(invocation)
+                        .into_iter()
+                        .map(|(mut command, mut args)| {
+                            if let Some(definition) = self.orb.commands.get(&command) {
+                                if let serde_yaml::Value::Mapping(args) = &mut args {
+                                    let args = args.iter_mut();
+                                    self.arguments(
+                                        &definition.parameters,
+                                        args.filter_map(|(k, v)| Some((k.as_str()?, v))),
+                                    );
+                                }
+                                self.reference(&self.orb.commands, &mut command);
+                            }
+                            (command, args)
+                        })
+                        .collect();
+                }
+                Step::Structured(_) => {}
+            }
+        }
+    }
+
+    fn parameters(&self, parameters: &mut HashMap<String, Parameter>) {
+        for parameter in parameters.values_mut() {
+            if let Some(default) = &mut parameter.default {
+                self.argument(parameter.param_type, default);
+            }
+        }
+    }
+
+    fn arguments<'v>(
+        &self,
+        declared: &HashMap<String, Parameter>,
+        args: impl Iterator<Item = (&'v str, &'v mut serde_yaml::Value)>,
+    ) {
+        for (arg, value) in args {
+            if let Some(parameter) = declared.get(arg) {
+                self.argument(parameter.param_type, value);
+            }
+        }
+    }
+
+    /// Qualify an `executor` or `steps` parameter `value`.
+    fn argument(&self, param_type: ParameterType, value: &mut serde_yaml::Value) {
+        match param_type {
+            ParameterType::Executor => {
+                let executor = match value {
+                    serde_yaml::Value::Mapping(executor) => executor.get_mut("name"),
+                    executor => Some(executor),
+                };
+                if let Some(serde_yaml::Value::String(executor)) = executor {
+                    self.reference(&self.orb.executors, executor);
+                }
+            }
+            ParameterType::Steps => {
+                if let Some(StepList::Steps(mut steps)) = StepList::from_value(value) {
+                    self.steps(&mut steps);
+                    if let Ok(steps) = serde_yaml::to_value(steps) {
+                        *value = steps;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 /// Validate that the orb name is valid for use in generated code.
 fn validate_orb_name(name: &str) -> Result<(), GeneratorError> {
     if name.is_empty() {
@@ -427,26 +1710,58 @@ fn validate_orb_name(name: &str) -> Result<(), GeneratorError> {
     Ok(())
 }
 
-/// Run rustfmt on a file.
-fn run_rustfmt(path: &Path) -> Result<(), GeneratorError> {
-    let output = Command::new("rustfmt").arg(path).output();
+/// Outcome of piping one file through rustfmt.
+enum Rustfmt {
+    Formatted(String),
+    Rejected,
+    Missing,
+}
 
-    match output {
-        Ok(output) if output.status.success() => Ok(()),
-        Ok(output) => {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            // If rustfmt is not installed or fails, we continue without formatting
-            tracing::warn!("rustfmt warning for {}: {}", path.display(), stderr);
-            Ok(())
-        }
+/// Run rustfmt on the source of the generated file `path`.
+fn run_rustfmt(path: &Path, source: &str) -> Result<Rustfmt, GeneratorError> {
+    use std::io::Write;
+
+    // The generated Cargo.toml declares edition 2021; rustfmt does not read it
+    // when formatting stdin
+    let child = Command::new("rustfmt")
+        .args(["--edition", "2021", "--emit", "stdout"])
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .stderr(std::process::Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
         Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
             // rustfmt not installed, skip formatting
             tracing::debug!("rustfmt not found, skipping formatting");
-            Ok(())
+            return Ok(Rustfmt::Missing);
+        }
+        Err(e) => {
+            return Err(GeneratorError::RustfmtFailed {
+                message: e.to_string(),
+            })
         }
-        Err(e) => Err(GeneratorError::RustfmtFailed {
+    };
+
+    let mut stdin = child.stdin.take().expect("stdin is piped");
+    let input = source.to_string();
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child
+        .wait_with_output()
+        .map_err(|e| GeneratorError::RustfmtFailed {
             message: e.to_string(),
-        }),
+        })?;
+    // A write error means rustfmt exited early, which its status reports
+    let _ = writer.join();
+
+    match String::from_utf8(output.stdout) {
+        Ok(formatted) if output.status.success() => Ok(Rustfmt::Formatted(formatted)),
+        _ => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            // If rustfmt fails, we continue without formatting
+            tracing::warn!("rustfmt warning for {}: {}", path.display(), stderr);
+            Ok(Rustfmt::Rejected)
+        }
     }
 }
 
@@ -460,11 +1775,25 @@ fn run_rustfmt(path: &Path) -> Result<(), GeneratorError> {
 ///   [u32 key_len (LE)] [key bytes (UTF-8 URI)]
 ///   [u32 val_len (LE)] [val bytes (UTF-8 JSON)]
 /// ```
-fn encode_bin_entries(entries: &[(&str, &str)]) -> Vec<u8> {
+fn encode_bin_entries(entries: &[(&str, &str)], max_bytes: Option<usize>) -> Vec<u8> {
+    let chunked: Vec<(String, String)>;
+    let entries: Vec<(&str, &str)> = match max_bytes {
+        Some(limit) => {
+            chunked = entries
+                .iter()
+                .flat_map(|(key, val)| chunk_resource(key, val, limit))
+                .collect();
+            chunked
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str()))
+                .collect()
+        }
+        None => entries.to_vec(),
+    };
     let count = entries.len() as u32;
     let mut data: Vec<u8> = Vec::new();
     data.extend_from_slice(&count.to_le_bytes());
-    for (key, val) in entries {
+    for (key, val) in &entries {
         let kb = key.as_bytes();
         let vb = val.as_bytes();
         data.extend_from_slice(&(kb.len() as u32).to_le_bytes());
@@ -475,13 +1804,76 @@ fn encode_bin_entries(entries: &[(&str, &str)]) -> Vec<u8> {
     data
 }
 
+/// Split resource `content` into parts of at most `limit` bytes.
+///
+/// Content within the limit is returned unchanged. Otherwise the first part
+/// keeps `uri` and part `n` is served at `{uri}/part/{n}`; each part ends with
+/// a note naming its position and the next part, whose length comes off the
+/// window. Parts break after a newline when one falls in the second half of
+/// the window. Only a limit too small for the note itself lets a part exceed
+/// it.
+fn chunk_resource(uri: &str, content: &str, limit: usize) -> Vec<(String, String)> {
+    if content.len() <= limit {
+        return vec![(uri.to_string(), content.to_string())];
+    }
+    // There are no more parts than bytes, so part numbers have at most as
+    // many digits as the content length
+    let digits = content.len().to_string().len();
+    let note_len = "\n\n[Part  of ; continued at /part/]".len() + uri.len() + 3 * digits;
+    let window = limit.saturating_sub(note_len).max(1);
+    let mut pieces = Vec::new();
+    let mut rest = content;
+    while !rest.is_empty() {
+        let mut end = window.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        if end == 0 {
+            // A single character wider than the limit
+            end = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        } else if end < rest.len() {
+            if let Some(newline) = rest[..end].rfind('\n').filter(|&i| i >= end / 2) {
+                end = newline + 1;
+            }
+        }
+        pieces.push(&rest[..end]);
+        rest = &rest[end..];
+    }
+
+    let total = pieces.len();
+    pieces
+        .into_iter()
+        .enumerate()
+        .map(|(i, piece)| {
+            let part = i + 1;
+            let key = if part == 1 {
+                uri.to_string()
+            } else {
+                format!("{uri}/part/{part}")
+            };
+            let note = if part < total {
+                format!(
+                    "[Part {part} of {total}; continued at {uri}/part/{}]",
+                    part + 1
+                )
+            } else {
+                format!("[Part {part} of {total}]")
+            };
+            (key, format!("{piece}\n\n{note}"))
+        })
+        .collect()
+}
+
 /// Build a compact binary data blob from all prior-version snapshots.
 ///
 /// The generated `src/versions/mod.rs` contains an identical sequential-scan
 /// lookup that reads from this blob via `include_bytes!`.  Using binary data
 /// avoids embedding the content as Rust string literals, which causes LLVM to
 /// run out of memory when compiling large orbs with many historical versions.
-fn build_versions_bin(prior_versions: &[context::VersionSnapshot]) -> Vec<u8> {
+fn build_versions_bin(
+    prior_versions: &[context::VersionSnapshot],
+    max_bytes: Option<usize>,
+) -> Vec<u8> {
     let mut entries: Vec<(&str, &str)> = Vec::new();
     for snap in prior_versions {
         for item in &snap.commands {
@@ -494,7 +1886,7 @@ fn build_versions_bin(prior_versions: &[context::VersionSnapshot]) -> Vec<u8> {
             entries.push((&item.uri, &item.json_content));
         }
     }
-    encode_bin_entries(&entries)
+    encode_bin_entries(&entries, max_bytes)
 }
 
 /// Build a compact binary data blob from the current-version resources.
@@ -504,154 +1896,1261 @@ fn build_versions_bin(prior_versions: &[context::VersionSnapshot]) -> Vec<u8> {
 /// embedding large JSON strings as inline Rust string literals inside the
 /// `read_resource` match expression, which causes LLVM to run out of memory
 /// when compiling large orbs with many commands/jobs/executors.
-fn build_current_bin(
-    commands: &[context::CommandContext],
-    jobs: &[context::JobContext],
-    executors: &[context::ExecutorContext],
-) -> Vec<u8> {
+fn build_current_bin(context: &GeneratorContext, max_bytes: Option<usize>) -> Vec<u8> {
+    encode_bin_entries(&current_entries(context), max_bytes)
+}
+
+/// `(uri, content)` of every current-version definition resource.
+fn current_entries(context: &GeneratorContext) -> Vec<(&str, &str)> {
     let mut entries: Vec<(&str, &str)> = Vec::new();
-    for item in commands {
+    for item in &context.commands {
+        entries.push((&item.uri, &item.json_content));
+    }
+    for item in &context.jobs {
+        entries.push((&item.uri, &item.json_content));
+    }
+    for item in &context.executors {
         entries.push((&item.uri, &item.json_content));
     }
-    for item in jobs {
+    for item in &context.examples {
         entries.push((&item.uri, &item.json_content));
     }
-    for item in executors {
+    for item in &context.workflows {
         entries.push((&item.uri, &item.json_content));
     }
-    encode_bin_entries(&entries)
-}
-
-/// Run clippy --fix on a project directory.
-#[allow(dead_code)]
-fn run_clippy_fix(project_dir: &Path) -> Result<(), GeneratorError> {
-    let output = Command::new("cargo")
-        .args(["clippy", "--fix", "--allow-dirty", "--allow-staged"])
-        .current_dir(project_dir)
-        .output();
+    for item in &context.sources {
+        entries.push((&item.uri, &item.yaml));
+    }
+    for item in &context.schemas {
+        entries.push((&item.uri, &item.json_content));
+    }
+    entries
+}
+
+/// Run `cargo clippy --fix` with `cargo`, a cargo command set up to run in
+/// the project directory.
+fn run_clippy_fix(mut cargo: Command) -> Result<(), GeneratorError> {
+    let output = cargo
+        .args([
+            "clippy",
+            "--fix",
+            "--all-targets",
+            "--allow-dirty",
+            "--allow-staged",
+            "--allow-no-vcs",
+        ])
+        .output();
+
+    match output {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            tracing::warn!("clippy warning: {}", stderr);
+            Ok(())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            tracing::warn!("cargo not found, skipping clippy --fix");
+            Ok(())
+        }
+        Err(e) => Err(GeneratorError::ClippyFailed {
+            message: e.to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::parser::{Command, OrbDefinition, Parameter, ParameterType};
+
+    fn create_test_orb() -> OrbDefinition {
+        let mut orb = OrbDefinition {
+            version: "2.1".to_string(),
+            description: Some("Test orb for unit tests".to_string()),
+            ..Default::default()
+        };
+
+        // Add a command
+        let mut params = HashMap::new();
+        params.insert(
+            "name".to_string(),
+            Parameter {
+                param_type: ParameterType::String,
+                description: Some("Name to greet".to_string()),
+                default: Some(serde_yaml::Value::String("World".to_string())),
+                enum_values: None,
+            },
+        );
+
+        orb.commands.insert(
+            "greet".to_string(),
+            Command {
+                description: Some("Greet someone".to_string()),
+                parameters: params,
+                steps: vec![],
+            },
+        );
+
+        orb
+    }
+
+    #[test]
+    fn test_code_generator_new() {
+        let generator = CodeGenerator::new();
+        assert!(generator.is_ok());
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_deprecated_generate_matches_generate_in_memory() {
+        let orb = create_test_orb();
+
+        let server = CodeGenerator::new()
+            .unwrap()
+            .with_property_tests(true)
+            .generate(&orb, "test-orb", "1.0.0")
+            .unwrap();
+
+        let opts = GenerateOptions {
+            property_tests: true,
+            ..GenerateOptions::new("test-orb", "1.0.0")
+        };
+        assert_eq!(server.files, generate_in_memory(&orb, &opts).unwrap().files);
+    }
+
+    #[test]
+    fn test_generate_produces_files() {
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+
+        assert!(server.files.contains_key(&PathBuf::from("src/main.rs")));
+        assert!(server.files.contains_key(&PathBuf::from("src/lib.rs")));
+        assert!(server.files.contains_key(&PathBuf::from("Cargo.toml")));
+        assert_eq!(server.crate_name, "test_orb_mcp");
+        assert_eq!(server.orb_name, "test-orb");
+    }
+
+    #[test]
+    fn test_generated_main_contains_tokio() {
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let main_rs = server.files.get(&PathBuf::from("src/main.rs")).unwrap();
+
+        assert!(main_rs.contains("#[tokio::main]"));
+        assert!(main_rs.contains("test_orb_mcp::OrbServer::new"));
+    }
+
+    #[test]
+    fn test_generated_lib_contains_resources() {
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+
+        assert!(lib_rs.contains("ServerHandler"));
+        assert!(lib_rs.contains("RawResource"));
+        assert!(lib_rs.contains("orb://commands/greet"));
+        assert!(lib_rs.contains("orb://overview"));
+    }
+
+    #[test]
+    fn test_overview_tables_link_definitions() {
+        let mut orb = create_test_orb();
+        orb.commands.get_mut("greet").unwrap().description =
+            Some("Greet someone | politely\n\nLonger text.".to_string());
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+
+        assert!(lib_rs.contains("## Contents"));
+        assert!(lib_rs.contains(
+            "| `greet` | Greet someone \\| politely | 1 | \
+             [`orb://commands/greet`](orb://commands/greet) |"
+        ));
+        // No table for categories the orb does not define
+        assert!(!lib_rs.contains("| Job | Description |"));
+    }
+
+    #[test]
+    fn test_enum_parameters_become_typed_enums() {
+        let mut orb = create_test_orb();
+        orb.commands.get_mut("greet").unwrap().parameters.insert(
+            "style".to_string(),
+            Parameter {
+                param_type: ParameterType::Enum,
+                description: None,
+                default: None,
+                enum_values: Some(vec!["plain".to_string(), "shout-loud".to_string()]),
+            },
+        );
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+        let cargo = server.files.get(&PathBuf::from("Cargo.toml")).unwrap();
+
+        assert!(lib_rs.contains("pub enum CommandGreetStyle {"));
+        assert!(lib_rs.contains("#[serde(rename = \"shout-loud\")]\n        ShoutLoud,"));
+        assert!(lib_rs.contains(
+            "(\"command\", \"greet\", \"style\") => {\n                \
+             serde_json::from_value::<enums::CommandGreetStyle>"
+        ));
+        assert!(lib_rs.contains("check_enum_arguments(kind, name, &parameters)?;"));
+        assert!(cargo.contains("serde = { version = \"1\", features = [\"derive\"] }"));
+
+        // Without enum parameters neither the module nor serde is added
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions::new("test-orb", "1.0.0"),
+        )
+        .unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+        assert!(!lib_rs.contains("pub mod enums"));
+        assert!(!server.files[&PathBuf::from("Cargo.toml")].contains("serde = "));
+    }
+
+    #[test]
+    fn test_generated_cargo_toml() {
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "2.5.0")).unwrap();
+        let cargo = server.files.get(&PathBuf::from("Cargo.toml")).unwrap();
+
+        assert!(cargo.contains("name = \"test_orb_mcp\""));
+        assert!(cargo.contains("version = \"2.5.0\""));
+        assert!(cargo.contains("rmcp = "));
+        assert!(cargo.contains("tokio = "));
+    }
+
+    #[test]
+    fn test_generated_lib_default_server_info() {
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+
+        assert!(lib_rs.contains("title: None,"));
+        assert!(lib_rs.contains("\"MCP server for the test-orb CircleCI orb\""));
+    }
+
+    #[test]
+    fn test_generated_lib_custom_server_info() {
+        let opts = GenerateOptions {
+            server_title: Some("Acme \"CI\" Toolkit".to_string()),
+            server_instructions: Some("Read orb://overview first.\nThen drill down.".to_string()),
+            ..GenerateOptions::new("test-orb", "1.0.0")
+        };
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &opts).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+
+        assert!(lib_rs.contains(r#"title: Some("Acme \"CI\" Toolkit".into()),"#));
+        assert!(lib_rs.contains(r#"Some("Read orb://overview first.\nThen drill down.".into())"#));
+        // The crate-derived server name is unchanged
+        assert!(lib_rs.contains("name: \"test-orb-mcp\".into(),"));
+    }
+
+    #[test]
+    fn test_generated_lib_server_title_escapes_backslashes() {
+        let opts = GenerateOptions {
+            server_title: Some(r"Orbs in C:\orbs".into()),
+            ..GenerateOptions::new("test-orb", "1.0.0")
+        };
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &opts).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+
+        assert!(lib_rs.contains(r#"title: Some("Orbs in C:\\orbs".into()),"#));
+    }
+
+    #[test]
+    fn test_generated_lib_example_description_escapes_backslashes() {
+        let mut orb = create_test_orb();
+        orb.examples.insert(
+            "tags".to_string(),
+            crate::parser::Example {
+                description: Some(r"Matches \d+ tags".to_string()),
+                ..Default::default()
+            },
+        );
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+
+        assert!(lib_rs.contains(r#""Example: tags - Matches \\d+ tags""#));
+    }
+
+    #[test]
+    fn test_property_tests_emitted_only_when_enabled() {
+        let orb = create_test_orb();
+        let tests_path = PathBuf::from("tests/properties.rs");
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        assert!(!server.files.contains_key(&tests_path));
+        let cargo_toml = server.files.get(&PathBuf::from("Cargo.toml")).unwrap();
+        assert!(!cargo_toml.contains("proptest"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                property_tests: true,
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let properties = server.files.get(&tests_path).unwrap();
+        assert!(properties.contains("use test_orb_mcp::OrbServer;"));
+        assert!(properties.contains("\"orb://commands/greet\","));
+        let cargo_toml = server.files.get(&PathBuf::from("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("[dev-dependencies]\nproptest = \"1\""));
+    }
+
+    #[test]
+    fn test_resource_integration_tests_emitted() {
+        let orb = create_test_orb();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let tests = server
+            .files
+            .get(&PathBuf::from("tests/resources.rs"))
+            .unwrap();
+        assert!(tests.contains("env!(\"CARGO_BIN_EXE_test_orb_mcp\")"));
+        assert!(tests.contains("assert_eq!(init[\"serverInfo\"][\"name\"], \"test-orb-mcp\");"));
+        assert!(tests.contains("    \"orb://commands/greet\",\n"));
+        assert!(!tests.contains("\"orb://changes\""));
+    }
+
+    #[test]
+    fn test_license_field_and_file() {
+        let orb = create_test_orb();
+        let manifest = PathBuf::from("Cargo.toml");
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        assert!(!server.files[&manifest].contains("license"));
+        assert!(!server.files.contains_key(&PathBuf::from("LICENSE")));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                license: Some("MIT OR Apache-2.0".to_string()),
+                license_text: Some("MIT License\n".to_string()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        assert!(server.files[&manifest].contains("license = \"MIT OR Apache-2.0\"\n"));
+        assert!(!server.files[&manifest].contains("license-file"));
+        assert_eq!(server.files[&PathBuf::from("LICENSE")], "MIT License\n");
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                license_text: Some("Proprietary\n".to_string()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        assert!(server.files[&manifest].contains("license-file = \"LICENSE\"\n"));
+    }
+
+    #[test]
+    fn test_logo_embedded_as_blob_resource_and_icon() {
+        let orb = create_test_orb();
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>".to_vec();
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        assert!(!server.files[&PathBuf::from("src/lib.rs")].contains("orb://logo"));
+        assert!(!server.files[&PathBuf::from("Cargo.toml")].contains("base64"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                logo: Some(Logo::from_bytes(svg.clone()).unwrap()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        assert_eq!(server.binary_files[&PathBuf::from("assets/logo.svg")], svg);
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("include_bytes!(\"../assets/logo.svg\")"));
+        assert!(lib_rs.contains("const LOGO_MIME_TYPE: &str = \"image/svg+xml\";"));
+        assert!(lib_rs.contains("\"orb://logo\","));
+        assert!(lib_rs.contains("BlobResourceContents"));
+        assert!(lib_rs.contains("sizes: Some(vec![\"any\".into()])"));
+        assert!(server.files[&PathBuf::from("Cargo.toml")].contains("base64 = \"0.22\"\n"));
+    }
+
+    #[test]
+    fn test_binstall_metadata() {
+        let orb = create_test_orb();
+        let manifest = PathBuf::from("Cargo.toml");
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        assert!(!server.files[&manifest].contains("binstall"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                binstall: Some(BinstallMetadata {
+                    pkg_url:
+                        "https://artifacts.example.com/{ name }/{ version }/{ name }-{ target }"
+                            .to_string(),
+                    pkg_fmt: BinstallPkgFmt::Tgz,
+                    bin_dir: Some("{ bin }{ binary-ext }".to_string()),
+                    overrides: BTreeMap::new(),
+                }),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let cargo_toml = &server.files[&manifest];
+        assert!(cargo_toml.contains(
+            "[package.metadata.binstall]\n\
+             pkg-url = \"https://artifacts.example.com/{ name }/{ version }/{ name }-{ target }\"\n\
+             pkg-fmt = \"tgz\"\n\
+             bin-dir = \"{ bin }{ binary-ext }\"\n"
+        ));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                binstall: Some(BinstallMetadata::github_release(
+                    "https://github.com/acme/ci-toolkit/",
+                    "v",
+                )),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let cargo_toml = &server.files[&manifest];
+        let release = "https://github.com/acme/ci-toolkit/releases/download/v{ version }";
+        assert!(cargo_toml.contains(&format!(
+            "[package.metadata.binstall]\n\
+             pkg-url = \"{release}/{{ name }}-{{ target }}{{ binary-ext }}\"\n\
+             pkg-fmt = \"bin\"\n\n\
+             [package.metadata.binstall.overrides.\"aarch64-apple-darwin\"]\n\
+             pkg-url = \"{release}/{{ name }}-macos-aarch64\"\n"
+        )));
+        assert!(cargo_toml.contains(&format!(
+            "[package.metadata.binstall.overrides.\"x86_64-pc-windows-msvc\"]\n\
+             pkg-url = \"{release}/{{ name }}-windows-x86_64.exe\"\n"
+        )));
+        assert_eq!(cargo_toml.matches("binstall.overrides").count(), 5);
+    }
+
+    #[test]
+    fn test_package_metadata() {
+        let orb = create_test_orb();
+        let manifest = PathBuf::from("Cargo.toml");
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        assert!(!server.files[&manifest].contains("readme"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                license: Some("MIT".to_string()),
+                package: Some(PackageMetadata {
+                    description: Some("MCP server for the \"test\" orb".to_string()),
+                    repository: Some("https://git.example.com/ci/test-orb".to_string()),
+                    keywords: vec!["circleci".to_string(), "mcp".to_string()],
+                    registries: vec!["internal".to_string()],
+                }),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let cargo_toml = &server.files[&manifest];
+        assert!(cargo_toml.contains(
+            "license = \"MIT\"\n\
+             description = \"MCP server for the \\\"test\\\" orb\"\n\
+             readme = \"README.md\"\n\
+             repository = \"https://git.example.com/ci/test-orb\"\n\
+             keywords = [\"circleci\", \"mcp\"]\n\
+             publish = [\"internal\"]\n"
+        ));
+        assert!(!cargo_toml.contains("description = \"\"\""));
+    }
+
+    #[test]
+    fn test_chunk_resource_within_limit_is_unchanged() {
+        let parts = chunk_resource("orb://commands/greet", "{}", 2);
+        assert_eq!(
+            parts,
+            [("orb://commands/greet".to_string(), "{}".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_chunk_resource_splits_with_continuation_notes() {
+        let content = "cargo build --release --locked\n".repeat(3);
+        // 56 bytes go to the longest possible note, leaving 34 for content
+        let parts = chunk_resource("orb://jobs/build", &content, 90);
+        assert!(parts.iter().all(|(_, v)| v.len() <= 90), "{parts:?}");
+
+        let keys: Vec<_> = parts.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(
+            keys,
+            [
+                "orb://jobs/build",
+                "orb://jobs/build/part/2",
+                "orb://jobs/build/part/3"
+            ]
+        );
+        assert_eq!(
+            parts[0].1,
+            "cargo build --release --locked\n\n\n[Part 1 of 3; continued at orb://jobs/build/part/2]"
+        );
+        assert!(parts[2].1.ends_with("\n\n[Part 3 of 3]"));
+
+        let rejoined: String = parts
+            .iter()
+            .map(|(_, v)| &v[..v.rfind("\n\n[Part").unwrap()])
+            .collect();
+        assert_eq!(rejoined, content);
+    }
+
+    #[test]
+    fn test_chunk_resource_respects_char_boundaries() {
+        let parts = chunk_resource("orb://x", "ééé", 3);
+        let pieces: Vec<_> = parts
+            .iter()
+            .map(|(_, v)| &v[..v.rfind("\n\n[Part").unwrap()])
+            .collect();
+        assert_eq!(pieces, ["é", "é", "é"]);
+    }
+
+    #[test]
+    fn test_max_resource_bytes_adds_part_resources() {
+        let orb = create_test_orb();
+        let current = PathBuf::from("data/current.bin");
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let whole = String::from_utf8_lossy(&server.binary_files[&current]).into_owned();
+        assert!(!whole.contains("/part/2"));
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(!lib_rs.contains("split into parts is no longer JSON"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                max_resource_bytes: Some(64),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let chunked = String::from_utf8_lossy(&server.binary_files[&current]).into_owned();
+        assert!(chunked.contains("orb://commands/greet/part/2"));
+        assert!(chunked.contains("[Part 1 of "));
+        // Split resources are listed as plain text
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("split into parts is no longer JSON"));
+    }
+
+    #[test]
+    fn test_sandboxed_server_reads_no_environment() {
+        let orb = create_test_orb();
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                sandboxed: true,
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let main_rs = &server.files[&PathBuf::from("src/main.rs")];
+        assert!(main_rs.contains("EnvFilter::new(\"error\")"));
+        assert!(!main_rs.contains("from_default_env"));
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        assert!(server.files[&PathBuf::from("src/main.rs")].contains("from_default_env"));
+    }
+
+    #[test]
+    fn test_http_transport_serves_streamable_http() {
+        let orb = create_test_orb();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let main_rs = &server.files[&PathBuf::from("src/main.rs")];
+        assert!(!main_rs.contains("StreamableHttpService"));
+        assert!(!server.files[&PathBuf::from("Cargo.toml")].contains("axum"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_transport(Transport::Http)
+                .with_http_bind("0.0.0.0:9000".parse().unwrap()),
+        )
+        .unwrap();
+        let main_rs = &server.files[&PathBuf::from("src/main.rs")];
+        assert!(main_rs.contains("const DEFAULT_BIND: &str = \"0.0.0.0:9000\";"));
+        assert!(main_rs.contains("nest_service(\"/mcp\", service)"));
+        let cargo_toml = &server.files[&PathBuf::from("Cargo.toml")];
+        assert!(cargo_toml.contains("\"transport-streamable-http-server\""));
+        assert!(cargo_toml.contains("axum = \"0.8\""));
+        let tests = &server.files[&PathBuf::from("tests/resources.rs")];
+        assert!(tests.contains(".arg(\"--stdio\")"));
+        let readme = &server.files[&PathBuf::from("README.md")];
+        assert!(readme.contains("http://0.0.0.0:9000/mcp"));
+        assert!(!main_rs.contains("tower_http"));
+        assert!(!cargo_toml.contains("tower-http"));
+    }
+
+    #[test]
+    fn test_http_compression_and_body_limit() {
+        let orb = create_test_orb();
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                http_compression: true,
+                http_body_limit: Some(65536),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_transport(Transport::Http),
+        )
+        .unwrap();
+        let main_rs = &server.files[&PathBuf::from("src/main.rs")];
+        assert!(main_rs.contains(".layer(tower_http::compression::CompressionLayer::new())"));
+        assert!(main_rs.contains(".layer(tower_http::limit::RequestBodyLimitLayer::new(65536))"));
+        let cargo_toml = &server.files[&PathBuf::from("Cargo.toml")];
+        assert!(cargo_toml.contains(
+            "tower-http = { version = \"0.6\", features = [\"compression-deflate\", \"compression-gzip\", \"limit\"] }"
+        ));
+
+        // Only the HTTP transport has responses to compress
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                http_compression: true,
+                http_body_limit: Some(65536),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        assert!(!server.files[&PathBuf::from("src/main.rs")].contains("tower_http"));
+        assert!(!server.files[&PathBuf::from("Cargo.toml")].contains("tower-http"));
+    }
+
+    #[test]
+    fn test_http_transport_rejected_when_sandboxed() {
+        let err = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                sandboxed: true,
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_transport(Transport::Http),
+        )
+        .unwrap_err();
+        assert!(matches!(err, GeneratorError::SandboxViolation { .. }));
+    }
+
+    #[test]
+    fn test_sandboxed_rejects_migration_tools() {
+        let err = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                sandboxed: true,
+                conformance_rules_json: Some("[]".to_string()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, GeneratorError::SandboxViolation { .. }));
+    }
+
+    #[test]
+    fn test_servers_support_resource_subscriptions() {
+        let orb = create_test_orb();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains(".enable_resources_subscribe()"));
+        assert!(lib_rs.contains(".enable_resources_list_changed()"));
+        assert!(lib_rs.contains("fn subscribe("));
+        assert!(lib_rs.contains("fn unsubscribe("));
+        assert!(lib_rs.contains("pub async fn notify_resources_changed()"));
+        // Only a dev-mode server changes its resources while running
+        assert!(!lib_rs.contains("notify_resources_changed().await"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0").with_dev_orb_path("/work/orb/src/@orb.yml"),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("notify_resources_changed().await;"));
+    }
+
+    #[test]
+    fn test_dev_mode_server_reads_orb_at_runtime() {
+        let orb = create_test_orb();
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0").with_dev_orb_path("/work/orb/src/@orb.yml"),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("pub const DEV_ORB_PATH: &str = \"/work/orb/src/@orb.yml\";"));
+        assert!(lib_rs.contains("DevOrb::load(path, \"test-orb\", VERSION)?"));
+        assert!(lib_rs.contains("let resources = Self::dev_resources(resources);"));
+        let main_rs = &server.files[&PathBuf::from("src/main.rs")];
+        assert!(main_rs.contains("test_orb_mcp::load_dev_orb(&orb_path)"));
+        assert!(main_rs.contains("tokio::spawn(test_orb_mcp::watch_dev_orb(orb_path));"));
+        let cargo_toml = &server.files[&PathBuf::from("Cargo.toml")];
+        assert!(cargo_toml.contains("gen-orb-mcp = "));
+        assert!(cargo_toml.contains("\"macros\", \"time\"]"));
+        // The embedded data stays as the fallback until the orb parses.
+        assert!(server
+            .binary_files
+            .contains_key(&PathBuf::from("data/current.bin")));
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        assert!(!server.files[&PathBuf::from("src/lib.rs")].contains("DEV_ORB_PATH"));
+        assert!(!server.files[&PathBuf::from("Cargo.toml")].contains("gen-orb-mcp = "));
+
+        let err = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                sandboxed: true,
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_dev_orb_path("orb.yml"),
+        )
+        .unwrap_err();
+        assert!(matches!(err, GeneratorError::SandboxViolation { .. }));
+        let err = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_dev_orb_path("orb.yml")
+                .with_categories(vec![DefinitionCategory::Commands]),
+        )
+        .unwrap_err();
+        assert!(matches!(err, GeneratorError::DevModeOption { .. }));
+        let err = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_dev_orb_path("orb.yml")
+                .with_language(Language::TypeScript),
+        )
+        .unwrap_err();
+        assert!(matches!(err, GeneratorError::UnsupportedOption { .. }));
+        let orbs = vec![("build".to_string(), orb)];
+        let err = CodeGenerator::new()
+            .unwrap()
+            .with_dev_orb_path("orb.yml")
+            .generate_multi(&orbs, "platform", "1.0.0")
+            .unwrap_err();
+        assert!(matches!(err, GeneratorError::MultiOrbOption { .. }));
+        let err = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_dev_orb_path("orb.yml")
+                .with_naming(Naming::default().with_uri_scheme("acme")),
+        )
+        .unwrap_err();
+        assert!(matches!(err, GeneratorError::DevModeOption { .. }));
+    }
+
+    #[test]
+    fn test_naming_shapes_names_and_uris() {
+        let naming = Naming::default()
+            .with_prefix("acme")
+            .with_suffix("server")
+            .with_uri_scheme("acme");
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                prior_versions: vec![("0.9.0".to_string(), create_test_orb())],
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_naming(naming.clone()),
+        )
+        .unwrap();
+        assert_eq!(server.crate_name, "acme_test_orb_server");
+        let main_rs = &server.files[&PathBuf::from("src/main.rs")];
+        assert!(main_rs.contains("acme_test_orb_server::OrbServer::new"));
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"acme-test-orb-server\""));
+        assert!(lib_rs.contains("\"acme://overview\""));
+        assert!(!lib_rs.contains("orb://"));
+        let current = &server.binary_files[&PathBuf::from("data/current.bin")];
+        let current = String::from_utf8_lossy(current);
+        assert!(current.contains("acme://commands/greet"));
+        assert!(!current.contains("orb://"));
+        let versions = &server.binary_files[&PathBuf::from("data/versions.bin")];
+        assert!(String::from_utf8_lossy(versions).contains("acme://v0.9.0/commands/greet"));
+
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_language(Language::TypeScript)
+                .with_naming(naming),
+        )
+        .unwrap();
+        assert_eq!(server.crate_name, "acme-test-orb-server");
+        let data_ts = &server.files[&PathBuf::from("src/data.ts")];
+        assert!(data_ts.contains("acme://overview"));
+        assert!(data_ts.contains("acme://commands/greet"));
+        assert!(!data_ts.contains("orb://"));
+
+        let err = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_naming(Naming::default().with_uri_scheme("Orb")),
+        )
+        .unwrap_err();
+        assert!(matches!(err, GeneratorError::InvalidNaming { .. }));
+    }
+
+    #[test]
+    fn test_typescript_package_layout() {
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                prior_versions: vec![("0.9.0".to_string(), create_test_orb())],
+                license: Some("MIT".to_string()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_language(Language::TypeScript),
+        )
+        .unwrap();
+        assert_eq!(server.crate_name, "test-orb-mcp");
+        let mut paths: Vec<_> = server.files.keys().cloned().collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "README.md",
+                "package.json",
+                "src/data.ts",
+                "src/index.ts",
+                "tsconfig.json"
+            ]
+            .map(PathBuf::from)
+        );
+
+        let package: serde_json::Value =
+            serde_json::from_str(&server.files[&PathBuf::from("package.json")]).unwrap();
+        assert_eq!(package["name"], "test-orb-mcp");
+        assert_eq!(package["version"], "1.0.0");
+        assert_eq!(package["license"], "MIT");
+        assert_eq!(package["bin"]["test-orb-mcp"], "dist/index.js");
+
+        let data_ts = &server.files[&PathBuf::from("src/data.ts")];
+        assert!(data_ts.contains("\"uri\": \"orb://commands/greet\""));
+        assert!(data_ts.contains("\"uri\": \"orb://v0.9.0/commands/greet\""));
+        assert!(data_ts.contains("# test-orb CircleCI Orb"));
+        let index_ts = &server.files[&PathBuf::from("src/index.ts")];
+        assert!(index_ts.contains("new ResourceTemplate(\"orb://v{version}/{kind}/{name}\""));
+        let readme = &server.files[&PathBuf::from("README.md")];
+        assert!(readme.starts_with("# test-orb-mcp\n"));
+        assert!(
+            readme.contains("claude mcp add test-orb -- node /path/to/test-orb-mcp/dist/index.js")
+        );
+        assert!(index_ts.contains("\"get_command\""));
+        assert!(index_ts.contains("\"search_orb\""));
+        assert!(!index_ts.contains("\"get_executor\""));
+        assert!(index_ts.contains("\"use_command\""));
+        assert!(!index_ts.contains("\"use_job\""));
+        assert!(index_ts.contains("\"generate_config_snippet\""));
+        assert!(index_ts.contains("\"validate_invocation\""));
+        assert!(index_ts.contains("function parameterIssues("));
+        assert!(index_ts
+            .contains("kind: z.enum([\"command\"]).describe(\"Kind of definition to invoke\")"));
+    }
+
+    #[test]
+    fn test_typescript_rejects_rust_only_options() {
+        let err = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                property_tests: true,
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_language(Language::TypeScript),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "property tests is not supported when generating typescript"
+        );
+    }
+
+    #[test]
+    fn test_python_package_layout() {
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                prior_versions: vec![("0.9.0".to_string(), create_test_orb())],
+                license: Some("MIT".to_string()),
+                logo: Some(Logo::from_bytes(b"\x89PNG\r\n\x1a\nrest".to_vec()).unwrap()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_language(Language::Python),
+        )
+        .unwrap();
+        assert_eq!(server.crate_name, "test-orb-mcp");
+        let mut paths: Vec<_> = server.files.keys().cloned().collect();
+        paths.sort();
+        assert_eq!(
+            paths,
+            [
+                "README.md",
+                "pyproject.toml",
+                "src/test_orb_mcp/__init__.py",
+                "src/test_orb_mcp/__main__.py",
+                "src/test_orb_mcp/data.json",
+                "src/test_orb_mcp/server.py",
+            ]
+            .map(PathBuf::from)
+        );
+        assert!(server
+            .binary_files
+            .contains_key(&PathBuf::from("src/test_orb_mcp/assets/logo.png")));
+
+        let pyproject = &server.files[&PathBuf::from("pyproject.toml")];
+        assert!(pyproject.contains("name = \"test-orb-mcp\"\nversion = \"1.0.0\"\n"));
+        assert!(pyproject.contains("license = \"MIT\""));
+        assert!(pyproject.contains("dependencies = [\"mcp>=1.12\"]"));
+        assert!(pyproject.contains("\"test-orb-mcp\" = \"test_orb_mcp.server:main\""));
+        assert!(pyproject.contains("packages = [\"src/test_orb_mcp\"]"));
+
+        let data: serde_json::Value =
+            serde_json::from_str(&server.files[&PathBuf::from("src/test_orb_mcp/data.json")])
+                .unwrap();
+        assert_eq!(data["server"]["name"], "test-orb-mcp");
+        let uris: Vec<_> = data["resources"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|r| r["uri"].as_str().unwrap())
+            .collect();
+        assert!(uris.contains(&"orb://commands/greet"));
+        assert!(uris.contains(&"orb://v0.9.0/commands/greet"));
+        assert_eq!(data["definitions"][0]["kind"], "command");
+
+        let server_py = &server.files[&PathBuf::from("src/test_orb_mcp/server.py")];
+        assert!(server_py.contains("uriTemplate=\"orb://v{version}/{kind}/{name}\""));
+        assert!(server_py.contains("LOGO = Path(__file__).with_name(\"assets\") / \"logo.png\""));
+        assert!(server_py.contains("name=\"get_command\""));
+        assert!(!server_py.contains("name=\"get_executor\""));
+        assert!(server_py.contains("name=\"validate_invocation\""));
+        assert!(server_py.contains("name=\"use_command\""));
+        assert!(!server_py.contains("name=\"use_job\""));
+        assert!(server_py.contains("KINDS = [\"command\"]"));
+        let readme = &server.files[&PathBuf::from("README.md")];
+        assert!(readme.starts_with("# test-orb-mcp\n"));
+        assert!(readme.contains("claude mcp add test-orb -- /path/to/venv/bin/test-orb-mcp"));
+
+        let err = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                server_title: Some("Test Orb".to_string()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_language(Language::Python),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "a server title is not supported when generating python"
+        );
+    }
+
+    #[test]
+    fn test_python_server_compiles() {
+        if Command::new("python3").arg("--version").output().is_err() {
+            return;
+        }
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                prior_versions: vec![("0.9.0".to_string(), create_test_orb())],
+                logo: Some(Logo::from_bytes(b"\x89PNG\r\n\x1a\nrest".to_vec()).unwrap()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_language(Language::Python),
+        )
+        .unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        server.write_to(temp_dir.path()).unwrap();
+
+        let output = Command::new("python3")
+            .args(["-m", "py_compile"])
+            .args(
+                server
+                    .files
+                    .keys()
+                    .filter(|path| path.extension().is_some_and(|e| e == "py"))
+                    .map(|path| temp_dir.path().join(path)),
+            )
+            .output()
+            .unwrap();
+        assert!(
+            output.status.success(),
+            "{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
 
-    match output {
-        Ok(output) if output.status.success() => Ok(()),
-        Ok(output) => {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            tracing::warn!("clippy warning: {}", stderr);
-            Ok(())
-        }
-        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
-            tracing::debug!("cargo not found, skipping clippy");
-            Ok(())
+    #[test]
+    fn test_typescript_server_parses() {
+        if Command::new("tsc").arg("--version").output().is_err() {
+            return;
         }
-        Err(e) => Err(GeneratorError::ClippyFailed {
-            message: e.to_string(),
-        }),
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                prior_versions: vec![("0.9.0".to_string(), create_test_orb())],
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_language(Language::TypeScript),
+        )
+        .unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        server.write_to(temp_dir.path()).unwrap();
+
+        let output = Command::new("tsc")
+            .args(["--noEmit", "-p"])
+            .arg(temp_dir.path())
+            .output()
+            .unwrap();
+        // The SDK is not installed, so only syntax errors (TS1xxx) count
+        let diagnostics = String::from_utf8_lossy(&output.stdout);
+        let syntax_errors: Vec<_> = diagnostics
+            .lines()
+            .filter(|line| line.contains("error TS1"))
+            .collect();
+        assert!(syntax_errors.is_empty(), "{}", syntax_errors.join("\n"));
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::collections::HashMap;
+    #[test]
+    fn test_templates_dir_overrides_builtins() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(
+            temp_dir.path().join("Cargo.toml.hbs"),
+            "# Managed by platform team\n[package]\nname = \"{{crate_name}}\"\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("overview.md.hbs"), "ignored").unwrap();
+
+        let opts = GenerateOptions::new("test-orb", "1.0.0")
+            .with_templates_dir(temp_dir.path())
+            .unwrap();
+        let server = generate_in_memory(&create_test_orb(), &opts).unwrap();
+        let cargo_toml = &server.files[&PathBuf::from("Cargo.toml")];
+        assert_eq!(
+            cargo_toml,
+            "# Managed by platform team\n[package]\nname = \"test_orb_mcp\"\n"
+        );
+        // Templates without an override, and non-overridable ones, are the
+        // built-ins.
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("orb://commands/greet"));
+        assert!(server.files[&PathBuf::from("src/main.rs")].contains("fn main"));
+
+        let err = generate_in_memory(
+            &create_test_orb(),
+            &opts.with_language(Language::TypeScript),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "template overrides is not supported when generating typescript"
+        );
 
-    use tempfile::TempDir;
+        fs::write(temp_dir.path().join("lib.rs.hbs"), "{{#if}}").unwrap();
+        let err = CodeGenerator::new()
+            .unwrap()
+            .with_templates_dir(temp_dir.path())
+            .err()
+            .unwrap();
+        assert!(matches!(err, GeneratorError::TemplateRegister { .. }));
 
-    use super::*;
-    use crate::parser::{Command, OrbDefinition, Parameter, ParameterType};
+        let err = CodeGenerator::new()
+            .unwrap()
+            .with_templates_dir(&temp_dir.path().join("missing"))
+            .err()
+            .unwrap();
+        assert!(matches!(err, GeneratorError::FileRead { .. }));
+    }
 
-    fn create_test_orb() -> OrbDefinition {
-        let mut orb = OrbDefinition {
-            version: "2.1".to_string(),
-            description: Some("Test orb for unit tests".to_string()),
-            ..Default::default()
-        };
+    #[test]
+    fn test_only_commands_omits_other_categories() {
+        let mut orb = create_test_orb();
+        orb.jobs.insert("build".to_string(), Default::default());
+        let opts = GenerateOptions::new("test-orb", "1.0.0")
+            .with_categories(vec![DefinitionCategory::Commands]);
 
-        // Add a command
-        let mut params = HashMap::new();
-        params.insert(
-            "name".to_string(),
-            Parameter {
-                param_type: ParameterType::String,
-                description: Some("Name to greet".to_string()),
-                default: Some(serde_yaml::Value::String("World".to_string())),
-                enum_values: None,
-            },
+        let server = generate_in_memory(&orb, &opts).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+
+        assert!(lib_rs.contains("orb://commands/greet"));
+        assert!(!lib_rs.contains("orb://jobs/build"));
+    }
+
+    #[test]
+    fn test_include_and_exclude_filter_definitions() {
+        let mut orb = create_test_orb();
+        for job in ["build", "deploy-staging", "deploy-prod"] {
+            orb.jobs.insert(job.to_string(), Default::default());
+        }
+        orb.executors
+            .insert("default".to_string(), Default::default());
+        let opts = GenerateOptions::new("test-orb", "1.0.0").with_filters(
+            vec!["commands/*".to_string(), "jobs/*".to_string()],
+            vec!["jobs/deploy-*".to_string()],
         );
 
-        orb.commands.insert(
-            "greet".to_string(),
-            Command {
-                description: Some("Greet someone".to_string()),
-                parameters: params,
-                steps: vec![],
-            },
+        let server = generate_in_memory(&orb, &opts).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("orb://commands/greet"));
+        assert!(lib_rs.contains("orb://jobs/build"));
+        assert!(!lib_rs.contains("orb://jobs/deploy-staging"));
+        assert!(!lib_rs.contains("orb://jobs/deploy-prod"));
+        assert!(!lib_rs.contains("orb://executors/default"));
+        assert_eq!(
+            CodeGenerator::from_options(&opts)
+                .unwrap()
+                .unmatched_includes(&create_test_orb()),
+            vec!["jobs/*"]
         );
 
-        orb
+        let err = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_filters(vec![], vec!["jobs/[deploy".to_string()]),
+        )
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("invalid pattern 'jobs/[deploy'"));
     }
 
     #[test]
-    fn test_code_generator_new() {
-        let generator = CodeGenerator::new();
-        assert!(generator.is_ok());
+    fn test_source_rev_embedded() {
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("pub const SOURCE_REV: Option<&str> = None;"));
+        assert!(lib_rs.contains("\"orb://server-info\" => Some(server_info_content()),"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                source_rev: Some("4f2c9e1".to_string()),
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("pub const SOURCE_REV: Option<&str> = Some(\"4f2c9e1\");"));
     }
 
     #[test]
-    fn test_generate_produces_files() {
-        let generator = CodeGenerator::new().unwrap();
-        let orb = create_test_orb();
+    fn test_graph_resource() {
+        let mut orb = create_test_orb();
+        orb.jobs.insert(
+            "hello".to_string(),
+            serde_yaml::from_str("steps:\n  - greet\n  - checkout\n").unwrap(),
+        );
 
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("\"orb://graph\" => Some(GRAPH_CONTENT.to_string()),"));
+        assert!(lib_rs.contains("\"test-orb Dependency Graph\""));
+        let content = lib_rs
+            .split_once("const GRAPH_CONTENT: &str = r##\"")
+            .and_then(|(_, rest)| rest.split_once("\"##;"))
+            .unwrap()
+            .0;
+        let graph: serde_json::Value = serde_json::from_str(content).unwrap();
+        assert_eq!(
+            graph["edges"],
+            serde_json::json!([
+                { "from": "jobs/hello", "to": "commands/greet", "kind": "invokes" }
+            ])
+        );
+    }
 
-        assert!(server.files.contains_key(&PathBuf::from("src/main.rs")));
-        assert!(server.files.contains_key(&PathBuf::from("src/lib.rs")));
-        assert!(server.files.contains_key(&PathBuf::from("Cargo.toml")));
-        assert_eq!(server.crate_name, "test_orb_mcp");
-        assert_eq!(server.orb_name, "test-orb");
+    #[test]
+    fn test_comparison_resource_with_prior_versions() {
+        let orb = create_test_orb();
+        let without = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "2.0.0")).unwrap();
+        let lib_rs = without.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+        assert!(!lib_rs.contains("orb://compare"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                prior_versions: vec![("1.6.0".to_string(), orb.clone())],
+                ..GenerateOptions::new("test-orb", "2.0.0")
+            },
+        )
+        .unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("\"orb://compare\" => Some(COMPARISON_CONTENT.to_string()),"));
+        assert!(lib_rs.contains("| Name | 1.6.0 | 2.0.0 |"));
+        assert!(lib_rs.contains("| `greet` | ✓ | ✓ |"));
     }
 
     #[test]
-    fn test_generated_main_contains_tokio() {
-        let generator = CodeGenerator::new().unwrap();
+    fn test_changes_resource_omitted_without_previous() {
         let orb = create_test_orb();
 
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
-        let main_rs = server.files.get(&PathBuf::from("src/main.rs")).unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
-        assert!(main_rs.contains("#[tokio::main]"));
-        assert!(main_rs.contains("test_orb_mcp::OrbServer::new"));
+        assert!(!lib_rs.contains("orb://changes"));
     }
 
     #[test]
-    fn test_generated_lib_contains_resources() {
-        let generator = CodeGenerator::new().unwrap();
+    fn test_changes_resource_from_previous_orb() {
         let orb = create_test_orb();
+        let mut previous = orb.clone();
+        previous
+            .commands
+            .insert("legacy".to_string(), Command::default());
+        let opts = GenerateOptions {
+            previous_orb: Some(("0.9.0".to_string(), previous)),
+            ..GenerateOptions::new("test-orb", "1.0.0")
+        };
 
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &opts).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
-        assert!(lib_rs.contains("ServerHandler"));
-        assert!(lib_rs.contains("RawResource"));
-        assert!(lib_rs.contains("orb://commands/greet"));
-        assert!(lib_rs.contains("orb://overview"));
+        assert!(lib_rs.contains("\"orb://changes\" => Some(CHANGES_CONTENT.to_string()),"));
+        assert!(lib_rs.contains("# test-orb changes since 0.9.0"));
+        assert!(lib_rs.contains("- Removed `legacy`"));
     }
 
     #[test]
-    fn test_generated_cargo_toml() {
-        let generator = CodeGenerator::new().unwrap();
+    fn test_changes_resource_defaults_to_newest_prior_version() {
         let orb = create_test_orb();
+        let opts = GenerateOptions {
+            prior_versions: vec![
+                ("0.10.0".to_string(), orb.clone()),
+                ("0.9.0".to_string(), orb.clone()),
+            ],
+            ..GenerateOptions::new("test-orb", "1.0.0")
+        };
 
-        let server = generator.generate(&orb, "test-orb", "2.5.0").unwrap();
-        let cargo = server.files.get(&PathBuf::from("Cargo.toml")).unwrap();
+        let server = generate_in_memory(&orb, &opts).unwrap();
+        let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
-        assert!(cargo.contains("name = \"test_orb_mcp\""));
-        assert!(cargo.contains("version = \"2.5.0\""));
-        assert!(cargo.contains("rmcp = "));
-        assert!(cargo.contains("tokio = "));
+        assert!(lib_rs.contains("# test-orb changes since 0.10.0"));
     }
 
     #[test]
     fn test_write_to_directory() {
-        let generator = CodeGenerator::new().unwrap();
         let orb = create_test_orb();
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
 
         let temp_dir = TempDir::new().unwrap();
         server.write_to(temp_dir.path()).unwrap();
@@ -661,6 +3160,172 @@ mod tests {
         assert!(temp_dir.path().join("Cargo.toml").exists());
     }
 
+    #[test]
+    fn test_content_hash_tracks_output() {
+        let orb = create_test_orb();
+        let first = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let second = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        assert_eq!(first.content_hash(), second.content_hash());
+        assert_eq!(first.content_hash().len(), 64);
+
+        let bumped = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.1")).unwrap();
+        assert_ne!(first.content_hash(), bumped.content_hash());
+    }
+
+    #[test]
+    fn test_output_independent_of_map_order() {
+        let names = ["alpha", "bravo", "charlie", "delta", "echo", "foxtrot"];
+        let build = |order: &[&str]| {
+            let mut orb = create_test_orb();
+            for name in order {
+                let mut command = Command::default();
+                for param in order {
+                    command
+                        .parameters
+                        .insert(param.to_string(), Parameter::default());
+                }
+                orb.commands.insert(name.to_string(), command);
+                orb.jobs.insert(name.to_string(), Default::default());
+            }
+            orb
+        };
+        let reversed: Vec<&str> = names.iter().rev().copied().collect();
+        let first =
+            generate_in_memory(&build(&names), &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let second = generate_in_memory(
+            &build(&reversed),
+            &GenerateOptions::new("test-orb", "1.0.0"),
+        )
+        .unwrap();
+        assert_eq!(first.content_hash(), second.content_hash());
+
+        let lib_rs = &first.files[&PathBuf::from("src/lib.rs")];
+        let alpha = lib_rs.find("\"orb://commands/alpha\"").unwrap();
+        let foxtrot = lib_rs.find("\"orb://commands/foxtrot\"").unwrap();
+        assert!(alpha < foxtrot);
+    }
+
+    #[test]
+    fn test_into_tarball_packs_files_under_crate_dir() {
+        use std::io::Read;
+
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions::new("test-orb", "1.0.0"),
+        )
+        .unwrap();
+        let cargo_toml = server.files[&PathBuf::from("Cargo.toml")].clone();
+        let tarball = server.clone().into_tarball();
+        assert_eq!(tarball, server.into_tarball());
+
+        let mut tar = Vec::new();
+        flate2::read::GzDecoder::new(tarball.as_slice())
+            .read_to_end(&mut tar)
+            .unwrap();
+        assert_eq!(&tar[..24], b"test_orb_mcp/Cargo.toml\0");
+        assert_eq!(&tar[512..512 + cargo_toml.len()], cargo_toml.as_bytes());
+    }
+
+    #[test]
+    fn test_diff_against_existing_output() {
+        let orb = create_test_orb();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+
+        let diff = server.diff_against(temp_dir.path()).unwrap();
+        assert!(diff.contains("--- /dev/null\n+++ b/Cargo.toml\n"));
+
+        server.write_to(temp_dir.path()).unwrap();
+        assert_eq!(server.diff_against(temp_dir.path()).unwrap(), "");
+
+        let bumped = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.1")).unwrap();
+        let diff = bumped.diff_against(temp_dir.path()).unwrap();
+        assert!(diff.contains("--- a/Cargo.toml\n+++ b/Cargo.toml\n"));
+        assert!(diff.contains("-version = \"1.0.0\"\n+version = \"1.0.1\"\n"));
+        assert!(!diff.contains("src/main.rs"));
+        // Nothing was written
+        let cargo_toml = fs::read_to_string(temp_dir.path().join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("version = \"1.0.0\""));
+    }
+
+    #[test]
+    fn test_format_sources_formats_rust_files_in_memory() {
+        let orb = create_test_orb();
+        let mut server =
+            generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let unformatted = "fn  greet( ){}\n".to_string();
+        server
+            .files
+            .insert(PathBuf::from("src/extra.rs"), unformatted.clone());
+        server
+            .files
+            .insert(PathBuf::from("NOTES.md"), unformatted.clone());
+
+        server.format_sources().unwrap();
+        if Command::new("rustfmt").arg("--version").output().is_ok() {
+            assert_eq!(server.files[Path::new("src/extra.rs")], "fn greet() {}\n");
+        }
+        assert_eq!(server.files[Path::new("NOTES.md")], unformatted);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_clippy_fix_reads_fixed_sources_back() {
+        let orb = create_test_orb();
+        let mut server =
+            generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        // Stands in for cargo; the clippy arguments become positional
+        // parameters of the script
+        let mut cargo = Command::new("sh");
+        cargo
+            .current_dir(temp_dir.path())
+            .args(["-c", "printf '// fixed\\n' >> src/main.rs"]);
+
+        server.clippy_fix(temp_dir.path(), cargo).unwrap();
+        assert!(server.files[Path::new("src/main.rs")].ends_with("}\n// fixed\n"));
+        assert_eq!(
+            fs::read_to_string(temp_dir.path().join("src/main.rs")).unwrap(),
+            server.files[Path::new("src/main.rs")]
+        );
+    }
+
+    #[test]
+    fn test_sanitize_orb_name() {
+        assert_eq!(sanitize_orb_name("my.orb@2"), "my-orb-2");
+        assert_eq!(sanitize_orb_name("my orb"), "my-orb");
+        assert_eq!(sanitize_orb_name("2fa"), "orb-2fa");
+        assert_eq!(sanitize_orb_name("_private"), "orb-_private");
+        assert_eq!(sanitize_orb_name("--x--"), "x");
+        assert_eq!(sanitize_orb_name("@@"), "orb");
+        for name in ["my.orb@2", "2fa", "", "@@", "_private"] {
+            assert!(validate_orb_name(&sanitize_orb_name(name)).is_ok());
+        }
+    }
+
+    #[test]
+    fn test_generate_sanitizes_invalid_name() {
+        let orb = create_test_orb();
+
+        let server = generate_in_memory(&orb, &GenerateOptions::new("my.orb@2", "1.0.0")).unwrap();
+
+        assert_eq!(server.orb_name, "my-orb-2");
+        assert_eq!(server.crate_name, "my_orb_2_mcp");
+    }
+
+    #[test]
+    fn test_generate_strict_names_rejects_invalid_name() {
+        let opts = GenerateOptions {
+            strict_names: true,
+            ..GenerateOptions::new("my.orb@2", "1.0.0")
+        };
+        let orb = create_test_orb();
+
+        let err = generate_in_memory(&orb, &opts).unwrap_err();
+
+        assert!(matches!(err, GeneratorError::InvalidOrbName { .. }));
+    }
+
     #[test]
     fn test_validate_orb_name() {
         assert!(validate_orb_name("my-orb").is_ok());
@@ -676,10 +3341,9 @@ mod tests {
 
     #[test]
     fn test_empty_orb_generates_valid_code() {
-        let generator = CodeGenerator::new().unwrap();
         let orb = OrbDefinition::default();
 
-        let server = generator.generate(&orb, "empty-orb", "0.1.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("empty-orb", "0.1.0")).unwrap();
 
         // Should still generate valid files even with no commands/jobs/executors
         assert!(server.files.contains_key(&PathBuf::from("src/main.rs")));
@@ -699,13 +3363,12 @@ mod tests {
         );
 
         let current_orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_prior_versions(vec![("1.0.0".to_string(), prior_orb)]);
+        let opts = GenerateOptions {
+            prior_versions: vec![("1.0.0".to_string(), prior_orb)],
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator
-            .generate(&current_orb, "test-orb", "2.0.0")
-            .unwrap();
+        let server = generate_in_memory(&current_orb, &opts).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         // Should expose the version list resource
@@ -729,11 +3392,12 @@ mod tests {
             r#"[{"type":"JobRenamed","from":"old","to":"new","since_version":"2.0.0","description":"renamed"}]"#
                 .to_string();
         let orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_conformance_rules_json(rules_json.clone());
+        let opts = GenerateOptions {
+            conformance_rules_json: Some(rules_json.clone()),
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator.generate(&orb, "test-orb", "2.0.0").unwrap();
+        let server = generate_in_memory(&orb, &opts).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         assert!(
@@ -753,9 +3417,7 @@ mod tests {
     #[test]
     fn test_generate_without_tools_has_no_tool_methods() {
         let orb = create_test_orb();
-        let generator = CodeGenerator::new().unwrap();
-
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         assert!(
@@ -772,11 +3434,12 @@ mod tests {
     fn test_generate_with_tools_cargo_toml_includes_gen_orb_mcp_dep() {
         let rules_json = r#"[]"#.to_string();
         let orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_conformance_rules_json(rules_json);
+        let opts = GenerateOptions {
+            conformance_rules_json: Some(rules_json),
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator.generate(&orb, "test-orb", "2.0.0").unwrap();
+        let server = generate_in_memory(&orb, &opts).unwrap();
         let cargo_toml = server.files.get(&PathBuf::from("Cargo.toml")).unwrap();
 
         // Check for a dependency entry (not just the comment "Generated by
@@ -790,9 +3453,7 @@ mod tests {
     #[test]
     fn test_generate_without_tools_cargo_toml_excludes_gen_orb_mcp_dep() {
         let orb = create_test_orb();
-        let generator = CodeGenerator::new().unwrap();
-
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
         let cargo_toml = server.files.get(&PathBuf::from("Cargo.toml")).unwrap();
 
         // Check absence of a dependency entry (the comment "Generated by gen-orb-mcp"
@@ -821,13 +3482,12 @@ mod tests {
         );
 
         let current_orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_prior_versions(vec![("1.0.0".to_string(), prior_orb)]);
+        let opts = GenerateOptions {
+            prior_versions: vec![("1.0.0".to_string(), prior_orb)],
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator
-            .generate(&current_orb, "test-orb", "2.0.0")
-            .unwrap();
+        let server = generate_in_memory(&current_orb, &opts).unwrap();
 
         // Binary data file must be present
         assert!(
@@ -855,9 +3515,7 @@ mod tests {
     #[test]
     fn test_generate_without_prior_versions_has_no_versions_dir() {
         let orb = create_test_orb();
-        let generator = CodeGenerator::new().unwrap();
-
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
 
         assert!(
             !server
@@ -880,13 +3538,12 @@ mod tests {
         );
 
         let current_orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_prior_versions(vec![("1.0.0".to_string(), prior_orb)]);
+        let opts = GenerateOptions {
+            prior_versions: vec![("1.0.0".to_string(), prior_orb)],
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator
-            .generate(&current_orb, "test-orb", "2.0.0")
-            .unwrap();
+        let server = generate_in_memory(&current_orb, &opts).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         assert!(
@@ -910,13 +3567,12 @@ mod tests {
         );
 
         let current_orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_prior_versions(vec![("1.0.0".to_string(), prior_orb)]);
+        let opts = GenerateOptions {
+            prior_versions: vec![("1.0.0".to_string(), prior_orb)],
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator
-            .generate(&current_orb, "test-orb", "2.0.0")
-            .unwrap();
+        let server = generate_in_memory(&current_orb, &opts).unwrap();
         let versions_mod = server
             .files
             .get(&PathBuf::from("src/versions/mod.rs"))
@@ -953,13 +3609,12 @@ mod tests {
         );
 
         let current_orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_prior_versions(vec![("1.0.0".to_string(), prior_orb)]);
+        let opts = GenerateOptions {
+            prior_versions: vec![("1.0.0".to_string(), prior_orb)],
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator
-            .generate(&current_orb, "test-orb", "2.0.0")
-            .unwrap();
+        let server = generate_in_memory(&current_orb, &opts).unwrap();
 
         let blob = server
             .binary_files
@@ -1040,13 +3695,12 @@ mod tests {
             );
         }
         let current_orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_prior_versions(vec![("1.0.0".to_string(), prior_orb)]);
+        let opts = GenerateOptions {
+            prior_versions: vec![("1.0.0".to_string(), prior_orb)],
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator
-            .generate(&current_orb, "test-orb", "2.0.0")
-            .unwrap();
+        let server = generate_in_memory(&current_orb, &opts).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         // Prior-version URIs must NOT appear in list_resources
@@ -1064,8 +3718,7 @@ mod tests {
         // Resources must be served via current::get(uri) backed by
         // data/current.bin (embedded via include_bytes!).
         let orb = create_test_orb(); // has "greet" command
-        let generator = CodeGenerator::new().unwrap();
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         assert!(
@@ -1077,8 +3730,7 @@ mod tests {
     #[test]
     fn test_current_bin_generated_for_orb_with_resources() {
         let orb = create_test_orb();
-        let generator = CodeGenerator::new().unwrap();
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
 
         assert!(
             server
@@ -1091,8 +3743,7 @@ mod tests {
     #[test]
     fn test_current_bin_not_generated_for_empty_orb() {
         let orb = OrbDefinition::default();
-        let generator = CodeGenerator::new().unwrap();
-        let server = generator.generate(&orb, "empty-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("empty-orb", "1.0.0")).unwrap();
 
         assert!(
             !server
@@ -1105,8 +3756,7 @@ mod tests {
     #[test]
     fn test_current_mod_uses_include_bytes() {
         let orb = create_test_orb();
-        let generator = CodeGenerator::new().unwrap();
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
 
         let current_mod = server
             .files
@@ -1127,8 +3777,7 @@ mod tests {
     fn test_current_bin_round_trips() {
         // Every current-version resource URI must be retrievable from current.bin.
         let orb = create_test_orb(); // has "greet" command
-        let generator = CodeGenerator::new().unwrap();
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
 
         let blob = server
             .binary_files
@@ -1145,8 +3794,7 @@ mod tests {
     #[test]
     fn test_lib_declares_mod_current_when_resources_exist() {
         let orb = create_test_orb();
-        let generator = CodeGenerator::new().unwrap();
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         assert!(
@@ -1155,12 +3803,391 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_query_tools_follow_exposed_categories() {
+        let mut orb = create_test_orb();
+        orb.jobs.insert("build".to_string(), Default::default());
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        for tool in ["get_command", "get_job", "list_parameters", "search_orb"] {
+            assert!(
+                lib_rs.contains(&format!("\"{tool}\" =>")),
+                "{tool} not handled"
+            );
+        }
+        assert!(lib_rs.contains("    (\"command\", \"orb://commands/greet\"),\n"));
+        assert!(lib_rs.contains("    (\"job\", \"orb://jobs/build\"),\n"));
+        assert!(!lib_rs.contains("\"get_executor\""));
+        assert!(lib_rs.contains("fn slugify(name: &str) -> String"));
+        assert!(!lib_rs.contains("\"version\": {"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                prior_versions: vec![("0.9.0".to_string(), orb.clone())],
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            }
+            .with_categories(vec![DefinitionCategory::Jobs]),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(!lib_rs.contains("\"get_command\""));
+        assert!(lib_rs.contains("\"get_job\" =>"));
+        assert!(lib_rs.contains("\"version\": {"));
+
+        let server = generate_in_memory(
+            &OrbDefinition::default(),
+            &GenerateOptions::new("empty-orb", "1.0.0"),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(!lib_rs.contains("list_parameters"));
+        assert!(!lib_rs.contains("search_orb"));
+    }
+
+    #[test]
+    fn test_generate_multi_namespaces_definitions() {
+        let orbs = vec![
+            ("build".to_string(), create_test_orb()),
+            ("deploy".to_string(), create_test_orb()),
+        ];
+        let server = CodeGenerator::new()
+            .unwrap()
+            .generate_multi(&orbs, "platform", "1.0.0")
+            .unwrap();
+        assert_eq!(server.crate_name, "platform_mcp");
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("names take the form <orb>/<name>"));
+        assert!(lib_rs.contains("\"orb://build/commands/greet\""));
+        assert!(lib_rs.contains("\"orb://deploy/commands/greet\""));
+        assert!(lib_rs.contains("\"deploy/greet\""));
+
+        let err = CodeGenerator::new()
+            .unwrap()
+            .with_prior_versions(vec![("0.9.0".to_string(), create_test_orb())])
+            .generate_multi(&orbs, "platform", "1.0.0")
+            .unwrap_err();
+        assert!(matches!(err, GeneratorError::MultiOrbOption { .. }));
+
+        let duplicate = vec![
+            ("build".to_string(), create_test_orb()),
+            ("build".to_string(), create_test_orb()),
+        ];
+        let err = CodeGenerator::new()
+            .unwrap()
+            .generate_multi(&duplicate, "platform", "1.0.0")
+            .unwrap_err();
+        assert!(matches!(err, GeneratorError::InvalidOrbName { .. }));
+    }
+
+    #[test]
+    fn test_merge_orbs_namespaces_references() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+version: 2.1
+commands:
+  install:
+    steps: [checkout]
+  wrap:
+    parameters:
+      then: {type: steps, default: [install]}
+    steps:
+      - steps: << parameters.then >>
+executors:
+  default:
+    docker: [{image: cimg/base:current}]
+jobs:
+  test:
+    executor: {name: default}
+    parameters:
+      runner: {type: executor, default: default}
+    steps:
+      - install
+      - when:
+          condition: true
+          steps: [{wrap: {then: [install, checkout]}}]
+workflows:
+  main:
+    jobs:
+      - test
+      - test:
+          name: again
+          requires: [test]
+          pre-steps: [install]
+"#,
+        )
+        .unwrap();
+        let merged = merge_orbs(&[("node".to_string(), &orb)]);
+
+        let yaml = serde_yaml::to_string(&merged.commands["node/wrap"]).unwrap();
+        assert!(yaml.contains("- node/install"), "{yaml}");
+        let yaml = serde_yaml::to_string(&merged.jobs["node/test"]).unwrap();
+        assert!(yaml.contains("name: node/default"), "{yaml}");
+        assert!(yaml.contains("default: node/default"), "{yaml}");
+        assert!(yaml.contains("- node/install\n"), "{yaml}");
+        assert!(yaml.contains("node/wrap:"), "{yaml}");
+        assert!(yaml.contains("- checkout\n"), "{yaml}");
+        assert!(!yaml.contains("node/checkout"), "{yaml}");
+
+        let jobs = &merged.workflows["node/main"].jobs;
+        assert_eq!(jobs[0].job, "node/test");
+        assert_eq!(jobs[1].job, "node/test");
+        assert_eq!(jobs[1].options.name.as_deref(), Some("again"));
+        assert_eq!(jobs[1].options.requires, ["node/test"]);
+        let pre_steps = jobs[1].options.pre_steps.as_ref().unwrap().steps();
+        assert_eq!(pre_steps, [Step::Simple("node/install".to_string())]);
+
+        assert!(merged.orbs.is_empty());
+        assert!(merged.parameters.is_empty());
+    }
+
+    #[test]
+    fn test_prompts_follow_exposed_categories() {
+        let mut orb = create_test_orb();
+        orb.jobs.insert("build".to_string(), Default::default());
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains(".enable_prompts()"));
+        for prompt in ["use_command", "use_job", "choose_executor"] {
+            assert!(
+                lib_rs.contains(&format!("\"{prompt}\" =>")),
+                "{prompt} not handled"
+            );
+        }
+        assert!(lib_rs.contains("\"Command name: greet\""));
+        assert!(lib_rs.contains("\"Job name: build\""));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_categories(vec![DefinitionCategory::Commands]),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"use_command\" =>"));
+        assert!(!lib_rs.contains("\"use_job\""));
+
+        let server = generate_in_memory(
+            &OrbDefinition::default(),
+            &GenerateOptions::new("empty-orb", "1.0.0"),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(!lib_rs.contains("enable_prompts"));
+        assert!(!lib_rs.contains("fn list_prompts"));
+    }
+
+    #[test]
+    fn test_readme_documents_server() {
+        let mut orb = create_test_orb();
+        orb.jobs.insert("build".to_string(), Default::default());
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                source_rev: Some("abc1234".to_string()),
+                ..GenerateOptions::new("test-orb", "1.2.0")
+            },
+        )
+        .unwrap();
+        let readme = &server.files[&PathBuf::from("README.md")];
+        assert!(readme.starts_with("# test_orb_mcp\n"));
+        assert!(readme.contains("| Server version | 1.2.0 |"));
+        assert!(readme.contains("| Orb source revision | `abc1234` |"));
+        assert!(readme.contains("| `orb://commands/greet` | Command `greet` |"));
+        assert!(readme.contains("| `orb://jobs/build` | Job `build` |"));
+        assert!(readme.contains("\"command\": \"/path/to/test_orb_mcp\""));
+        assert!(readme.contains("| `use_job` |"));
+        assert!(!readme.contains("plan_migration"));
+    }
+
+    #[test]
+    fn test_examples_exposed_as_resources() {
+        let mut orb = create_test_orb();
+        orb.examples.insert(
+            "basic".to_string(),
+            crate::parser::Example {
+                description: Some("Greet the world".to_string()),
+                ..Default::default()
+            },
+        );
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"orb://examples/basic\""));
+        assert!(lib_rs.contains("\"Example: basic - Greet the world\""));
+        assert!(lib_rs.contains("## Examples (1)"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_categories(vec![DefinitionCategory::Commands]),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(!lib_rs.contains("orb://examples/"));
+    }
+
+    #[test]
+    fn test_definition_sources_served_as_yaml() {
+        let yaml = "parameters:\n  name:\n    type: string\nsteps: []\n";
+        let sources = HashMap::from([
+            ("commands/greet".to_string(), yaml.to_string()),
+            ("jobs/missing".to_string(), "steps: []\n".to_string()),
+        ]);
+        let opts = GenerateOptions {
+            sources,
+            ..GenerateOptions::new("test-orb", "1.0.0")
+        };
+        let server = generate_in_memory(&create_test_orb(), &opts).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"orb://source/commands/greet\""));
+        assert!(lib_rs.contains("\"application/yaml\""));
+        assert!(!lib_rs.contains("orb://source/jobs/"));
+        let blob = &server.binary_files[&PathBuf::from("data/current.bin")];
+        assert_eq!(
+            lookup_versions_bin(blob, "orb://source/commands/greet").as_deref(),
+            Some(yaml)
+        );
+
+        let orbs = vec![("build".to_string(), create_test_orb())];
+        let err = CodeGenerator::from_options(&opts)
+            .unwrap()
+            .generate_multi(&orbs, "platform", "1.0.0")
+            .unwrap_err();
+        assert!(matches!(err, GeneratorError::MultiOrbOption { .. }));
+    }
+
+    #[test]
+    fn test_parameter_schemas_served_and_checked() {
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                property_tests: true,
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"application/schema+json\""));
+        assert!(
+            lib_rs.contains("    (\"command\", \"greet\", \"orb://schemas/commands/greet\"),\n")
+        );
+        assert!(lib_rs.contains("\"check_parameters\" =>"));
+        assert!(lib_rs.contains("fn check_parameters_tool() -> Tool"));
+        assert!(lib_rs.contains("\"validate_invocation\" =>"));
+        assert!(lib_rs.contains("name: \"validate_invocation\".into(),"));
+        assert!(lib_rs.contains("\"code\": \"unknown_definition\","));
+        let blob = &server.binary_files[&PathBuf::from("data/current.bin")];
+        let schema: serde_json::Value = serde_json::from_str(
+            &lookup_versions_bin(blob, "orb://schemas/commands/greet").unwrap(),
+        )
+        .unwrap();
+        assert_eq!(schema["properties"]["name"]["default"], "World");
+        assert_eq!(schema["required"], serde_json::json!([]));
+        let properties = &server.files[&PathBuf::from("tests/properties.rs")];
+        assert!(properties.contains("\"check_parameters\","));
+        assert!(properties.contains("\"validate_invocation\","));
+
+        let server = generate_in_memory(
+            &OrbDefinition::default(),
+            &GenerateOptions::new("empty-orb", "1.0.0"),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(!lib_rs.contains("check_parameters"));
+        assert!(!lib_rs.contains("validate_invocation"));
+    }
+
+    #[test]
+    fn test_config_snippet_tool_generated_for_commands_and_jobs() {
+        let server = generate_in_memory(
+            &create_test_orb(),
+            &GenerateOptions {
+                property_tests: true,
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"generate_config_snippet\" =>"));
+        assert!(lib_rs.contains("fn config_snippet("));
+        assert!(lib_rs.contains("\"enum\": [\"command\"],\n"));
+        assert!(lib_rs.contains("None => format!(\"<namespace>/{orb}@{version}\"),"));
+        let properties = &server.files[&PathBuf::from("tests/properties.rs")];
+        assert!(properties.contains("\"generate_config_snippet\","));
+
+        let mut orb = OrbDefinition::default();
+        orb.executors
+            .insert("default".to_string(), Default::default());
+        let server =
+            generate_in_memory(&orb, &GenerateOptions::new("executor-orb", "1.0.0")).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(!lib_rs.contains("generate_config_snippet"));
+        assert!(!lib_rs.contains("fn config_snippet("));
+    }
+
+    #[test]
+    fn test_imported_orbs_served_under_their_alias() {
+        let mut orb = create_test_orb();
+        orb.orbs
+            .insert("node".to_string(), "circleci/node@5.1.0".to_string());
+        let node: OrbDefinition = serde_yaml::from_str(
+            "version: 2.1\ncommands:\n  install:\n    description: Install packages\n    steps: []\n",
+        )
+        .unwrap();
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions {
+                imported_orbs: vec![("node".to_string(), node.clone())],
+                ..GenerateOptions::new("test-orb", "1.0.0")
+            },
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"orb://commands/greet\""));
+        assert!(lib_rs.contains("\"orb://node/commands/install\""));
+        assert!(lib_rs.contains("\"Command: node/install - Install packages\""));
+        assert!(lib_rs.contains(r#"["node"].contains(alias)"#));
+
+        let orbs = vec![
+            ("build".to_string(), create_test_orb()),
+            ("deploy".to_string(), create_test_orb()),
+        ];
+        let err = CodeGenerator::new()
+            .unwrap()
+            .with_imported_orbs(vec![("node".to_string(), node)])
+            .generate_multi(&orbs, "platform", "1.0.0")
+            .unwrap_err();
+        assert!(matches!(err, GeneratorError::MultiOrbOption { .. }));
+    }
+
+    #[test]
+    fn test_workflows_exposed_as_resources() {
+        let mut orb = create_test_orb();
+        orb.workflows.insert(
+            "main".to_string(),
+            serde_yaml::from_str("jobs: [build, test]").unwrap(),
+        );
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"orb://workflows/main\""));
+        assert!(lib_rs.contains("\"Workflow: main\""));
+        assert!(lib_rs.contains("## Workflows (1)"));
+
+        let server = generate_in_memory(
+            &orb,
+            &GenerateOptions::new("test-orb", "1.0.0")
+                .with_categories(vec![DefinitionCategory::Commands]),
+        )
+        .unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(!lib_rs.contains("orb://workflows/"));
+    }
+
     #[test]
     fn test_get_version_tool_always_present() {
         // get_version must appear in list_tools even without conformance rules
         let orb = create_test_orb();
-        let generator = CodeGenerator::new().unwrap();
-        let server = generator.generate(&orb, "test-orb", "6.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "6.0.0")).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
         assert!(
             lib_rs.contains("get_version"),
@@ -1178,10 +4205,11 @@ mod tests {
             r#"[{"type":"JobRenamed","from":"old","to":"new","since_version":"2.0.0","description":"renamed"}]"#
                 .to_string();
         let orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_conformance_rules_json(rules_json);
-        let server = generator.generate(&orb, "test-orb", "2.0.0").unwrap();
+        let opts = GenerateOptions {
+            conformance_rules_json: Some(rules_json),
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
+        let server = generate_in_memory(&orb, &opts).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
         assert!(
             lib_rs.contains("get_version"),
@@ -1192,8 +4220,7 @@ mod tests {
     #[test]
     fn test_lib_delegates_current_resources_to_current_module() {
         let orb = create_test_orb();
-        let generator = CodeGenerator::new().unwrap();
-        let server = generator.generate(&orb, "test-orb", "1.0.0").unwrap();
+        let server = generate_in_memory(&orb, &GenerateOptions::new("test-orb", "1.0.0")).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         assert!(
@@ -1215,13 +4242,12 @@ mod tests {
         );
 
         let current_orb = create_test_orb();
-        let generator = CodeGenerator::new()
-            .unwrap()
-            .with_prior_versions(vec![("1.0.0".to_string(), prior_orb)]);
+        let opts = GenerateOptions {
+            prior_versions: vec![("1.0.0".to_string(), prior_orb)],
+            ..GenerateOptions::new("test-orb", "2.0.0")
+        };
 
-        let server = generator
-            .generate(&current_orb, "test-orb", "2.0.0")
-            .unwrap();
+        let server = generate_in_memory(&current_orb, &opts).unwrap();
         let lib_rs = server.files.get(&PathBuf::from("src/lib.rs")).unwrap();
 
         // lib.rs should delegate to versions::get, not inline the JSON
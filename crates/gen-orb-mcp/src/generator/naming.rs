@@ -0,0 +1,193 @@
+//! Names of generated servers and the scheme of their resource URIs.
+//!
+//! By default the orb `my-toolkit` becomes the crate `my_toolkit_mcp` (the
+//! package `my-toolkit-mcp` for TypeScript and Python) with the server struct
+//! `MyToolkitMcp`, serving its resources at `orb://...`. [`Naming`] adds a
+//! prefix, changes or drops the `mcp` suffix and picks another URI scheme, so
+//! organizations can follow their own naming conventions. With the prefix
+//! `acme`, the suffix `server` and the scheme `acme`, the same orb becomes the
+//! crate `acme_my_toolkit_server`, serving `acme://commands/...`.
+
+use super::GeneratorError;
+
+/// Word appended to server names unless told otherwise.
+pub const DEFAULT_SUFFIX: &str = "mcp";
+
+/// Scheme of resource URIs unless told otherwise.
+pub const DEFAULT_URI_SCHEME: &str = "orb";
+
+/// How a generated server and its resources are named.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Naming {
+    /// Word put before the orb name; empty adds none.
+    pub prefix: String,
+    /// Word put after the orb name; empty adds none.
+    pub suffix: String,
+    /// Scheme of every resource URI, e.g. `orb` for `orb://overview`.
+    pub uri_scheme: String,
+}
+
+impl Default for Naming {
+    fn default() -> Self {
+        Self {
+            prefix: String::new(),
+            suffix: DEFAULT_SUFFIX.to_string(),
+            uri_scheme: DEFAULT_URI_SCHEME.to_string(),
+        }
+    }
+}
+
+impl Naming {
+    /// Put `prefix` before the orb name.
+    pub fn with_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Put `suffix` after the orb name instead of `mcp`.
+    pub fn with_suffix(mut self, suffix: impl Into<String>) -> Self {
+        self.suffix = suffix.into();
+        self
+    }
+
+    /// Serve resources at `<scheme>://...` instead of `orb://...`.
+    pub fn with_uri_scheme(mut self, scheme: impl Into<String>) -> Self {
+        self.uri_scheme = scheme.into();
+        self
+    }
+
+    /// The kebab-case package name of the server for `orb_name`, which is
+    /// also the server name reported to MCP clients.
+    pub fn package_name(&self, orb_name: &str) -> String {
+        [self.prefix.as_str(), orb_name, self.suffix.as_str()]
+            .into_iter()
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<_>>()
+            .join("-")
+    }
+
+    /// Whether resources are served at the default `orb://` URIs.
+    pub fn is_default_scheme(&self) -> bool {
+        self.uri_scheme == DEFAULT_URI_SCHEME
+    }
+
+    /// `uri` with its `orb://` scheme replaced by the configured one.
+    pub fn uri(&self, uri: &str) -> String {
+        match uri.strip_prefix("orb://") {
+            Some(rest) => format!("{}://{rest}", self.uri_scheme),
+            None => uri.to_string(),
+        }
+    }
+
+    /// Reject a prefix or suffix that cannot be part of a crate name, and a
+    /// URI scheme that RFC 3986 does not allow.
+    pub fn validate(&self) -> Result<(), GeneratorError> {
+        for (option, word) in [
+            ("name prefix", &self.prefix),
+            ("crate suffix", &self.suffix),
+        ] {
+            if let Some(c) = word
+                .chars()
+                .find(|&c| !(c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-'))
+            {
+                return Err(GeneratorError::InvalidNaming {
+                    option: option.to_string(),
+                    value: word.clone(),
+                    reason: format!(
+                        "'{c}' is not allowed; use lowercase letters, digits and hyphens"
+                    ),
+                });
+            }
+            if word.starts_with('-') || word.ends_with('-') {
+                return Err(GeneratorError::InvalidNaming {
+                    option: option.to_string(),
+                    value: word.clone(),
+                    reason: "must not start or end with a hyphen".to_string(),
+                });
+            }
+        }
+        if self.prefix.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(GeneratorError::InvalidNaming {
+                option: "name prefix".to_string(),
+                value: self.prefix.clone(),
+                reason: "must start with a letter".to_string(),
+            });
+        }
+        let scheme = &self.uri_scheme;
+        let valid_scheme = scheme.starts_with(|c: char| c.is_ascii_lowercase())
+            && scheme.chars().all(|c| {
+                c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '+' | '-' | '.')
+            });
+        if !valid_scheme {
+            return Err(GeneratorError::InvalidNaming {
+                option: "URI scheme".to_string(),
+                value: scheme.clone(),
+                reason: "must be a lowercase letter followed by lowercase letters, digits, \
+                         '+', '-' or '.'"
+                    .to_string(),
+            });
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_name_joins_non_empty_words() {
+        assert_eq!(Naming::default().package_name("toolkit"), "toolkit-mcp");
+        assert_eq!(
+            Naming::default()
+                .with_prefix("acme")
+                .with_suffix("server")
+                .package_name("my-toolkit"),
+            "acme-my-toolkit-server"
+        );
+        assert_eq!(
+            Naming::default().with_suffix("").package_name("toolkit"),
+            "toolkit"
+        );
+    }
+
+    #[test]
+    fn test_uri_replaces_the_scheme() {
+        let naming = Naming::default().with_uri_scheme("circleci");
+        assert!(!naming.is_default_scheme());
+        assert_eq!(
+            naming.uri("orb://commands/greet"),
+            "circleci://commands/greet"
+        );
+        assert_eq!(naming.uri("https://example.com"), "https://example.com");
+        assert_eq!(Naming::default().uri("orb://overview"), "orb://overview");
+    }
+
+    #[test]
+    fn test_validate() {
+        assert!(Naming::default().validate().is_ok());
+        assert!(Naming::default()
+            .with_prefix("acme-ci")
+            .with_suffix("")
+            .with_uri_scheme("acme+orb")
+            .validate()
+            .is_ok());
+        for invalid in [
+            Naming::default().with_prefix("Acme"),
+            Naming::default().with_prefix("1acme"),
+            Naming::default().with_suffix("-mcp"),
+            Naming::default().with_suffix("mcp_server"),
+            Naming::default().with_uri_scheme(""),
+            Naming::default().with_uri_scheme("orb://"),
+            Naming::default().with_uri_scheme("2orb"),
+        ] {
+            assert!(
+                matches!(
+                    invalid.validate(),
+                    Err(GeneratorError::InvalidNaming { .. })
+                ),
+                "{invalid:?}"
+            );
+        }
+    }
+}
@@ -0,0 +1,348 @@
+//! Options-driven generation.
+//!
+//! [`GenerateOptions`] describes a server: its name and version plus every
+//! `generate` setting that does not name a file. [`generate_in_memory`] takes
+//! it and returns the server without touching the filesystem, and
+//! [`GeneratedServer::into_tarball`] packs the result for download or storage.
+//! New generator features add fields and builder methods here, so callers
+//! are not broken by new positional arguments.
+//!
+//! ```no_run
+//! use gen_orb_mcp::{
+//!     generator::{generate_in_memory, GenerateOptions, Transport},
+//!     parser::OrbParser,
+//! };
+//!
+//! let yaml = "version: 2.1\ncommands:\n  greet:\n    steps: []\n";
+//! let orb = OrbParser::parse_packed_content(yaml, "orb.yml".as_ref()).unwrap();
+//! let opts = GenerateOptions::new("my-orb", "1.0.0")
+//!     .with_transport(Transport::Http)
+//!     .with_filters(vec!["commands/*".to_string()], vec![]);
+//! let server = generate_in_memory(&orb, &opts).unwrap();
+//! let tarball = server.into_tarball();
+//! ```
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+use super::{
+    read_template_overrides, BinstallMetadata, CodeGenerator, DefinitionCategory, GeneratedServer,
+    GeneratorError, Language, Logo, Naming, PackageMetadata, Transport,
+};
+use crate::parser::OrbDefinition;
+
+/// Everything that shapes a generated server, matching the `generate` flags
+/// that do not name files.
+///
+/// Build it with [`GenerateOptions::new`] and the `with_*` methods, or set
+/// the public fields directly.
+#[derive(Debug, Clone, Default)]
+pub struct GenerateOptions {
+    /// Orb name the crate and server are named after.
+    pub name: String,
+    /// Version of the generated server.
+    pub version: String,
+    /// Language of the generated server.
+    pub language: Language,
+    /// Definition categories to expose; empty exposes every category.
+    pub categories: Vec<DefinitionCategory>,
+    /// `<category>/<name>` globs selecting the definitions to expose.
+    pub include: Vec<String>,
+    /// `<category>/<name>` globs of definitions to leave out.
+    pub exclude: Vec<String>,
+    /// Reject an invalid `name` instead of sanitizing it.
+    pub strict_names: bool,
+    /// Friendly server title shown by MCP clients.
+    pub server_title: Option<String>,
+    /// Usage guidance returned in the MCP `instructions` field.
+    pub server_instructions: Option<String>,
+    /// Orb repository revision the server is generated from.
+    pub source_rev: Option<String>,
+    /// SPDX license expression of the generated crate.
+    pub license: Option<String>,
+    /// Text of the generated LICENSE file.
+    pub license_text: Option<String>,
+    /// `[package.metadata.binstall]` values.
+    pub binstall: Option<BinstallMetadata>,
+    /// Publishing fields of `[package]`.
+    pub package: Option<PackageMetadata>,
+    /// Logo served at `orb://logo` and as the server icon.
+    pub logo: Option<Logo>,
+    /// Split resources larger than this many bytes into parts.
+    pub max_resource_bytes: Option<usize>,
+    /// Emit the proptest suite.
+    pub property_tests: bool,
+    /// Serve embedded data only, with no runtime I/O.
+    pub sandboxed: bool,
+    /// Transport the server speaks MCP over.
+    pub transport: Transport,
+    /// Default bind address of an HTTP server.
+    pub http_bind: Option<SocketAddr>,
+    /// Compress HTTP responses.
+    pub http_compression: bool,
+    /// Largest HTTP request body accepted, in bytes.
+    pub http_body_limit: Option<usize>,
+    /// Prior versions to embed, as `(version, definition)`.
+    pub prior_versions: Vec<(String, OrbDefinition)>,
+    /// Previous release `orb://changes` compares against, as `(label,
+    /// definition)`.
+    pub previous_orb: Option<(String, OrbDefinition)>,
+    /// Conformance rules as JSON, enabling the migration tools.
+    pub conformance_rules_json: Option<String>,
+    /// Imported orbs to serve under their alias, as `(alias, definition)`.
+    pub imported_orbs: Vec<(String, OrbDefinition)>,
+    /// YAML of commands, jobs and executors keyed by `<category>/<name>`,
+    /// served at `orb://source/...`.
+    pub sources: HashMap<String, String>,
+    /// Replacement template sources keyed by file name (`main.rs.hbs`,
+    /// `lib.rs.hbs` or `Cargo.toml.hbs`).
+    pub templates: BTreeMap<String, String>,
+    /// Orb a dev-mode server reads at runtime instead of serving the
+    /// embedded definitions.
+    pub dev_orb_path: Option<PathBuf>,
+    /// Prefix and suffix of the crate name, and the resource URI scheme.
+    pub naming: Naming,
+}
+
+impl GenerateOptions {
+    /// Options generating a server named after `name` at `version`, with
+    /// every other option at its `generate` default.
+    pub fn new(name: impl Into<String>, version: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            version: version.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Generate a server in `language`.
+    pub fn with_language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    /// Serve MCP over `transport`.
+    pub fn with_transport(mut self, transport: Transport) -> Self {
+        self.transport = transport;
+        self
+    }
+
+    /// Default bind address of an HTTP server.
+    pub fn with_http_bind(mut self, addr: SocketAddr) -> Self {
+        self.http_bind = Some(addr);
+        self
+    }
+
+    /// Generate a dev-mode server that reads the orb at `orb_path` at
+    /// runtime.
+    pub fn with_dev_orb_path(mut self, orb_path: impl Into<PathBuf>) -> Self {
+        self.dev_orb_path = Some(orb_path.into());
+        self
+    }
+
+    /// Name the server and its resource URIs after `naming`.
+    pub fn with_naming(mut self, naming: Naming) -> Self {
+        self.naming = naming;
+        self
+    }
+
+    /// Expose only the definitions in `categories`.
+    pub fn with_categories(mut self, categories: Vec<DefinitionCategory>) -> Self {
+        self.categories = categories;
+        self
+    }
+
+    /// Expose the definitions matching an `include` glob and no `exclude`
+    /// glob, as `--include` and `--exclude` do.
+    pub fn with_filters(mut self, include: Vec<String>, exclude: Vec<String>) -> Self {
+        self.include = include;
+        self.exclude = exclude;
+        self
+    }
+
+    /// Replace the template `file` (`main.rs.hbs`, `lib.rs.hbs` or
+    /// `Cargo.toml.hbs`) with `source`.
+    pub fn with_template(mut self, file: impl Into<String>, source: impl Into<String>) -> Self {
+        self.templates.insert(file.into(), source.into());
+        self
+    }
+
+    /// Replace the templates with those found in `dir`, as `--templates-dir`
+    /// does.
+    ///
+    /// The files are read now, so generating from these options still does
+    /// no I/O.
+    pub fn with_templates_dir(mut self, dir: &Path) -> Result<Self, GeneratorError> {
+        for (file, _, source) in read_template_overrides(dir)? {
+            self.templates.insert(file.to_string(), source);
+        }
+        Ok(self)
+    }
+}
+
+impl CodeGenerator<'_> {
+    /// A generator configured by `opts`, for callers that need more than
+    /// [`generate_in_memory`], such as
+    /// [`generate_multi`](Self::generate_multi).
+    pub fn from_options(opts: &GenerateOptions) -> Result<Self, GeneratorError> {
+        let mut generator = CodeGenerator::new()?
+            .with_language(opts.language)
+            .with_categories(&opts.categories)
+            .with_filters(&opts.include, &opts.exclude)?
+            .with_strict_names(opts.strict_names)
+            .with_property_tests(opts.property_tests)
+            .with_sandboxed(opts.sandboxed)
+            .with_transport(opts.transport)
+            .with_http_compression(opts.http_compression)
+            .with_prior_versions(opts.prior_versions.clone())
+            .with_conformance_rules_json_opt(opts.conformance_rules_json.clone())
+            .with_imported_orbs(opts.imported_orbs.clone())
+            .with_sources(opts.sources.clone())
+            .with_naming(opts.naming.clone());
+        for (file, source) in &opts.templates {
+            generator = generator.with_template(file, source)?;
+        }
+        if let Some(title) = &opts.server_title {
+            generator = generator.with_server_title(title);
+        }
+        if let Some(instructions) = &opts.server_instructions {
+            generator = generator.with_server_instructions(instructions);
+        }
+        if let Some(rev) = &opts.source_rev {
+            generator = generator.with_source_rev(rev);
+        }
+        if let Some(spdx) = &opts.license {
+            generator = generator.with_license(spdx);
+        }
+        if let Some(text) = &opts.license_text {
+            generator = generator.with_license_text(text);
+        }
+        if let Some(binstall) = &opts.binstall {
+            generator = generator.with_binstall(binstall.clone());
+        }
+        if let Some(package) = &opts.package {
+            generator = generator.with_package(package.clone());
+        }
+        if let Some(logo) = &opts.logo {
+            generator = generator.with_logo(logo.clone());
+        }
+        if let Some(max_bytes) = opts.max_resource_bytes {
+            generator = generator.with_max_resource_bytes(max_bytes);
+        }
+        if let Some(addr) = opts.http_bind {
+            generator = generator.with_http_bind(addr);
+        }
+        if let Some(max_bytes) = opts.http_body_limit {
+            generator = generator.with_http_body_limit(max_bytes);
+        }
+        if let Some((label, previous)) = &opts.previous_orb {
+            generator = generator.with_previous_orb(label, previous.clone());
+        }
+        if let Some(orb_path) = &opts.dev_orb_path {
+            generator = generator.with_dev_orb_path(orb_path);
+        }
+        Ok(generator)
+    }
+}
+
+/// Generate the MCP server for `orb` described by `opts` without reading or
+/// writing any files.
+pub fn generate_in_memory(
+    orb: &OrbDefinition,
+    opts: &GenerateOptions,
+) -> Result<GeneratedServer, GeneratorError> {
+    CodeGenerator::from_options(opts)?.generate_single(orb, &opts.name, &opts.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::parser::Command;
+
+    fn orb() -> OrbDefinition {
+        OrbDefinition {
+            commands: HashMap::from([("greet".to_string(), Command::default())]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_generate_in_memory_applies_options() {
+        let opts = GenerateOptions {
+            server_title: Some("Greeter".to_string()),
+            license: Some("MIT".to_string()),
+            ..GenerateOptions::new("test-orb", "1.2.3")
+        };
+        let server = generate_in_memory(&orb(), &opts).unwrap();
+        assert_eq!(server.crate_name, "test_orb_mcp");
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"orb://commands/greet\""));
+        assert!(lib_rs.contains("Greeter"));
+        let cargo_toml = &server.files[&PathBuf::from("Cargo.toml")];
+        assert!(cargo_toml.contains("version = \"1.2.3\""));
+        assert!(cargo_toml.contains("license = \"MIT\""));
+
+        let opts = GenerateOptions {
+            include: vec!["[".to_string()],
+            ..GenerateOptions::new("test-orb", "1.2.3")
+        };
+        let err = generate_in_memory(&orb(), &opts).unwrap_err();
+        assert!(matches!(err, GeneratorError::InvalidPattern { .. }));
+
+        let opts = GenerateOptions {
+            language: Language::TypeScript,
+            ..GenerateOptions::new("test-orb", "1.2.3")
+        };
+        let server = generate_in_memory(&orb(), &opts).unwrap();
+        assert!(server.files.contains_key(&PathBuf::from("src/index.ts")));
+    }
+
+    #[test]
+    fn test_builder_applies_filters_and_template_overrides() {
+        let mut orb = orb();
+        orb.commands
+            .insert("farewell".to_string(), Command::default());
+        let opts = GenerateOptions::new("test-orb", "1.0.0")
+            .with_filters(
+                vec!["commands/*".to_string()],
+                vec!["commands/farewell".to_string()],
+            )
+            .with_template("Cargo.toml.hbs", "# custom {{crate_name}}\n");
+        let server = generate_in_memory(&orb, &opts).unwrap();
+        let lib_rs = &server.files[&PathBuf::from("src/lib.rs")];
+        assert!(lib_rs.contains("\"orb://commands/greet\""));
+        assert!(!lib_rs.contains("\"orb://commands/farewell\""));
+        assert_eq!(
+            server.files[&PathBuf::from("Cargo.toml")],
+            "# custom test_orb_mcp\n"
+        );
+
+        let opts = GenerateOptions::new("test-orb", "1.0.0").with_template("README.md.hbs", "");
+        let err = generate_in_memory(&orb, &opts).unwrap_err();
+        assert!(matches!(err, GeneratorError::UnknownTemplate { .. }));
+    }
+
+    #[test]
+    fn test_with_templates_dir_reads_overrides_up_front() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("lib.rs.hbs"), "// {{orb_name}}\n").unwrap();
+        std::fs::write(dir.path().join("README.md.hbs"), "ignored").unwrap();
+        let opts = GenerateOptions::new("test-orb", "1.0.0")
+            .with_templates_dir(dir.path())
+            .unwrap();
+        assert_eq!(
+            opts.templates,
+            BTreeMap::from([("lib.rs.hbs".to_string(), "// {{orb_name}}\n".to_string())])
+        );
+        drop(dir);
+        let server = generate_in_memory(&orb(), &opts).unwrap();
+        assert_eq!(server.files[&PathBuf::from("src/lib.rs")], "// test-orb\n");
+    }
+}
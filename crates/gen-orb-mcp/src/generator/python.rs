@@ -0,0 +1,130 @@
+//! Python target: a package using the Python MCP SDK.
+//!
+//! ```text
+//! out/
+//! ├── README.md
+//! ├── pyproject.toml
+//! └── src/
+//!     └── <orb>_mcp/
+//!         ├── __init__.py
+//!         ├── __main__.py  # `python -m <orb>_mcp`
+//!         ├── server.py    # server, resources and tools (templates/python)
+//!         └── data.json    # embedded resource contents
+//! ```
+//!
+//! Resource contents are written to `data.json`, read by the server at
+//! start-up, rather than through templates, so orb text never needs escaping
+//! for Python. The package is named after the crate a Rust server would be.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use super::{
+    embedded::{self, to_json},
+    CodeGenerator, GeneratorContext, GeneratorError,
+};
+
+/// PEP 508 requirement on the `mcp` package (needs tool annotations and
+/// input validation in the low-level server).
+const MCP_SDK_REQUIREMENT: &str = "mcp>=1.12";
+
+/// Directory of the Python package for `context`, relative to the output.
+pub(super) fn package_dir(context: &GeneratorContext) -> PathBuf {
+    PathBuf::from("src").join(&context.crate_name)
+}
+
+/// Render the Python package for `context`.
+pub(super) fn render(
+    generator: &CodeGenerator<'_>,
+    context: &GeneratorContext,
+    ctx_json: &serde_json::Value,
+) -> Result<HashMap<PathBuf, String>, GeneratorError> {
+    let render = |name: &str| {
+        generator
+            .handlebars
+            .render(name, ctx_json)
+            .map_err(|e| GeneratorError::TemplateRender {
+                name: name.to_string(),
+                source: e,
+            })
+    };
+
+    let package = package_dir(context);
+    let data = embedded::data(generator, context, render("overview.md")?)?;
+    let mut data_json = to_json(&data)?;
+    data_json.push('\n');
+
+    let mut files = HashMap::new();
+    files.insert(package.join("server.py"), render("server.py")?);
+    files.insert(package.join("data.json"), data_json);
+    files.insert(
+        package.join("__init__.py"),
+        format!(
+            "\"\"\"MCP server for the {} CircleCI orb.\n\nGenerated by gen-orb-mcp.\n\"\"\"\n",
+            context.orb_name
+        ),
+    );
+    files.insert(
+        package.join("__main__.py"),
+        "from .server import main\n\nmain()\n".to_string(),
+    );
+    files.insert(
+        PathBuf::from("pyproject.toml"),
+        pyproject_toml(generator, context)?,
+    );
+    Ok(files)
+}
+
+/// `pyproject.toml` for the generated server, built with hatchling.
+fn pyproject_toml(
+    generator: &CodeGenerator<'_>,
+    context: &GeneratorContext,
+) -> Result<String, GeneratorError> {
+    let package = &context.package_name;
+    let module = &context.crate_name;
+    let mut project = vec![
+        format!("name = {}", toml_string(package)?),
+        format!("version = {}", toml_string(&context.version)?),
+    ];
+    // Core metadata allows a single-line summary only
+    if let Some(description) = &context.description {
+        let summary = description.split_whitespace().collect::<Vec<_>>().join(" ");
+        project.push(format!("description = {}", toml_string(&summary)?));
+    }
+    project.push("readme = \"README.md\"".to_string());
+    match (&generator.license, &generator.license_text) {
+        (Some(spdx), _) => project.push(format!("license = {}", toml_string(spdx)?)),
+        (None, Some(_)) => project.push("license = { file = \"LICENSE\" }".to_string()),
+        (None, None) => {}
+    }
+    project.push("requires-python = \">=3.10\"".to_string());
+    project.push(format!(
+        "dependencies = [{}]",
+        toml_string(MCP_SDK_REQUIREMENT)?
+    ));
+
+    Ok(format!(
+        "# Generated by gen-orb-mcp.\n\
+         \n\
+         [build-system]\n\
+         requires = [\"hatchling\"]\n\
+         build-backend = \"hatchling.build\"\n\
+         \n\
+         [project]\n\
+         {}\n\
+         \n\
+         [project.scripts]\n\
+         {} = {}\n\
+         \n\
+         [tool.hatch.build.targets.wheel]\n\
+         packages = [{}]\n",
+        project.join("\n"),
+        toml_string(package)?,
+        toml_string(&format!("{module}.server:main"))?,
+        toml_string(&format!("src/{module}"))?,
+    ))
+}
+
+/// `value` as a TOML basic string; JSON string escapes are valid TOML.
+fn toml_string(value: &str) -> Result<String, GeneratorError> {
+    serde_json::to_string(value).map_err(|e| GeneratorError::Serialization { source: e })
+}
@@ -0,0 +1,443 @@
+//! Audit of generated sources for sandboxed servers.
+//!
+//! Some MCP hosts run servers under policies that deny filesystem, network
+//! and environment access. In sandboxed mode the generator only emits code
+//! paths that serve embedded data, and this audit checks the rendered
+//! `src/` files for APIs that would break that promise. Comments and string
+//! literals are skipped, so orb descriptions embedded in the overview cannot
+//! trigger false positives.
+//!
+//! Paths are resolved through the file's `use` declarations (groups,
+//! `self`, renames and globs) before they are checked, so
+//! `use std::{env, fs}; fs::read(..)` is caught as `std::fs` and
+//! `use std::process as p` as `std::process`. Command-line arguments
+//! (`std::env::args`) stay allowed: the host hands them over when it starts
+//! the server, they are not read from its environment.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use super::GeneratorError;
+
+/// Modules a sandboxed server must not use, with the capability each
+/// implies.
+const FORBIDDEN_PATHS: &[(&str, &str)] = &[
+    ("std::fs", "filesystem access"),
+    ("tokio::fs", "filesystem access"),
+    ("std::net", "network access"),
+    ("tokio::net", "network access"),
+    ("std::process", "process spawning"),
+    ("tokio::process", "process spawning"),
+    ("std::env", "environment access"),
+];
+
+/// Paths inside [`FORBIDDEN_PATHS`] that a sandboxed server may use.
+const ALLOWED_PATHS: &[&str] = &["std::env::args", "std::env::args_os"];
+
+/// Functions that read the environment whichever path reaches them.
+const FORBIDDEN_FUNCTIONS: &[(&str, &str)] = &[
+    ("from_default_env", "environment variables"),
+    ("try_from_default_env", "environment variables"),
+];
+
+/// Check every generated `src/` file for forbidden APIs.
+pub(crate) fn audit(files: &HashMap<PathBuf, String>) -> Result<(), GeneratorError> {
+    let mut paths: Vec<_> = files
+        .keys()
+        .filter(|p| p.starts_with("src") && p.extension().is_some_and(|e| e == "rs"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let code = strip_comments_and_literals(&files[path]);
+        if let Some((api, capability)) = violation(&tokenize(&code)) {
+            return Err(GeneratorError::SandboxViolation {
+                reason: format!("{} uses `{api}` ({capability})", path.display()),
+            });
+        }
+    }
+    Ok(())
+}
+
+/// The first forbidden API `tokens` import or reach, with its capability.
+fn violation(tokens: &[Token]) -> Option<(&'static str, &'static str)> {
+    let imports = imports(tokens);
+    let mut aliases: HashMap<&str, Vec<&str>> = HashMap::new();
+    for import in &imports {
+        let path = resolve(&import.path, &aliases);
+        if let Some(found) = forbidden(&path, import.alias.is_none()) {
+            return Some(found);
+        }
+        if let Some(alias) = import.alias {
+            aliases.insert(alias, path);
+        }
+    }
+
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i] == Token::Ident("use") {
+            // Already checked above
+            parse_use_tree(tokens, &mut i, vec![], &mut vec![]);
+            continue;
+        }
+        let absolute = tokens[i] == Token::PathSep
+            && matches!(tokens.get(i + 1), Some(Token::Ident(_)))
+            && !matches!(i.checked_sub(1).map(|p| &tokens[p]), Some(Token::Ident(_)));
+        let starts_path = absolute
+            || (matches!(tokens[i], Token::Ident(_))
+                && !matches!(
+                    i.checked_sub(1).map(|p| &tokens[p]),
+                    Some(Token::PathSep | Token::Punct('.'))
+                ));
+        if !starts_path {
+            if let Some(found) = forbidden_function(&tokens[i]) {
+                return Some(found);
+            }
+            i += 1;
+            continue;
+        }
+        if absolute {
+            i += 1;
+        }
+        let mut path = vec![];
+        while let Some(Token::Ident(segment)) = tokens.get(i) {
+            path.push(*segment);
+            i += 1;
+            if tokens.get(i) == Some(&Token::PathSep)
+                && matches!(tokens.get(i + 1), Some(Token::Ident(_)))
+            {
+                i += 1;
+            } else {
+                break;
+            }
+        }
+        let path = if absolute {
+            path
+        } else {
+            resolve(&path, &aliases)
+        };
+        if let Some(found) = forbidden(&path, false) {
+            return Some(found);
+        }
+        if let Some(found) = path
+            .last()
+            .and_then(|&last| forbidden_function(&Token::Ident(last)))
+        {
+            return Some(found);
+        }
+    }
+    None
+}
+
+/// The entry of [`FORBIDDEN_FUNCTIONS`] naming `token`.
+fn forbidden_function(token: &Token) -> Option<(&'static str, &'static str)> {
+    FORBIDDEN_FUNCTIONS
+        .iter()
+        .find(|(f, _)| *token == Token::Ident(f))
+        .copied()
+}
+
+/// The forbidden module `path` lies in, unless it is allowed. A `glob`
+/// import also fails when a forbidden module lies in `path`.
+fn forbidden(path: &[&str], glob: bool) -> Option<(&'static str, &'static str)> {
+    let segments = |p: &'static str| p.split("::").collect::<Vec<_>>();
+    let allowed = ALLOWED_PATHS.iter().any(|a| {
+        let a = segments(a);
+        !glob && (path.starts_with(&a) || a.starts_with(path))
+    });
+    if allowed {
+        return None;
+    }
+    FORBIDDEN_PATHS
+        .iter()
+        .find(|(api, _)| {
+            let api = segments(api);
+            path.starts_with(&api) || (glob && api.starts_with(path))
+        })
+        .copied()
+}
+
+/// `path` with a leading alias replaced by the path it imports.
+fn resolve<'a>(path: &[&'a str], aliases: &HashMap<&str, Vec<&'a str>>) -> Vec<&'a str> {
+    match path.split_first() {
+        Some((first, rest)) => match aliases.get(first) {
+            Some(target) => target.iter().chain(rest).copied().collect(),
+            None => path.to_vec(),
+        },
+        None => vec![],
+    }
+}
+
+/// A token of code stripped of comments and literals.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token<'a> {
+    Ident(&'a str),
+    /// `::`
+    PathSep,
+    Punct(char),
+}
+
+fn tokenize(code: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut chars = code.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        if is_ident(c) {
+            let mut end = start + c.len_utf8();
+            while let Some(&(i, c)) = chars.peek() {
+                if !is_ident(c) {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+            let word = &code[start..end];
+            tokens.push(if c.is_ascii_digit() {
+                Token::Punct('0')
+            } else {
+                Token::Ident(word)
+            });
+        } else if c == ':' && chars.peek().is_some_and(|&(_, c)| c == ':') {
+            chars.next();
+            tokens.push(Token::PathSep);
+        } else if !c.is_whitespace() {
+            tokens.push(Token::Punct(c));
+        }
+    }
+    tokens
+}
+
+/// One name a `use` declaration brings into scope.
+struct Import<'a> {
+    /// The imported path
+    path: Vec<&'a str>,
+    /// The name it is imported as; `None` for a glob
+    alias: Option<&'a str>,
+}
+
+/// Every `use` import in `tokens`, in order.
+fn imports<'a>(tokens: &[Token<'a>]) -> Vec<Import<'a>> {
+    let mut imports = vec![];
+    let mut i = 0;
+    while i < tokens.len() {
+        if tokens[i] == Token::Ident("use") {
+            parse_use_tree(tokens, &mut i, vec![], &mut imports);
+        } else {
+            i += 1;
+        }
+    }
+    imports
+}
+
+/// Parse the `use` declaration or use tree at `i` under `prefix`, leaving
+/// `i` after it.
+fn parse_use_tree<'a>(
+    tokens: &[Token<'a>],
+    i: &mut usize,
+    mut prefix: Vec<&'a str>,
+    imports: &mut Vec<Import<'a>>,
+) {
+    if tokens.get(*i) == Some(&Token::Ident("use")) {
+        *i += 1;
+        parse_use_tree(tokens, i, prefix, imports);
+        if tokens.get(*i) == Some(&Token::Punct(';')) {
+            *i += 1;
+        }
+        return;
+    }
+    if tokens.get(*i) == Some(&Token::PathSep) {
+        *i += 1;
+    }
+    loop {
+        match tokens.get(*i) {
+            Some(&Token::Ident(name)) => {
+                *i += 1;
+                if tokens.get(*i) == Some(&Token::PathSep) {
+                    prefix.push(name);
+                    *i += 1;
+                    continue;
+                }
+                let alias = if tokens.get(*i) == Some(&Token::Ident("as")) {
+                    *i += 1;
+                    match tokens.get(*i) {
+                        Some(&Token::Ident(alias)) => {
+                            *i += 1;
+                            alias
+                        }
+                        _ => name,
+                    }
+                } else if name == "self" {
+                    prefix.last().copied().unwrap_or(name)
+                } else {
+                    name
+                };
+                if name != "self" {
+                    prefix.push(name);
+                }
+                imports.push(Import {
+                    path: prefix,
+                    alias: Some(alias),
+                });
+                return;
+            }
+            Some(Token::Punct('*')) => {
+                *i += 1;
+                imports.push(Import {
+                    path: prefix,
+                    alias: None,
+                });
+                return;
+            }
+            Some(Token::Punct('{')) => {
+                *i += 1;
+                loop {
+                    match tokens.get(*i) {
+                        Some(Token::Punct('}')) => {
+                            *i += 1;
+                            return;
+                        }
+                        Some(Token::Punct(',')) => *i += 1,
+                        None => return,
+                        Some(_) => {
+                            let start = *i;
+                            parse_use_tree(tokens, i, prefix.clone(), imports);
+                            if *i == start {
+                                *i += 1;
+                            }
+                        }
+                    }
+                }
+            }
+            _ => return,
+        }
+    }
+}
+
+/// Blank out comments, string literals and char literals, keeping code.
+fn strip_comments_and_literals(src: &str) -> String {
+    let chars: Vec<char> = src.chars().collect();
+    let mut out = String::with_capacity(src.len());
+    let mut i = 0;
+    while i < chars.len() {
+        match chars[i] {
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            'r' if matches!(chars.get(i + 1), Some('#') | Some('"'))
+                && (i == 0 || !is_ident(chars[i - 1])) =>
+            {
+                let hashes = chars[i + 1..].iter().take_while(|&&c| c == '#').count();
+                if chars.get(i + 1 + hashes) != Some(&'"') {
+                    out.push('r');
+                    i += 1;
+                    continue;
+                }
+                i += hashes + 2;
+                while i < chars.len() {
+                    if chars[i] == '"'
+                        && chars[i + 1..].iter().take_while(|&&c| c == '#').count() >= hashes
+                    {
+                        i += hashes + 1;
+                        break;
+                    }
+                    i += 1;
+                }
+                out.push_str("\"\"");
+            }
+            '"' => {
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += if chars[i] == '\\' { 2 } else { 1 };
+                }
+                i += 1;
+                out.push_str("\"\"");
+            }
+            '\'' if chars.get(i + 2) == Some(&'\'') => {
+                i += 3;
+                out.push_str("' '");
+            }
+            '\'' if chars.get(i + 1) == Some(&'\\') => {
+                i += 2;
+                while i < chars.len() && chars[i] != '\'' {
+                    i += 1;
+                }
+                i += 1;
+                out.push_str("' '");
+            }
+            c => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+    out
+}
+
+fn is_ident(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn files(src: &str) -> HashMap<PathBuf, String> {
+        HashMap::from([(PathBuf::from("src/lib.rs"), src.to_string())])
+    }
+
+    #[test]
+    fn test_audit_accepts_embedded_data_only() {
+        let src = "// Reads std::fs::read_to_string in docs only\n\
+                   const OVERVIEW: &str = r##\"Uses std::net and env::var\"##;\n\
+                   fn get() -> &'static str { \"tokio::fs\" }\n\
+                   const Q: char = '\"';\n";
+        assert!(audit(&files(src)).is_ok());
+    }
+
+    #[test]
+    fn test_audit_rejects_filesystem_access() {
+        let err = audit(&files("fn f() { let _ = std::fs::read(\"x\"); }")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "generated server is not sandbox-safe: src/lib.rs uses `std::fs` (filesystem access)"
+        );
+    }
+
+    #[test]
+    fn test_audit_resolves_use_trees() {
+        for (src, api) in [
+            ("use std::{env, fs};\nfn f() { let _ = fs::read(\"x\"); }", "std::fs"),
+            ("use std::env;\nfn f() { let _ = env::vars(); }", "std::env"),
+            ("fn f() { let _ = std::env::vars(); }", "std::env"),
+            ("use std::{self as s};\nfn f() { s::env::var(\"X\"); }", "std::env"),
+            ("use std::process as p;\nfn f() { p::exit(0); }", "std::process"),
+            ("use std::*;\nfn f() { let _ = fs::read(\"x\"); }", "std::fs"),
+            ("fn f() { let _ = ::tokio::fs::read(\"x\"); }", "tokio::fs"),
+            (
+                "use tracing_subscriber::{self, EnvFilter};\nfn f() { EnvFilter::from_default_env(); }",
+                "from_default_env",
+            ),
+        ] {
+            let err = audit(&files(src)).unwrap_err();
+            assert!(err.to_string().contains(&format!("uses `{api}`")), "{src}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_audit_allows_command_line_arguments() {
+        let src = "use std::env;\n\
+                   use super::*;\n\
+                   use std::sync::{Arc, Mutex};\n\
+                   fn main() { let _ = env::args().skip(1); let _ = std::env::args_os(); }\n\
+                   fn g(x: &Self) { x.fs.len(); }\n";
+        assert!(audit(&files(src)).is_ok());
+    }
+
+    #[test]
+    fn test_audit_ignores_test_files() {
+        let files = HashMap::from([(
+            PathBuf::from("tests/properties.rs"),
+            "fn f() { std::env::temp_dir(); }".to_string(),
+        )]);
+        assert!(audit(&files).is_ok());
+    }
+}
@@ -0,0 +1,175 @@
+//! Gzip-compressed tar archives of generated servers.
+//!
+//! Archives are written in the POSIX ustar format with fixed modes, owners
+//! and timestamps, so identical output archives identically. Paths that do
+//! not fit the ustar name fields get a PAX extended header.
+
+use std::io::Write;
+
+use flate2::{write::GzEncoder, Compression};
+
+/// Tar block size.
+const BLOCK: usize = 512;
+
+/// Gzip-compressed tar of `entries` (path and contents), in the given order.
+pub(super) fn tar_gz<'a>(entries: impl IntoIterator<Item = (String, &'a [u8])>) -> Vec<u8> {
+    let mut tar = Vec::new();
+    for (path, content) in entries {
+        let (prefix, name) = match ustar_name(&path) {
+            Some(split) => split,
+            None => {
+                let record = pax_record("path", &path);
+                append(&mut tar, &header("PaxHeader", record.len(), b'x'));
+                append(&mut tar, record.as_bytes());
+                // Readers without PAX support see the truncated path.
+                ("", truncate(&path, 100))
+            }
+        };
+        let mut block = header(name, content.len(), b'0');
+        block[345..345 + prefix.len()].copy_from_slice(prefix.as_bytes());
+        set_checksum(&mut block);
+        append(&mut tar, &block);
+        append(&mut tar, content);
+    }
+    tar.resize(tar.len() + 2 * BLOCK, 0);
+
+    let mut gz = GzEncoder::new(Vec::new(), Compression::default());
+    gz.write_all(&tar)
+        .and_then(|()| gz.finish())
+        .expect("writing to memory cannot fail")
+}
+
+/// Append `bytes` to `tar`, padded to a whole number of blocks.
+fn append(tar: &mut Vec<u8>, bytes: &[u8]) {
+    tar.extend_from_slice(bytes);
+    tar.resize(tar.len().next_multiple_of(BLOCK), 0);
+}
+
+/// `path` split into the ustar prefix (up to 155 bytes) and name (up to 100
+/// bytes) fields, or `None` when it fits neither way.
+fn ustar_name(path: &str) -> Option<(&str, &str)> {
+    if path.len() <= 100 {
+        return Some(("", path));
+    }
+    path.match_indices('/')
+        .map(|(i, _)| (&path[..i], &path[i + 1..]))
+        .find(|(prefix, name)| prefix.len() <= 155 && name.len() <= 100 && !name.is_empty())
+}
+
+/// The longest prefix of `s` of at most `max` bytes ending on a character
+/// boundary.
+fn truncate(s: &str, max: usize) -> &str {
+    let mut end = max.min(s.len());
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// A PAX record `"<length> <key>=<value>\n"`, whose length counts itself.
+fn pax_record(key: &str, value: &str) -> String {
+    let body = format!(" {key}={value}\n");
+    let mut len = body.len();
+    while len != body.len() + len.to_string().len() {
+        len = body.len() + len.to_string().len();
+    }
+    format!("{len}{body}")
+}
+
+/// Header block of a `kind` entry named `name` holding `size` bytes, with
+/// its checksum set.
+fn header(name: &str, size: usize, kind: u8) -> [u8; BLOCK] {
+    let mut block = [0u8; BLOCK];
+    let name = truncate(name, 100);
+    block[..name.len()].copy_from_slice(name.as_bytes());
+    block[100..108].copy_from_slice(b"0000644\0");
+    block[108..116].copy_from_slice(b"0000000\0");
+    block[116..124].copy_from_slice(b"0000000\0");
+    block[124..136].copy_from_slice(format!("{size:011o}\0").as_bytes());
+    block[136..148].copy_from_slice(b"00000000000\0");
+    block[156] = kind;
+    block[257..263].copy_from_slice(b"ustar\0");
+    block[263..265].copy_from_slice(b"00");
+    set_checksum(&mut block);
+    block
+}
+
+/// Fill in the checksum of a header `block`: the sum of its bytes, counting
+/// the checksum field as spaces.
+fn set_checksum(block: &mut [u8; BLOCK]) {
+    block[148..156].fill(b' ');
+    let sum: u32 = block.iter().map(|&b| u32::from(b)).sum();
+    block[148..156].copy_from_slice(format!("{sum:06o}\0 ").as_bytes());
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Read;
+
+    use flate2::read::GzDecoder;
+
+    use super::*;
+
+    fn untar_gz(archive: &[u8]) -> Vec<u8> {
+        let mut tar = Vec::new();
+        GzDecoder::new(archive).read_to_end(&mut tar).unwrap();
+        tar
+    }
+
+    fn field(block: &[u8], range: std::ops::Range<usize>) -> &str {
+        let bytes = &block[range];
+        let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+        std::str::from_utf8(&bytes[..end]).unwrap()
+    }
+
+    #[test]
+    fn test_tar_gz_writes_ustar_entries() {
+        let tar = untar_gz(&tar_gz([
+            ("demo/Cargo.toml".to_string(), b"[package]\n".as_slice()),
+            ("demo/data/current.bin".to_string(), [0u8, 1, 2].as_slice()),
+        ]));
+        // Two headers, two padded contents and the two-block trailer.
+        assert_eq!(tar.len(), 6 * BLOCK);
+        assert!(tar[4 * BLOCK..].iter().all(|&b| b == 0));
+
+        let first = &tar[..BLOCK];
+        assert_eq!(field(first, 0..100), "demo/Cargo.toml");
+        assert_eq!(field(first, 124..136), "00000000012");
+        assert_eq!(field(first, 257..263), "ustar");
+        assert_eq!(&tar[BLOCK..BLOCK + 10], b"[package]\n");
+        let mut unsummed: [u8; BLOCK] = first.try_into().unwrap();
+        unsummed[148..156].fill(b' ');
+        let sum: u32 = unsummed.iter().map(|&b| u32::from(b)).sum();
+        assert_eq!(field(first, 148..154), format!("{sum:06o}"));
+
+        let second = &tar[2 * BLOCK..3 * BLOCK];
+        assert_eq!(field(second, 0..100), "demo/data/current.bin");
+        assert_eq!(&tar[3 * BLOCK..3 * BLOCK + 3], &[0, 1, 2]);
+    }
+
+    #[test]
+    fn test_long_paths_use_prefix_or_pax_header() {
+        let dir = "d".repeat(120);
+        let path = format!("{dir}/file.rs");
+        let tar = untar_gz(&tar_gz([(path.clone(), b"".as_slice())]));
+        assert_eq!(field(&tar, 0..100), "file.rs");
+        assert_eq!(field(&tar, 345..500), dir);
+
+        let path = "f".repeat(300);
+        let tar = untar_gz(&tar_gz([(path.clone(), b"".as_slice())]));
+        assert_eq!(tar[156], b'x');
+        let record = pax_record("path", &path);
+        assert_eq!(&tar[BLOCK..BLOCK + record.len()], record.as_bytes());
+        // A full name field runs straight into the mode field.
+        assert_eq!(&tar[2 * BLOCK..2 * BLOCK + 100], "f".repeat(100).as_bytes());
+    }
+
+    #[test]
+    fn test_pax_record_length_counts_itself() {
+        assert_eq!(pax_record("path", "a"), "9 path=a\n");
+        // 98 bytes plus a two-digit length is 100, which needs three digits.
+        let record = pax_record("path", &"a".repeat(91));
+        assert_eq!(record.len(), 101);
+        assert!(record.starts_with("101 "));
+    }
+}
@@ -1,7 +1,8 @@
 //! Embedded Handlebars templates for code generation.
 //!
 //! Templates are embedded at compile time using `include_str!` for simplicity
-//! and to ensure the generator is self-contained.
+//! and to ensure the generator is self-contained. The templates listed in
+//! [`OVERRIDABLE`] can be replaced at run time with `--templates-dir`.
 
 /// Template for the main entry point (main.rs).
 pub const MAIN_RS: &str = include_str!("../../templates/main.rs.hbs");
@@ -12,6 +13,14 @@ pub const LIB_RS: &str = include_str!("../../templates/lib.rs.hbs");
 /// Template for the Cargo manifest (Cargo.toml).
 pub const CARGO_TOML: &str = include_str!("../../templates/Cargo.toml.hbs");
 
+/// Templates that `--templates-dir` may replace, as `(file name,
+/// registered name)`.
+pub const OVERRIDABLE: &[(&str, &str)] = &[
+    ("main.rs.hbs", "main.rs"),
+    ("lib.rs.hbs", "lib.rs"),
+    ("Cargo.toml.hbs", "Cargo.toml"),
+];
+
 /// Template for a per-version module file (src/versions/v{version_ident}.rs).
 pub const VERSION_MODULE_RS: &str = include_str!("../../templates/version_module.rs.hbs");
 
@@ -20,3 +29,30 @@ pub const VERSIONS_MOD_RS: &str = include_str!("../../templates/versions_mod.rs.
 
 /// Template for the current-version resource lookup module (src/current/mod.rs).
 pub const CURRENT_MOD_RS: &str = include_str!("../../templates/current_mod.rs.hbs");
+
+/// Template for the generated integration tests (tests/resources.rs).
+pub const RESOURCES_RS: &str = include_str!("../../templates/resources.rs.hbs");
+
+/// Template for the generated proptest suite (tests/properties.rs).
+pub const PROPERTIES_RS: &str = include_str!("../../templates/properties.rs.hbs");
+
+/// Overview Markdown, shared by every language (also the `overview` partial).
+pub const OVERVIEW_MD: &str = include_str!("../../templates/overview.md.hbs");
+
+/// README for the generated server, shared by every language.
+pub const README_MD: &str = include_str!("../../templates/README.md.hbs");
+
+/// Template for the TypeScript server entry point (src/index.ts).
+pub const TS_INDEX: &str = include_str!("../../templates/typescript/index.ts.hbs");
+
+/// Template for the TypeScript compiler configuration (tsconfig.json).
+pub const TS_TSCONFIG: &str = include_str!("../../templates/typescript/tsconfig.json.hbs");
+
+/// Template for the Python server module (src/<package>/server.py).
+pub const PY_SERVER: &str = include_str!("../../templates/python/server.py.hbs");
+
+/// Template for the `docs` index page (README.md).
+pub const DOCS_INDEX_MD: &str = include_str!("../../templates/docs/index.md.hbs");
+
+/// Template for a `docs` page of one command, job or executor.
+pub const DOCS_PAGE_MD: &str = include_str!("../../templates/docs/page.md.hbs");
@@ -0,0 +1,65 @@
+//! Transports generated servers speak MCP over.
+//!
+//! Servers default to stdio, launched once per editor by the MCP client. The
+//! HTTP transport serves rmcp's streamable HTTP endpoint (responses streamed
+//! as server-sent events) at `/mcp`, so one server can be shared by a whole
+//! team.
+
+use std::{
+    fmt,
+    net::{Ipv4Addr, SocketAddr, SocketAddrV4},
+};
+
+/// Address an HTTP server binds to unless told otherwise.
+pub const DEFAULT_HTTP_BIND: SocketAddr =
+    SocketAddr::V4(SocketAddrV4::new(Ipv4Addr::LOCALHOST, 8080));
+
+/// Transport of the generated MCP server.
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    Default,
+    clap::ValueEnum,
+    serde::Serialize,
+    serde::Deserialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum Transport {
+    /// MCP over stdin/stdout, one server per client
+    #[default]
+    Stdio,
+    /// Streamable HTTP with server-sent events, served at `/mcp`
+    Http,
+}
+
+impl fmt::Display for Transport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Transport::Stdio => "stdio",
+            Transport::Http => "http",
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::ValueEnum;
+
+    use super::*;
+
+    #[test]
+    fn test_transport_names_match_cli_values() {
+        for transport in Transport::value_variants() {
+            let value = transport.to_possible_value().unwrap();
+            assert_eq!(value.get_name(), transport.to_string());
+            assert_eq!(
+                serde_json::to_string(transport).unwrap(),
+                format!("\"{transport}\"")
+            );
+        }
+        assert_eq!(DEFAULT_HTTP_BIND.to_string(), "127.0.0.1:8080");
+    }
+}
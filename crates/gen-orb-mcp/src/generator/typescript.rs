@@ -0,0 +1,141 @@
+//! TypeScript target: a Node package using the TypeScript MCP SDK.
+//!
+//! ```text
+//! out/
+//! ├── README.md
+//! ├── package.json
+//! ├── tsconfig.json
+//! └── src/
+//!     ├── index.ts   # server, resources and tools (templates/typescript)
+//!     └── data.ts    # embedded resource contents
+//! ```
+//!
+//! Resource contents are written as JSON literals into `data.ts` rather than
+//! through templates, so orb text never needs escaping for TypeScript.
+
+use std::{collections::HashMap, path::PathBuf};
+
+use serde_json::json;
+
+use super::{
+    embedded::{self, to_json},
+    CodeGenerator, GeneratorContext, GeneratorError,
+};
+
+/// npm version range of `@modelcontextprotocol/sdk` (needs `registerTool`).
+const MCP_SDK_VERSION: &str = "^1.17.0";
+
+/// Render the TypeScript package for `context`.
+pub(super) fn render(
+    generator: &CodeGenerator<'_>,
+    context: &GeneratorContext,
+    ctx_json: &serde_json::Value,
+) -> Result<HashMap<PathBuf, String>, GeneratorError> {
+    let render = |name: &str| {
+        generator
+            .handlebars
+            .render(name, ctx_json)
+            .map_err(|e| GeneratorError::TemplateRender {
+                name: name.to_string(),
+                source: e,
+            })
+    };
+
+    let mut files = HashMap::new();
+    files.insert(PathBuf::from("src/index.ts"), render("index.ts")?);
+    files.insert(PathBuf::from("tsconfig.json"), render("tsconfig.json")?);
+    files.insert(
+        PathBuf::from("src/data.ts"),
+        data_ts(generator, context, render("overview.md")?)?,
+    );
+    files.insert(
+        PathBuf::from("package.json"),
+        package_json(generator, context)?,
+    );
+    Ok(files)
+}
+
+/// `src/data.ts`: server info, every resource and the definition index.
+fn data_ts(
+    generator: &CodeGenerator<'_>,
+    context: &GeneratorContext,
+    overview: String,
+) -> Result<String, GeneratorError> {
+    let data = embedded::data(generator, context, overview)?;
+    Ok(format!(
+        "// Embedded resources for the {} MCP server.\n\
+         //\n\
+         // Generated by gen-orb-mcp.\n\
+         \n\
+         export interface EmbeddedResource {{\n  \
+           uri: string;\n  \
+           name: string;\n  \
+           description: string;\n  \
+           mimeType: string;\n  \
+           text: string;\n  \
+           listed: boolean;\n\
+         }}\n\
+         \n\
+         export interface Definition {{\n  \
+           version: string;\n  \
+           kind: string;\n  \
+           name: string;\n  \
+           uri: string;\n\
+         }}\n\
+         \n\
+         export const SERVER = {} as const;\n\
+         \n\
+         export const RESOURCES: EmbeddedResource[] = {};\n\
+         \n\
+         export const DEFINITIONS: Definition[] = {};\n",
+        context.orb_name,
+        to_json(&data.server)?,
+        to_json(&data.resources)?,
+        to_json(&data.definitions)?,
+    ))
+}
+
+/// `package.json` for the generated server.
+fn package_json(
+    generator: &CodeGenerator<'_>,
+    context: &GeneratorContext,
+) -> Result<String, GeneratorError> {
+    let package = &context.package_name;
+    let mut files = vec!["dist"];
+    if generator.logo.is_some() {
+        files.push("assets");
+    }
+    let license = match (&generator.license, &generator.license_text) {
+        (Some(spdx), _) => Some(spdx.as_str()),
+        (None, Some(_)) => Some("SEE LICENSE IN LICENSE"),
+        (None, None) => None,
+    };
+    let mut manifest = json!({
+        "name": package,
+        "version": context.version,
+        "description": context.description.as_deref().map(str::trim),
+        "license": license,
+        "type": "module",
+        "bin": { package.as_str(): "dist/index.js" },
+        "files": files,
+        "scripts": {
+            "build": "tsc",
+            "start": "node dist/index.js",
+        },
+        "engines": { "node": ">=18" },
+        "dependencies": {
+            "@modelcontextprotocol/sdk": MCP_SDK_VERSION,
+            "zod": "^3.25.0",
+        },
+        "devDependencies": {
+            "@types/node": "^20.0.0",
+            "typescript": "^5.5.0",
+        },
+    });
+    if let Some(fields) = manifest.as_object_mut() {
+        fields.retain(|_, value| !value.is_null());
+    }
+    let mut json = to_json(&manifest)?;
+    json.push('\n');
+    Ok(json)
+}
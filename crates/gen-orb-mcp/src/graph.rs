@@ -0,0 +1,458 @@
+//! Dependency graph of an orb's definitions for the `graph` subcommand.
+//!
+//! Every command, job and executor of the orb is a node. Jobs and commands
+//! point at the commands their steps invoke (including steps nested in
+//! `when`/`unless`, inline `steps:` lists and arguments to `steps`
+//! parameters), and jobs point at their executor. Built-in steps, imported orb
+//! definitions and names that do not resolve are left out, so the graph shows
+//! how the orb's own definitions depend on each other: which commands a
+//! change affects, and which are never used. Generated servers serve the same
+//! graph as JSON at `orb://graph`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Write as _,
+};
+
+use serde::Serialize;
+
+use crate::parser::{ExecutorRef, OrbDefinition, ParameterType, Step, StepList, StructuredStep};
+
+/// A command, job or executor.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Node {
+    /// Identifier of the node, e.g. `commands/build`, which is also the path
+    /// of its resource URI.
+    pub id: String,
+    /// `commands`, `jobs` or `executors`.
+    pub category: &'static str,
+    /// Definition name.
+    pub name: String,
+}
+
+/// How one definition depends on another.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EdgeKind {
+    /// A job or command step invokes a command
+    Invokes,
+    /// A job runs on an executor
+    Executor,
+}
+
+/// A dependency of `from` on `to`, both node identifiers.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct Edge {
+    /// Identifier of the dependent job or command.
+    pub from: String,
+    /// Identifier of the command or executor depended on.
+    pub to: String,
+    /// What the dependency is.
+    pub kind: EdgeKind,
+}
+
+/// The definitions of an orb and their dependencies.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrbGraph {
+    /// Every definition, sorted by category and name.
+    pub nodes: Vec<Node>,
+    /// Every dependency once, sorted by `from`, then `to`.
+    pub edges: Vec<Edge>,
+}
+
+/// Build the dependency graph of `orb`.
+pub fn build(orb: &OrbDefinition) -> OrbGraph {
+    let mut nodes: Vec<Node> = orb
+        .commands
+        .keys()
+        .map(|name| node("commands", name))
+        .chain(orb.jobs.keys().map(|name| node("jobs", name)))
+        .chain(orb.executors.keys().map(|name| node("executors", name)))
+        .collect();
+    nodes.sort();
+
+    let mut edges = BTreeSet::new();
+    for (name, command) in &orb.commands {
+        let from = node("commands", name).id;
+        collect_invocations(orb, &from, &command.steps, &mut edges);
+    }
+    for (name, job) in &orb.jobs {
+        let from = node("jobs", name).id;
+        collect_invocations(orb, &from, &job.steps, &mut edges);
+        let executor = match &job.executor {
+            Some(ExecutorRef::Name(name) | ExecutorRef::WithParams { name, .. }) => name,
+            None => continue,
+        };
+        if orb.executors.contains_key(executor) {
+            edges.insert(Edge {
+                from,
+                to: node("executors", executor).id,
+                kind: EdgeKind::Executor,
+            });
+        }
+    }
+
+    OrbGraph {
+        nodes,
+        edges: edges.into_iter().collect(),
+    }
+}
+
+fn node(category: &'static str, name: &str) -> Node {
+    Node {
+        id: format!("{category}/{name}"),
+        category,
+        name: name.to_string(),
+    }
+}
+
+/// Add an edge from `from` to every command of `orb` that `steps` invoke.
+fn collect_invocations(
+    orb: &OrbDefinition,
+    from: &str,
+    steps: &[Step],
+    edges: &mut BTreeSet<Edge>,
+) {
+    for step in steps {
+        match step {
+            Step::Simple(name) => invoke(orb, from, name, None, edges),
+            Step::Structured(StructuredStep::When(c) | StructuredStep::Unless(c)) => {
+                collect_invocations(orb, from, &c.steps, edges)
+            }
+            Step::Structured(StructuredStep::Steps(list)) => {
+                collect_invocations(orb, from, list.steps(), edges)
+            }
+            Step::Structured(StructuredStep::CommandInvocation(invocation)) => {
+                for (name, args) in invocation {
+                    invoke(orb, from, name, Some(args), edges);
+                }
+            }
+            Step::Structured(_) => {}
+        }
+    }
+}
+
+fn invoke(
+    orb: &OrbDefinition,
+    from: &str,
+    name: &str,
+    args: Option<&serde_yaml::Value>,
+    edges: &mut BTreeSet<Edge>,
+) {
+    // Built-ins and imported orb commands are not in `orb.commands`.
+    let Some(command) = orb.commands.get(name) else {
+        return;
+    };
+    edges.insert(Edge {
+        from: from.to_string(),
+        to: node("commands", name).id,
+        kind: EdgeKind::Invokes,
+    });
+    // Steps passed to a `steps` parameter run on behalf of the invoker.
+    let Some(serde_yaml::Value::Mapping(args)) = args else {
+        return;
+    };
+    for (arg, value) in args {
+        let is_steps = arg
+            .as_str()
+            .and_then(|arg| command.parameters.get(arg))
+            .is_some_and(|p| p.param_type == ParameterType::Steps);
+        if let Some(list) = StepList::from_value(value).filter(|_| is_steps) {
+            collect_invocations(orb, from, list.steps(), edges);
+        }
+    }
+}
+
+impl OrbGraph {
+    /// Cycles of commands that invoke themselves, directly or through other
+    /// commands, each as the command names along it with the first repeated
+    /// at the end (e.g. `["a", "b", "a"]`).
+    ///
+    /// At least one cycle is reported for every set of commands invoking one
+    /// another, starting at its alphabetically first command. CircleCI only
+    /// rejects such an orb when it is published.
+    pub fn command_cycles(&self) -> Vec<Vec<String>> {
+        let mut invokes: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+        for edge in &self.edges {
+            if edge.kind == EdgeKind::Invokes && edge.from.starts_with("commands/") {
+                invokes.entry(&edge.from).or_default().push(&edge.to);
+            }
+        }
+        let mut cycles = BTreeSet::new();
+        let mut done = BTreeSet::new();
+        for node in self.nodes.iter().filter(|n| n.category == "commands") {
+            find_cycles(&node.id, &invokes, &mut Vec::new(), &mut done, &mut cycles);
+        }
+        cycles.into_iter().collect()
+    }
+
+    /// The graph in Graphviz DOT, jobs as boxes, commands as ellipses and
+    /// executors as components, with executor edges dashed.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph orb {\n  rankdir=LR;\n");
+        for node in &self.nodes {
+            let shape = match node.category {
+                "jobs" => "box",
+                "executors" => "component",
+                _ => "ellipse",
+            };
+            let _ = writeln!(
+                dot,
+                "  \"{}\" [label=\"{}\", shape={shape}];",
+                dot_escape(&node.id),
+                dot_escape(&node.name)
+            );
+        }
+        for edge in &self.edges {
+            let style = match edge.kind {
+                EdgeKind::Invokes => "",
+                EdgeKind::Executor => " [style=dashed]",
+            };
+            let _ = writeln!(
+                dot,
+                "  \"{}\" -> \"{}\"{style};",
+                dot_escape(&edge.from),
+                dot_escape(&edge.to)
+            );
+        }
+        dot.push_str("}\n");
+        dot
+    }
+
+    /// The graph as a Mermaid flowchart, jobs as rectangles, commands as
+    /// rounded rectangles and executors as hexagons, with executor edges
+    /// dotted.
+    ///
+    /// Nodes get positional identifiers (`n0`, `n1`, ...) because definition
+    /// names may contain characters Mermaid does not accept in one.
+    pub fn to_mermaid(&self) -> String {
+        let key = |id: &str| {
+            self.nodes
+                .iter()
+                .position(|node| node.id == id)
+                .unwrap_or_default()
+        };
+        let mut mermaid = String::from("flowchart LR\n");
+        for (index, node) in self.nodes.iter().enumerate() {
+            let label = node.name.replace('"', "#quot;");
+            let _ = match node.category {
+                "jobs" => writeln!(mermaid, "  n{index}[\"{label}\"]"),
+                "executors" => writeln!(mermaid, "  n{index}{{{{\"{label}\"}}}}"),
+                _ => writeln!(mermaid, "  n{index}(\"{label}\")"),
+            };
+        }
+        for edge in &self.edges {
+            let arrow = match edge.kind {
+                EdgeKind::Invokes => "-->",
+                EdgeKind::Executor => "-.->",
+            };
+            let _ = writeln!(mermaid, "  n{} {arrow} n{}", key(&edge.from), key(&edge.to));
+        }
+        mermaid
+    }
+}
+
+/// Depth-first search from `id` along `invokes`, recording a cycle whenever
+/// the search reaches a command already on `path`.
+fn find_cycles<'a>(
+    id: &'a str,
+    invokes: &BTreeMap<&'a str, Vec<&'a str>>,
+    path: &mut Vec<&'a str>,
+    done: &mut BTreeSet<&'a str>,
+    cycles: &mut BTreeSet<Vec<String>>,
+) {
+    if let Some(start) = path.iter().position(|on_path| *on_path == id) {
+        let cycle = &path[start..];
+        let first = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+        let mut names: Vec<String> = cycle[first..]
+            .iter()
+            .chain(&cycle[..first])
+            .map(|id| id.trim_start_matches("commands/").to_string())
+            .collect();
+        names.push(names[0].clone());
+        cycles.insert(names);
+        return;
+    }
+    if done.contains(id) {
+        return;
+    }
+    path.push(id);
+    for to in invokes.get(id).into_iter().flatten() {
+        find_cycles(to, invokes, path, done, cycles);
+    }
+    path.pop();
+    done.insert(id);
+}
+
+fn dot_escape(text: &str) -> String {
+    text.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORB: &str = r#"
+version: 2.1
+orbs:
+  node: circleci/node@5.1.0
+commands:
+  setup:
+    steps:
+      - checkout
+  build:
+    parameters:
+      after-build:
+        type: steps
+        default: []
+    steps:
+      - setup
+      - run: cargo build
+      - steps: << parameters.after-build >>
+  publish:
+    steps:
+      - run: cargo publish
+  unused:
+    steps: []
+jobs:
+  test:
+    executor: rust
+    steps:
+      - when:
+          condition: true
+          steps:
+            - build:
+                after-build:
+                  - publish
+      - node/install
+      - setup
+  lint:
+    executor:
+      name: node/default
+    steps:
+      - setup
+executors:
+  rust:
+    docker:
+      - image: cimg/rust:1.80
+"#;
+
+    fn graph() -> OrbGraph {
+        build(&serde_yaml::from_str(ORB).unwrap())
+    }
+
+    #[test]
+    fn test_build_links_invocations_and_executors() {
+        let graph = graph();
+        let nodes: Vec<_> = graph.nodes.iter().map(|n| n.id.as_str()).collect();
+        assert_eq!(
+            nodes,
+            [
+                "commands/build",
+                "commands/publish",
+                "commands/setup",
+                "commands/unused",
+                "executors/rust",
+                "jobs/lint",
+                "jobs/test",
+            ]
+        );
+        let edges: Vec<_> = graph
+            .edges
+            .iter()
+            .map(|e| (e.from.as_str(), e.to.as_str(), e.kind))
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                ("commands/build", "commands/setup", EdgeKind::Invokes),
+                ("jobs/lint", "commands/setup", EdgeKind::Invokes),
+                ("jobs/test", "commands/build", EdgeKind::Invokes),
+                ("jobs/test", "commands/publish", EdgeKind::Invokes),
+                ("jobs/test", "commands/setup", EdgeKind::Invokes),
+                ("jobs/test", "executors/rust", EdgeKind::Executor),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_command_cycles() {
+        assert!(graph().command_cycles().is_empty());
+
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+version: 2.1
+commands:
+  wrap:
+    parameters:
+      body:
+        type: steps
+        default: []
+    steps:
+      - steps: << parameters.body >>
+  retry:
+    steps:
+      - retry
+  build:
+    steps:
+      - when:
+          condition: true
+          steps:
+            - test
+  test:
+    steps:
+      - wrap:
+          body:
+            - build
+  lint:
+    steps:
+      - build
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            build(&orb).command_cycles(),
+            [vec!["build", "test", "build"], vec!["retry", "retry"]]
+        );
+    }
+
+    #[test]
+    fn test_renderings() {
+        let graph = graph();
+
+        let dot = graph.to_dot();
+        assert!(dot.starts_with("digraph orb {\n  rankdir=LR;\n"), "{dot}");
+        assert!(
+            dot.contains("  \"jobs/test\" [label=\"test\", shape=box];\n"),
+            "{dot}"
+        );
+        assert!(
+            dot.contains("  \"jobs/test\" -> \"commands/build\";\n"),
+            "{dot}"
+        );
+        assert!(
+            dot.contains("  \"jobs/test\" -> \"executors/rust\" [style=dashed];\n"),
+            "{dot}"
+        );
+
+        let mermaid = graph.to_mermaid();
+        assert!(
+            mermaid.starts_with("flowchart LR\n  n0(\"build\")\n"),
+            "{mermaid}"
+        );
+        assert!(mermaid.contains("  n4{{\"rust\"}}\n"), "{mermaid}");
+        assert!(mermaid.contains("  n6[\"test\"]\n"), "{mermaid}");
+        assert!(mermaid.contains("  n0 --> n2\n"), "{mermaid}");
+        assert!(mermaid.ends_with("  n6 -.-> n4\n"), "{mermaid}");
+
+        let json = serde_json::to_value(&graph).unwrap();
+        assert_eq!(
+            json["nodes"][0],
+            serde_json::json!({ "id": "commands/build", "category": "commands", "name": "build" })
+        );
+        assert_eq!(
+            json["edges"][5],
+            serde_json::json!({ "from": "jobs/test", "to": "executors/rust", "kind": "executor" })
+        );
+    }
+}
@@ -12,17 +12,29 @@
 //! gen-orb-mcp generate --orb-path ./src/@orb.yml --output ./dist/
 //! ```
 
+pub mod binary_cache;
 pub mod conformance_rule;
 pub mod consumer_parser;
 pub mod differ;
+pub mod docs;
+pub mod failure;
 pub mod generator;
+pub mod graph;
+pub mod lint;
+pub mod metadata;
 pub mod migrator;
+pub mod parse_cache;
 pub mod parser;
 pub mod primer;
+pub mod registry;
+pub mod scaffold;
+pub mod stats;
+pub mod suggest;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
-use generator::CodeGenerator;
+use failure::FailureKind;
+use generator::{CodeGenerator, GenerateOptions};
 use parser::OrbParser;
 
 /// Generate MCP servers from CircleCI orb definitions.
@@ -39,43 +51,290 @@ use parser::OrbParser;
         orb updates."
 )]
 pub struct Cli {
+    /// Maximum parallel jobs: orbs generated concurrently by `generate --all`,
+    /// and passed to cargo as `-j` for binary builds
+    #[arg(
+        short = 'j',
+        long,
+        global = true,
+        value_name = "N",
+        env = "GEN_ORB_MCP_JOBS"
+    )]
+    jobs: Option<std::num::NonZeroUsize>,
+
+    /// Compiler wrapper for binary builds, passed to cargo as RUSTC_WRAPPER
+    /// (e.g. `sccache`)
+    #[arg(
+        long,
+        global = true,
+        value_name = "PROGRAM",
+        env = "GEN_ORB_MCP_RUSTC_WRAPPER"
+    )]
+    rustc_wrapper: Option<std::path::PathBuf>,
+
+    /// Cargo target directory for binary builds, passed to cargo as
+    /// CARGO_TARGET_DIR so several servers can share build artifacts
+    #[arg(
+        long,
+        global = true,
+        value_name = "DIR",
+        env = "GEN_ORB_MCP_CARGO_TARGET_DIR"
+    )]
+    cargo_target_dir: Option<std::path::PathBuf>,
+
     #[command(subcommand)]
     command: Commands,
 }
 
 #[derive(Debug, Subcommand)]
+#[allow(clippy::large_enum_variant)]
 enum Commands {
     /// Generate an MCP server from an orb definition
     Generate {
         /// Path to the orb YAML file (e.g., src/@orb.yml)
-        #[arg(short = 'p', long, default_value = "src/@orb.yml")]
-        orb_path: std::path::PathBuf,
+        ///
+        /// Repeat to merge several orbs into one server (requires --name).
+        /// Definitions are then named `<orb>/<name>` and served at
+        /// `orb://<orb>/commands/<name>` and so on.
+        #[arg(
+            short = 'p',
+            long,
+            default_value = "src/@orb.yml",
+            env = "GEN_ORB_MCP_ORB_PATH"
+        )]
+        orb_path: Vec<std::path::PathBuf>,
+
+        /// What --orb-path points at [default: detected]
+        ///
+        /// A file directly inside a `.circleci` directory is read as a
+        /// project config, a file named `action.yml` or `action.yaml` as a
+        /// GitHub Actions composite action (`gha-action`), a file named
+        /// `.gitlab-ci.yml` or `<name>.gitlab-ci.yml` as a GitLab CI template
+        /// (`gitlab-template`), anything else as an orb. A config contributes
+        /// its commands, jobs, executors, workflows, pipeline parameters and
+        /// `setup` flag, and must define at least one workflow. An action
+        /// becomes a single command whose parameters are its inputs. A
+        /// template's inputs and variables become pipeline parameters, its
+        /// jobs jobs and its hidden jobs commands.
+        #[arg(long, value_enum, value_name = "KIND", env = "GEN_ORB_MCP_KIND")]
+        kind: Option<parser::SourceKind>,
+
+        /// Published orb to fetch from the CircleCI registry instead of
+        /// reading --orb-path (e.g. `circleci/node@5.1.0`)
+        ///
+        /// Without a version the latest release is used. Private orbs need a
+        /// CircleCI API token in CIRCLECI_TOKEN.
+        #[arg(
+            long,
+            value_name = "NAMESPACE/NAME[@VERSION]",
+            conflicts_with = "orb_path",
+            env = "GEN_ORB_MCP_ORB_REF"
+        )]
+        orb_ref: Option<registry::OrbRef>,
+
+        /// Read a packed orb from standard input instead of --orb-path
+        ///
+        /// Same as `--orb-path -`, for piping in the output of `circleci orb
+        /// source`. Without --name the server is named from the orb's
+        /// `display` URLs.
+        #[arg(
+            long,
+            conflicts_with_all = ["orb_path", "orb_ref"],
+            env = "GEN_ORB_MCP_STDIN"
+        )]
+        stdin: bool,
+
+        /// CircleCI host queried for --orb-ref and --resolve-orbs (for
+        /// CircleCI server installations)
+        #[arg(
+            long,
+            value_name = "URL",
+            default_value = registry::DEFAULT_HOST,
+            env = "GEN_ORB_MCP_CIRCLECI_HOST"
+        )]
+        circleci_host: String,
+
+        /// Fetch the orbs imported under `orbs:` and serve their commands,
+        /// jobs and executors as `orb://<alias>/commands/<name>` and so on
+        ///
+        /// The definitions are named `<alias>/<name>`, as the orb invokes
+        /// them. Imports of imported orbs are not followed.
+        #[arg(long, env = "GEN_ORB_MCP_RESOLVE_ORBS")]
+        resolve_orbs: bool,
+
+        /// Cache directory for orbs fetched by --resolve-orbs
+        /// [default: $XDG_CACHE_HOME/gen-orb-mcp/orbs]
+        ///
+        /// Pinned versions (`x.y.z`) are reused from the cache; `volatile`,
+        /// partial versions and dev labels are fetched every time.
+        #[arg(
+            long,
+            value_name = "DIR",
+            requires = "resolve_orbs",
+            env = "GEN_ORB_MCP_ORB_CACHE"
+        )]
+        orb_cache: Option<std::path::PathBuf>,
 
         /// Output directory for generated server
-        #[arg(short = 'o', long, default_value = "./dist")]
+        #[arg(
+            short = 'o',
+            long,
+            default_value = "./dist",
+            env = "GEN_ORB_MCP_OUTPUT"
+        )]
         output: std::path::PathBuf,
 
         /// Output format
-        #[arg(short, long, value_enum, default_value = "source")]
+        #[arg(
+            short,
+            long,
+            value_enum,
+            default_value = "source",
+            env = "GEN_ORB_MCP_FORMAT"
+        )]
         format: OutputFormat,
 
-        /// Name for the generated orb server (defaults to filename)
-        #[arg(short, long)]
+        /// Language of the generated server
+        ///
+        /// `typescript` writes a Node package using the TypeScript MCP SDK,
+        /// `python` a Python package using the Python MCP SDK. Neither can be
+        /// combined with --format binary or the Rust-only options
+        /// --migrations, --property-tests, --binstall-*,
+        /// --max-resource-bytes, --sandboxed, --transport http,
+        /// --templates-dir and --dev; Python servers also take no
+        /// --server-title.
+        #[arg(long, value_enum, value_name = "LANG", env = "GEN_ORB_MCP_LANGUAGE")]
+        language: Option<generator::Language>,
+
+        /// Render with the main.rs.hbs, lib.rs.hbs and Cargo.toml.hbs found in
+        /// this directory instead of the built-in templates
+        ///
+        /// Templates missing from the directory fall back to the built-ins.
+        #[arg(long, value_name = "DIR", env = "GEN_ORB_MCP_TEMPLATES_DIR")]
+        templates_dir: Option<std::path::PathBuf>,
+
+        /// Reuse binaries from this cache directory when the generated source
+        /// and toolchain are unchanged (binary format only)
+        ///
+        /// Builds are keyed by a hash of the generated files, `rustc -vV` and
+        /// RUSTFLAGS. A hit copies the cached binary into place instead of
+        /// running cargo; a miss builds and stores the result.
+        #[arg(long, value_name = "DIR", env = "GEN_ORB_MCP_BINARY_CACHE")]
+        binary_cache: Option<std::path::PathBuf>,
+
+        /// Reuse parsed orbs from this cache directory when the orb's files
+        /// are unchanged [default DIR: $XDG_CACHE_HOME/gen-orb-mcp/parsed]
+        ///
+        /// Entries are keyed by a hash of the orb's source files, so later
+        /// `validate`, `generate` and `diff` steps of a pipeline skip parsing
+        /// an orb an earlier step has read.
+        #[arg(
+            long,
+            value_name = "DIR",
+            num_args = 0..=1,
+            env = "GEN_ORB_MCP_PARSE_CACHE"
+        )]
+        parse_cache: Option<Option<std::path::PathBuf>>,
+
+        /// Name for the generated orb server
+        ///
+        /// Defaults to the repository name in the orb's `display.source_url`
+        /// (or `display.home_url`), then to the directory or filename.
+        #[arg(short, long, env = "GEN_ORB_MCP_NAME")]
         name: Option<String>,
 
+        /// Word put before the orb name in the crate, package and server
+        /// names (e.g. `acme` names the `toolkit` orb's crate
+        /// `acme_toolkit_mcp`)
+        #[arg(long, value_name = "PREFIX", env = "GEN_ORB_MCP_NAME_PREFIX")]
+        name_prefix: Option<String>,
+
+        /// Word put after the orb name in place of `mcp`; an empty value
+        /// leaves the orb name unsuffixed
+        #[arg(long, value_name = "SUFFIX", env = "GEN_ORB_MCP_CRATE_SUFFIX")]
+        crate_suffix: Option<String>,
+
+        /// Scheme of the server's resource URIs, e.g. `acme` serves
+        /// `acme://overview` [default: orb]
+        ///
+        /// Cannot be combined with --dev.
+        #[arg(long, value_name = "SCHEME", env = "GEN_ORB_MCP_URI_SCHEME")]
+        uri_scheme: Option<String>,
+
         /// Version for the generated MCP server crate (e.g., "1.0.0")
         ///
         /// Required when regenerating an existing output directory.
         /// For CI workflows, this should match the orb release version.
-        #[arg(long = "crate-version")]
+        #[arg(long = "crate-version", env = "GEN_ORB_MCP_CRATE_VERSION")]
         crate_version: Option<String>,
 
+        /// Read the crate version from a single source: `file:<PATH>` (e.g.
+        /// `file:VERSION`), `orb` (`display.version`, else `x-version`, in the
+        /// orb) or `tag` (the latest git tag with --tag-prefix)
+        ///
+        /// The source must yield a version; nothing else is tried. Takes
+        /// precedence over an `x-version` key in the orb and over git tags,
+        /// but not over --crate-version.
+        #[arg(long, value_name = "SOURCE", value_parser = parse_version_from, env = "GEN_ORB_MCP_VERSION_FROM")]
+        version_from: Option<VersionSource>,
+
+        /// Read the crate version from the orb's metadata, as `--version-from
+        /// orb`
+        #[arg(
+            long,
+            conflicts_with = "version_from",
+            env = "GEN_ORB_MCP_VERSION_FROM_ORB"
+        )]
+        version_from_orb: bool,
+
         /// Overwrite existing files without confirmation
         ///
         /// Required for non-interactive CI environments when output exists.
-        #[arg(long)]
+        #[arg(long, env = "GEN_ORB_MCP_FORCE")]
         force: bool,
 
+        /// Print a unified diff of what regeneration would change in the
+        /// output directory instead of writing it
+        ///
+        /// Everything is rendered in memory; no files are written and
+        /// --force is not needed. With --format binary nothing is built.
+        #[arg(long, env = "GEN_ORB_MCP_DRY_RUN")]
+        dry_run: bool,
+
+        /// Leave the generated Rust sources as rendered instead of running
+        /// them through rustfmt
+        ///
+        /// Formatting is skipped anyway when rustfmt is not installed.
+        #[arg(long, env = "GEN_ORB_MCP_NO_FMT")]
+        no_fmt: bool,
+
+        /// Apply `cargo clippy --fix` to the generated crate after writing it
+        /// (Rust only)
+        ///
+        /// Builds the crate, so its dependencies must be available. Fixed
+        /// sources are formatted again unless --no-fmt is given; lints
+        /// clippy cannot fix and build failures are reported as warnings.
+        #[arg(long, conflicts_with = "dry_run", env = "GEN_ORB_MCP_CLIPPY_FIX")]
+        clippy_fix: bool,
+
+        /// Print nothing on success; errors still go to stderr
+        #[arg(short = 'q', long, conflicts_with = "json", env = "GEN_ORB_MCP_QUIET")]
+        quiet: bool,
+
+        /// Print a JSON summary on success instead of text: output
+        /// directory, files written, crate name, version, definition counts
+        /// and the built binary or packaged crate (an array with --all)
+        #[arg(long, conflicts_with = "dry_run", env = "GEN_ORB_MCP_JSON")]
+        json: bool,
+
+        /// Print `mcpServers` blocks for Claude Desktop and Cursor, and the
+        /// command line for other stdio clients, pointing at the built server
+        ///
+        /// Also printed with --quiet, and included as `client_config` in the
+        /// --json summary.
+        #[arg(long, env = "GEN_ORB_MCP_PRINT_CLIENT_CONFIG")]
+        print_client_config: bool,
+
         /// Directory containing conformance rule JSON files to embed in the
         /// server
         ///
@@ -83,7 +342,7 @@ enum Commands {
         /// migration tooling in the generated server. When provided,
         /// the server gains plan_migration and apply_migration MCP
         /// Tools in addition to Resources.
-        #[arg(long)]
+        #[arg(long, env = "GEN_ORB_MCP_MIGRATIONS")]
         migrations: Option<std::path::PathBuf>,
 
         /// Directory of prior orb version YAML snapshots to embed in the server
@@ -91,21 +350,527 @@ enum Commands {
         /// Each file should be named `<version>.yml` (e.g., `4.7.1.yml`). The
         /// generated server will expose version-specific resources for each
         /// prior version alongside the current version.
-        #[arg(long)]
+        #[arg(long, env = "GEN_ORB_MCP_PRIOR_VERSIONS")]
         prior_versions: Option<std::path::PathBuf>,
 
+        /// Additional orb version to embed, as `<version>=<path>` (repeatable)
+        ///
+        /// Each version is served under `orb://v<version>/...` alongside the
+        /// current one, like the snapshots in --prior-versions, and listed in
+        /// the `orb://compare` resource.
+        #[arg(
+            long = "orb-version",
+            value_name = "VERSION=PATH",
+            value_parser = parse_orb_version,
+            env = "GEN_ORB_MCP_ORB_VERSION"
+        )]
+        orb_versions: Vec<(String, std::path::PathBuf)>,
+
         /// Tag prefix used to discover the orb version from git tags
         ///
         /// The git repository is derived automatically from --orb-path.
         /// Defaults to "v" (matches tags like v6.0.0).
-        #[arg(long, default_value = "v")]
+        #[arg(long, default_value = "v", env = "GEN_ORB_MCP_TAG_PREFIX")]
         tag_prefix: String,
+
+        /// Friendly server title advertised to MCP clients (e.g. "Acme CI
+        /// Toolkit")
+        #[arg(long, env = "GEN_ORB_MCP_SERVER_TITLE")]
+        server_title: Option<String>,
+
+        /// Usage guidance returned in the MCP `instructions` field
+        ///
+        /// Defaults to "MCP server for the <name> CircleCI orb".
+        #[arg(long, env = "GEN_ORB_MCP_SERVER_INSTRUCTIONS")]
+        server_instructions: Option<String>,
+
+        /// Previous orb release to summarise in the `orb://changes` resource
+        ///
+        /// Defaults to the newest snapshot in --prior-versions, if any. A file
+        /// named `<version>.yml` is labelled with that version.
+        #[arg(long, env = "GEN_ORB_MCP_PREVIOUS_ORB")]
+        previous_orb: Option<std::path::PathBuf>,
+
+        /// Only expose these definition categories (comma-separated, e.g.
+        /// `commands` or `jobs,examples`; default: all)
+        #[arg(
+            long,
+            value_enum,
+            value_delimiter = ',',
+            value_name = "CATEGORIES",
+            env = "GEN_ORB_MCP_ONLY"
+        )]
+        only: Vec<generator::DefinitionCategory>,
+
+        /// Only expose definitions whose `<category>/<name>` matches one of
+        /// these globs (repeatable, e.g. `commands/*`)
+        #[arg(
+            long,
+            value_name = "GLOB",
+            value_delimiter = ',',
+            env = "GEN_ORB_MCP_INCLUDE"
+        )]
+        include: Vec<String>,
+
+        /// Hide definitions whose `<category>/<name>` matches one of these
+        /// globs (repeatable, e.g. `jobs/deploy-*`); applied after --include
+        #[arg(
+            long,
+            value_name = "GLOB",
+            value_delimiter = ',',
+            env = "GEN_ORB_MCP_EXCLUDE"
+        )]
+        exclude: Vec<String>,
+
+        /// Orb repository revision to embed in the server (default: HEAD of
+        /// the git repository containing --orb-path, when there is one)
+        #[arg(long, value_name = "SHA", env = "GEN_ORB_MCP_SOURCE_REV")]
+        source_rev: Option<String>,
+
+        /// Fail on orb names that are not valid crate names instead of
+        /// sanitizing them
+        ///
+        /// By default a name such as `my.orb@2` is rewritten to `my-orb-2`
+        /// with a warning.
+        #[arg(long, env = "GEN_ORB_MCP_STRICT_NAMES")]
+        strict_names: bool,
+
+        /// Fail when the orb has lint warnings (implies --strict-names)
+        #[arg(long, env = "GEN_ORB_MCP_FAIL_ON_WARNINGS")]
+        fail_on_warnings: bool,
+
+        /// Lint ruleset with rule severities and suppressions (default:
+        /// .gen-orb-mcp-lint.toml in cwd, else `[lint]` in gen-orb-mcp.toml)
+        #[arg(long, value_name = "PATH", env = "GEN_ORB_MCP_LINT_CONFIG")]
+        lint_config: Option<std::path::PathBuf>,
+
+        /// Emit a proptest suite (`tests/properties.rs`) that fuzzes resource
+        /// reads and tool inputs in the generated crate
+        #[arg(long, env = "GEN_ORB_MCP_PROPERTY_TESTS")]
+        property_tests: bool,
+
+        /// SPDX license expression for the generated Cargo.toml (e.g.
+        /// `MIT OR Apache-2.0`)
+        #[arg(
+            long,
+            value_name = "SPDX",
+            value_parser = parse_spdx,
+            env = "GEN_ORB_MCP_LICENSE"
+        )]
+        license: Option<String>,
+
+        /// License text to copy into the generated crate as LICENSE
+        ///
+        /// Without --license the manifest references it via `license-file`.
+        #[arg(long, value_name = "PATH", env = "GEN_ORB_MCP_LICENSE_FILE")]
+        license_file: Option<std::path::PathBuf>,
+
+        /// Logo image (PNG, JPEG, GIF, WebP, ICO or SVG) to serve as
+        /// `orb://logo` and advertise as the server icon
+        ///
+        /// Accepts a local path or an http(s) URL to download.
+        #[arg(long, value_name = "PATH|URL", env = "GEN_ORB_MCP_LOGO")]
+        logo: Option<String>,
+
+        /// cargo-binstall download URL template for the generated server
+        /// (e.g. `https://artifacts.example.com/{ name }-{ version }-{ target }`)
+        ///
+        /// Adds `[package.metadata.binstall]` to the generated Cargo.toml.
+        #[arg(long, value_name = "TEMPLATE", env = "GEN_ORB_MCP_BINSTALL_PKG_URL")]
+        binstall_pkg_url: Option<String>,
+
+        /// GitHub repository whose releases hold the server binaries
+        /// uploaded by `publish` (e.g. `https://github.com/acme/ci-toolkit`)
+        ///
+        /// Adds `[package.metadata.binstall]` pointing at the
+        /// `<tag-prefix><version>` release, with per-target overrides for the
+        /// `<crate>-<os>-<arch>` asset names `publish` uses.
+        #[arg(
+            long,
+            value_name = "URL",
+            env = "GEN_ORB_MCP_BINSTALL_REPO",
+            conflicts_with = "binstall_pkg_url"
+        )]
+        binstall_repo: Option<String>,
+
+        /// cargo-binstall package format [default: bin]
+        #[arg(long, value_name = "FORMAT", env = "GEN_ORB_MCP_BINSTALL_PKG_FMT")]
+        binstall_pkg_fmt: Option<generator::BinstallPkgFmt>,
+
+        /// cargo-binstall template for the binary's path inside an archive
+        #[arg(long, value_name = "TEMPLATE", env = "GEN_ORB_MCP_BINSTALL_BIN_DIR")]
+        binstall_bin_dir: Option<String>,
+
+        /// Crate description for --format crate (default: `description` in
+        /// the config file's `[package]` table, then the orb's description)
+        #[arg(long, value_name = "TEXT", env = "GEN_ORB_MCP_CRATE_DESCRIPTION")]
+        crate_description: Option<String>,
+
+        /// Repository URL for --format crate (default: `repository` in the
+        /// config file's `[package]` table)
+        #[arg(long, value_name = "URL", env = "GEN_ORB_MCP_REPOSITORY")]
+        repository: Option<String>,
+
+        /// Crate keywords for --format crate, at most five (comma-separated;
+        /// default: `keywords` in the config file's `[package]` table)
+        #[arg(
+            long,
+            value_name = "KEYWORDS",
+            value_delimiter = ',',
+            env = "GEN_ORB_MCP_KEYWORDS"
+        )]
+        keywords: Vec<String>,
+
+        /// Cargo registry, as named in `.cargo/config.toml`, that --format
+        /// crate packages for and restricts publishing to (default:
+        /// `registry` in the config file's `[package]` table)
+        #[arg(long, value_name = "NAME", env = "GEN_ORB_MCP_REGISTRY")]
+        registry: Option<String>,
+
+        /// Split embedded resources larger than this many bytes into parts
+        /// served at `<uri>/part/2`, `<uri>/part/3`, ...
+        ///
+        /// Each part, note included, fits the limit and ends with a note
+        /// naming the next one, so clients with small context windows can
+        /// page through large definitions. Split resources are listed as
+        /// `text/plain`, since no part parses as JSON on its own.
+        #[arg(long, value_name = "BYTES", env = "GEN_ORB_MCP_MAX_RESOURCE_BYTES")]
+        max_resource_bytes: Option<std::num::NonZeroUsize>,
+
+        /// Generate a server that runs with embedded data only: no filesystem,
+        /// network or environment access at runtime
+        ///
+        /// The rendered sources are audited and generation fails if they use
+        /// such APIs. The migration tools need the filesystem, so --migrations
+        /// is not allowed.
+        #[arg(long, conflicts_with = "migrations", env = "GEN_ORB_MCP_SANDBOXED")]
+        sandboxed: bool,
+
+        /// Transport the generated server speaks MCP over [default: stdio]
+        ///
+        /// `http` serves streamable HTTP (with server-sent events) at `/mcp`,
+        /// so one server can be shared by a team. The server binds to
+        /// --http-bind unless started with `--bind <ADDR>`, and still speaks
+        /// stdio when started with `--stdio`.
+        #[arg(
+            long,
+            value_enum,
+            value_name = "TRANSPORT",
+            env = "GEN_ORB_MCP_TRANSPORT"
+        )]
+        transport: Option<generator::Transport>,
+
+        /// Default bind address of an HTTP server [default: 127.0.0.1:8080]
+        #[arg(long, value_name = "ADDR", env = "GEN_ORB_MCP_HTTP_BIND")]
+        http_bind: Option<std::net::SocketAddr>,
+
+        /// Compress the responses of an HTTP server with gzip or deflate
+        ///
+        /// Embedded JSON and YAML compress well, which helps clients on slow
+        /// links. Server-sent event streams stay uncompressed.
+        #[arg(long, env = "GEN_ORB_MCP_HTTP_COMPRESSION")]
+        http_compression: bool,
+
+        /// Largest request body an HTTP server accepts, in bytes [default:
+        /// no limit]
+        ///
+        /// Larger requests are answered with `413 Payload Too Large`.
+        #[arg(long, value_name = "BYTES", env = "GEN_ORB_MCP_HTTP_BODY_LIMIT")]
+        http_body_limit: Option<std::num::NonZeroUsize>,
+
+        /// Generate a dev-mode server that reads --orb-path when it starts,
+        /// instead of serving the definitions embedded now
+        ///
+        /// Started with `--watch`, the server also reloads the orb whenever
+        /// one of its files changes, so edits show up without regenerating
+        /// or recompiling. Search, prompts and the parameter-checking tools
+        /// still cover the definitions present at generation. The server
+        /// depends on gen-orb-mcp to parse the orb, so it cannot be combined
+        /// with --sandboxed, --only, --include, --exclude or --resolve-orbs.
+        #[arg(
+            long,
+            conflicts_with_all = ["sandboxed", "all", "orb_ref", "stdin"],
+            env = "GEN_ORB_MCP_DEV"
+        )]
+        dev: bool,
+
+        /// Generate a server for every orb in the repository
+        ///
+        /// Scans the git repository containing the current directory for
+        /// `@orb.yml` files (or the --orb-glob patterns) and writes each
+        /// server to `<output>/<orb-name>`. Per-orb inputs such as --orb-path
+        /// and --name cannot be combined with --all.
+        #[arg(
+            long,
+            conflicts_with_all = [
+                "orb_path",
+                "orb_ref",
+                "stdin",
+                "name",
+                "migrations",
+                "prior_versions",
+                "orb_versions",
+                "previous_orb",
+                "server_title",
+                "server_instructions",
+            ],
+            env = "GEN_ORB_MCP_ALL"
+        )]
+        all: bool,
+
+        /// Glob, relative to the repository root, selecting orb files for
+        /// --all (repeatable; replaces the default `@orb.yml` scan)
+        #[arg(
+            long = "orb-glob",
+            value_name = "GLOB",
+            requires = "all",
+            env = "GEN_ORB_MCP_ORB_GLOB"
+        )]
+        orb_globs: Vec<String>,
+
+        /// With --all, make the output directory a Cargo workspace
+        ///
+        /// Writes a root `Cargo.toml` listing each generated crate as a
+        /// member, so the servers share one target directory and lockfile.
+        /// Requires --language rust and --format source.
+        #[arg(long, requires = "all", env = "GEN_ORB_MCP_WORKSPACE")]
+        workspace: bool,
+
+        /// Apply the named `[profiles.<NAME>]` table from the config file
+        ///
+        /// A profile presets --language, --only, --include, --exclude,
+        /// --name-prefix, --crate-suffix, --uri-scheme,
+        /// --server-title, --server-instructions, --strict-names,
+        /// --fail-on-warnings, --property-tests, --sandboxed,
+        /// --max-resource-bytes, --transport, --http-bind, --resolve-orbs and
+        /// the --binstall-* options. Options given on the command line take
+        /// precedence.
+        #[arg(long, value_name = "NAME", env = "GEN_ORB_MCP_PROFILE")]
+        profile: Option<String>,
+
+        /// Path to the config file holding --profile and the `[package]`
+        /// table read by --format crate (default: gen-orb-mcp.toml in cwd)
+        #[arg(long, value_name = "PATH", env = "GEN_ORB_MCP_CONFIG")]
+        config: Option<std::path::PathBuf>,
     },
     /// Validate an orb definition without generating
     Validate {
         /// Path to the orb YAML file
-        #[arg(short = 'p', long, default_value = "src/@orb.yml")]
+        #[arg(
+            short = 'p',
+            long,
+            default_value = "src/@orb.yml",
+            env = "GEN_ORB_MCP_ORB_PATH"
+        )]
+        orb_path: std::path::PathBuf,
+
+        /// Exit non-zero when any lint warning is reported
+        #[arg(long, env = "GEN_ORB_MCP_FAIL_ON_WARNINGS")]
+        fail_on_warnings: bool,
+
+        /// Reject keys the orb schema does not define, such as a misspelt
+        /// `decription:`, reporting each as a parse error
+        #[arg(long, env = "GEN_ORB_MCP_STRICT")]
+        strict: bool,
+
+        /// Reuse parsed orbs from this cache directory when the orb's files
+        /// are unchanged [default DIR: $XDG_CACHE_HOME/gen-orb-mcp/parsed]
+        ///
+        /// Entries are keyed by a hash of the orb's source files, so later
+        /// `validate`, `generate` and `diff` steps of a pipeline skip parsing
+        /// an orb an earlier step has read.
+        #[arg(
+            long,
+            value_name = "DIR",
+            num_args = 0..=1,
+            env = "GEN_ORB_MCP_PARSE_CACHE"
+        )]
+        parse_cache: Option<Option<std::path::PathBuf>>,
+
+        /// Lint ruleset with rule severities and suppressions (default:
+        /// .gen-orb-mcp-lint.toml in cwd, else `[lint]` in gen-orb-mcp.toml)
+        #[arg(long, value_name = "PATH", env = "GEN_ORB_MCP_LINT_CONFIG")]
+        lint_config: Option<std::path::PathBuf>,
+
+        /// Report format; `sarif` prints a SARIF 2.1.0 log to stdout for code
+        /// scanning dashboards
+        #[arg(
+            long,
+            visible_alias = "output-format",
+            value_enum,
+            default_value = "text",
+            env = "GEN_ORB_MCP_VALIDATE_FORMAT"
+        )]
+        format: ValidateFormat,
+    },
+    /// Report lint findings for an orb
+    ///
+    /// Checks for missing descriptions, undocumented parameters, unpinned
+    /// docker images, overly long `run` steps and widely used parameters
+    /// without defaults. Exits non-zero on findings with `error` severity, or
+    /// on any finding with --fail-on-warnings.
+    Lint {
+        /// Path to the orb YAML file
+        #[arg(
+            short = 'p',
+            long,
+            default_value = "src/@orb.yml",
+            env = "GEN_ORB_MCP_ORB_PATH"
+        )]
+        orb_path: std::path::PathBuf,
+
+        /// Exit non-zero when any lint warning is reported
+        #[arg(long, env = "GEN_ORB_MCP_FAIL_ON_WARNINGS")]
+        fail_on_warnings: bool,
+
+        /// Lint ruleset with rule severities, limits and suppressions
+        /// (default: .gen-orb-mcp-lint.toml in cwd, else `[lint]` in
+        /// gen-orb-mcp.toml)
+        #[arg(long, value_name = "PATH", env = "GEN_ORB_MCP_LINT_CONFIG")]
+        lint_config: Option<std::path::PathBuf>,
+
+        /// Report format; `sarif` prints a SARIF 2.1.0 log to stdout for code
+        /// scanning dashboards
+        #[arg(
+            long,
+            visible_alias = "output-format",
+            value_enum,
+            default_value = "text",
+            env = "GEN_ORB_MCP_LINT_FORMAT"
+        )]
+        format: ValidateFormat,
+    },
+    /// Report complexity metrics for each command, job and executor
+    ///
+    /// Prints parameter counts, step counts (including steps nested in
+    /// `when`/`unless`), step nesting depth and `run` script line counts,
+    /// followed by orb-wide totals, the parameter type distribution, the
+    /// largest commands by step count, the docker image inventory and the
+    /// imported orbs.
+    Stats {
+        /// Path to the orb YAML file
+        #[arg(
+            short = 'p',
+            long,
+            default_value = "src/@orb.yml",
+            env = "GEN_ORB_MCP_ORB_PATH"
+        )]
+        orb_path: std::path::PathBuf,
+
+        /// Print the metrics as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Print the dependency graph of the orb's definitions
+    ///
+    /// Shows which commands each job and command invokes (including steps
+    /// nested in `when`/`unless` and steps passed to `steps` parameters) and
+    /// which executor each job runs on, as Graphviz DOT, a Mermaid flowchart
+    /// or JSON.
+    Graph {
+        /// Path to the orb YAML file
+        #[arg(
+            short = 'p',
+            long,
+            default_value = "src/@orb.yml",
+            env = "GEN_ORB_MCP_ORB_PATH"
+        )]
+        orb_path: std::path::PathBuf,
+
+        /// Output format
+        #[arg(
+            long,
+            value_enum,
+            default_value = "dot",
+            env = "GEN_ORB_MCP_GRAPH_FORMAT"
+        )]
+        format: GraphFormat,
+    },
+    /// Dump the parsed orb definition
+    ///
+    /// Prints the fully parsed orb (commands, jobs, executors, parameters and
+    /// steps, with partials and script includes resolved) so other tooling
+    /// can consume it without generating a server.
+    Inspect {
+        /// Path to the orb YAML file
+        #[arg(
+            short = 'p',
+            long,
+            default_value = "src/@orb.yml",
+            env = "GEN_ORB_MCP_ORB_PATH"
+        )]
+        orb_path: std::path::PathBuf,
+
+        /// Output format
+        #[arg(
+            long,
+            value_enum,
+            default_value = "json",
+            env = "GEN_ORB_MCP_INSPECT_FORMAT"
+        )]
+        format: InspectFormat,
+    },
+    /// Pack an unpacked orb into a single YAML file
+    ///
+    /// Produces the same document as `circleci orb pack`: every file in
+    /// `commands/`, `jobs/`, `executors/` and `examples/` is merged into
+    /// `@orb.yml`, with partials and script includes expanded.
+    Pack {
+        /// Orb source directory containing @orb.yml
+        #[arg(long, default_value = "src", env = "GEN_ORB_MCP_ORB_DIR")]
+        orb_dir: std::path::PathBuf,
+
+        /// File to write the packed orb to (default: stdout)
+        #[arg(short = 'o', long, env = "GEN_ORB_MCP_PACK_OUTPUT")]
+        output: Option<std::path::PathBuf>,
+    },
+    /// Create a new unpacked orb
+    ///
+    /// Writes `src/@orb.yml` with starter `commands/`, `jobs/`,
+    /// `executors/`, `examples/` and `scripts/` files that validate without
+    /// findings, ready for `validate`, `pack` and `generate`.
+    Init {
+        /// Name of the orb (letters, digits, `-` and `_`)
+        name: String,
+
+        /// Registry namespace the orb is published under, used in the
+        /// `display` URLs and the usage example
+        #[arg(long, default_value = "my-namespace")]
+        namespace: String,
+
+        /// Directory to create the orb in (default: ./<NAME>)
+        #[arg(short = 'o', long)]
+        output: Option<std::path::PathBuf>,
+
+        /// Overwrite starter files that already exist
+        #[arg(long)]
+        force: bool,
+    },
+    /// Write Markdown documentation for an orb
+    ///
+    /// Renders a page per command, job and executor (parameter table,
+    /// defaults, executor, docker images and an example invocation) and an
+    /// index linking them, from the same parsed model `generate` serves.
+    Docs {
+        /// Path to the orb YAML file
+        #[arg(
+            short = 'p',
+            long,
+            default_value = "src/@orb.yml",
+            env = "GEN_ORB_MCP_ORB_PATH"
+        )]
         orb_path: std::path::PathBuf,
+
+        /// Directory to write the pages to
+        #[arg(short, long, default_value = "docs", env = "GEN_ORB_MCP_DOCS_OUTPUT")]
+        output: std::path::PathBuf,
+
+        /// Orb name used in headings and example invocations
+        ///
+        /// Defaults to the repository name in the orb's `display.source_url`
+        /// (or `display.home_url`), then to the directory or filename.
+        #[arg(short, long, env = "GEN_ORB_MCP_NAME")]
+        name: Option<String>,
     },
     /// Compute conformance rules by diffing two orb versions
     ///
@@ -129,6 +894,30 @@ enum Commands {
         /// Optional output file for the JSON rules (default: stdout)
         #[arg(long)]
         output: Option<std::path::PathBuf>,
+
+        /// When to colour the summary (auto respects NO_COLOR and non-tty
+        /// output)
+        #[arg(long, value_enum, default_value = "auto", value_name = "WHEN")]
+        color: differ::render::ColorChoice,
+
+        /// Show changed commands, jobs and executors in previous/current
+        /// columns
+        #[arg(long)]
+        side_by_side: bool,
+
+        /// Reuse parsed orbs from this cache directory when the orb's files
+        /// are unchanged [default DIR: $XDG_CACHE_HOME/gen-orb-mcp/parsed]
+        ///
+        /// Entries are keyed by a hash of the orb's source files, so later
+        /// `validate`, `generate` and `diff` steps of a pipeline skip parsing
+        /// an orb an earlier step has read.
+        #[arg(
+            long,
+            value_name = "DIR",
+            num_args = 0..=1,
+            env = "GEN_ORB_MCP_PARSE_CACHE"
+        )]
+        parse_cache: Option<Option<std::path::PathBuf>>,
     },
     /// Apply conformance-based migration to a consumer's .circleci/ directory
     ///
@@ -293,7 +1082,8 @@ enum Commands {
         /// Orb binary base name (e.g. "gen-orb-mcp"). Derives the binary path
         /// and asset name when --binary / --asset-name are not given:
         ///   binary = <input>/target/release/<name_underscored>_mcp
-        ///   asset  = <name_underscored>_mcp-linux-x86_64
+        ///   asset  = <name_underscored>_mcp-<os>-<arch> (e.g. linux-x86_64)
+        /// CARGO_TARGET_DIR replaces <input>/target, as it does for cargo.
         #[arg(short = 'n', long)]
         name: Option<String>,
 
@@ -347,58 +1137,584 @@ enum Commands {
         #[arg(long)]
         dry_run: bool,
     },
+    /// Check that an orb generates a server that compiles, without keeping
+    /// the generated code
+    ///
+    /// Generates the Rust server into a scratch directory under the system
+    /// temp directory, runs `cargo check` there and removes the directory
+    /// again. Pass --cargo-target-dir to reuse compiled dependencies between
+    /// runs.
+    Verify {
+        /// Path to the orb YAML file
+        #[arg(
+            short = 'p',
+            long,
+            default_value = "src/@orb.yml",
+            env = "GEN_ORB_MCP_ORB_PATH"
+        )]
+        orb_path: std::path::PathBuf,
+
+        /// What --orb-path points at [default: detected]
+        #[arg(long, value_enum, value_name = "KIND")]
+        kind: Option<parser::SourceKind>,
+
+        /// Orb name (defaults to the display.source_url repo name, then the
+        /// directory/filename)
+        #[arg(short = 'n', long)]
+        name: Option<String>,
+
+        /// Transport the generated server speaks MCP over
+        #[arg(long, value_enum, value_name = "TRANSPORT", default_value_t)]
+        transport: generator::Transport,
+
+        /// Check the server generated with --sandboxed
+        #[arg(long)]
+        sandboxed: bool,
+
+        /// Also check tests/properties.rs, as generated with
+        /// --property-tests
+        #[arg(long)]
+        property_tests: bool,
+    },
 }
 
 /// Output format for generated MCP server
 #[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
 pub enum OutputFormat {
-    /// Compile to native binary (Linux x86_64)
+    /// Compile to a native binary for the host platform
     Binary,
     /// Generate Rust source code
     Source,
+    /// Generate Rust source with full package metadata and run `cargo
+    /// package` on it, ready to publish to a registry
+    Crate,
 }
 
-/// Optional embedding inputs for `run_generate`.
-struct GenerateExtras<'a> {
-    migrations: &'a Option<std::path::PathBuf>,
-    prior_versions_dir: &'a Option<std::path::PathBuf>,
-    tag_prefix: &'a str,
+impl OutputFormat {
+    /// The `--format` value naming this format.
+    fn as_str(self) -> &'static str {
+        match self {
+            OutputFormat::Binary => "binary",
+            OutputFormat::Source => "source",
+            OutputFormat::Crate => "crate",
+        }
+    }
+}
+
+/// Report format for `validate` and `lint`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum ValidateFormat {
+    /// Human-readable summary
+    Text,
+    /// SARIF 2.1.0 log with file/line locations
+    Sarif,
+}
+
+/// Output format for `inspect`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum InspectFormat {
+    /// Pretty-printed JSON
+    Json,
+    /// YAML, as accepted by `--orb-path` for a packed orb
+    Yaml,
+}
+
+/// Output format for `graph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT, for `dot -Tsvg`
+    Dot,
+    /// Mermaid flowchart, for Markdown renderers such as GitHub's
+    Mermaid,
+    /// Pretty-printed JSON, as served at `orb://graph`
+    Json,
+}
+
+/// Optional embedding inputs for `run_generate`.
+#[derive(Clone, Copy)]
+struct GenerateExtras<'a> {
+    kind: Option<parser::SourceKind>,
+    circleci_host: &'a str,
+    resolve_orbs: bool,
+    orb_cache: &'a Option<std::path::PathBuf>,
+    language: generator::Language,
+    templates_dir: &'a Option<std::path::PathBuf>,
+    migrations: &'a Option<std::path::PathBuf>,
+    prior_versions_dir: &'a Option<std::path::PathBuf>,
+    tag_prefix: &'a str,
+    naming: &'a generator::Naming,
+    server_title: &'a Option<String>,
+    server_instructions: &'a Option<String>,
+    previous_orb: &'a Option<std::path::PathBuf>,
+    only: &'a [generator::DefinitionCategory],
+    include: &'a [String],
+    exclude: &'a [String],
+    strict_names: bool,
+    version_from: &'a Option<VersionSource>,
+    jobs: Option<std::num::NonZeroUsize>,
+    cargo: &'a CargoEnv,
+    fail_on_warnings: bool,
+    source_rev: &'a Option<String>,
+    orb_versions: &'a [(String, std::path::PathBuf)],
+    property_tests: bool,
+    license: &'a Option<String>,
+    license_file: &'a Option<std::path::PathBuf>,
+    logo: &'a Option<String>,
+    binstall: &'a Option<generator::BinstallMetadata>,
+    package: &'a Option<generator::PackageMetadata>,
+    registry: &'a Option<String>,
+    max_resource_bytes: Option<std::num::NonZeroUsize>,
+    sandboxed: bool,
+    transport: generator::Transport,
+    http_bind: Option<std::net::SocketAddr>,
+    http_compression: bool,
+    http_body_limit: Option<std::num::NonZeroUsize>,
+    dev: bool,
+    lint_config: &'a Option<std::path::PathBuf>,
+    binary_cache: &'a Option<std::path::PathBuf>,
+    parse_cache: &'a Option<Option<std::path::PathBuf>>,
+    dry_run: bool,
+    fmt: bool,
+    clippy_fix: bool,
+    print_client_config: bool,
+    report: Report,
+    fetched: Option<&'a registry::FetchedOrb>,
+    stdin: Option<&'a str>,
+}
+
+/// How `generate` reports success on stdout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum Report {
+    /// Progress and a human-readable summary
+    #[default]
+    Text,
+    /// Nothing (`--quiet`)
+    Quiet,
+    /// A JSON [`GenerateSummary`] (`--json`)
+    Json,
+}
+
+impl Report {
+    /// Print a progress line in the text report.
+    fn say(self, line: &str) {
+        if self == Report::Text {
+            println!("{line}");
+        }
+    }
+}
+
+/// The result of generating one server, printed by `generate --json`.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+struct GenerateSummary {
+    orb_name: String,
+    crate_name: String,
+    version: String,
+    format: &'static str,
+    language: generator::Language,
+    output: std::path::PathBuf,
+    /// Files written, relative to `output`, in path order.
+    files: Vec<std::path::PathBuf>,
+    /// Compiled binary (`--format binary`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    binary: Option<std::path::PathBuf>,
+    /// Packaged `.crate` file (`--format crate`).
+    #[serde(rename = "crate", skip_serializing_if = "Option::is_none")]
+    crate_file: Option<std::path::PathBuf>,
+    counts: DefinitionCounts,
+    /// MCP client configuration (`--print-client-config`).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    client_config: Option<generator::clients::ClientConfig>,
+}
+
+/// Definitions a generated server exposes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+struct DefinitionCounts {
+    orbs: usize,
+    commands: usize,
+    jobs: usize,
+    executors: usize,
+    examples: usize,
+    workflows: usize,
+    pipeline_parameters: usize,
+    imported_orbs: usize,
+}
+
+impl DefinitionCounts {
+    /// Totals across the merged `orbs`.
+    fn of(orbs: &[parser::OrbDefinition], imported_orbs: usize) -> Self {
+        let sum =
+            |count: fn(&parser::OrbDefinition) -> usize| -> usize { orbs.iter().map(count).sum() };
+        Self {
+            orbs: orbs.len(),
+            commands: sum(|o| o.commands.len()),
+            jobs: sum(|o| o.jobs.len()),
+            executors: sum(|o| o.executors.len()),
+            examples: sum(|o| o.examples.len()),
+            workflows: sum(|o| o.workflows.len()),
+            pipeline_parameters: sum(|o| o.parameters.len()),
+            imported_orbs,
+        }
+    }
 }
 
 impl Cli {
     /// Execute the CLI command
     pub fn run(&self) -> Result<()> {
+        let cargo = CargoEnv::new(self.rustc_wrapper.clone(), self.cargo_target_dir.as_deref())?;
         match &self.command {
             Commands::Generate {
                 orb_path,
+                kind,
+                orb_ref,
+                stdin,
+                circleci_host,
+                resolve_orbs,
+                orb_cache,
                 output,
                 format,
+                language,
+                templates_dir,
+                binary_cache,
+                parse_cache,
                 name,
+                name_prefix,
+                crate_suffix,
+                uri_scheme,
                 crate_version,
                 force,
+                dry_run,
+                no_fmt,
+                clippy_fix,
+                quiet,
+                json,
+                print_client_config,
                 migrations,
                 prior_versions,
+                orb_versions,
                 tag_prefix,
-            } => run_generate(
-                orb_path,
-                output,
-                format,
-                name,
-                crate_version,
-                *force,
-                GenerateExtras {
+                server_title,
+                server_instructions,
+                previous_orb,
+                only,
+                include,
+                exclude,
+                strict_names,
+                fail_on_warnings,
+                lint_config,
+                property_tests,
+                license,
+                license_file,
+                logo,
+                binstall_pkg_url,
+                binstall_repo,
+                binstall_pkg_fmt,
+                binstall_bin_dir,
+                crate_description,
+                repository,
+                keywords,
+                registry,
+                max_resource_bytes,
+                sandboxed,
+                transport,
+                http_bind,
+                http_compression,
+                http_body_limit,
+                dev,
+                source_rev,
+                version_from,
+                version_from_orb,
+                all,
+                orb_globs,
+                workspace,
+                profile,
+                config,
+            } => {
+                let config_path = config
+                    .clone()
+                    .unwrap_or_else(|| std::path::PathBuf::from(DEFAULT_CONFIG_FILE));
+                let profile = match profile {
+                    Some(name) => load_generate_profile(&config_path, name)?,
+                    None => GenerateProfile::default(),
+                };
+                let binstall = resolve_binstall(
+                    BinstallSource::new(
+                        binstall_pkg_url.clone().or(profile.binstall_pkg_url),
+                        binstall_repo.clone().or(profile.binstall_repo),
+                        tag_prefix,
+                    )?,
+                    binstall_pkg_fmt.or(profile.binstall_pkg_fmt),
+                    binstall_bin_dir.clone().or(profile.binstall_bin_dir),
+                )?;
+                let mut naming = generator::Naming::default();
+                if let Some(prefix) = name_prefix.clone().or(profile.name_prefix) {
+                    naming = naming.with_prefix(prefix);
+                }
+                if let Some(suffix) = crate_suffix.clone().or(profile.crate_suffix) {
+                    naming = naming.with_suffix(suffix);
+                }
+                if let Some(scheme) = uri_scheme.clone().or(profile.uri_scheme) {
+                    naming = naming.with_uri_scheme(scheme);
+                }
+                let server_title = server_title.clone().or(profile.server_title);
+                let server_instructions =
+                    server_instructions.clone().or(profile.server_instructions);
+                let language = language.or(profile.language).unwrap_or_default();
+                let templates_dir = templates_dir.clone().or(profile.templates_dir);
+                if language != generator::Language::Rust && *format == OutputFormat::Binary {
+                    anyhow::bail!("--format binary requires --language rust");
+                }
+                if *clippy_fix && language != generator::Language::Rust {
+                    anyhow::bail!("--clippy-fix requires --language rust");
+                }
+                if *workspace {
+                    if language != generator::Language::Rust {
+                        anyhow::bail!("--workspace requires --language rust");
+                    }
+                    if *format != OutputFormat::Source {
+                        anyhow::bail!("--workspace requires --format source");
+                    }
+                }
+                let mut license = license.clone();
+                let (package, registry) = if *format == OutputFormat::Crate {
+                    if language != generator::Language::Rust {
+                        anyhow::bail!("--format crate requires --language rust");
+                    }
+                    let config = load_package_config(&config_path)?;
+                    license = license.or(config.license);
+                    let package = generator::PackageMetadata {
+                        description: crate_description.clone().or(config.description),
+                        repository: repository.clone().or(config.repository),
+                        keywords: if keywords.is_empty() {
+                            config.keywords
+                        } else {
+                            keywords.clone()
+                        },
+                        registries: vec![],
+                    };
+                    if package.keywords.len() > MAX_KEYWORDS {
+                        anyhow::bail!(
+                            "At most {MAX_KEYWORDS} keywords are allowed, got {}",
+                            package.keywords.len()
+                        );
+                    }
+                    if license.is_none() && license_file.is_none() {
+                        anyhow::bail!(
+                            "--format crate requires a license: pass --license or \
+                             --license-file, or set `license` in the `[package]` table of {}",
+                            config_path.display()
+                        );
+                    }
+                    let registry = registry.clone().or(config.registry);
+                    let package = generator::PackageMetadata {
+                        registries: registry.iter().cloned().collect(),
+                        ..package
+                    };
+                    (Some(package), registry)
+                } else {
+                    if crate_description.is_some()
+                        || repository.is_some()
+                        || !keywords.is_empty()
+                        || registry.is_some()
+                    {
+                        tracing::warn!(
+                            "--crate-description, --repository, --keywords and --registry only \
+                             apply to --format crate"
+                        );
+                    }
+                    (None, None)
+                };
+                let version_from = if *version_from_orb {
+                    Some(VersionSource::Orb)
+                } else {
+                    version_from.clone()
+                };
+                let stdin_path = [std::path::PathBuf::from(STDIN_ORB_PATH)];
+                let orb_path = if *stdin {
+                    &stdin_path[..]
+                } else {
+                    orb_path.as_slice()
+                };
+                let stdin_source = if orb_path.iter().any(|p| p.as_os_str() == STDIN_ORB_PATH) {
+                    let source = std::io::read_to_string(std::io::stdin())
+                        .map_err(|e| anyhow::anyhow!("Failed to read the orb from stdin: {}", e))?;
+                    tracing::info!(bytes = source.len(), "Read orb from stdin");
+                    Some(source)
+                } else {
+                    None
+                };
+                let extras = GenerateExtras {
+                    kind: *kind,
+                    circleci_host,
+                    resolve_orbs: *resolve_orbs || profile.resolve_orbs,
+                    orb_cache,
+                    language,
+                    templates_dir: &templates_dir,
                     migrations,
                     prior_versions_dir: prior_versions,
                     tag_prefix,
-                },
+                    naming: &naming,
+                    server_title: &server_title,
+                    server_instructions: &server_instructions,
+                    previous_orb,
+                    only: if only.is_empty() { &profile.only } else { only },
+                    include: if include.is_empty() {
+                        &profile.include
+                    } else {
+                        include
+                    },
+                    exclude: if exclude.is_empty() {
+                        &profile.exclude
+                    } else {
+                        exclude
+                    },
+                    strict_names: *strict_names || profile.strict_names,
+                    version_from: &version_from,
+                    jobs: self.jobs,
+                    cargo: &cargo,
+                    fail_on_warnings: *fail_on_warnings || profile.fail_on_warnings,
+                    source_rev,
+                    orb_versions,
+                    property_tests: *property_tests || profile.property_tests,
+                    license: &license,
+                    license_file,
+                    logo,
+                    binstall: &binstall,
+                    package: &package,
+                    registry: &registry,
+                    max_resource_bytes: max_resource_bytes.or(profile.max_resource_bytes),
+                    sandboxed: *sandboxed || profile.sandboxed,
+                    transport: transport.or(profile.transport).unwrap_or_default(),
+                    http_bind: http_bind.or(profile.http_bind),
+                    http_compression: *http_compression || profile.http_compression,
+                    http_body_limit: http_body_limit.or(profile.http_body_limit),
+                    dev: *dev,
+                    lint_config,
+                    binary_cache,
+                    parse_cache,
+                    dry_run: *dry_run,
+                    fmt: !*no_fmt,
+                    clippy_fix: *clippy_fix,
+                    print_client_config: *print_client_config,
+                    report: match (*quiet, *json) {
+                        (_, true) => Report::Json,
+                        (true, _) => Report::Quiet,
+                        _ => Report::Text,
+                    },
+                    fetched: None,
+                    stdin: stdin_source.as_deref(),
+                };
+                if *all {
+                    run_generate_all(
+                        orb_globs,
+                        output,
+                        format,
+                        crate_version,
+                        *force,
+                        *workspace,
+                        extras,
+                    )
+                } else if let Some(orb_ref) = orb_ref {
+                    let fetched = registry::RegistryClient::new(circleci_host).fetch(orb_ref)?;
+                    tracing::info!(orb = %orb_ref, version = %fetched.version, "Fetched orb");
+                    let dir = std::env::temp_dir().join(format!(
+                        "gen-orb-mcp-{}-{}",
+                        std::process::id(),
+                        orb_ref.name
+                    ));
+                    let path = dir.join(format!("{}.yml", orb_ref.name));
+                    std::fs::create_dir_all(&dir)?;
+                    std::fs::write(&path, &fetched.source)?;
+                    let extras = GenerateExtras {
+                        fetched: Some(&fetched),
+                        ..extras
+                    };
+                    let result = run_generate(
+                        std::slice::from_ref(&path),
+                        output,
+                        format,
+                        name,
+                        crate_version,
+                        *force,
+                        extras,
+                    );
+                    let _ = std::fs::remove_dir_all(&dir);
+                    report_summary(extras.report, &result?)
+                } else {
+                    let summary = run_generate(
+                        orb_path,
+                        output,
+                        format,
+                        name,
+                        crate_version,
+                        *force,
+                        extras,
+                    )?;
+                    report_summary(extras.report, &summary)
+                }
+            }
+            Commands::Validate {
+                orb_path,
+                fail_on_warnings,
+                strict,
+                parse_cache,
+                lint_config,
+                format,
+            } => run_validate(
+                orb_path,
+                *fail_on_warnings,
+                *strict,
+                open_parse_cache(parse_cache).as_ref(),
+                lint_config.as_deref(),
+                *format,
             ),
-            Commands::Validate { orb_path } => run_validate(orb_path),
+            Commands::Lint {
+                orb_path,
+                fail_on_warnings,
+                lint_config,
+                format,
+            } => run_lint(orb_path, *fail_on_warnings, lint_config.as_deref(), *format),
+            Commands::Stats { orb_path, json } => run_stats(orb_path, *json),
+            Commands::Graph { orb_path, format } => {
+                let orb = OrbParser::parse(orb_path).map_err(|e| anyhow::anyhow!("{}", e))?;
+                print!("{}", render_graph(&orb, *format)?);
+                Ok(())
+            }
+            Commands::Inspect { orb_path, format } => {
+                let orb = OrbParser::parse(orb_path).map_err(|e| anyhow::anyhow!("{}", e))?;
+                print!("{}", render_inspect(&orb, *format)?);
+                Ok(())
+            }
+            Commands::Pack { orb_dir, output } => run_pack(orb_dir, output.as_deref()),
+            Commands::Init {
+                name,
+                namespace,
+                output,
+                force,
+            } => run_init(name, namespace, output.as_deref(), *force),
+            Commands::Docs {
+                orb_path,
+                output,
+                name,
+            } => run_docs(orb_path, output, name.as_deref()),
             Commands::Diff {
                 current,
                 previous,
                 since_version,
                 output,
-            } => run_diff(current, previous, since_version, output),
+                color,
+                side_by_side,
+                parse_cache,
+            } => run_diff(
+                current,
+                previous,
+                since_version,
+                output,
+                DiffView {
+                    color: color.enabled(),
+                    side_by_side: *side_by_side,
+                },
+                open_parse_cache(parse_cache).as_ref(),
+            ),
             Commands::Migrate {
                 ci_dir,
                 orb,
@@ -492,39 +1808,493 @@ impl Cli {
                 name,
                 target,
                 dry_run,
-            } => run_build(input, name.as_deref(), target.as_deref(), *dry_run),
+            } => run_build(
+                input,
+                name.as_deref(),
+                target.as_deref(),
+                self.jobs,
+                &cargo,
+                *dry_run,
+            ),
+            Commands::Verify {
+                orb_path,
+                kind,
+                name,
+                transport,
+                sandboxed,
+                property_tests,
+            } => {
+                let kind = kind.unwrap_or_else(|| parser::SourceKind::detect(orb_path));
+                let orb = OrbParser::parse_as(orb_path, kind)
+                    .map_err(|e| FailureKind::Parse.wrap(anyhow::anyhow!("{}", e)))?;
+                let opts = GenerateOptions {
+                    sandboxed: *sandboxed,
+                    property_tests: *property_tests,
+                    sources: lint::source::definition_sources(orb_path)
+                        .into_iter()
+                        .collect(),
+                    ..GenerateOptions::new(
+                        name.clone()
+                            .or_else(|| orb_name_from_display(&orb))
+                            .unwrap_or_else(|| derive_orb_name(orb_path)),
+                        "0.0.0",
+                    )
+                }
+                .with_transport(*transport);
+                run_verify(&orb, &opts, self.jobs, &cargo)
+            }
         }
     }
 }
 
-fn run_generate(
-    orb_path: &std::path::PathBuf,
-    output: &std::path::PathBuf,
+/// Directories never descended into when scanning for orbs.
+const ORB_SCAN_SKIP_DIRS: &[&str] = &["target", "node_modules"];
+
+fn run_generate_all(
+    orb_globs: &[String],
+    output: &std::path::Path,
     format: &OutputFormat,
-    name: &Option<String>,
     crate_version: &Option<String>,
     force: bool,
+    workspace: bool,
     extras: GenerateExtras<'_>,
 ) -> Result<()> {
-    tracing::info!(?orb_path, ?output, ?format, "Generating MCP server");
+    let cwd = std::env::current_dir()?;
+    let root = find_git_root(&cwd).unwrap_or(cwd);
+    let orb_paths = discover_orb_paths(&root, orb_globs, output)?;
+    if orb_paths.is_empty() {
+        anyhow::bail!("No orbs found under {}", root.display());
+    }
+    tracing::info!(root = %root.display(), orbs = orb_paths.len(), "Discovered orbs");
 
-    let orb = OrbParser::parse(orb_path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let mut seen = std::collections::HashMap::new();
+    for orb_path in &orb_paths {
+        let orb_name = derive_orb_name(orb_path);
+        if let Some(other) = seen.insert(orb_name.clone(), orb_path.clone()) {
+            anyhow::bail!(
+                "Orbs {} and {} both resolve to the name '{}'; generate them individually \
+                 with --name",
+                other.display(),
+                orb_path.display(),
+                orb_name
+            );
+        }
+    }
+
+    // Workers pull orbs from a shared index; the first failure is reported
+    // after all in-flight generations finish.
+    let workers = extras.jobs.map_or(1, |n| n.get()).min(orb_paths.len());
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let results: Vec<Result<Vec<GenerateSummary>>> = std::thread::scope(|scope| {
+        let handles: Vec<_> = (0..workers)
+            .map(|_| {
+                scope.spawn(|| -> Result<Vec<GenerateSummary>> {
+                    let mut summaries = vec![];
+                    loop {
+                        let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let Some(orb_path) = orb_paths.get(i) else {
+                            break;
+                        };
+                        let orb_output = output.join(derive_orb_name(orb_path));
+                        let result = run_generate(
+                            std::slice::from_ref(orb_path),
+                            &orb_output,
+                            format,
+                            &None,
+                            crate_version,
+                            force,
+                            extras,
+                        )
+                        .map_err(|e| {
+                            let error = anyhow::anyhow!("{}: {}", orb_path.display(), e);
+                            match failure::kind_of(&e) {
+                                Some(kind) => kind.wrap(error),
+                                None => error,
+                            }
+                        });
+                        extras.report.say("");
+                        summaries.push(result?);
+                    }
+                    Ok(summaries)
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .map(|h| h.join().expect("generate worker panicked"))
+            .collect()
+    });
+    let mut summaries: Vec<GenerateSummary> = results
+        .into_iter()
+        .collect::<Result<Vec<_>>>()?
+        .into_iter()
+        .flatten()
+        .collect();
+    summaries.sort_by(|a, b| a.output.cmp(&b.output));
+    if workspace {
+        let mut members: Vec<&String> = seen.keys().collect();
+        members.sort();
+        let manifest_path = output.join("Cargo.toml");
+        let manifest = workspace_manifest(&members)?;
+        if extras.dry_run {
+            let existing = std::fs::read_to_string(&manifest_path).ok();
+            if existing.as_deref() != Some(manifest.as_str()) {
+                eprintln!("(Dry run — {} not written)", manifest_path.display());
+            }
+        } else {
+            std::fs::write(&manifest_path, manifest).map_err(|e| {
+                FailureKind::Generation.wrap(anyhow::anyhow!(
+                    "Failed to write {}: {}",
+                    manifest_path.display(),
+                    e
+                ))
+            })?;
+        }
+    }
+    if extras.report != Report::Json {
+        for summary in &summaries {
+            print_client_config(extras.report, summary);
+        }
+    }
+    match extras.report {
+        Report::Json => println!("{}", serde_json::to_string_pretty(&summaries)?),
+        Report::Text if !extras.dry_run => {
+            println!(
+                "Generated {} MCP servers under {}",
+                orb_paths.len(),
+                output.display()
+            );
+            if workspace {
+                println!(
+                    "Workspace: cd {} && cargo build --release",
+                    output.display()
+                );
+            }
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Root `Cargo.toml` of the `--workspace` output, with one member per orb
+/// directory.
+fn workspace_manifest(members: &[&String]) -> Result<String> {
+    let mut manifest =
+        String::from("# Generated by gen-orb-mcp.\n\n[workspace]\nresolver = \"2\"\nmembers = [\n");
+    for member in members {
+        // JSON string escapes are valid in a TOML basic string
+        manifest.push_str(&format!("    {},\n", serde_json::to_string(member)?));
+    }
+    manifest.push_str("]\n");
+    Ok(manifest)
+}
+
+/// Print the `--json` summary of a single `generate` run.
+fn report_summary(report: Report, summary: &GenerateSummary) -> Result<()> {
+    match report {
+        Report::Json => println!("{}", serde_json::to_string_pretty(summary)?),
+        _ => print_client_config(report, summary),
+    }
+    Ok(())
+}
+
+/// Print the `--print-client-config` snippets of `summary`, if any, after
+/// the text report.
+fn print_client_config(report: Report, summary: &GenerateSummary) {
+    if let Some(config) = &summary.client_config {
+        if report == Report::Text {
+            println!();
+        }
+        print!("{config}");
+    }
+}
+
+/// Client configuration launching the server generated into `output` where
+/// its build puts it.
+fn client_config(
+    server: &generator::GeneratedServer,
+    output: &std::path::Path,
+    extras: &GenerateExtras<'_>,
+) -> generator::clients::ClientConfig {
+    let absolute = |path: std::path::PathBuf| {
+        std::path::absolute(&path)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    };
+    let http = extras.transport == generator::Transport::Http;
+    let stdio = match extras.language {
+        generator::Language::Rust => generator::clients::StdioLaunch {
+            command: absolute(release_binary_path(
+                &extras.cargo.target_dir(output),
+                None,
+                &server.crate_name,
+            )),
+            args: if http {
+                vec!["--stdio".to_string()]
+            } else {
+                vec![]
+            },
+        },
+        generator::Language::TypeScript => generator::clients::StdioLaunch {
+            command: "node".to_string(),
+            args: vec![absolute(output.join("dist").join("index.js"))],
+        },
+        // The console script `pip install <output>` puts on PATH
+        generator::Language::Python => generator::clients::StdioLaunch {
+            command: server.crate_name.clone(),
+            args: vec![],
+        },
+    };
+    let url = http.then(|| {
+        format!(
+            "http://{}/mcp",
+            extras
+                .http_bind
+                .unwrap_or(generator::transport::DEFAULT_HTTP_BIND)
+        )
+    });
+    generator::clients::ClientConfig::new(&server.orb_name, stdio, url.as_deref())
+}
+
+/// Find orb entry files under `root`, sorted by path.
+///
+/// With no globs, every `@orb.yml` is an orb root; hidden directories, build
+/// directories and the output directory are skipped.
+fn discover_orb_paths(
+    root: &std::path::Path,
+    globs: &[String],
+    output: &std::path::Path,
+) -> Result<Vec<std::path::PathBuf>> {
+    let mut found = Vec::new();
+    if globs.is_empty() {
+        let output = output.canonicalize().ok();
+        let mut pending = vec![root.to_path_buf()];
+        while let Some(dir) = pending.pop() {
+            for entry in std::fs::read_dir(&dir)?.flatten() {
+                let path = entry.path();
+                let file_name = entry.file_name();
+                let file_name = file_name.to_string_lossy();
+                if path.is_dir() {
+                    let skip = file_name.starts_with('.')
+                        || ORB_SCAN_SKIP_DIRS.contains(&file_name.as_ref())
+                        || output.as_deref() == path.canonicalize().ok().as_deref();
+                    if !skip {
+                        pending.push(path);
+                    }
+                } else if file_name == "@orb.yml" {
+                    found.push(path);
+                }
+            }
+        }
+    } else {
+        for pattern in globs {
+            let full = root.join(pattern);
+            let paths = glob::glob(&full.to_string_lossy())
+                .map_err(|e| anyhow::anyhow!("Invalid --orb-glob '{}': {}", pattern, e))?;
+            found.extend(paths.flatten().filter(|p| p.is_file()));
+        }
+    }
+    found.sort();
+    found.dedup();
+    Ok(found)
+}
+
+/// `--orb-path` value that reads a packed orb from standard input.
+const STDIN_ORB_PATH: &str = "-";
+
+/// Source path naming an orb read from standard input in parse errors, lint
+/// messages and `.gen-orb-mcp.json`.
+const STDIN_SOURCE: &str = "<stdin>";
+
+/// Parse and lint the orb at `orb_path` (or piped to stdin) for `generate`.
+fn load_orb(
+    orb_path: &std::path::Path,
+    extras: &GenerateExtras<'_>,
+) -> Result<parser::OrbDefinition> {
+    let (orb, kind) = if let Some(source) = extras.stdin {
+        match extras.kind {
+            Some(parser::SourceKind::Config) => {
+                anyhow::bail!("--kind config cannot be read from stdin; pipe in a packed orb")
+            }
+            Some(parser::SourceKind::GhaAction) => {
+                anyhow::bail!("--kind gha-action cannot be read from stdin; pipe in a packed orb")
+            }
+            Some(parser::SourceKind::GitlabTemplate) => anyhow::bail!(
+                "--kind gitlab-template cannot be read from stdin; pipe in a packed orb"
+            ),
+            _ => {}
+        }
+        let orb = OrbParser::parse_packed_content(source, std::path::Path::new(STDIN_SOURCE))
+            .map_err(|e| FailureKind::Parse.wrap(anyhow::anyhow!("{}", e)))?;
+        (orb, parser::SourceKind::Orb)
+    } else {
+        let kind = extras
+            .kind
+            .unwrap_or_else(|| parser::SourceKind::detect(orb_path));
+        let cache = open_parse_cache(extras.parse_cache);
+        let orb = parse_cached(cache.as_ref(), orb_path, kind, false, || {
+            OrbParser::parse_as(orb_path, kind)
+        })
+        .map_err(|e| FailureKind::Parse.wrap(anyhow::anyhow!("{}", e)))?;
+        (orb, kind)
+    };
     tracing::info!(
+        ?kind,
         commands = orb.commands.len(),
         jobs = orb.jobs.len(),
         executors = orb.executors.len(),
         "Parsed orb definition"
     );
 
-    let orb_name = name.clone().unwrap_or_else(|| derive_orb_name(orb_path));
+    let lint_config = extras.lint_config.as_deref();
+    let (mut warnings, shown) = match extras.stdin {
+        Some(source) => (
+            lint_orb_with(lint::config::packed_suppressions(source), &orb, lint_config)?,
+            STDIN_SOURCE.to_string(),
+        ),
+        None => (
+            lint_orb(orb_path, &orb, lint_config)?,
+            orb_path.display().to_string(),
+        ),
+    };
+    if matches!(
+        kind,
+        parser::SourceKind::Config | parser::SourceKind::GitlabTemplate
+    ) {
+        // Project configs and GitLab templates have no top-level description
+        // to document.
+        warnings.retain(|w| w.location != "orb");
+    }
+    for warning in &warnings {
+        tracing::warn!(%warning, "Lint warning");
+    }
+    let errors = count_lint_errors(&warnings);
+    if errors > 0 {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "{} lint error(s) in {}:\n{}",
+            errors,
+            shown,
+            format_warnings(&warnings)
+        )));
+    }
+    if extras.fail_on_warnings && !warnings.is_empty() {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "{} lint warning(s) in {} (--fail-on-warnings):\n{}",
+            warnings.len(),
+            shown,
+            format_warnings(&warnings)
+        )));
+    }
+    Ok(orb)
+}
+
+/// Fetch and parse the orbs `orb` imports, as `(alias, pinned reference,
+/// definition)` in alias order.
+fn resolve_imported_orbs(
+    orb: &parser::OrbDefinition,
+    extras: &GenerateExtras<'_>,
+) -> Result<Vec<(String, String, parser::OrbDefinition)>> {
+    let client = registry::RegistryClient::new(extras.circleci_host);
+    let cache = registry::OrbCache::new(
+        extras
+            .orb_cache
+            .clone()
+            .unwrap_or_else(registry::OrbCache::default_dir),
+    );
+    let mut imports: Vec<(&String, &String)> = orb.orbs.iter().collect();
+    imports.sort();
+    imports
+        .into_iter()
+        .map(|(alias, reference)| {
+            let orb_ref: registry::OrbRef = reference
+                .parse()
+                .map_err(|e| anyhow::anyhow!("Imported orb `{}`: {}", alias, e))?;
+            let fetched = cache.fetch(&client, &orb_ref)?;
+            let resolved = fetched.resolved_ref();
+            let definition =
+                OrbParser::parse_packed_content(&fetched.source, std::path::Path::new(&resolved))
+                    .map_err(|e| anyhow::anyhow!("{}", e))?;
+            tracing::info!(alias = %alias, orb = %resolved, "Resolved imported orb");
+            Ok((alias.clone(), resolved, definition))
+        })
+        .collect()
+}
+
+/// Generate one server from `orb_paths`; several paths are merged into a
+/// single server named by `name`.
+fn run_generate(
+    orb_paths: &[std::path::PathBuf],
+    output: &std::path::PathBuf,
+    format: &OutputFormat,
+    name: &Option<String>,
+    crate_version: &Option<String>,
+    force: bool,
+    extras: GenerateExtras<'_>,
+) -> Result<GenerateSummary> {
+    tracing::info!(?orb_paths, ?output, ?format, "Generating MCP server");
+    let Some(orb_path) = orb_paths.first() else {
+        anyhow::bail!("no --orb-path given");
+    };
+    if extras.stdin.is_some() {
+        if orb_paths.len() > 1 {
+            anyhow::bail!("an orb read from stdin cannot be merged with other orbs");
+        }
+        if extras.dev {
+            anyhow::bail!("--dev watches the orb on disk and cannot read it from stdin");
+        }
+    }
+    let orbs = orb_paths
+        .iter()
+        .map(|path| load_orb(path, &extras))
+        .collect::<Result<Vec<_>>>()?;
+    let multi = orbs.len() > 1;
+    if multi && name.is_none() {
+        anyhow::bail!("--name is required when merging several orbs with --orb-path");
+    }
+    let orb = &orbs[0];
+
+    let orb_name = match name.clone().or_else(|| orb_name_from_display(orb)) {
+        Some(orb_name) => orb_name,
+        None if extras.stdin.is_some() => {
+            anyhow::bail!("--name is required for an orb read from stdin without `display` URLs")
+        }
+        None => derive_orb_name(orb_path),
+    };
 
-    // Auto-discover version from the git repo containing orb_path
-    let git_hint: Option<String> = match find_git_root(orb_path) {
-        Ok(repo) => discover_latest_version(&repo, extras.tag_prefix)?,
-        Err(_) => None,
+    // Version hint: the --version-from source, then the orb's x-version key,
+    // then the registry version of --orb-ref, then the latest tag in the git
+    // repo containing (the first) orb_path
+    let x_version = orb.x_version.as_ref().filter(|_| !multi);
+    let version_hint: Option<String> = if let Some(source) = extras.version_from {
+        if multi && *source == VersionSource::Orb {
+            anyhow::bail!("--version-from orb cannot be combined with several orbs");
+        }
+        Some(read_version_source(
+            source,
+            orb,
+            orb_path,
+            extras.tag_prefix,
+        )?)
+    } else if let Some(v) = x_version {
+        Some(checked_version(v.trim(), "x-version")?)
+    } else if let Some(fetched) = extras.fetched {
+        // Dev versions such as `dev:alpha` are not semver
+        semver::Version::parse(&fetched.version)
+            .ok()
+            .map(|v| v.to_string())
+    } else {
+        match find_git_root(orb_path) {
+            Ok(repo) => discover_latest_version(&repo, extras.tag_prefix)?,
+            Err(_) => None,
+        }
     };
-    let resolved_version =
-        resolve_version(output, crate_version.as_deref(), force, git_hint.as_deref())?;
+    let resolved_version = resolve_version(
+        output,
+        crate_version.as_deref(),
+        force || extras.dry_run,
+        version_hint.as_deref(),
+    )?;
     tracing::info!(version = %resolved_version, "Using version");
 
     let conformance_rules = if let Some(migrations_dir) = extras.migrations {
@@ -536,11 +2306,18 @@ fn run_generate(
         tracing::info!(rules = conformance_rules.len(), "Loaded conformance rules");
     }
 
-    let prior_versions_data = if let Some(dir) = extras.prior_versions_dir {
+    let mut prior_versions_data = if let Some(dir) = extras.prior_versions_dir {
         load_prior_versions(dir)?
     } else {
         vec![]
     };
+    for (version, path) in extras.orb_versions {
+        let orb_def = OrbParser::parse(path).map_err(|e| {
+            FailureKind::Parse.wrap(anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))
+        })?;
+        prior_versions_data.retain(|(v, _)| v != version);
+        prior_versions_data.push((version.clone(), orb_def));
+    }
     if !prior_versions_data.is_empty() {
         tracing::info!(
             versions = prior_versions_data.len(),
@@ -554,68 +2331,510 @@ fn run_generate(
         None
     };
 
-    let generator = CodeGenerator::new()
-        .map_err(|e| anyhow::anyhow!("{}", e))?
-        .with_prior_versions(prior_versions_data)
-        .with_conformance_rules_json_opt(conformance_rules_json);
-    let server = generator
-        .generate(&orb, &orb_name, &resolved_version)
-        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let mut embedded_versions: Vec<String> =
+        prior_versions_data.iter().map(|(v, _)| v.clone()).collect();
+    embedded_versions.sort();
+
+    let imported = if extras.resolve_orbs {
+        if multi {
+            anyhow::bail!("--resolve-orbs cannot be combined with several orbs");
+        }
+        resolve_imported_orbs(orb, &extras)?
+    } else {
+        vec![]
+    };
+
+    let mut opts = GenerateOptions {
+        categories: extras.only.to_vec(),
+        strict_names: extras.strict_names || extras.fail_on_warnings,
+        property_tests: extras.property_tests,
+        sandboxed: extras.sandboxed,
+        license: extras.license.clone(),
+        binstall: extras.binstall.clone(),
+        max_resource_bytes: extras.max_resource_bytes.map(|n| n.get()),
+        http_bind: extras.http_bind,
+        http_compression: extras.http_compression,
+        http_body_limit: extras.http_body_limit.map(|n| n.get()),
+        server_title: extras.server_title.clone(),
+        server_instructions: extras.server_instructions.clone(),
+        prior_versions: prior_versions_data,
+        conformance_rules_json,
+        imported_orbs: imported
+            .iter()
+            .map(|(alias, _, definition)| (alias.clone(), definition.clone()))
+            .collect(),
+        ..GenerateOptions::new(orb_name, resolved_version.clone())
+    }
+    .with_language(extras.language)
+    .with_transport(extras.transport)
+    .with_filters(extras.include.to_vec(), extras.exclude.to_vec())
+    .with_naming(extras.naming.clone());
+    if let Some(dir) = extras.templates_dir {
+        opts = opts
+            .with_templates_dir(dir)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+    }
+    if extras.transport != generator::Transport::Http {
+        for (option, set) in [
+            ("--http-bind", extras.http_bind.is_some()),
+            ("--http-compression", extras.http_compression),
+            ("--http-body-limit", extras.http_body_limit.is_some()),
+        ] {
+            if set {
+                tracing::warn!("{option} only applies to --transport http");
+            }
+        }
+    }
+    if let Some(path) = extras.license_file {
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            anyhow::anyhow!("Failed to read license file {}: {}", path.display(), e)
+        })?;
+        opts.license_text = Some(text);
+    } else if let Some(spdx) = extras.license {
+        tracing::warn!(
+            license = %spdx,
+            "No --license-file given; the generated crate has no LICENSE file"
+        );
+    }
+    if let Some(package) = extras.package {
+        if package.description.is_none() && orbs.iter().all(|o| o.description.is_none()) {
+            anyhow::bail!(
+                "--format crate requires a description: pass --crate-description, set \
+                 `description` in the config file's `[package]` table, or describe the orb"
+            );
+        }
+        opts.package = Some(package.clone());
+    }
+    if let Some(source) = extras.logo {
+        let logo = load_logo(source)?;
+        tracing::info!(source = %source, mime_type = logo.mime_type(), "Embedding logo");
+        opts.logo = Some(logo);
+    }
+    let source_rev = extras
+        .source_rev
+        .clone()
+        .or_else(|| detect_source_rev(orb_path));
+    if let Some(rev) = &source_rev {
+        tracing::info!(source_rev = %rev, "Embedding source revision");
+        opts.source_rev = Some(rev.clone());
+    }
+    if let Some(path) = extras.previous_orb {
+        let previous = OrbParser::parse(path).map_err(|e| {
+            FailureKind::Parse.wrap(anyhow::anyhow!("Failed to parse {}: {}", path.display(), e))
+        })?;
+        let label = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .filter(|s| semver::Version::parse(s).is_ok())
+            .unwrap_or("previous");
+        tracing::info!(path = %path.display(), label = %label, "Loaded previous orb");
+        opts.previous_orb = Some((label.to_string(), previous));
+    }
+    if extras.dev {
+        // The server reads the orb wherever it is started from
+        let orb_path = std::fs::canonicalize(orb_path)
+            .map_err(|e| anyhow::anyhow!("Failed to resolve {}: {}", orb_path.display(), e))?;
+        tracing::info!(orb_path = %orb_path.display(), "Generating a dev-mode server");
+        opts.dev_orb_path = Some(orb_path);
+    }
+    let mut server = if multi {
+        let named: Vec<(String, parser::OrbDefinition)> = orb_paths
+            .iter()
+            .zip(&orbs)
+            .map(|(path, orb)| {
+                let name = orb_name_from_display(orb).unwrap_or_else(|| derive_orb_name(path));
+                (name, orb.clone())
+            })
+            .collect();
+        CodeGenerator::from_options(&opts)
+            .and_then(|generator| generator.generate_multi(&named, &opts.name, &opts.version))
+    } else {
+        opts.sources = match extras.stdin {
+            Some(source) => lint::source::inline_definition_sources(source),
+            None => lint::source::definition_sources(orb_path),
+        }
+        .into_iter()
+        .collect();
+        generator::generate_in_memory(orb, &opts)
+    }
+    .map_err(|e| FailureKind::Generation.wrap(anyhow::anyhow!("{}", e)))?;
+    if extras.fmt {
+        server
+            .format_sources()
+            .map_err(|e| FailureKind::Generation.wrap(anyhow::anyhow!("{}", e)))?;
+    }
+
+    let mut files: Vec<std::path::PathBuf> = server
+        .files
+        .keys()
+        .chain(server.binary_files.keys())
+        .cloned()
+        .chain([std::path::PathBuf::from(metadata::METADATA_FILE)])
+        .collect();
+    files.sort();
+    let mut summary = GenerateSummary {
+        orb_name: server.orb_name.clone(),
+        crate_name: server.crate_name.clone(),
+        version: resolved_version.clone(),
+        format: format.as_str(),
+        language: extras.language,
+        output: output.clone(),
+        files,
+        binary: None,
+        crate_file: None,
+        counts: DefinitionCounts::of(&orbs, imported.len()),
+        client_config: extras
+            .print_client_config
+            .then(|| client_config(&server, output, &extras)),
+    };
+
+    if extras.dry_run {
+        let diff = server
+            .diff_against(output)
+            .map_err(|e| anyhow::anyhow!("{}", e))?;
+        if diff.is_empty() {
+            eprintln!("(Dry run — {} is up to date)", output.display());
+        } else {
+            print!("{diff}");
+            eprintln!("(Dry run — no files written to {})", output.display());
+        }
+        return Ok(summary);
+    }
+
+    if extras.clippy_fix {
+        extras.report.say("Running cargo clippy --fix...");
+        server
+            .clippy_fix(output, extras.cargo.command(output))
+            .map_err(|e| FailureKind::Generation.wrap(anyhow::anyhow!("{}", e)))?;
+        if extras.fmt {
+            server
+                .format_sources()
+                .map_err(|e| FailureKind::Generation.wrap(anyhow::anyhow!("{}", e)))?;
+        }
+    }
+    server
+        .write_to(output)
+        .map_err(|e| FailureKind::Generation.wrap(anyhow::anyhow!("{}", e)))?;
+    let metadata = metadata::GenerationMetadata {
+        generator: metadata::GeneratorInfo::default(),
+        orb: metadata::OrbSource {
+            path: match (extras.fetched, extras.stdin) {
+                (Some(fetched), _) => fetched.resolved_ref().into(),
+                (None, Some(_)) => STDIN_SOURCE.into(),
+                (None, None) => orb_path.clone(),
+            },
+            source_rev,
+            content_hash: match extras.stdin {
+                Some(source) => metadata::packed_content_hash(
+                    std::path::Path::new(STDIN_SOURCE),
+                    source.as_bytes(),
+                ),
+                None => orb_hash(orb_path)?,
+            },
+            merged: orb_paths[1..]
+                .iter()
+                .map(|path| {
+                    Ok(metadata::OrbSource {
+                        path: path.clone(),
+                        source_rev: detect_source_rev(path),
+                        content_hash: orb_hash(path)?,
+                        merged: vec![],
+                    })
+                })
+                .collect::<Result<_>>()?,
+        },
+        output: metadata::OutputInfo {
+            orb_name: server.orb_name.clone(),
+            crate_name: server.crate_name.clone(),
+            version: resolved_version.clone(),
+            format: format.as_str().to_string(),
+            content_hash: server.content_hash(),
+        },
+        options: metadata::ResolvedOptions {
+            language: extras.language,
+            templates_dir: extras.templates_dir.clone(),
+            only: extras.only.to_vec(),
+            include: extras.include.to_vec(),
+            exclude: extras.exclude.to_vec(),
+            prior_versions: embedded_versions,
+            migrations: extras.migrations.clone(),
+            previous_orb: extras.previous_orb.clone(),
+            server_title: extras.server_title.clone(),
+            server_instructions: extras.server_instructions.clone(),
+            strict_names: extras.strict_names || extras.fail_on_warnings,
+            property_tests: extras.property_tests,
+            license: extras.license.clone(),
+            license_file: extras.license_file.clone(),
+            logo: extras.logo.clone(),
+            binstall_pkg_url: extras.binstall.as_ref().map(|b| b.pkg_url.clone()),
+            max_resource_bytes: extras.max_resource_bytes.map(|n| n.get()),
+            sandboxed: extras.sandboxed,
+            transport: extras.transport,
+            http_bind: (extras.transport == generator::Transport::Http).then(|| {
+                extras
+                    .http_bind
+                    .unwrap_or(generator::transport::DEFAULT_HTTP_BIND)
+            }),
+            http_compression: extras.transport == generator::Transport::Http
+                && extras.http_compression,
+            http_body_limit: extras
+                .http_body_limit
+                .filter(|_| extras.transport == generator::Transport::Http)
+                .map(|n| n.get()),
+            imported_orbs: imported
+                .iter()
+                .map(|(_, reference, _)| reference.clone())
+                .collect(),
+        },
+        generated_at: metadata::generated_at(),
+    };
+    metadata.write_to(output).map_err(|e| {
+        FailureKind::Generation.wrap(anyhow::anyhow!(
+            "Failed to write {}: {}",
+            output.join(metadata::METADATA_FILE).display(),
+            e
+        ))
+    })?;
 
     match format {
         OutputFormat::Source => {
-            server
-                .write_to(output)
-                .map_err(|e| anyhow::anyhow!("{}", e))?;
-            println!("Generated MCP server source code:");
-            println!("  Output: {}", output.display());
-            println!("  Crate: {}", server.crate_name);
-            println!("  Version: {}", resolved_version);
-            println!("  Commands: {}", orb.commands.len());
-            println!("  Jobs: {}", orb.jobs.len());
-            println!("  Executors: {}", orb.executors.len());
-            println!();
-            println!("To build: cd {} && cargo build --release", output.display());
+            if extras.binary_cache.is_some() {
+                tracing::warn!("--binary-cache only applies to --format binary");
+            }
+            if extras.report == Report::Text {
+                print_source_summary(&summary, multi, &imported, extras.language);
+            }
         }
-        OutputFormat::Binary => {
-            server
-                .write_to(output)
-                .map_err(|e| anyhow::anyhow!("{}", e))?;
-            println!("Compiling MCP server...");
-            let status = std::process::Command::new("cargo")
-                .args(["build", "--release"])
-                .current_dir(output)
+        OutputFormat::Crate => {
+            if extras.binary_cache.is_some() {
+                tracing::warn!("--binary-cache only applies to --format binary");
+            }
+            extras.cargo.check()?;
+            extras.report.say("Packaging MCP server crate...");
+            let status = extras
+                .cargo
+                .command(output)
+                .args(cargo_package_args(extras.registry.as_deref(), extras.jobs))
                 .status();
             match status {
                 Ok(s) if s.success() => {
-                    let binary_path = output.join("target/release").join(&server.crate_name);
-                    println!("Successfully compiled MCP server:");
-                    println!("  Binary: {}", binary_path.display());
-                    println!("  Version: {}", resolved_version);
+                    let package = extras
+                        .cargo
+                        .target_dir(output)
+                        .join("package")
+                        .join(format!("{}-{}.crate", server.crate_name, resolved_version));
+                    if extras.report == Report::Text {
+                        println!("Successfully packaged MCP server crate:");
+                        println!("  Crate: {}", package.display());
+                        println!("  Version: {}", resolved_version);
+                        println!();
+                        match extras.registry {
+                            Some(registry) => println!(
+                                "To publish: cd {} && cargo publish --registry {}",
+                                output.display(),
+                                registry
+                            ),
+                            None => {
+                                println!("To publish: cd {} && cargo publish", output.display())
+                            }
+                        }
+                    }
+                    summary.crate_file = Some(package);
                 }
                 Ok(_) => {
-                    anyhow::bail!(
-                        "Compilation failed. Source code is available at: {}",
+                    return Err(FailureKind::Compile.wrap(anyhow::anyhow!(
+                        "cargo package failed. Source code is available at: {}",
                         output.display()
-                    );
+                    )));
                 }
                 Err(e) => {
-                    anyhow::bail!(
+                    return Err(FailureKind::Compile.wrap(anyhow::anyhow!(
                         "Failed to run cargo: {}. Source code is available at: {}",
                         e,
                         output.display()
-                    );
+                    )));
                 }
             }
         }
-    }
-
-    Ok(())
-}
-
-fn run_validate(orb_path: &std::path::PathBuf) -> Result<()> {
-    tracing::info!(?orb_path, "Validating orb definition");
-    let orb = OrbParser::parse(orb_path).map_err(|e| anyhow::anyhow!("{}", e))?;
+        OutputFormat::Binary => {
+            let binary_path =
+                release_binary_path(&extras.cargo.target_dir(output), None, &server.crate_name);
+            let cache = extras.binary_cache.as_ref().and_then(|dir| {
+                let Some(toolchain) = binary_cache::toolchain_fingerprint(output) else {
+                    tracing::warn!("rustc -vV failed; not using the binary cache");
+                    return None;
+                };
+                let key = binary_cache::BinaryCache::key(&server.content_hash(), &toolchain);
+                Some((binary_cache::BinaryCache::new(dir), key))
+            });
+            let file_name = binary_path
+                .file_name()
+                .map(|f| f.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            if let Some((cache, key)) = &cache {
+                if let Some(cached) = cache.lookup(key, &file_name) {
+                    binary_cache::BinaryCache::restore(&cached, &binary_path).map_err(|e| {
+                        anyhow::anyhow!("Failed to restore {}: {}", cached.display(), e)
+                    })?;
+                    if extras.report == Report::Text {
+                        println!("Reused cached MCP server binary (unchanged source):");
+                        println!("  Binary: {}", binary_path.display());
+                        println!("  Cached: {}", cached.display());
+                        println!("  Version: {}", resolved_version);
+                    }
+                    summary.binary = Some(binary_path);
+                    return Ok(summary);
+                }
+            }
+            extras.cargo.check()?;
+            extras.report.say("Compiling MCP server...");
+            let status = extras
+                .cargo
+                .command(output)
+                .args(cargo_build_args(None, extras.jobs))
+                .status();
+            match status {
+                Ok(s) if s.success() => {
+                    if let Some((cache, key)) = &cache {
+                        match cache.store(key, &binary_path) {
+                            Ok(cached) => {
+                                tracing::info!(cached = %cached.display(), "Cached binary")
+                            }
+                            Err(e) => tracing::warn!(%e, "Failed to cache binary"),
+                        }
+                    }
+                    if extras.report == Report::Text {
+                        println!("Successfully compiled MCP server:");
+                        println!("  Binary: {}", binary_path.display());
+                        println!("  Version: {}", resolved_version);
+                    }
+                    summary.binary = Some(binary_path);
+                }
+                Ok(_) => {
+                    return Err(FailureKind::Compile.wrap(anyhow::anyhow!(
+                        "Compilation failed. Source code is available at: {}",
+                        output.display()
+                    )));
+                }
+                Err(e) => {
+                    return Err(FailureKind::Compile.wrap(anyhow::anyhow!(
+                        "Failed to run cargo: {}. Source code is available at: {}",
+                        e,
+                        output.display()
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// The text report of `generate --format source`.
+fn print_source_summary(
+    summary: &GenerateSummary,
+    multi: bool,
+    imported: &[(String, String, parser::OrbDefinition)],
+    language: generator::Language,
+) {
+    let counts = &summary.counts;
+    println!("Generated MCP server source code:");
+    println!("  Output: {}", summary.output.display());
+    match language {
+        generator::Language::Rust => println!("  Crate: {}", summary.crate_name),
+        _ => println!("  Package: {}", summary.crate_name),
+    }
+    println!("  Version: {}", summary.version);
+    if multi {
+        println!("  Orbs: {}", counts.orbs);
+    }
+    println!("  Commands: {}", counts.commands);
+    println!("  Jobs: {}", counts.jobs);
+    println!("  Executors: {}", counts.executors);
+    if counts.examples > 0 {
+        println!("  Examples: {}", counts.examples);
+    }
+    if counts.workflows > 0 {
+        println!("  Workflows: {}", counts.workflows);
+    }
+    if counts.pipeline_parameters > 0 {
+        println!("  Pipeline parameters: {}", counts.pipeline_parameters);
+    }
+    if !imported.is_empty() {
+        println!("  Imported orbs: {}", imported.len());
+        for (alias, reference, _) in imported {
+            println!("    - {} ({})", alias, reference);
+        }
+    }
+    println!();
+    println!(
+        "To build: cd {} && {}",
+        summary.output.display(),
+        language.build_command()
+    );
+}
+
+fn run_validate(
+    orb_path: &std::path::PathBuf,
+    fail_on_warnings: bool,
+    strict: bool,
+    cache: Option<&parse_cache::ParseCache>,
+    lint_config: Option<&std::path::Path>,
+    format: ValidateFormat,
+) -> Result<()> {
+    tracing::info!(?orb_path, strict, "Validating orb definition");
+    if format == ValidateFormat::Sarif {
+        return run_validate_sarif(orb_path, fail_on_warnings, strict, cache, lint_config);
+    }
+    let orb = parse_for_validation(orb_path, strict, cache).map_err(|errors| {
+        FailureKind::Parse.wrap(anyhow::anyhow!(
+            "Orb validation failed: {} parse error(s):\n{}",
+            errors.len(),
+            errors
+                .iter()
+                .map(|e| match e {
+                    parser::ParseError::UnknownKey { location, key, .. } => {
+                        let (file, line) =
+                            lint::source::locate_nearest(orb_path, &format!("{location}/{key}"));
+                        format!("    ✗ {e} ({}:{line})", file.display())
+                    }
+                    _ => format!("    ✗ {e}"),
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        ))
+    })?;
+    let unresolved = parser::references::unresolved(&orb);
+    if !unresolved.is_empty() {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "Orb validation failed: {} unresolved reference(s):\n{}",
+            unresolved.len(),
+            unresolved
+                .iter()
+                .map(|r| {
+                    let (file, line) = lint::source::locate_nearest(orb_path, &r.location);
+                    format!("    ✗ {r} ({}:{line})", file.display())
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        )));
+    }
+    let warnings = lint_orb(orb_path, &orb, lint_config)?;
+    let errors = count_lint_errors(&warnings);
+    if errors > 0 {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "Orb validation failed: {} lint error(s):\n{}",
+            errors,
+            format_warnings(&warnings)
+        )));
+    }
+    if fail_on_warnings && !warnings.is_empty() {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "Orb validation failed: {} lint warning(s) (--fail-on-warnings):\n{}",
+            warnings.len(),
+            format_warnings(&warnings)
+        )));
+    }
 
     println!("Orb validation successful!");
     println!("  Version: {}", orb.version);
@@ -634,25 +2853,395 @@ fn run_validate(orb_path: &std::path::PathBuf) -> Result<()> {
     for name in orb.executors.keys() {
         println!("    - {}", name);
     }
+    if !orb.examples.is_empty() {
+        println!("  Examples: {}", orb.examples.len());
+        for name in orb.examples.keys() {
+            println!("    - {}", name);
+        }
+    }
+    if !orb.workflows.is_empty() {
+        println!("  Workflows: {}", orb.workflows.len());
+        for name in orb.workflows.keys() {
+            println!("    - {}", name);
+        }
+    }
+    if !warnings.is_empty() {
+        println!("  Warnings: {}", warnings.len());
+        println!("{}", format_warnings(&warnings));
+    }
+    Ok(())
+}
+
+/// `validate --format sarif`: print a SARIF log covering parse errors and
+/// lint findings, then fail under the same rules as the text report.
+fn run_validate_sarif(
+    orb_path: &std::path::Path,
+    fail_on_warnings: bool,
+    strict: bool,
+    cache: Option<&parse_cache::ParseCache>,
+    lint_config: Option<&std::path::Path>,
+) -> Result<()> {
+    let print = |report: &serde_json::Value| -> Result<()> {
+        println!("{}", serde_json::to_string_pretty(report)?);
+        Ok(())
+    };
+    let orb = match parse_for_validation(orb_path, strict, cache) {
+        Ok(orb) => orb,
+        Err(errors) => {
+            print(&lint::sarif::parse_failures(orb_path, &errors))?;
+            return Err(FailureKind::Parse.wrap(anyhow::anyhow!(
+                "Orb validation failed: {} parse error(s)",
+                errors.len()
+            )));
+        }
+    };
+    let unresolved = parser::references::unresolved(&orb);
+    let warnings = lint_orb(orb_path, &orb, lint_config)?;
+    print(&lint::sarif::report(orb_path, &unresolved, &warnings))?;
+
+    if !unresolved.is_empty() {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "Orb validation failed: {} unresolved reference(s)",
+            unresolved.len()
+        )));
+    }
+    let errors = count_lint_errors(&warnings);
+    if errors > 0 {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "Orb validation failed: {} lint error(s)",
+            errors
+        )));
+    }
+    if fail_on_warnings && !warnings.is_empty() {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "Orb validation failed: {} lint warning(s) (--fail-on-warnings)",
+            warnings.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Parse the orb at `orb_path` for `validate`, collecting every parse error
+/// and, when `strict`, every key outside the orb schema.
+fn parse_for_validation(
+    orb_path: &std::path::Path,
+    strict: bool,
+    cache: Option<&parse_cache::ParseCache>,
+) -> std::result::Result<parser::OrbDefinition, Vec<parser::ParseError>> {
+    parse_cached(cache, orb_path, parser::SourceKind::Orb, strict, || {
+        if strict {
+            OrbParser::parse_all_strict(orb_path)
+        } else {
+            OrbParser::parse_all(orb_path)
+        }
+    })
+}
+
+/// The parse cache selected by `--parse-cache [DIR]`, if any.
+fn open_parse_cache(dir: &Option<Option<std::path::PathBuf>>) -> Option<parse_cache::ParseCache> {
+    dir.as_ref().map(|dir| {
+        parse_cache::ParseCache::new(
+            dir.clone()
+                .unwrap_or_else(parse_cache::ParseCache::default_dir),
+        )
+    })
+}
+
+/// `parse` the source at `path` as `kind`, through `cache` when given.
+fn parse_cached<E>(
+    cache: Option<&parse_cache::ParseCache>,
+    path: &std::path::Path,
+    kind: parser::SourceKind,
+    strict: bool,
+    parse: impl FnOnce() -> std::result::Result<parser::OrbDefinition, E>,
+) -> std::result::Result<parser::OrbDefinition, E> {
+    match cache {
+        Some(cache) => cache.parse(path, kind, strict, parse),
+        None => parse(),
+    }
+}
+
+/// `lint`: report every lint finding with its file and line, then fail on
+/// errors (or on any finding with `fail_on_warnings`).
+fn run_lint(
+    orb_path: &std::path::Path,
+    fail_on_warnings: bool,
+    lint_config: Option<&std::path::Path>,
+    format: ValidateFormat,
+) -> Result<()> {
+    tracing::info!(?orb_path, "Linting orb definition");
+    let orb = match OrbParser::parse_all(orb_path) {
+        Ok(orb) => orb,
+        Err(errors) if format == ValidateFormat::Sarif => {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&lint::sarif::parse_failures(orb_path, &errors))?
+            );
+            return Err(FailureKind::Parse.wrap(anyhow::anyhow!(
+                "Lint failed: {} parse error(s)",
+                errors.len()
+            )));
+        }
+        Err(mut errors) => {
+            return Err(FailureKind::Parse.wrap(anyhow::anyhow!("{}", errors.remove(0))))
+        }
+    };
+    let warnings = lint_orb(orb_path, &orb, lint_config)?;
+    match format {
+        ValidateFormat::Sarif => println!(
+            "{}",
+            serde_json::to_string_pretty(&lint::sarif::report(orb_path, &[], &warnings))?
+        ),
+        ValidateFormat::Text => {
+            for w in &warnings {
+                let (file, line) = lint::source::locate_nearest(orb_path, &w.location);
+                let level = match w.severity {
+                    lint::Severity::Error => "error",
+                    _ => "warning",
+                };
+                println!("{}:{line}: {level}: {w}", file.display());
+            }
+        }
+    }
+
+    let errors = count_lint_errors(&warnings);
+    if format == ValidateFormat::Text {
+        if warnings.is_empty() {
+            println!("No lint findings");
+        } else {
+            println!(
+                "{} error(s), {} warning(s)",
+                errors,
+                warnings.len() - errors
+            );
+        }
+    }
+    if errors > 0 {
+        return Err(
+            FailureKind::Validation.wrap(anyhow::anyhow!("Lint failed: {} error(s)", errors))
+        );
+    }
+    if fail_on_warnings && !warnings.is_empty() {
+        return Err(FailureKind::Validation.wrap(anyhow::anyhow!(
+            "Lint failed: {} warning(s) (--fail-on-warnings)",
+            warnings.len()
+        )));
+    }
+    Ok(())
+}
+
+/// Serialize `orb` for `inspect`.
+fn render_inspect(orb: &parser::OrbDefinition, format: InspectFormat) -> Result<String> {
+    Ok(match format {
+        InspectFormat::Json => serde_json::to_string_pretty(orb)? + "\n",
+        InspectFormat::Yaml => serde_yaml::to_string(orb)?,
+    })
+}
+
+/// Render the dependency graph of `orb` for `graph`.
+fn render_graph(orb: &parser::OrbDefinition, format: GraphFormat) -> Result<String> {
+    let graph = graph::build(orb);
+    Ok(match format {
+        GraphFormat::Dot => graph.to_dot(),
+        GraphFormat::Mermaid => graph.to_mermaid(),
+        GraphFormat::Json => serde_json::to_string_pretty(&graph)? + "\n",
+    })
+}
+
+fn orb_hash(orb_path: &std::path::Path) -> Result<String> {
+    metadata::orb_content_hash(orb_path)
+        .map_err(|e| anyhow::anyhow!("Failed to hash {}: {}", orb_path.display(), e))
+}
+
+fn run_pack(orb_dir: &std::path::Path, output: Option<&std::path::Path>) -> Result<()> {
+    let packed = OrbParser::pack(orb_dir).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let yaml = serde_yaml::to_string(&packed)?;
+    match output {
+        Some(path) => {
+            std::fs::write(path, yaml)
+                .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+            println!("Packed {} into {}", orb_dir.display(), path.display());
+        }
+        None => print!("{yaml}"),
+    }
+    Ok(())
+}
+
+fn run_init(
+    name: &str,
+    namespace: &str,
+    output: Option<&std::path::Path>,
+    force: bool,
+) -> Result<()> {
+    for (flag, value) in [("name", name), ("--namespace", namespace)] {
+        if !scaffold::is_valid_name(value) {
+            anyhow::bail!(
+                "Invalid {} '{}': use letters, digits, '-' and '_'",
+                flag,
+                value
+            );
+        }
+    }
+    let root = output.map_or_else(|| std::path::PathBuf::from(name), ToOwned::to_owned);
+    let files = scaffold::files(name, namespace);
+    if !force {
+        let existing: Vec<_> = files
+            .iter()
+            .map(|(rel_path, _)| root.join(rel_path))
+            .filter(|path| path.exists())
+            .map(|path| path.display().to_string())
+            .collect();
+        if !existing.is_empty() {
+            anyhow::bail!(
+                "{} already exist(s); pass --force to overwrite:\n  {}",
+                existing.len(),
+                existing.join("\n  ")
+            );
+        }
+    }
+    for (rel_path, content) in &files {
+        let path = root.join(rel_path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(&path, content)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    }
+    println!("Created orb {} in {}", name, root.display());
+    println!(
+        "Next: cd {} && gen-orb-mcp validate && gen-orb-mcp generate --dry-run",
+        root.display()
+    );
+    Ok(())
+}
+
+fn run_docs(
+    orb_path: &std::path::Path,
+    output: &std::path::Path,
+    name: Option<&str>,
+) -> Result<()> {
+    let orb = OrbParser::parse(orb_path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let orb_name = name
+        .map(str::to_string)
+        .or_else(|| orb_name_from_display(&orb))
+        .unwrap_or_else(|| derive_orb_name(orb_path));
+    let pages = docs::render(&orb, &orb_name).map_err(|e| anyhow::anyhow!("{}", e))?;
+    for (rel_path, content) in &pages {
+        let path = output.join(rel_path);
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| anyhow::anyhow!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(&path, content)
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", path.display(), e))?;
+    }
+    println!("Wrote {} page(s) to {}", pages.len(), output.display());
+    Ok(())
+}
+
+fn run_stats(orb_path: &std::path::Path, json: bool) -> Result<()> {
+    let orb = OrbParser::parse(orb_path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let stats = stats::compute(&orb);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&stats)?);
+    } else {
+        println!("{stats}");
+    }
     Ok(())
 }
 
+/// Lint `orb`, applying the ruleset at `lint_config` (else
+/// `.gen-orb-mcp-lint.toml` in the working directory, else the `[lint]` table
+/// of `gen-orb-mcp.toml`, when present) and the inline allow comments in the
+/// orb files.
+fn lint_orb(
+    orb_path: &std::path::Path,
+    orb: &parser::OrbDefinition,
+    lint_config: Option<&std::path::Path>,
+) -> Result<Vec<lint::LintWarning>> {
+    let inline = lint::config::inline_suppressions(orb_path)?;
+    lint_orb_with(inline, orb, lint_config)
+}
+
+/// Lint `orb` as [`lint_orb`] does, with `inline` allow comments collected
+/// by the caller.
+fn lint_orb_with(
+    inline: Vec<lint::config::Suppression>,
+    orb: &parser::OrbDefinition,
+    lint_config: Option<&std::path::Path>,
+) -> Result<Vec<lint::LintWarning>> {
+    let default_path = std::path::Path::new(lint::config::DEFAULT_LINT_CONFIG);
+    let mut config = match lint_config {
+        Some(path) => lint::LintConfig::load(path)?,
+        None if default_path.is_file() => lint::LintConfig::load(default_path)?,
+        None => {
+            let project_path = std::path::Path::new(DEFAULT_CONFIG_FILE);
+            if project_path.is_file() {
+                lint::LintConfig::load_project(project_path)?.unwrap_or_default()
+            } else {
+                lint::LintConfig::default()
+            }
+        }
+    };
+    config.suppress.extend(inline);
+    Ok(lint::lint_with_config(orb, &config))
+}
+
+fn count_lint_errors(warnings: &[lint::LintWarning]) -> usize {
+    warnings
+        .iter()
+        .filter(|w| w.severity == lint::Severity::Error)
+        .count()
+}
+
+/// One indented line per lint finding, marked `!` for warnings and `✗` for
+/// errors.
+fn format_warnings(warnings: &[lint::LintWarning]) -> String {
+    warnings
+        .iter()
+        .map(|w| match w.severity {
+            lint::Severity::Error => format!("    ✗ {w}"),
+            _ => format!("    ! {w}"),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Presentation options for the `diff` summary.
+struct DiffView {
+    color: bool,
+    side_by_side: bool,
+}
+
 fn run_diff(
     current: &std::path::PathBuf,
     previous: &std::path::PathBuf,
     since_version: &str,
     output: &Option<std::path::PathBuf>,
+    view: DiffView,
+    cache: Option<&parse_cache::ParseCache>,
 ) -> Result<()> {
     tracing::info!(?current, ?previous, "Diffing orb versions");
 
-    let new_orb = OrbParser::parse(current).map_err(|e| anyhow::anyhow!("{}", e))?;
-    let old_orb = OrbParser::parse(previous).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let parse = |path: &std::path::Path| {
+        parse_cached(cache, path, parser::SourceKind::Orb, false, || {
+            OrbParser::parse(path)
+        })
+        .map_err(|e| anyhow::anyhow!("{}", e))
+    };
+    let new_orb = parse(current)?;
+    let old_orb = parse(previous)?;
 
     let rules = differ::diff(&old_orb, &new_orb, since_version);
-    println!("Computed {} conformance rule(s):", rules.len());
-    for rule in &rules {
-        println!("  • {}", rule.description());
+    if view.side_by_side {
+        print!(
+            "{}",
+            differ::render::render_side_by_side(&old_orb, &new_orb, &rules, view.color)
+        );
     }
+    println!("Computed {} conformance rule(s):", rules.len());
+    print!("{}", differ::render::render_rules(&rules, view.color));
 
     let json = serde_json::to_string_pretty(&rules)?;
 
@@ -957,6 +3546,170 @@ fn resolve_sign_env_names(
     })
 }
 
+/// Generate options preset by a `[profiles.<name>]` table in the config file.
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct GenerateProfile {
+    language: Option<generator::Language>,
+    templates_dir: Option<std::path::PathBuf>,
+    only: Vec<generator::DefinitionCategory>,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    name_prefix: Option<String>,
+    crate_suffix: Option<String>,
+    uri_scheme: Option<String>,
+    server_title: Option<String>,
+    server_instructions: Option<String>,
+    strict_names: bool,
+    fail_on_warnings: bool,
+    property_tests: bool,
+    binstall_pkg_url: Option<String>,
+    binstall_repo: Option<String>,
+    binstall_pkg_fmt: Option<generator::BinstallPkgFmt>,
+    binstall_bin_dir: Option<String>,
+    max_resource_bytes: Option<std::num::NonZeroUsize>,
+    sandboxed: bool,
+    transport: Option<generator::Transport>,
+    http_bind: Option<std::net::SocketAddr>,
+    http_compression: bool,
+    http_body_limit: Option<std::num::NonZeroUsize>,
+    resolve_orbs: bool,
+}
+
+/// Most keywords crates.io accepts on a crate.
+const MAX_KEYWORDS: usize = 5;
+
+/// Package metadata for `generate --format crate` from the `[package]` table
+/// in the config file. CLI flags take precedence.
+#[derive(Debug, Default, PartialEq, serde::Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct PackageConfig {
+    description: Option<String>,
+    #[serde(deserialize_with = "deserialize_spdx")]
+    license: Option<String>,
+    repository: Option<String>,
+    keywords: Vec<String>,
+    registry: Option<String>,
+}
+
+fn deserialize_spdx<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<String>, D::Error> {
+    let value: Option<String> = serde::Deserialize::deserialize(deserializer)?;
+    value
+        .map(|v| parse_spdx(&v))
+        .transpose()
+        .map_err(serde::de::Error::custom)
+}
+
+/// Load the `[package]` table from `config_path`; a missing file or table
+/// yields the defaults.
+fn load_package_config(config_path: &std::path::Path) -> Result<PackageConfig> {
+    let cfg = config::Config::builder()
+        .add_source(config::File::from(config_path).required(false))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", config_path.display(), e))?;
+    match cfg.get("package") {
+        Ok(package) => Ok(package),
+        Err(config::ConfigError::NotFound(_)) => Ok(PackageConfig::default()),
+        Err(e) => Err(anyhow::anyhow!(
+            "Invalid `[package]` table in {}: {e}",
+            config_path.display()
+        )),
+    }
+}
+
+/// Load the generate profile `name` from `config_path`.
+///
+/// Unlike the `[sign]` and `[publish]` lookups the file is required, since a
+/// named profile cannot fall back to a default.
+fn load_generate_profile(config_path: &std::path::Path, name: &str) -> Result<GenerateProfile> {
+    let cfg = config::Config::builder()
+        .add_source(config::File::from(config_path))
+        .build()
+        .map_err(|e| anyhow::anyhow!("Failed to read {}: {}", config_path.display(), e))?;
+    let profiles: std::collections::HashMap<String, config::Value> =
+        cfg.get("profiles").unwrap_or_default();
+    let Some(value) = profiles.get(name) else {
+        let mut known: Vec<_> = profiles.keys().map(String::as_str).collect();
+        known.sort_unstable();
+        anyhow::bail!(
+            "Profile `{name}` not found in {}{} (available: {})",
+            config_path.display(),
+            suggest::did_you_mean(name, known.iter().copied()),
+            if known.is_empty() {
+                "none".to_string()
+            } else {
+                known.join(", ")
+            }
+        );
+    };
+    value
+        .clone()
+        .try_deserialize()
+        .map_err(|e| anyhow::anyhow!("Invalid profile `{name}`: {e}"))
+}
+
+/// Where cargo-binstall downloads the generated server from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum BinstallSource {
+    /// An explicit `--binstall-pkg-url` template
+    PkgUrl(String),
+    /// The releases of a `--binstall-repo`, tagged `<tag_prefix><version>`
+    Repo { repo: String, tag_prefix: String },
+}
+
+impl BinstallSource {
+    /// The source named by at most one of `pkg_url` and `repo`.
+    fn new(
+        pkg_url: Option<String>,
+        repo: Option<String>,
+        tag_prefix: &str,
+    ) -> Result<Option<Self>> {
+        match (pkg_url, repo) {
+            (Some(_), Some(_)) => {
+                anyhow::bail!("--binstall-pkg-url and --binstall-repo cannot be combined")
+            }
+            (Some(pkg_url), None) => Ok(Some(BinstallSource::PkgUrl(pkg_url))),
+            (None, Some(repo)) => Ok(Some(BinstallSource::Repo {
+                repo,
+                tag_prefix: tag_prefix.to_string(),
+            })),
+            (None, None) => Ok(None),
+        }
+    }
+}
+
+/// Combine the binstall options; the format and bin dir are only meaningful
+/// alongside a download source.
+fn resolve_binstall(
+    source: Option<BinstallSource>,
+    pkg_fmt: Option<generator::BinstallPkgFmt>,
+    bin_dir: Option<String>,
+) -> Result<Option<generator::BinstallMetadata>> {
+    let binstall = match source {
+        Some(BinstallSource::PkgUrl(pkg_url)) => generator::BinstallMetadata {
+            pkg_url,
+            pkg_fmt: generator::BinstallPkgFmt::default(),
+            bin_dir: None,
+            overrides: Default::default(),
+        },
+        Some(BinstallSource::Repo { repo, tag_prefix }) => {
+            generator::BinstallMetadata::github_release(&repo, &tag_prefix)
+        }
+        None if pkg_fmt.is_some() || bin_dir.is_some() => anyhow::bail!(
+            "--binstall-pkg-fmt and --binstall-bin-dir require --binstall-pkg-url or \
+             --binstall-repo"
+        ),
+        None => return Ok(None),
+    };
+    Ok(Some(generator::BinstallMetadata {
+        pkg_fmt: pkg_fmt.unwrap_or(binstall.pkg_fmt),
+        bin_dir: bin_dir.or(binstall.bin_dir),
+        ..binstall
+    }))
+}
+
 /// Resolve the env-var NAME holding the release tag (used when `--tag` is not
 /// given). Precedence: `--tag-env` flag > `gen-orb-mcp.toml` `[publish].tag_env`
 /// > `CIRCLE_TAG`.
@@ -1205,7 +3958,10 @@ fn save_git_push(repo: &git2::Repository) -> Result<()> {
 /// Explicit `--binary` / `--asset-name` take precedence; otherwise both are
 /// derived from `--name` and the `input` directory:
 ///   binary = `<input>/target/release/<name_underscored>_mcp`
-///   asset  = `<name_underscored>_mcp-linux-x86_64`
+///   asset  = `<name_underscored>_mcp-<os>-<arch>` for the host platform
+///
+/// The binary is looked up in `CARGO_TARGET_DIR` when set, like
+/// [`cargo_target_dir`]. On Windows hosts both carry the `.exe` suffix.
 fn resolve_publish_target(
     name: Option<&str>,
     input: &std::path::Path,
@@ -1214,11 +3970,9 @@ fn resolve_publish_target(
 ) -> Result<(std::path::PathBuf, String)> {
     let derived = name.map(|n| {
         let underscored = n.replace('-', "_");
-        let bin = input
-            .join("target")
-            .join("release")
-            .join(format!("{underscored}_mcp"));
-        let asset = format!("{underscored}_mcp-linux-x86_64");
+        let binary_name = format!("{underscored}_mcp");
+        let bin = release_binary_path(&cargo_target_dir(input), None, &binary_name);
+        let asset = format!("{underscored}_mcp-{}", host_asset_suffix());
         (bin, asset)
     });
 
@@ -1302,6 +4056,8 @@ fn run_build(
     input: &std::path::Path,
     name: Option<&str>,
     target: Option<&str>,
+    jobs: Option<std::num::NonZeroUsize>,
+    cargo: &CargoEnv,
     dry_run: bool,
 ) -> Result<()> {
     let cargo_toml = input.join("Cargo.toml");
@@ -1317,37 +4073,35 @@ fn run_build(
         None => read_crate_name(input)?,
     };
 
-    let mut cargo_args = vec!["build", "--release"];
-    if let Some(t) = target {
-        cargo_args.extend(["--target", t]);
-    }
+    let cargo_args = cargo_build_args(target, jobs);
 
-    let binary_dir = match target {
-        Some(t) => input.join("target").join(t).join("release"),
-        None => input.join("target").join("release"),
-    };
-    let binary_path = binary_dir.join(&binary_name);
+    let binary_path = release_binary_path(&cargo.target_dir(input), target, &binary_name);
 
     if dry_run {
-        println!("Would run: cargo {}", cargo_args.join(" "));
+        println!(
+            "Would run: {}cargo {}",
+            cargo.env_prefix(),
+            cargo_args.join(" ")
+        );
         println!("  Input:  {}", input.display());
         println!("  Binary: {}", binary_path.display());
         return Ok(());
     }
 
     tracing::info!(input = %input.display(), binary = %binary_path.display(), "Compiling MCP server");
+    cargo.check()?;
     println!("Compiling MCP server...");
-    let status = std::process::Command::new("cargo")
+    let status = cargo
+        .command(input)
         .args(&cargo_args)
-        .current_dir(input)
         .status()
-        .map_err(|e| anyhow::anyhow!("Failed to run cargo: {}", e))?;
+        .map_err(|e| FailureKind::Compile.wrap(anyhow::anyhow!("Failed to run cargo: {}", e)))?;
 
     if !status.success() {
-        anyhow::bail!(
+        return Err(FailureKind::Compile.wrap(anyhow::anyhow!(
             "cargo build failed. Source code is available at: {}",
             input.display()
-        );
+        )));
     }
 
     println!("Successfully compiled MCP server:");
@@ -1356,6 +4110,218 @@ fn run_build(
     Ok(())
 }
 
+/// `verify`: generate the server for `orb` into a scratch directory, `cargo
+/// check` it and remove the directory, whether or not the check passed.
+fn run_verify(
+    orb: &parser::OrbDefinition,
+    opts: &GenerateOptions,
+    jobs: Option<std::num::NonZeroUsize>,
+    cargo: &CargoEnv,
+) -> Result<()> {
+    let server = generator::generate_in_memory(orb, opts)
+        .map_err(|e| FailureKind::Generation.wrap(anyhow::anyhow!("{}", e)))?;
+    cargo.check()?;
+    let dir = std::env::temp_dir().join(format!(
+        "gen-orb-mcp-verify-{}-{}",
+        std::process::id(),
+        server.crate_name
+    ));
+    tracing::info!(dir = %dir.display(), crate_name = %server.crate_name, "Checking generated server");
+    let result = server
+        .write_to(&dir)
+        .map_err(|e| FailureKind::Generation.wrap(anyhow::anyhow!("{}", e)))
+        .and_then(|()| {
+            cargo
+                .command(&dir)
+                .args(cargo_check_args(opts.property_tests, jobs))
+                .status()
+                .map_err(|e| {
+                    FailureKind::Compile.wrap(anyhow::anyhow!("Failed to run cargo: {}", e))
+                })
+        });
+    let _ = std::fs::remove_dir_all(&dir);
+    if !result?.success() {
+        return Err(FailureKind::Compile.wrap(anyhow::anyhow!(
+            "cargo check failed: the server generated from {} does not compile",
+            server.orb_name
+        )));
+    }
+
+    println!("Generated MCP server compiles:");
+    println!("  Crate: {}", server.crate_name);
+    println!(
+        "  Commands: {}, Jobs: {}, Executors: {}",
+        orb.commands.len(),
+        orb.jobs.len(),
+        orb.executors.len()
+    );
+    Ok(())
+}
+
+/// Arguments for `cargo check`, covering the tests too when `all_targets`,
+/// with an optional job limit.
+fn cargo_check_args(all_targets: bool, jobs: Option<std::num::NonZeroUsize>) -> Vec<String> {
+    let mut args = vec!["check".to_string()];
+    if all_targets {
+        args.push("--all-targets".to_string());
+    }
+    if let Some(n) = jobs {
+        args.extend(["-j".to_string(), n.to_string()]);
+    }
+    args
+}
+
+/// Arguments for `cargo build --release`, with an optional target triple and
+/// job limit.
+fn cargo_build_args(target: Option<&str>, jobs: Option<std::num::NonZeroUsize>) -> Vec<String> {
+    let mut args = vec!["build".to_string(), "--release".to_string()];
+    if let Some(t) = target {
+        args.extend(["--target".to_string(), t.to_string()]);
+    }
+    if let Some(n) = jobs {
+        args.extend(["-j".to_string(), n.to_string()]);
+    }
+    args
+}
+
+/// Arguments for `cargo package`, verifying against an optional registry with
+/// an optional job limit.
+///
+/// `--allow-dirty` lets the generated crate be packaged inside an orb
+/// repository with uncommitted output.
+fn cargo_package_args(registry: Option<&str>, jobs: Option<std::num::NonZeroUsize>) -> Vec<String> {
+    let mut args = vec!["package".to_string(), "--allow-dirty".to_string()];
+    if let Some(r) = registry {
+        args.extend(["--registry".to_string(), r.to_string()]);
+    }
+    if let Some(n) = jobs {
+        args.extend(["-j".to_string(), n.to_string()]);
+    }
+    args
+}
+
+/// Cargo settings for binary builds (`generate --format binary` and `build`).
+#[derive(Debug, Clone, Default)]
+struct CargoEnv {
+    /// `RUSTC_WRAPPER` for cargo, e.g. `sccache`.
+    rustc_wrapper: Option<std::path::PathBuf>,
+    /// Absolute `CARGO_TARGET_DIR` shared by every build.
+    target_dir: Option<std::path::PathBuf>,
+}
+
+impl CargoEnv {
+    /// Settings from the global options; a relative target directory is
+    /// resolved against the working directory, not the generated crate.
+    fn new(
+        rustc_wrapper: Option<std::path::PathBuf>,
+        target_dir: Option<&std::path::Path>,
+    ) -> Result<Self> {
+        let target_dir = target_dir
+            .map(std::path::absolute)
+            .transpose()
+            .map_err(|e| anyhow::anyhow!("Invalid --cargo-target-dir: {}", e))?;
+        Ok(Self {
+            rustc_wrapper,
+            target_dir,
+        })
+    }
+
+    /// A `cargo` command run in `crate_dir` with these settings applied.
+    fn command(&self, crate_dir: &std::path::Path) -> std::process::Command {
+        let mut command = std::process::Command::new("cargo");
+        command.current_dir(crate_dir);
+        if let Some(wrapper) = &self.rustc_wrapper {
+            command.env("RUSTC_WRAPPER", wrapper);
+        }
+        if let Some(dir) = &self.target_dir {
+            command.env("CARGO_TARGET_DIR", dir);
+        }
+        command
+    }
+
+    /// Target directory cargo will use for a crate in `crate_dir`.
+    fn target_dir(&self, crate_dir: &std::path::Path) -> std::path::PathBuf {
+        self.target_dir
+            .clone()
+            .unwrap_or_else(|| cargo_target_dir(crate_dir))
+    }
+
+    /// Environment assignments shown by `build --dry-run`.
+    fn env_prefix(&self) -> String {
+        let mut prefix = String::new();
+        if let Some(wrapper) = &self.rustc_wrapper {
+            prefix.push_str(&format!("RUSTC_WRAPPER={} ", wrapper.display()));
+        }
+        if let Some(dir) = &self.target_dir {
+            prefix.push_str(&format!("CARGO_TARGET_DIR={} ", dir.display()));
+        }
+        prefix
+    }
+
+    /// Fail early when the compiler wrapper cannot be run, instead of letting
+    /// cargo report a less specific error for every crate.
+    fn check(&self) -> Result<()> {
+        let Some(wrapper) = &self.rustc_wrapper else {
+            return Ok(());
+        };
+        match std::process::Command::new(wrapper)
+            .arg("--version")
+            .output()
+        {
+            Ok(output) if output.status.success() => Ok(()),
+            Ok(output) => anyhow::bail!(
+                "--rustc-wrapper {} is not usable: `--version` exited with {}",
+                wrapper.display(),
+                output.status
+            ),
+            Err(e) => anyhow::bail!(
+                "--rustc-wrapper {} is not available: {}",
+                wrapper.display(),
+                e
+            ),
+        }
+    }
+}
+
+/// Cargo's target directory for a crate in `crate_dir`.
+///
+/// Honours `CARGO_TARGET_DIR` (resolved against `crate_dir` when relative, as
+/// cargo does), defaulting to `<crate_dir>/target`.
+fn cargo_target_dir(crate_dir: &std::path::Path) -> std::path::PathBuf {
+    match std::env::var_os("CARGO_TARGET_DIR") {
+        Some(dir) if !dir.is_empty() => crate_dir.join(dir),
+        _ => crate_dir.join("target"),
+    }
+}
+
+/// Path of a release binary under `target_dir`, with the platform executable
+/// suffix (`.exe` on Windows hosts or `*-windows-*` targets).
+fn release_binary_path(
+    target_dir: &std::path::Path,
+    target: Option<&str>,
+    binary_name: &str,
+) -> std::path::PathBuf {
+    let (dir, exe_suffix) = match target {
+        Some(t) => (
+            target_dir.join(t).join("release"),
+            if t.contains("-windows") { ".exe" } else { "" },
+        ),
+        None => (target_dir.join("release"), std::env::consts::EXE_SUFFIX),
+    };
+    dir.join(format!("{binary_name}{exe_suffix}"))
+}
+
+/// Release asset suffix for the host platform, e.g. `linux-x86_64`,
+/// `macos-aarch64` or `windows-x86_64.exe`.
+fn host_asset_suffix() -> String {
+    format!(
+        "{}-{}{}",
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        std::env::consts::EXE_SUFFIX
+    )
+}
+
 fn read_crate_name(input: &std::path::Path) -> Result<String> {
     let content = std::fs::read_to_string(input.join("Cargo.toml"))
         .map_err(|e| anyhow::anyhow!("Failed to read Cargo.toml: {}", e))?;
@@ -1418,15 +4384,67 @@ fn find_git_root(start: &std::path::Path) -> Result<std::path::PathBuf> {
     }
 }
 
+/// Derive orb name from the orb's `display` metadata.
+///
+/// Uses the last path segment of `source_url`, falling back to `home_url`
+/// (e.g. `https://github.com/jerus-org/circleci-toolkit` → `circleci-toolkit`).
+/// Returns `None` when neither URL yields a segment.
+fn orb_name_from_display(orb: &parser::OrbDefinition) -> Option<String> {
+    let display = orb.display.as_ref()?;
+    [&display.source_url, &display.home_url]
+        .into_iter()
+        .flatten()
+        .find_map(|url| {
+            let path = url.split(['?', '#']).next().unwrap_or(url);
+            let path = path.split_once("://").map_or(path, |(_, rest)| rest);
+            let (_host, path) = path.split_once('/')?;
+            let segment = path.trim_end_matches('/').rsplit('/').next()?;
+            let segment = segment.trim_end_matches(".git");
+            (!segment.is_empty()).then(|| segment.to_string())
+        })
+}
+
 /// Derive orb name from the orb path.
 ///
 /// For unpacked orbs (`@orb.yml`), uses the project directory name.
 /// Handles the common `project/src/@orb.yml` structure by skipping the `src`
-/// directory. For packed orbs, uses the file stem (filename without extension).
+/// directory. Project configs (`project/.circleci/config.yml`) also use the
+/// project directory name, and GitHub actions (`project/action.yml`) the
+/// directory holding the action. GitLab templates use the lowercased name
+/// before `.gitlab-ci.yml`, or the directory holding a bare
+/// `.gitlab-ci.yml`. For packed orbs, uses the file stem (filename without
+/// extension).
 fn derive_orb_name(path: &std::path::Path) -> String {
     let filename = path.file_name().and_then(|s| s.to_str()).unwrap_or("orb");
 
-    if filename == "@orb.yml" {
+    let kind = parser::SourceKind::detect(path);
+    if kind == parser::SourceKind::Config {
+        path.parent()
+            .and_then(|p| p.parent())
+            .and_then(|p| p.file_name())
+            .and_then(|s| s.to_str())
+            .unwrap_or("config")
+            .to_string()
+    } else if let Some(name) = filename
+        .strip_suffix(".gitlab-ci.yml")
+        .filter(|name| kind == parser::SourceKind::GitlabTemplate && !name.is_empty())
+    {
+        // GitLab's own templates are capitalized (`Docker.gitlab-ci.yml`)
+        name.to_ascii_lowercase()
+    } else if matches!(
+        kind,
+        parser::SourceKind::GhaAction | parser::SourceKind::GitlabTemplate
+    ) {
+        // A bare `action.yml` or `.gitlab-ci.yml` has an empty parent
+        std::path::absolute(path)
+            .ok()
+            .as_deref()
+            .and_then(|p| p.parent())
+            .and_then(|p| p.file_name())
+            .and_then(|s| s.to_str())
+            .unwrap_or("action")
+            .to_string()
+    } else if filename == "@orb.yml" {
         // Get parent directory
         let parent = path.parent();
         let parent_name = parent.and_then(|p| p.file_name()).and_then(|s| s.to_str());
@@ -1451,6 +4469,16 @@ fn derive_orb_name(path: &std::path::Path) -> String {
     }
 }
 
+/// Commit SHA of `HEAD` in the git repository containing `orb_path`.
+///
+/// Returns `None` outside a git repository or when `HEAD` has no commit.
+fn detect_source_rev(orb_path: &std::path::Path) -> Option<String> {
+    let root = find_git_root(orb_path).ok()?;
+    let repo = git2::Repository::open(root).ok()?;
+    let commit = repo.head().ok()?.peel_to_commit().ok()?;
+    Some(commit.id().to_string())
+}
+
 /// Discover the latest version tag in a git repository with the given prefix.
 ///
 /// Returns `None` when no matching tags exist. On error (e.g. not a git repo),
@@ -1463,12 +4491,135 @@ fn discover_latest_version(repo: &std::path::Path, tag_prefix: &str) -> Result<O
     Ok(tags.into_iter().last())
 }
 
+/// Parse an `--orb-version` value (`<version>=<path>`).
+///
+/// The version ends up in resource URIs and generated identifiers, so it must
+/// be a semantic version.
+fn parse_orb_version(value: &str) -> Result<(String, std::path::PathBuf), String> {
+    match value.split_once('=') {
+        Some((version, path)) if !version.is_empty() && !path.is_empty() => {
+            semver::Version::parse(version)
+                .map_err(|e| format!("invalid version '{version}': {e}"))?;
+            Ok((version.to_string(), std::path::PathBuf::from(path)))
+        }
+        _ => Err(format!("expected <version>=<path>, got '{value}'")),
+    }
+}
+
+/// Parse a `--license` SPDX expression.
+///
+/// Only the character set is checked; cargo validates the expression itself.
+fn parse_spdx(value: &str) -> Result<String, String> {
+    let valid = value
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || " .+-()".contains(c));
+    if value.trim().is_empty() || !valid {
+        return Err(format!("'{value}' is not an SPDX license expression"));
+    }
+    Ok(value.trim().to_string())
+}
+
+/// Where `--version-from` reads the crate version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum VersionSource {
+    /// A file holding just the version, such as `VERSION`
+    File(std::path::PathBuf),
+    /// The orb's `display.version`, else its `x-version` key
+    Orb,
+    /// The latest git tag with the `--tag-prefix`
+    Tag,
+}
+
+/// Parse a `--version-from` source (`file:<PATH>`, `orb` or `tag`).
+fn parse_version_from(source: &str) -> Result<VersionSource, String> {
+    match source.split_once(':') {
+        Some(("file", path)) if !path.is_empty() => {
+            Ok(VersionSource::File(std::path::PathBuf::from(path)))
+        }
+        None if source == "orb" => Ok(VersionSource::Orb),
+        None if source == "tag" => Ok(VersionSource::Tag),
+        _ => Err(format!(
+            "unsupported version source '{source}' (expected file:<PATH>, orb or tag)"
+        )),
+    }
+}
+
+/// Read the crate version from `source`, failing when it has none.
+fn read_version_source(
+    source: &VersionSource,
+    orb: &parser::OrbDefinition,
+    orb_path: &std::path::Path,
+    tag_prefix: &str,
+) -> Result<String> {
+    match source {
+        VersionSource::File(path) => read_version_file(path),
+        VersionSource::Orb => {
+            let display_version = orb.display.as_ref().and_then(|d| d.version.as_ref());
+            if let Some(v) = display_version {
+                checked_version(v.trim(), "display.version")
+            } else if let Some(v) = &orb.x_version {
+                checked_version(v.trim(), "x-version")
+            } else {
+                anyhow::bail!(
+                    "--version-from orb: {} has no display.version or x-version key",
+                    orb_path.display()
+                )
+            }
+        }
+        VersionSource::Tag => {
+            let repo = find_git_root(orb_path)
+                .map_err(|e| anyhow::anyhow!("--version-from tag: {}", e))?;
+            discover_latest_version(&repo, tag_prefix)?.ok_or_else(|| {
+                anyhow::anyhow!(
+                    "--version-from tag: no '{}<version>' tags in {}",
+                    tag_prefix,
+                    repo.display()
+                )
+            })
+        }
+    }
+}
+
+/// Read the `--logo` image from a local path or download it from an http(s)
+/// URL.
+fn load_logo(source: &str) -> Result<generator::Logo> {
+    let bytes = if source.starts_with("https://") || source.starts_with("http://") {
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        rt.block_on(async {
+            let response = reqwest::get(source).await?.error_for_status()?;
+            response.bytes().await
+        })
+        .map_err(|e| anyhow::anyhow!("Failed to download logo {source}: {e}"))?
+        .to_vec()
+    } else {
+        std::fs::read(source).map_err(|e| anyhow::anyhow!("Failed to read logo {source}: {e}"))?
+    };
+    generator::Logo::from_bytes(bytes).map_err(|e| anyhow::anyhow!("{source}: {e}"))
+}
+
+/// Read a semantic version from a file such as `VERSION`.
+fn read_version_file(path: &std::path::Path) -> Result<String> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read version file {}: {}", path.display(), e))?;
+    checked_version(content.trim(), &path.display().to_string())
+}
+
+/// Validate that `version` is a semantic version, naming `source` on error.
+fn checked_version(version: &str, source: &str) -> Result<String> {
+    semver::Version::parse(version)
+        .map_err(|e| anyhow::anyhow!("Invalid version '{}' in {}: {}", version, source, e))?;
+    Ok(version.to_string())
+}
+
 /// Resolve the version to use for the generated MCP server.
 ///
 /// # Version Resolution Rules (priority order)
 ///
 /// 1. Explicit `--version` — always wins
-/// 2. `git_hint` — version auto-discovered from git tags via `--git-repo`
+/// 2. `version_hint` — from the `--version-from` source, the orb's
+///    `x-version`, or git tags
 /// 3. Fresh generation with no hints — `DEFAULT_VERSION`
 /// 4. Existing output with no version — error (must specify `--version`)
 ///
@@ -1477,10 +4628,15 @@ fn resolve_version(
     output: &std::path::Path,
     version: Option<&str>,
     force: bool,
-    git_hint: Option<&str>,
+    version_hint: Option<&str>,
 ) -> Result<String> {
-    let cargo_toml = output.join("Cargo.toml");
-    let output_exists = cargo_toml.exists();
+    let output_exists = [
+        generator::Language::Rust,
+        generator::Language::TypeScript,
+        generator::Language::Python,
+    ]
+    .iter()
+    .any(|language| output.join(language.manifest_file()).exists());
 
     // Explicit version always wins (with force check if output exists)
     if let Some(v) = version {
@@ -1494,15 +4650,15 @@ fn resolve_version(
         return Ok(v.to_string());
     }
 
-    // Git-discovered version
-    if let Some(v) = git_hint {
+    // Discovered version
+    if let Some(v) = version_hint {
         if output_exists && !force {
             anyhow::bail!(
                 "Output directory '{}' already exists. Use --force to overwrite.",
                 output.display()
             );
         }
-        tracing::debug!(version = %v, "Using git-discovered version");
+        tracing::debug!(version = %v, "Using discovered version");
         return Ok(v.to_string());
     }
 
@@ -1558,9 +4714,10 @@ mod tests {
         );
         if let Ok(Cli {
             command: Commands::Generate { orb_path, .. },
+            ..
         }) = cli
         {
-            assert_eq!(orb_path, std::path::PathBuf::from("src/@orb.yml"));
+            assert_eq!(orb_path, [std::path::PathBuf::from("src/@orb.yml")]);
         }
     }
 
@@ -1572,7 +4729,8 @@ mod tests {
             "validate should work without --orb-path (default: src/@orb.yml)"
         );
         if let Ok(Cli {
-            command: Commands::Validate { orb_path },
+            command: Commands::Validate { orb_path, .. },
+            ..
         }) = cli
         {
             assert_eq!(orb_path, std::path::PathBuf::from("src/@orb.yml"));
@@ -1666,6 +4824,36 @@ mod tests {
         assert_eq!(derive_orb_name(path), "orb");
     }
 
+    #[test]
+    fn test_orb_name_from_display() {
+        use parser::{DisplayInfo, OrbDefinition};
+        let with_display = |home_url: Option<&str>, source_url: Option<&str>| OrbDefinition {
+            display: Some(DisplayInfo {
+                home_url: home_url.map(String::from),
+                source_url: source_url.map(String::from),
+                version: None,
+            }),
+            ..Default::default()
+        };
+
+        let orb = with_display(
+            Some("https://circleci.com/developer/orbs/orb/jerus-org/toolkit"),
+            Some("https://github.com/jerus-org/circleci-toolkit.git"),
+        );
+        assert_eq!(
+            orb_name_from_display(&orb).as_deref(),
+            Some("circleci-toolkit")
+        );
+
+        let orb = with_display(Some("https://example.com/orbs/my-orb/"), None);
+        assert_eq!(orb_name_from_display(&orb).as_deref(), Some("my-orb"));
+
+        let orb = with_display(Some("https://example.com"), None);
+        assert_eq!(orb_name_from_display(&orb), None);
+
+        assert_eq!(orb_name_from_display(&OrbDefinition::default()), None);
+    }
+
     #[test]
     fn test_derive_orb_name_from_packed() {
         use std::path::Path;
@@ -1677,779 +4865,2757 @@ mod tests {
     }
 
     #[test]
-    fn test_resolve_version_fresh_with_explicit() {
-        let temp_dir = TempDir::new().unwrap();
-        let result = resolve_version(temp_dir.path(), Some("2.0.0"), false, None);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "2.0.0");
-    }
+    fn test_cli_parse_parse_cache() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "validate", "--parse-cache"]).unwrap();
+        if let Commands::Validate { parse_cache, .. } = cli.command {
+            assert_eq!(parse_cache, Some(None));
+        } else {
+            panic!("Expected Validate command");
+        }
 
-    #[test]
-    fn test_resolve_version_fresh_no_version_errors() {
-        let temp_dir = TempDir::new().unwrap();
-        let result = resolve_version(temp_dir.path(), None, false, None);
-        assert!(result.is_err());
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "diff",
+            "--current",
+            "new.yml",
+            "--previous",
+            "old.yml",
+            "--since-version",
+            "2.0.0",
+            "--parse-cache",
+            "/tmp/parsed",
+        ])
+        .unwrap();
+        if let Commands::Diff { parse_cache, .. } = cli.command {
+            assert_eq!(
+                parse_cache,
+                Some(Some(std::path::PathBuf::from("/tmp/parsed")))
+            );
+        } else {
+            panic!("Expected Diff command");
+        }
     }
 
     #[test]
-    fn test_resolve_version_existing_without_version_fails() {
-        let temp_dir = TempDir::new().unwrap();
-        // Create a Cargo.toml to simulate existing output
-        std::fs::write(
-            temp_dir.path().join("Cargo.toml"),
-            "[package]\nname = \"test\"",
-        )
+    fn test_cli_parse_generate_binary_cache() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--format",
+            "binary",
+            "--binary-cache",
+            "/tmp/orb-cache",
+        ])
         .unwrap();
-
-        let result = resolve_version(temp_dir.path(), None, false, None);
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("already exists"));
-        assert!(err.contains("--crate-version"));
+        if let Commands::Generate { binary_cache, .. } = cli.command {
+            assert_eq!(
+                binary_cache,
+                Some(std::path::PathBuf::from("/tmp/orb-cache"))
+            );
+        } else {
+            panic!("Expected Generate command");
+        }
     }
 
     #[test]
-    fn test_resolve_version_existing_with_version_no_force_fails() {
-        let temp_dir = TempDir::new().unwrap();
-        std::fs::write(
-            temp_dir.path().join("Cargo.toml"),
-            "[package]\nname = \"test\"",
-        )
-        .unwrap();
-
-        let result = resolve_version(temp_dir.path(), Some("1.5.0"), false, None);
-        assert!(result.is_err());
-        let err = result.unwrap_err().to_string();
-        assert!(err.contains("--force"));
+    fn test_cli_parse_generate_only() {
+        let cli =
+            Cli::try_parse_from(["gen-orb-mcp", "generate", "--only", "jobs,executors"]).unwrap();
+        if let Commands::Generate { only, .. } = cli.command {
+            assert_eq!(
+                only,
+                vec![
+                    generator::DefinitionCategory::Jobs,
+                    generator::DefinitionCategory::Executors
+                ]
+            );
+        } else {
+            panic!("expected Generate variant");
+        }
     }
 
     #[test]
-    fn test_resolve_version_existing_with_version_and_force_succeeds() {
-        let temp_dir = TempDir::new().unwrap();
-        std::fs::write(
-            temp_dir.path().join("Cargo.toml"),
-            "[package]\nname = \"test\"",
-        )
+    fn test_cli_parse_generate_include_exclude() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--include",
+            "commands/*",
+            "--include",
+            "jobs/*",
+            "--exclude",
+            "jobs/deploy-*",
+        ])
         .unwrap();
+        if let Commands::Generate {
+            include, exclude, ..
+        } = cli.command
+        {
+            assert_eq!(include, ["commands/*", "jobs/*"]);
+            assert_eq!(exclude, ["jobs/deploy-*"]);
+        } else {
+            panic!("Expected Generate command");
+        }
+    }
 
-        let result = resolve_version(temp_dir.path(), Some("1.5.0"), true, None);
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "1.5.0");
+    #[test]
+    fn test_cli_parse_generate_property_tests() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--property-tests"]).unwrap();
+        if let Commands::Generate { property_tests, .. } = cli.command {
+            assert!(property_tests);
+        } else {
+            panic!("expected Generate variant");
+        }
     }
 
     #[test]
-    fn test_cli_parse_generate_with_prior_versions() {
+    fn test_cli_parse_generate_profile() {
         let cli = Cli::try_parse_from([
             "gen-orb-mcp",
             "generate",
-            "--orb-path",
-            "test.yml",
-            "--output",
-            "./out",
-            "--prior-versions",
-            "./prior",
-        ]);
-        assert!(cli.is_ok(), "expected --prior-versions flag to be accepted");
+            "--profile",
+            "minimal",
+            "--config",
+            "ci/gen-orb-mcp.toml",
+        ])
+        .unwrap();
+        if let Commands::Generate {
+            profile, config, ..
+        } = cli.command
+        {
+            assert_eq!(profile.as_deref(), Some("minimal"));
+            assert_eq!(
+                config,
+                Some(std::path::PathBuf::from("ci/gen-orb-mcp.toml"))
+            );
+        } else {
+            panic!("expected Generate variant");
+        }
     }
 
-    // Tests 11-15: prime command CLI parsing
-
     #[test]
-    fn test_cli_parse_prime_defaults() {
-        let cli = Cli::try_parse_from(["gen-orb-mcp", "prime"]);
-        assert!(cli.is_ok(), "prime with all defaults should parse");
-        if let Commands::Prime {
-            orb_path,
-            tag_prefix,
-            earliest_version,
-            since,
-            prior_versions_dir,
-            migrations_dir,
-            rename_map,
-            ephemeral,
-            dry_run,
-            git_repo,
-        } = cli.unwrap().command
+    fn test_cli_parse_generate_license() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--license",
+            "MIT OR Apache-2.0",
+            "--license-file",
+            "LICENSE-MIT",
+            "--logo",
+            "https://example.com/logo.svg",
+        ])
+        .unwrap();
+        if let Commands::Generate {
+            license,
+            license_file,
+            logo,
+            ..
+        } = cli.command
         {
-            assert_eq!(orb_path.to_str().unwrap(), "src/@orb.yml");
-            assert_eq!(tag_prefix, "v");
-            assert!(earliest_version.is_none());
-            assert!(since.is_none());
-            assert_eq!(prior_versions_dir.to_str().unwrap(), "prior-versions");
-            assert_eq!(migrations_dir.to_str().unwrap(), "migrations");
-            assert!(rename_map.is_empty());
-            assert!(!ephemeral);
-            assert!(!dry_run);
-            assert!(git_repo.is_none());
+            assert_eq!(license.as_deref(), Some("MIT OR Apache-2.0"));
+            assert_eq!(license_file, Some(std::path::PathBuf::from("LICENSE-MIT")));
+            assert_eq!(logo.as_deref(), Some("https://example.com/logo.svg"));
         } else {
-            panic!("expected Prime variant");
+            panic!("expected Generate variant");
         }
+
+        let err = Cli::try_parse_from(["gen-orb-mcp", "generate", "--license", "MIT\"; x"]);
+        assert!(err.is_err());
     }
 
     #[test]
-    fn test_cli_parse_prime_earliest_version() {
-        let cli = Cli::try_parse_from(["gen-orb-mcp", "prime", "--earliest-version", "4.1.0"]);
-        assert!(cli.is_ok(), "prime --earliest-version should parse");
-        if let Commands::Prime {
-            earliest_version, ..
-        } = cli.unwrap().command
+    fn test_cli_parse_generate_max_resource_bytes() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--max-resource-bytes", "4096"])
+            .unwrap();
+        if let Commands::Generate {
+            max_resource_bytes, ..
+        } = cli.command
         {
-            assert_eq!(earliest_version.as_deref(), Some("4.1.0"));
+            assert_eq!(max_resource_bytes.map(|n| n.get()), Some(4096));
         } else {
-            panic!("expected Prime variant");
+            panic!("expected Generate variant");
         }
+        assert!(
+            Cli::try_parse_from(["gen-orb-mcp", "generate", "--max-resource-bytes", "0"]).is_err()
+        );
     }
 
     #[test]
-    fn test_cli_parse_prime_since() {
-        let cli = Cli::try_parse_from(["gen-orb-mcp", "prime", "--since", "3 months"]);
-        assert!(cli.is_ok(), "prime --since should parse");
-        if let Commands::Prime { since, .. } = cli.unwrap().command {
-            assert_eq!(since.as_deref(), Some("3 months"));
+    fn test_cli_parse_generate_language() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate"]).unwrap();
+        if let Commands::Generate { language, .. } = cli.command {
+            assert_eq!(language, None);
         } else {
-            panic!("expected Prime variant");
+            panic!("expected Generate variant");
+        }
+        let cli =
+            Cli::try_parse_from(["gen-orb-mcp", "generate", "--language", "typescript"]).unwrap();
+        if let Commands::Generate { language, .. } = cli.command {
+            assert_eq!(language, Some(generator::Language::TypeScript));
+        } else {
+            panic!("expected Generate variant");
+        }
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--language", "python"]).unwrap();
+        if let Commands::Generate { language, .. } = cli.command {
+            assert_eq!(language, Some(generator::Language::Python));
+        } else {
+            panic!("expected Generate variant");
         }
-    }
 
-    #[test]
-    fn test_cli_parse_prime_exclusive_flags() {
-        // --earliest-version and --since are mutually exclusive
-        let cli = Cli::try_parse_from([
+        let err = Cli::try_parse_from([
             "gen-orb-mcp",
-            "prime",
-            "--earliest-version",
-            "4.1.0",
-            "--since",
-            "6 months",
-        ]);
-        assert!(
-            cli.is_err(),
-            "prime with both --earliest-version and --since should be rejected"
-        );
+            "generate",
+            "--language",
+            "typescript",
+            "--format",
+            "binary",
+        ])
+        .unwrap()
+        .run()
+        .unwrap_err();
+        assert_eq!(err.to_string(), "--format binary requires --language rust");
     }
 
     #[test]
-    fn test_cli_parse_prime_rename_map() {
-        let cli = Cli::try_parse_from([
-            "gen-orb-mcp",
-            "prime",
-            "--rename-map",
-            "common_tests_rolling=common_tests",
-            "--rename-map",
-            "required_builds_rolling=required_builds",
-        ]);
-        assert!(cli.is_ok(), "prime --rename-map should parse");
-        if let Commands::Prime { rename_map, .. } = cli.unwrap().command {
-            assert_eq!(rename_map.len(), 2);
-            assert!(rename_map.contains(&"common_tests_rolling=common_tests".to_string()));
-            assert!(rename_map.contains(&"required_builds_rolling=required_builds".to_string()));
+    fn test_cli_parse_generate_report() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "-q"]).unwrap();
+        if let Commands::Generate { quiet, json, .. } = cli.command {
+            assert!(quiet);
+            assert!(!json);
         } else {
-            panic!("expected Prime variant");
+            panic!("expected Generate variant");
+        }
+
+        for conflicting in [["--quiet", "--json"], ["--json", "--dry-run"]] {
+            let err =
+                Cli::try_parse_from(["gen-orb-mcp", "generate"].into_iter().chain(conflicting))
+                    .unwrap_err();
+            assert_eq!(err.exit_code(), i32::from(failure::USAGE_EXIT_CODE));
         }
     }
 
     #[test]
-    fn test_cli_parse_prime_ephemeral() {
-        let cli = Cli::try_parse_from(["gen-orb-mcp", "prime", "--ephemeral"]);
-        assert!(cli.is_ok(), "prime --ephemeral should parse");
-        if let Commands::Prime { ephemeral, .. } = cli.unwrap().command {
-            assert!(ephemeral);
-        } else {
-            panic!("expected Prime variant");
-        }
-    }
-
-    // Serialises tests that mutate the global CWD.
-    static CWD_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
-
-    /// Regression test: `find_git_root` with a *relative* orb path must return
-    /// an **absolute** path.
-    ///
-    /// When the user runs `gen-orb-mcp prime --orb-path src/@orb.yml` (the
-    /// default), `orb_path` is relative.  `find_git_root` walks up from
-    /// `src/@orb.yml` → `src` → `""` (Rust `Path::parent` of `"src"` is `""`).
-    /// If the function returns `""`, `repo_abs` cannot be canonicalised, so
-    /// `strip_prefix("")` on the absolute `orb_abs` returns the full absolute
-    /// path.  `worktree.join(absolute_path)` then ignores the worktree and
-    /// reads the current working copy — producing snapshots with
-    /// current-version content for every historical tag.
-    ///
-    /// The fix: canonicalise `start` at the top of `find_git_root` so the
-    /// walk-up always operates on absolute paths and returns an absolute
-    /// result.
-    #[test]
-    fn test_find_git_root_returns_absolute_path_for_relative_input() {
-        let _cwd_guard = CWD_LOCK.lock().unwrap();
-        let original = std::env::current_dir().unwrap();
+    fn test_generate_exit_codes() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb = temp_dir.path().join("orb.yml");
+        let output = temp_dir.path().join("out");
+        let generate = |orb: &std::path::Path| {
+            Cli::try_parse_from([
+                "gen-orb-mcp",
+                "generate",
+                "--orb-path",
+                orb.to_str().unwrap(),
+                "--output",
+                output.to_str().unwrap(),
+                "--crate-version",
+                "1.0.0",
+                "--fail-on-warnings",
+                "--quiet",
+            ])
+            .unwrap()
+            .run()
+        };
 
-        let tmp = TempDir::new().unwrap();
-        std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
-        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
         std::fs::write(
-            tmp.path().join("src").join("@orb.yml"),
-            "version: 2.1\ndescription: test",
+            &orb,
+            "version: 2.1
+commands: [
+",
         )
         .unwrap();
+        let err = generate(&orb).unwrap_err();
+        assert_eq!(failure::exit_code(&err), 3, "{err}");
 
-        // Change to the fake repo root so that "src/@orb.yml" is a valid
-        // relative path.
-        std::env::set_current_dir(tmp.path()).unwrap();
-
-        let result = find_git_root(std::path::Path::new("src/@orb.yml"));
+        std::fs::write(
+            &orb,
+            "version: 2.1
+commands:
+  greet:
+    steps: []
+",
+        )
+        .unwrap();
+        let err = generate(&orb).unwrap_err();
+        assert_eq!(failure::exit_code(&err), 4, "{err}");
+        assert!(err.to_string().contains("--fail-on-warnings"), "{err}");
 
-        // Always restore CWD before asserting so a failure doesn't leave the
-        // process in the tmp directory.
-        std::env::set_current_dir(&original).unwrap();
+        std::fs::write(
+            &orb,
+            "version: 2.1
+description: Greeter
+commands:
+  greet:
+    description: Greet
+    \
+             steps: []
+",
+        )
+        .unwrap();
+        generate(&orb).unwrap();
+        assert!(output.join("Cargo.toml").is_file());
+    }
 
-        let result = result.expect("find_git_root should succeed");
-        assert!(
-            result.is_absolute(),
-            "find_git_root must return an absolute path, got: {:?}",
-            result
-        );
+    #[test]
+    fn test_generate_summary_json() {
+        let orbs = [
+            parser::OrbDefinition {
+                commands: std::collections::HashMap::from([(
+                    "greet".to_string(),
+                    parser::Command::default(),
+                )]),
+                ..Default::default()
+            },
+            parser::OrbDefinition::default(),
+        ];
+        let summary = GenerateSummary {
+            orb_name: "toolkit".to_string(),
+            crate_name: "toolkit_mcp".to_string(),
+            version: "1.2.0".to_string(),
+            format: OutputFormat::Crate.as_str(),
+            language: generator::Language::Rust,
+            output: std::path::PathBuf::from("dist"),
+            files: vec![std::path::PathBuf::from("Cargo.toml")],
+            binary: None,
+            crate_file: Some(std::path::PathBuf::from(
+                "dist/target/package/toolkit_mcp-1.2.0.crate",
+            )),
+            counts: DefinitionCounts::of(&orbs, 1),
+            client_config: None,
+        };
         assert_eq!(
-            result.canonicalize().unwrap(),
-            tmp.path().canonicalize().unwrap(),
+            serde_json::to_value(&summary).unwrap(),
+            serde_json::json!({
+                "orb_name": "toolkit",
+                "crate_name": "toolkit_mcp",
+                "version": "1.2.0",
+                "format": "crate",
+                "language": "rust",
+                "output": "dist",
+                "files": ["Cargo.toml"],
+                "crate": "dist/target/package/toolkit_mcp-1.2.0.crate",
+                "counts": {
+                    "orbs": 2,
+                    "commands": 1,
+                    "jobs": 0,
+                    "executors": 0,
+                    "examples": 0,
+                    "workflows": 0,
+                    "pipeline_parameters": 0,
+                    "imported_orbs": 1
+                }
+            })
         );
     }
 
-    // --- Tests for discover_latest_version ---
+    #[test]
+    fn test_cli_parse_generate_crate() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--format",
+            "crate",
+            "--crate-description",
+            "MCP server for the toolkit orb",
+            "--repository",
+            "https://github.com/example/toolkit-orb",
+            "--keywords",
+            "circleci,mcp",
+            "--registry",
+            "internal",
+        ])
+        .unwrap();
+        if let Commands::Generate {
+            format,
+            crate_description,
+            repository,
+            keywords,
+            registry,
+            ..
+        } = cli.command
+        {
+            assert_eq!(format, OutputFormat::Crate);
+            assert_eq!(
+                crate_description.as_deref(),
+                Some("MCP server for the toolkit orb")
+            );
+            assert_eq!(
+                repository.as_deref(),
+                Some("https://github.com/example/toolkit-orb")
+            );
+            assert_eq!(keywords, ["circleci", "mcp"]);
+            assert_eq!(registry.as_deref(), Some("internal"));
+        } else {
+            panic!("expected Generate variant");
+        }
+
+        let err = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--language",
+            "typescript",
+            "--format",
+            "crate",
+        ])
+        .unwrap()
+        .run()
+        .unwrap_err();
+        assert_eq!(err.to_string(), "--format crate requires --language rust");
+    }
 
     #[test]
-    fn test_discover_latest_version_returns_none_for_no_tags() {
-        let tmp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(tmp.path())
-            .output()
-            .unwrap();
-        let result = discover_latest_version(tmp.path(), "v");
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), None);
+    fn test_generate_from_project_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let circleci = temp_dir.path().join("my-app/.circleci");
+        std::fs::create_dir_all(&circleci).unwrap();
+        let config = circleci.join("config.yml");
+        std::fs::write(
+            &config,
+            "version: 2.1\nparameters:\n  deploy:\n    type: boolean\n    default: false\n\
+             jobs:\n  build:\n    description: Build it\n    docker:\n      - image: cimg/base:2024.01\n    \
+             steps: [checkout]\nworkflows:\n  main:\n    jobs: [build]\n",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("out");
+
+        Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            config.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--crate-version",
+            "1.0.0",
+            "--fail-on-warnings",
+        ])
+        .unwrap()
+        .run()
+        .unwrap();
+        let lib_rs = std::fs::read_to_string(output.join("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("\"orb://jobs/build\""));
+        assert!(lib_rs.contains("\"orb://workflows/main\""));
+        assert!(lib_rs.contains("`deploy` (boolean, optional)"));
+        let cargo_toml = std::fs::read_to_string(output.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"my_app_mcp\""));
+
+        // Forcing `--kind config` on a file without workflows is an error.
+        let orb = temp_dir.path().join("orb.yml");
+        std::fs::write(&orb, "version: 2.1\ncommands: {}\n").unwrap();
+        let err = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            orb.to_str().unwrap(),
+            "--kind",
+            "config",
+            "--output",
+            temp_dir.path().join("out2").to_str().unwrap(),
+        ])
+        .unwrap()
+        .run()
+        .unwrap_err();
+        assert!(err.to_string().contains("defines no workflows"));
     }
 
     #[test]
-    fn test_discover_latest_version_returns_highest_semver_tag() {
-        let tmp = TempDir::new().unwrap();
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(tmp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.email", "test@test.com"])
-            .current_dir(tmp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.name", "Test"])
-            .current_dir(tmp.path())
-            .output()
-            .unwrap();
-        std::fs::write(tmp.path().join("README.md"), "test").unwrap();
-        std::process::Command::new("git")
-            .args(["add", "."])
-            .current_dir(tmp.path())
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["commit", "-m", "init"])
-            .current_dir(tmp.path())
-            .output()
-            .unwrap();
-        for tag in ["v1.0.0", "v2.0.0", "v1.5.0"] {
-            std::process::Command::new("git")
-                .args(["tag", tag])
-                .current_dir(tmp.path())
-                .output()
-                .unwrap();
-        }
-        let result = discover_latest_version(tmp.path(), "v");
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), Some("2.0.0".to_string()));
+    fn test_generate_from_gha_action() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = temp_dir.path().join("setup-greeter");
+        std::fs::create_dir_all(&dir).unwrap();
+        let action = dir.join("action.yml");
+        std::fs::write(
+            &action,
+            "name: Setup Greeter
+description: Install the greeter
+inputs:
+  version:
+    \
+             description: Greeter version
+    required: true
+outputs:
+  path:
+    \
+             description: Install location
+runs:
+  using: composite
+  steps:
+    \
+             - run: ./install.sh ${{ inputs.version }}
+      shell: bash
+",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("out");
+
+        Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            action.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--crate-version",
+            "1.0.0",
+        ])
+        .unwrap()
+        .run()
+        .unwrap();
+        let lib_rs = std::fs::read_to_string(output.join("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("\"orb://commands/setup-greeter\""));
+        assert!(lib_rs.contains("`version` (string): Greeter version"));
+        let cargo_toml = std::fs::read_to_string(output.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"setup_greeter_mcp\""));
     }
 
     #[test]
-    fn test_resolve_version_uses_git_hint_when_no_explicit_version() {
+    fn test_generate_from_gitlab_template() {
         let temp_dir = TempDir::new().unwrap();
-        let result = resolve_version(temp_dir.path(), None, false, Some("3.1.0"));
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "3.1.0");
+        let template = temp_dir.path().join("Scan.gitlab-ci.yml");
+        std::fs::write(
+            &template,
+            "spec:\n  inputs:\n    stage:\n      description: Stage to scan in\n      default: \
+             test\n---\n.scan-base:\n  script: [scanner --init]\nscan:\n  extends: \
+             .scan-base\n  stage: $[[ inputs.stage ]]\n  image: alpine:3.20\n  script:\n    \
+             - scanner .\n",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("out");
+
+        Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            template.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--crate-version",
+            "1.0.0",
+        ])
+        .unwrap()
+        .run()
+        .unwrap();
+        let lib_rs = std::fs::read_to_string(output.join("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("\"orb://jobs/scan\""));
+        assert!(lib_rs.contains("\"orb://commands/scan-base\""));
+        assert!(lib_rs.contains("`stage` (string, optional): Stage to scan in"));
+        let cargo_toml = std::fs::read_to_string(output.join("Cargo.toml")).unwrap();
+        assert!(cargo_toml.contains("name = \"scan_mcp\""));
     }
 
     #[test]
-    fn test_resolve_version_explicit_overrides_git_hint() {
+    fn test_generate_resolves_imported_orbs_from_cache() {
         let temp_dir = TempDir::new().unwrap();
-        let result = resolve_version(temp_dir.path(), Some("5.0.0"), false, Some("3.1.0"));
-        assert!(result.is_ok());
-        assert_eq!(result.unwrap(), "5.0.0");
+        let orb = temp_dir.path().join("toolkit.yml");
+        std::fs::write(
+            &orb,
+            "version: 2.1\norbs:\n  node: circleci/node@5.1.0\n\
+             commands:\n  greet:\n    steps: []\n",
+        )
+        .unwrap();
+        let cache = temp_dir.path().join("cache");
+        std::fs::create_dir_all(cache.join("circleci")).unwrap();
+        std::fs::write(
+            cache.join("circleci/node@5.1.0.yml"),
+            "version: 2.1\njobs:\n  test:\n    docker:\n      - image: cimg/node:20.0\n    steps: []\n",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("out");
+
+        // Nothing listens on the host, so the import must come from the cache.
+        Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            orb.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--crate-version",
+            "1.0.0",
+            "--resolve-orbs",
+            "--orb-cache",
+            cache.to_str().unwrap(),
+            "--circleci-host",
+            "http://127.0.0.1:9",
+        ])
+        .unwrap()
+        .run()
+        .unwrap();
+        let lib_rs = std::fs::read_to_string(output.join("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("\"orb://commands/greet\""));
+        assert!(lib_rs.contains("\"orb://node/jobs/test\""));
+        let metadata = metadata::GenerationMetadata::read_from(&output)
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.options.imported_orbs, vec!["circleci/node@5.1.0"]);
+
+        assert!(Cli::try_parse_from(["gen-orb-mcp", "generate", "--orb-cache", "x"]).is_err());
     }
 
     #[test]
-    fn test_resolve_version_errors_without_version_or_hint() {
+    fn test_generate_multiple_orbs_requires_name() {
         let temp_dir = TempDir::new().unwrap();
-        let result = resolve_version(temp_dir.path(), None, false, None);
-        assert!(result.is_err());
-        let msg = result.unwrap_err().to_string();
-        assert!(msg.contains("No version could be determined"), "got: {msg}");
+        let first = temp_dir.path().join("build.yml");
+        let second = temp_dir.path().join("deploy.yml");
+        for path in [&first, &second] {
+            std::fs::write(path, "version: 2.1\ncommands:\n  greet:\n    steps: []\n").unwrap();
+        }
+        let output = temp_dir.path().join("out");
+        let args = |name: Option<&str>| {
+            let mut args = vec![
+                "gen-orb-mcp".to_string(),
+                "generate".to_string(),
+                "--orb-path".to_string(),
+                first.display().to_string(),
+                "--orb-path".to_string(),
+                second.display().to_string(),
+                "--output".to_string(),
+                output.display().to_string(),
+                "--crate-version".to_string(),
+                "1.0.0".to_string(),
+            ];
+            if let Some(name) = name {
+                args.extend(["--name".to_string(), name.to_string()]);
+            }
+            args
+        };
+
+        let err = Cli::try_parse_from(args(None)).unwrap().run().unwrap_err();
+        assert!(err.to_string().contains("--name is required"));
+
+        Cli::try_parse_from(args(Some("platform")))
+            .unwrap()
+            .run()
+            .unwrap();
+        let lib_rs = std::fs::read_to_string(output.join("src/lib.rs")).unwrap();
+        assert!(lib_rs.contains("\"orb://build/commands/greet\""));
+        assert!(lib_rs.contains("\"orb://deploy/commands/greet\""));
+        let metadata = metadata::GenerationMetadata::read_from(&output)
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.orb.merged.len(), 1);
+        assert_eq!(metadata.orb.merged[0].path, second);
     }
 
     #[test]
-    fn test_cli_parse_generate_with_tag_prefix() {
+    fn test_cli_parse_generate_orb_ref() {
         let cli = Cli::try_parse_from([
             "gen-orb-mcp",
             "generate",
-            "--orb-path",
-            "test.yml",
-            "--output",
-            "./out",
-            "--tag-prefix",
-            "orb-v",
-        ]);
-        assert!(cli.is_ok(), "generate --tag-prefix should parse");
-        if let Commands::Generate { tag_prefix, .. } = cli.unwrap().command {
-            assert_eq!(tag_prefix, "orb-v");
+            "--orb-ref",
+            "circleci/node@5.1.0",
+        ])
+        .unwrap();
+        if let Commands::Generate {
+            orb_ref,
+            circleci_host,
+            ..
+        } = cli.command
+        {
+            assert_eq!(orb_ref.unwrap().to_string(), "circleci/node@5.1.0");
+            assert_eq!(circleci_host, "https://circleci.com");
         } else {
             panic!("expected Generate variant");
         }
+
+        assert!(Cli::try_parse_from(["gen-orb-mcp", "generate", "--orb-ref", "node"]).is_err());
+        assert!(Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-ref",
+            "circleci/node",
+            "--orb-path",
+            "src/@orb.yml",
+        ])
+        .is_err());
     }
 
     #[test]
-    fn test_cli_parse_generate_tag_prefix_defaults_to_v() {
-        let cli = Cli::try_parse_from([
+    fn test_cli_parse_generate_sandboxed_conflicts_with_migrations() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--sandboxed"]).unwrap();
+        if let Commands::Generate { sandboxed, .. } = cli.command {
+            assert!(sandboxed);
+        } else {
+            panic!("expected Generate variant");
+        }
+        assert!(Cli::try_parse_from([
             "gen-orb-mcp",
             "generate",
-            "--orb-path",
-            "test.yml",
-            "--output",
-            "./out",
-        ]);
-        assert!(cli.is_ok());
-        if let Commands::Generate { tag_prefix, .. } = cli.unwrap().command {
-            assert_eq!(tag_prefix, "v");
+            "--sandboxed",
+            "--migrations",
+            "migrations"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_generate_dev_conflicts_with_sandboxed() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--dev"]).unwrap();
+        if let Commands::Generate { dev, .. } = cli.command {
+            assert!(dev);
         } else {
             panic!("expected Generate variant");
         }
+        assert!(Cli::try_parse_from(["gen-orb-mcp", "generate", "--dev", "--sandboxed"]).is_err());
+        assert!(Cli::try_parse_from(["gen-orb-mcp", "generate", "--dev", "--all"]).is_err());
     }
 
-    // --- save subcommand tests ---
+    #[test]
+    fn test_cli_parse_generate_stdin() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--stdin"]).unwrap();
+        if let Commands::Generate { stdin, .. } = cli.command {
+            assert!(stdin);
+        } else {
+            panic!("expected Generate variant");
+        }
+        let conflicts: [&[&str]; 4] = [
+            &["--orb-path", "src/@orb.yml"],
+            &["--orb-ref", "circleci/node@5.1.0"],
+            &["--dev"],
+            &["--all"],
+        ];
+        for conflicting in conflicts {
+            let mut args = vec!["gen-orb-mcp", "generate", "--stdin"];
+            args.extend(conflicting);
+            assert!(Cli::try_parse_from(args).is_err());
+        }
+    }
 
-    fn init_git_repo(dir: &std::path::Path) {
-        std::process::Command::new("git")
-            .args(["init"])
-            .current_dir(dir)
-            .output()
-            .unwrap();
-        std::process::Command::new("git")
-            .args(["config", "user.email", "test@test.com"])
-            .current_dir(dir)
-            .output()
-            .unwrap();
+    #[test]
+    fn test_cli_parse_generate_dry_run() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--dry-run"]).unwrap();
+        if let Commands::Generate { dry_run, force, .. } = cli.command {
+            assert!(dry_run);
+            assert!(!force);
+        } else {
+            panic!("expected Generate variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_transport() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate"]).unwrap();
+        if let Commands::Generate {
+            transport,
+            http_bind,
+            ..
+        } = cli.command
+        {
+            assert_eq!(transport, None);
+            assert_eq!(http_bind, None);
+        } else {
+            panic!("expected Generate variant");
+        }
+
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--transport",
+            "http",
+            "--http-bind",
+            "0.0.0.0:9000",
+        ])
+        .unwrap();
+        if let Commands::Generate {
+            transport,
+            http_bind,
+            ..
+        } = cli.command
+        {
+            assert_eq!(transport, Some(generator::Transport::Http));
+            assert_eq!(http_bind, Some("0.0.0.0:9000".parse().unwrap()));
+        } else {
+            panic!("expected Generate variant");
+        }
+        assert!(
+            Cli::try_parse_from(["gen-orb-mcp", "generate", "--http-bind", "localhost"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_generate_http_layers() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate"]).unwrap();
+        if let Commands::Generate {
+            http_compression,
+            http_body_limit,
+            ..
+        } = cli.command
+        {
+            assert!(!http_compression);
+            assert_eq!(http_body_limit, None);
+        } else {
+            panic!("expected Generate variant");
+        }
+
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--http-compression",
+            "--http-body-limit",
+            "65536",
+        ])
+        .unwrap();
+        if let Commands::Generate {
+            http_compression,
+            http_body_limit,
+            ..
+        } = cli.command
+        {
+            assert!(http_compression);
+            assert_eq!(http_body_limit.map(|n| n.get()), Some(65536));
+        } else {
+            panic!("expected Generate variant");
+        }
+        assert!(
+            Cli::try_parse_from(["gen-orb-mcp", "generate", "--http-body-limit", "0"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_generate_strict_names() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--strict-names"]).unwrap();
+        if let Commands::Generate { strict_names, .. } = cli.command {
+            assert!(strict_names);
+        } else {
+            panic!("expected Generate variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_version_from() {
+        let cli =
+            Cli::try_parse_from(["gen-orb-mcp", "generate", "--version-from", "file:VERSION"])
+                .unwrap();
+        if let Commands::Generate { version_from, .. } = cli.command {
+            assert_eq!(
+                version_from,
+                Some(VersionSource::File(std::path::PathBuf::from("VERSION")))
+            );
+        } else {
+            panic!("expected Generate variant");
+        }
+
+        let cli =
+            Cli::try_parse_from(["gen-orb-mcp", "generate", "--version-from", "tag"]).unwrap();
+        if let Commands::Generate { version_from, .. } = cli.command {
+            assert_eq!(version_from, Some(VersionSource::Tag));
+        } else {
+            panic!("expected Generate variant");
+        }
+
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--version-from-orb"]).unwrap();
+        if let Commands::Generate {
+            version_from_orb, ..
+        } = cli.command
+        {
+            assert!(version_from_orb);
+        } else {
+            panic!("expected Generate variant");
+        }
+
+        let result = Cli::try_parse_from(["gen-orb-mcp", "generate", "--version-from", "VERSION"]);
+        assert!(result.is_err());
+        let result = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--version-from",
+            "tag",
+            "--version-from-orb",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_version_source_orb_and_tag() {
+        let orb: parser::OrbDefinition =
+            serde_yaml::from_str("version: 2.1\ndisplay:\n  version: 2.3.0\nx-version: 1.0.0\n")
+                .unwrap();
+        let path = std::path::Path::new("src/@orb.yml");
+        assert_eq!(
+            read_version_source(&VersionSource::Orb, &orb, path, "v").unwrap(),
+            "2.3.0"
+        );
+        let orb: parser::OrbDefinition =
+            serde_yaml::from_str("version: 2.1\nx-version: 1.0.0\n").unwrap();
+        assert_eq!(
+            read_version_source(&VersionSource::Orb, &orb, path, "v").unwrap(),
+            "1.0.0"
+        );
+        let err = read_version_source(&VersionSource::Orb, &Default::default(), path, "v")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no display.version or x-version"), "{err}");
+
+        let temp_dir = TempDir::new().unwrap();
+        git2::Repository::init(temp_dir.path()).unwrap();
+        let orb_path = temp_dir.path().join("@orb.yml");
+        std::fs::write(&orb_path, "version: 2.1\n").unwrap();
+        let err = read_version_source(&VersionSource::Tag, &orb, &orb_path, "v")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("no 'v<version>' tags"), "{err}");
+    }
+
+    #[test]
+    fn test_read_version_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("VERSION");
+
+        std::fs::write(&path, "4.9.6\n").unwrap();
+        assert_eq!(read_version_file(&path).unwrap(), "4.9.6");
+
+        std::fs::write(&path, "not-a-version\n").unwrap();
+        assert!(read_version_file(&path).is_err());
+
+        assert!(read_version_file(&temp_dir.path().join("missing")).is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_generate_orb_versions() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-version",
+            "1.6.0=old/1.6.0.yml",
+            "--orb-version",
+            "2.0.0=old/2.0.0.yml",
+        ])
+        .unwrap();
+        if let Commands::Generate { orb_versions, .. } = cli.command {
+            assert_eq!(
+                orb_versions,
+                vec![
+                    ("1.6.0".to_string(), "old/1.6.0.yml".into()),
+                    ("2.0.0".to_string(), "old/2.0.0.yml".into()),
+                ]
+            );
+        } else {
+            panic!("expected Generate variant");
+        }
+
+        let result = Cli::try_parse_from(["gen-orb-mcp", "generate", "--orb-version", "1.6.0"]);
+        assert!(result.is_err());
+        for version in ["1.0+build", "v1 beta", "1.6"] {
+            let value = format!("{version}=old/orb.yml");
+            let result = Cli::try_parse_from(["gen-orb-mcp", "generate", "--orb-version", &value]);
+            assert!(result.is_err(), "{version}");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_all() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--all",
+            "--orb-glob",
+            "orbs/*/orb.yml",
+        ])
+        .unwrap();
+        if let Commands::Generate { all, orb_globs, .. } = cli.command {
+            assert!(all);
+            assert_eq!(orb_globs, vec!["orbs/*/orb.yml".to_string()]);
+        } else {
+            panic!("expected Generate variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_all_conflicts_with_orb_path() {
+        let result = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--all",
+            "--orb-path",
+            "src/@orb.yml",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_cli_parse_generate_clippy_fix_conflicts_with_dry_run() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--clippy-fix"]).unwrap();
+        if let Commands::Generate { clippy_fix, .. } = cli.command {
+            assert!(clippy_fix);
+        } else {
+            panic!("expected Generate variant");
+        }
+        assert!(
+            Cli::try_parse_from(["gen-orb-mcp", "generate", "--clippy-fix", "--dry-run"]).is_err()
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_generate_print_client_config() {
+        for extra in ["--quiet", "--json"] {
+            let cli =
+                Cli::try_parse_from(["gen-orb-mcp", "generate", "--print-client-config", extra])
+                    .unwrap();
+            if let Commands::Generate {
+                print_client_config,
+                ..
+            } = cli.command
+            {
+                assert!(print_client_config);
+            } else {
+                panic!("expected Generate variant");
+            }
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_naming() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--name-prefix",
+            "acme",
+            "--crate-suffix",
+            "",
+            "--uri-scheme",
+            "acme",
+        ])
+        .unwrap();
+        if let Commands::Generate {
+            name_prefix,
+            crate_suffix,
+            uri_scheme,
+            ..
+        } = cli.command
+        {
+            assert_eq!(name_prefix.as_deref(), Some("acme"));
+            assert_eq!(crate_suffix.as_deref(), Some(""));
+            assert_eq!(uri_scheme.as_deref(), Some("acme"));
+        } else {
+            panic!("expected Generate variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_workspace_requires_all() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--all", "--workspace"]).unwrap();
+        if let Commands::Generate { workspace, .. } = cli.command {
+            assert!(workspace);
+        } else {
+            panic!("expected Generate variant");
+        }
+
+        let result = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            "src/@orb.yml",
+            "--workspace",
+        ]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_workspace_manifest_lists_members() {
+        let (alpha, beta) = ("alpha".to_string(), "beta".to_string());
+        let manifest = workspace_manifest(&[&alpha, &beta]).unwrap();
+        assert_eq!(
+            manifest,
+            "# Generated by gen-orb-mcp.\n\n[workspace]\nresolver = \"2\"\n\
+             members = [\n    \"alpha\",\n    \"beta\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn test_discover_orb_paths_skips_hidden_build_and_output_dirs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in [
+            "orbs/alpha/src",
+            "orbs/beta/src",
+            ".git/src",
+            "target/src",
+            "dist/alpha/src",
+        ] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("@orb.yml"), "version: 2.1\n").unwrap();
+        }
+
+        let found = discover_orb_paths(root, &[], &root.join("dist")).unwrap();
+
+        assert_eq!(
+            found,
+            vec![
+                root.join("orbs/alpha/src/@orb.yml"),
+                root.join("orbs/beta/src/@orb.yml"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_discover_orb_paths_with_globs() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::create_dir_all(root.join("packed")).unwrap();
+        std::fs::write(root.join("packed/one.yml"), "version: 2.1\n").unwrap();
+        std::fs::write(root.join("packed/two.yml"), "version: 2.1\n").unwrap();
+        std::fs::write(root.join("packed/notes.txt"), "").unwrap();
+
+        let found =
+            discover_orb_paths(root, &["packed/*.yml".to_string()], &root.join("dist")).unwrap();
+
+        assert_eq!(
+            found,
+            vec![root.join("packed/one.yml"), root.join("packed/two.yml")]
+        );
+    }
+
+    #[test]
+    fn test_resolve_version_fresh_with_explicit() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = resolve_version(temp_dir.path(), Some("2.0.0"), false, None);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "2.0.0");
+    }
+
+    #[test]
+    fn test_resolve_version_fresh_no_version_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = resolve_version(temp_dir.path(), None, false, None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_resolve_version_existing_without_version_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        // Create a Cargo.toml to simulate existing output
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"",
+        )
+        .unwrap();
+
+        let result = resolve_version(temp_dir.path(), None, false, None);
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("already exists"));
+        assert!(err.contains("--crate-version"));
+    }
+
+    #[test]
+    fn test_resolve_version_existing_with_version_no_force_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"",
+        )
+        .unwrap();
+
+        let result = resolve_version(temp_dir.path(), Some("1.5.0"), false, None);
+        assert!(result.is_err());
+        let err = result.unwrap_err().to_string();
+        assert!(err.contains("--force"));
+    }
+
+    #[test]
+    fn test_resolve_version_existing_with_version_and_force_succeeds() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("Cargo.toml"),
+            "[package]\nname = \"test\"",
+        )
+        .unwrap();
+
+        let result = resolve_version(temp_dir.path(), Some("1.5.0"), true, None);
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "1.5.0");
+    }
+
+    #[test]
+    fn test_cli_parse_generate_with_prior_versions() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            "test.yml",
+            "--output",
+            "./out",
+            "--prior-versions",
+            "./prior",
+        ]);
+        assert!(cli.is_ok(), "expected --prior-versions flag to be accepted");
+    }
+
+    // Tests 11-15: prime command CLI parsing
+
+    #[test]
+    fn test_cli_parse_prime_defaults() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "prime"]);
+        assert!(cli.is_ok(), "prime with all defaults should parse");
+        if let Commands::Prime {
+            orb_path,
+            tag_prefix,
+            earliest_version,
+            since,
+            prior_versions_dir,
+            migrations_dir,
+            rename_map,
+            ephemeral,
+            dry_run,
+            git_repo,
+        } = cli.unwrap().command
+        {
+            assert_eq!(orb_path.to_str().unwrap(), "src/@orb.yml");
+            assert_eq!(tag_prefix, "v");
+            assert!(earliest_version.is_none());
+            assert!(since.is_none());
+            assert_eq!(prior_versions_dir.to_str().unwrap(), "prior-versions");
+            assert_eq!(migrations_dir.to_str().unwrap(), "migrations");
+            assert!(rename_map.is_empty());
+            assert!(!ephemeral);
+            assert!(!dry_run);
+            assert!(git_repo.is_none());
+        } else {
+            panic!("expected Prime variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_prime_earliest_version() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "prime", "--earliest-version", "4.1.0"]);
+        assert!(cli.is_ok(), "prime --earliest-version should parse");
+        if let Commands::Prime {
+            earliest_version, ..
+        } = cli.unwrap().command
+        {
+            assert_eq!(earliest_version.as_deref(), Some("4.1.0"));
+        } else {
+            panic!("expected Prime variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_prime_since() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "prime", "--since", "3 months"]);
+        assert!(cli.is_ok(), "prime --since should parse");
+        if let Commands::Prime { since, .. } = cli.unwrap().command {
+            assert_eq!(since.as_deref(), Some("3 months"));
+        } else {
+            panic!("expected Prime variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_prime_exclusive_flags() {
+        // --earliest-version and --since are mutually exclusive
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "prime",
+            "--earliest-version",
+            "4.1.0",
+            "--since",
+            "6 months",
+        ]);
+        assert!(
+            cli.is_err(),
+            "prime with both --earliest-version and --since should be rejected"
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_prime_rename_map() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "prime",
+            "--rename-map",
+            "common_tests_rolling=common_tests",
+            "--rename-map",
+            "required_builds_rolling=required_builds",
+        ]);
+        assert!(cli.is_ok(), "prime --rename-map should parse");
+        if let Commands::Prime { rename_map, .. } = cli.unwrap().command {
+            assert_eq!(rename_map.len(), 2);
+            assert!(rename_map.contains(&"common_tests_rolling=common_tests".to_string()));
+            assert!(rename_map.contains(&"required_builds_rolling=required_builds".to_string()));
+        } else {
+            panic!("expected Prime variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_prime_ephemeral() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "prime", "--ephemeral"]);
+        assert!(cli.is_ok(), "prime --ephemeral should parse");
+        if let Commands::Prime { ephemeral, .. } = cli.unwrap().command {
+            assert!(ephemeral);
+        } else {
+            panic!("expected Prime variant");
+        }
+    }
+
+    // Serialises tests that mutate the global CWD.
+    static CWD_LOCK: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    /// Regression test: `find_git_root` with a *relative* orb path must return
+    /// an **absolute** path.
+    ///
+    /// When the user runs `gen-orb-mcp prime --orb-path src/@orb.yml` (the
+    /// default), `orb_path` is relative.  `find_git_root` walks up from
+    /// `src/@orb.yml` → `src` → `""` (Rust `Path::parent` of `"src"` is `""`).
+    /// If the function returns `""`, `repo_abs` cannot be canonicalised, so
+    /// `strip_prefix("")` on the absolute `orb_abs` returns the full absolute
+    /// path.  `worktree.join(absolute_path)` then ignores the worktree and
+    /// reads the current working copy — producing snapshots with
+    /// current-version content for every historical tag.
+    ///
+    /// The fix: canonicalise `start` at the top of `find_git_root` so the
+    /// walk-up always operates on absolute paths and returns an absolute
+    /// result.
+    #[test]
+    fn test_find_git_root_returns_absolute_path_for_relative_input() {
+        let _cwd_guard = CWD_LOCK.lock().unwrap();
+        let original = std::env::current_dir().unwrap();
+
+        let tmp = TempDir::new().unwrap();
+        std::fs::create_dir_all(tmp.path().join(".git")).unwrap();
+        std::fs::create_dir_all(tmp.path().join("src")).unwrap();
+        std::fs::write(
+            tmp.path().join("src").join("@orb.yml"),
+            "version: 2.1\ndescription: test",
+        )
+        .unwrap();
+
+        // Change to the fake repo root so that "src/@orb.yml" is a valid
+        // relative path.
+        std::env::set_current_dir(tmp.path()).unwrap();
+
+        let result = find_git_root(std::path::Path::new("src/@orb.yml"));
+
+        // Always restore CWD before asserting so a failure doesn't leave the
+        // process in the tmp directory.
+        std::env::set_current_dir(&original).unwrap();
+
+        let result = result.expect("find_git_root should succeed");
+        assert!(
+            result.is_absolute(),
+            "find_git_root must return an absolute path, got: {:?}",
+            result
+        );
+        assert_eq!(
+            result.canonicalize().unwrap(),
+            tmp.path().canonicalize().unwrap(),
+        );
+    }
+
+    // --- Tests for discover_latest_version ---
+
+    #[test]
+    fn test_discover_latest_version_returns_none_for_no_tags() {
+        let tmp = TempDir::new().unwrap();
+        std::process::Command::new("git")
+            .args(["init"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        let result = discover_latest_version(tmp.path(), "v");
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), None);
+    }
+
+    #[test]
+    fn test_discover_latest_version_returns_highest_semver_tag() {
+        let tmp = TempDir::new().unwrap();
+        std::process::Command::new("git")
+            .args(["init"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        std::process::Command::new("git")
+            .args(["config", "user.email", "test@test.com"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        std::process::Command::new("git")
+            .args(["config", "user.name", "Test"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        std::fs::write(tmp.path().join("README.md"), "test").unwrap();
+        std::process::Command::new("git")
+            .args(["add", "."])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        std::process::Command::new("git")
+            .args(["commit", "-m", "init"])
+            .current_dir(tmp.path())
+            .output()
+            .unwrap();
+        for tag in ["v1.0.0", "v2.0.0", "v1.5.0"] {
+            std::process::Command::new("git")
+                .args(["tag", tag])
+                .current_dir(tmp.path())
+                .output()
+                .unwrap();
+        }
+        let result = discover_latest_version(tmp.path(), "v");
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), Some("2.0.0".to_string()));
+    }
+
+    #[test]
+    fn test_resolve_version_uses_git_hint_when_no_explicit_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = resolve_version(temp_dir.path(), None, false, Some("3.1.0"));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "3.1.0");
+    }
+
+    #[test]
+    fn test_resolve_version_explicit_overrides_git_hint() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = resolve_version(temp_dir.path(), Some("5.0.0"), false, Some("3.1.0"));
+        assert!(result.is_ok());
+        assert_eq!(result.unwrap(), "5.0.0");
+    }
+
+    #[test]
+    fn test_resolve_version_errors_without_version_or_hint() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = resolve_version(temp_dir.path(), None, false, None);
+        assert!(result.is_err());
+        let msg = result.unwrap_err().to_string();
+        assert!(msg.contains("No version could be determined"), "got: {msg}");
+    }
+
+    #[test]
+    fn test_cli_parse_generate_with_tag_prefix() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            "test.yml",
+            "--output",
+            "./out",
+            "--tag-prefix",
+            "orb-v",
+        ]);
+        assert!(cli.is_ok(), "generate --tag-prefix should parse");
+        if let Commands::Generate { tag_prefix, .. } = cli.unwrap().command {
+            assert_eq!(tag_prefix, "orb-v");
+        } else {
+            panic!("expected Generate variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_tag_prefix_defaults_to_v() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            "test.yml",
+            "--output",
+            "./out",
+        ]);
+        assert!(cli.is_ok());
+        if let Commands::Generate { tag_prefix, .. } = cli.unwrap().command {
+            assert_eq!(tag_prefix, "v");
+        } else {
+            panic!("expected Generate variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_with_server_info() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--server-title",
+            "Acme CI Toolkit",
+            "--server-instructions",
+            "Use the toolkit resources to answer CI questions.",
+        ])
+        .unwrap();
+        if let Commands::Generate {
+            server_title,
+            server_instructions,
+            ..
+        } = cli.command
+        {
+            assert_eq!(server_title.as_deref(), Some("Acme CI Toolkit"));
+            assert_eq!(
+                server_instructions.as_deref(),
+                Some("Use the toolkit resources to answer CI questions.")
+            );
+        } else {
+            panic!("expected Generate variant");
+        }
+    }
+
+    #[test]
+    fn test_cli_parse_generate_with_previous_orb() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--previous-orb", "4.7.1.yml"])
+            .unwrap();
+        if let Commands::Generate { previous_orb, .. } = cli.command {
+            assert_eq!(previous_orb.unwrap().to_str().unwrap(), "4.7.1.yml");
+        } else {
+            panic!("expected Generate variant");
+        }
+    }
+
+    // --- save subcommand tests ---
+
+    #[test]
+    fn test_detect_source_rev_reads_head_commit() {
+        let dir = TempDir::new().unwrap();
+        init_git_repo(dir.path());
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(dir.path().join("src/@orb.yml"), "version: 2.1\n").unwrap();
+
+        let head = std::process::Command::new("git")
+            .args(["rev-parse", "HEAD"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        let head = String::from_utf8(head.stdout).unwrap();
+
+        assert_eq!(
+            detect_source_rev(&dir.path().join("src/@orb.yml")).as_deref(),
+            Some(head.trim())
+        );
+    }
+
+    fn init_git_repo(dir: &std::path::Path) {
+        std::process::Command::new("git")
+            .args(["init"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        std::process::Command::new("git")
+            .args(["config", "user.email", "test@test.com"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
         std::process::Command::new("git")
             .args(["config", "user.name", "Test"])
             .current_dir(dir)
             .output()
             .unwrap();
-        // Initial commit so HEAD exists
-        std::fs::write(dir.join("README.md"), "test").unwrap();
-        std::process::Command::new("git")
-            .args(["add", "."])
-            .current_dir(dir)
+        // Initial commit so HEAD exists
+        std::fs::write(dir.join("README.md"), "test").unwrap();
+        std::process::Command::new("git")
+            .args(["add", "."])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+        std::process::Command::new("git")
+            .args(["commit", "-m", "init"])
+            .current_dir(dir)
+            .output()
+            .unwrap();
+    }
+
+    #[test]
+    fn test_save_clean_tree_exits_without_commit() {
+        let dir = TempDir::new().unwrap();
+        init_git_repo(dir.path());
+        let _cwd_guard = CWD_LOCK.lock().unwrap();
+        let original = std::env::current_dir().unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        // Stage the path we already committed — tree is clean after staging
+        let result = run_save(
+            &[std::path::PathBuf::from("README.md")],
+            "chore: test",
+            false,
+            false,
+            false,
+            std::path::Path::new("gen-orb-mcp.toml"),
+            &SignEnvNameOverrides::default(),
+        );
+        std::env::set_current_dir(&original).unwrap();
+        assert!(
+            result.is_ok(),
+            "clean tree should exit 0 without creating a commit: {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_save_changed_path_creates_commit() {
+        let dir = TempDir::new().unwrap();
+        init_git_repo(dir.path());
+        std::fs::write(dir.path().join("new-file.txt"), "hello").unwrap();
+        let _cwd_guard = CWD_LOCK.lock().unwrap();
+        let original = std::env::current_dir().unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let result = run_save(
+            &[std::path::PathBuf::from("new-file.txt")],
+            "chore: add generated file",
+            false,
+            false,
+            false,
+            std::path::Path::new("gen-orb-mcp.toml"),
+            &SignEnvNameOverrides::default(),
+        );
+        std::env::set_current_dir(&original).unwrap();
+        assert!(
+            result.is_ok(),
+            "changed path should commit successfully: {result:?}"
+        );
+        // Verify a commit was created beyond the initial one
+        let log = std::process::Command::new("git")
+            .args(["log", "--oneline"])
+            .current_dir(dir.path())
+            .output()
+            .unwrap();
+        let log_str = String::from_utf8_lossy(&log.stdout);
+        assert!(
+            log_str.lines().count() >= 2,
+            "expected at least 2 commits, got: {log_str}"
+        );
+    }
+
+    #[test]
+    fn test_save_directory_path_stages_contents() {
+        let dir = TempDir::new().unwrap();
+        init_git_repo(dir.path());
+        // Create a directory with files inside — mirrors the prior-versions/ and
+        // migrations/ case
+        let subdir = dir.path().join("generated");
+        std::fs::create_dir(&subdir).unwrap();
+        std::fs::write(subdir.join("a.json"), r#"{"v": 1}"#).unwrap();
+        std::fs::write(subdir.join("b.json"), r#"{"v": 2}"#).unwrap();
+        let _cwd_guard = CWD_LOCK.lock().unwrap();
+        let original = std::env::current_dir().unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let result = run_save(
+            &[std::path::PathBuf::from("generated")],
+            "chore: add generated dir",
+            false,
+            false,
+            false,
+            std::path::Path::new("gen-orb-mcp.toml"),
+            &SignEnvNameOverrides::default(),
+        );
+        std::env::set_current_dir(&original).unwrap();
+        assert!(
+            result.is_ok(),
+            "directory path should stage all contents and commit: {result:?}"
+        );
+        let log = std::process::Command::new("git")
+            .args(["log", "--oneline"])
+            .current_dir(dir.path())
             .output()
             .unwrap();
-        std::process::Command::new("git")
-            .args(["commit", "-m", "init"])
-            .current_dir(dir)
+        let log_str = String::from_utf8_lossy(&log.stdout);
+        assert!(
+            log_str.lines().count() >= 2,
+            "expected at least 2 commits after staging directory, got: {log_str}"
+        );
+    }
+
+    #[test]
+    fn test_save_dry_run_does_not_commit() {
+        let dir = TempDir::new().unwrap();
+        init_git_repo(dir.path());
+        std::fs::write(dir.path().join("artifact.txt"), "generated").unwrap();
+        let _cwd_guard = CWD_LOCK.lock().unwrap();
+        let original = std::env::current_dir().unwrap();
+        std::env::set_current_dir(dir.path()).unwrap();
+        let result = run_save(
+            &[std::path::PathBuf::from("artifact.txt")],
+            "chore: generated",
+            false,
+            true,
+            false,
+            std::path::Path::new("gen-orb-mcp.toml"),
+            &SignEnvNameOverrides::default(),
+        );
+        std::env::set_current_dir(&original).unwrap();
+        assert!(result.is_ok(), "dry_run should succeed: {result:?}");
+        // Only the initial commit should exist
+        let log = std::process::Command::new("git")
+            .args(["log", "--oneline"])
+            .current_dir(dir.path())
             .output()
             .unwrap();
+        let log_str = String::from_utf8_lossy(&log.stdout);
+        assert_eq!(
+            log_str.lines().count(),
+            1,
+            "dry_run must not create a commit, got: {log_str}"
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_save_required_paths() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "save",
+            "--paths",
+            "prior-versions",
+            "--paths",
+            "migrations",
+        ]);
+        assert!(cli.is_ok(), "save with --paths should parse");
+    }
+
+    #[test]
+    fn test_cli_parse_save_sign_flag() {
+        let cli =
+            Cli::try_parse_from(["gen-orb-mcp", "save", "--paths", "prior-versions", "--sign"]);
+        assert!(
+            cli.is_ok(),
+            "--sign flag should be accepted on save command"
+        );
+        if let Commands::Save { sign, .. } = cli.unwrap().command {
+            assert!(sign, "--sign should be true when flag is passed");
+        } else {
+            panic!("expected Save variant");
+        }
+    }
+
+    #[test]
+    fn read_sign_env_missing_var_errors_with_resolved_name() {
+        // Use a unique, definitely-absent var name so this is parallel-safe and
+        // independent of the ambient environment.
+        let names = SignEnvNames {
+            gpg_key: "T185_MISSING_GPG_KEY".to_string(),
+            trust: "T185_MISSING_TRUST".to_string(),
+            user_name: "T185_MISSING_UN".to_string(),
+            user_email: "T185_MISSING_UE".to_string(),
+            sign_key: "T185_MISSING_SK".to_string(),
+        };
+        for k in [
+            "T185_MISSING_GPG_KEY",
+            "T185_MISSING_TRUST",
+            "T185_MISSING_UN",
+            "T185_MISSING_UE",
+            "T185_MISSING_SK",
+        ] {
+            std::env::remove_var(k);
+        }
+        let result = read_sign_env(&names);
+        assert!(
+            result.is_err(),
+            "should fail when the resolved var is absent"
+        );
+        let msg = result.unwrap_err().to_string();
+        assert!(
+            msg.contains("T185_MISSING_GPG_KEY"),
+            "error should mention the resolved var name, got: {msg}"
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_save_all_flags() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "save",
+            "--paths",
+            "prior-versions",
+            "--message",
+            "custom message",
+            "--no-push",
+            "--dry-run",
+        ]);
+        assert!(cli.is_ok(), "save with all flags should parse");
+        if let Commands::Save {
+            paths,
+            message,
+            no_push,
+            dry_run,
+            ..
+        } = cli.unwrap().command
+        {
+            assert_eq!(paths, vec![std::path::PathBuf::from("prior-versions")]);
+            assert_eq!(message, "custom message");
+            assert!(no_push);
+            assert!(dry_run);
+        } else {
+            panic!("expected Save variant");
+        }
+    }
+
+    // --- publish subcommand tests ---
+
+    #[test]
+    fn test_publish_missing_binary_returns_error() {
+        let dir = TempDir::new().unwrap();
+        let result = PublishJob {
+            name: None,
+            input: std::path::Path::new("."),
+            binary: Some(&dir.path().join("missing-binary")),
+            asset_name: Some("asset.tar.gz"),
+            tag: None,
+            dry_run: false,
+            config_path: std::path::Path::new("no-such-config-185.toml"),
+            tag_env_override: None,
+        }
+        .run();
+        assert!(result.is_err());
+        let msg = result.unwrap_err().to_string();
+        assert!(
+            msg.contains("Binary not found"),
+            "error should mention missing binary, got: {msg}"
+        );
+    }
+
+    #[test]
+    fn test_publish_dry_run_succeeds_without_token() {
+        let dir = TempDir::new().unwrap();
+        let binary = dir.path().join("my-binary");
+        std::fs::write(&binary, b"fake binary").unwrap();
+        // dry_run must succeed without credentials — no API call is made
+        std::env::remove_var("GITHUB_TOKEN");
+        let result = PublishJob {
+            name: None,
+            input: std::path::Path::new("."),
+            binary: Some(&binary),
+            asset_name: Some("my-asset"),
+            tag: Some("v1.0.0"),
+            dry_run: true,
+            config_path: std::path::Path::new("no-such-config-185.toml"),
+            tag_env_override: None,
+        }
+        .run();
+        assert!(
+            result.is_ok(),
+            "dry_run should not require credentials: {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_publish_dry_run_missing_tag_returns_error() {
+        let dir = TempDir::new().unwrap();
+        let binary = dir.path().join("my-binary");
+        std::fs::write(&binary, b"fake binary").unwrap();
+        std::env::set_var("GITHUB_TOKEN", "fake-token");
+        std::env::remove_var("CIRCLE_TAG");
+        // no --tag and no CIRCLE_TAG — should fail with a clear message
+        let result = PublishJob {
+            name: None,
+            input: std::path::Path::new("."),
+            binary: Some(&binary),
+            asset_name: Some("my-asset"),
+            tag: None,
+            dry_run: true,
+            config_path: std::path::Path::new("no-such-config-185.toml"),
+            tag_env_override: None,
+        }
+        .run();
+        std::env::remove_var("GITHUB_TOKEN");
+        assert!(result.is_err());
+        let msg = result.unwrap_err().to_string();
+        assert!(
+            msg.contains("tag") || msg.contains("CIRCLE_TAG"),
+            "error should mention tag or CIRCLE_TAG, got: {msg}"
+        );
     }
 
     #[test]
-    fn test_save_clean_tree_exits_without_commit() {
+    fn test_publish_dry_run_prints_parameters() {
         let dir = TempDir::new().unwrap();
-        init_git_repo(dir.path());
-        let _cwd_guard = CWD_LOCK.lock().unwrap();
-        let original = std::env::current_dir().unwrap();
-        std::env::set_current_dir(dir.path()).unwrap();
-        // Stage the path we already committed — tree is clean after staging
-        let result = run_save(
-            &[std::path::PathBuf::from("README.md")],
-            "chore: test",
+        let binary = dir.path().join("my-binary");
+        std::fs::write(&binary, b"fake binary").unwrap();
+        std::env::set_var("GITHUB_TOKEN", "fake-token");
+        std::env::set_var("CIRCLE_PROJECT_USERNAME", "jerus-org");
+        std::env::set_var("CIRCLE_PROJECT_REPONAME", "my-orb");
+        let result = PublishJob {
+            name: None,
+            input: std::path::Path::new("."),
+            binary: Some(&binary),
+            asset_name: Some("my-asset-linux-x86_64"),
+            tag: Some("v1.0.0"),
+            dry_run: true,
+            config_path: std::path::Path::new("no-such-config-185.toml"),
+            tag_env_override: None,
+        }
+        .run();
+        std::env::remove_var("GITHUB_TOKEN");
+        std::env::remove_var("CIRCLE_PROJECT_USERNAME");
+        std::env::remove_var("CIRCLE_PROJECT_REPONAME");
+        assert!(
+            result.is_ok(),
+            "dry_run with all params should succeed: {result:?}"
+        );
+    }
+
+    #[test]
+    fn test_cli_parse_publish_required_args() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "publish",
+            "--binary",
+            "/tmp/my-binary",
+            "--asset-name",
+            "my-binary-linux-x86_64",
+        ]);
+        assert!(cli.is_ok(), "publish with required args should parse");
+    }
+
+    #[test]
+    fn test_cli_parse_publish_all_flags() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "publish",
+            "--binary",
+            "/tmp/my-binary",
+            "--asset-name",
+            "my-binary-linux-x86_64",
+            "--tag",
+            "v2.0.0",
+            "--dry-run",
+        ]);
+        assert!(cli.is_ok(), "publish with all flags should parse");
+        if let Commands::Publish {
+            binary,
+            asset_name,
+            tag,
+            dry_run,
+            ..
+        } = cli.unwrap().command
+        {
+            assert_eq!(
+                binary.as_deref().and_then(|p| p.to_str()),
+                Some("/tmp/my-binary")
+            );
+            assert_eq!(asset_name.as_deref(), Some("my-binary-linux-x86_64"));
+            assert_eq!(tag.as_deref(), Some("v2.0.0"));
+            assert!(dry_run);
+        } else {
+            panic!("expected Publish variant");
+        }
+    }
+
+    /// Serialises the tests that set `CARGO_TARGET_DIR` with the tests that
+    /// read it.
+    static CARGO_TARGET_DIR_ENV: std::sync::Mutex<()> = std::sync::Mutex::new(());
+
+    #[test]
+    fn test_resolve_publish_target_derives_from_name() {
+        let _env = CARGO_TARGET_DIR_ENV.lock().unwrap();
+        let saved = std::env::var_os("CARGO_TARGET_DIR");
+        std::env::remove_var("CARGO_TARGET_DIR");
+        let result = resolve_publish_target(
+            Some("gen-orb-mcp"),
+            std::path::Path::new("/tmp/mcp-server"),
+            None,
+            None,
+        );
+        if let Some(dir) = saved {
+            std::env::set_var("CARGO_TARGET_DIR", dir);
+        }
+        let (binary, asset) = result.expect("derivation from name should succeed");
+        assert_eq!(
+            binary,
+            std::path::PathBuf::from(format!(
+                "/tmp/mcp-server/target/release/gen_orb_mcp_mcp{}",
+                std::env::consts::EXE_SUFFIX
+            ))
+        );
+        assert_eq!(asset, format!("gen_orb_mcp_mcp-{}", host_asset_suffix()));
+        #[cfg(all(target_os = "linux", target_arch = "x86_64"))]
+        assert_eq!(asset, "gen_orb_mcp_mcp-linux-x86_64");
+    }
+
+    #[test]
+    fn test_validate_reuses_parse_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ndescription: Toolkit\ncommands:\n  greet:\n    steps: []\n",
+        )
+        .unwrap();
+        let cache_dir = temp_dir.path().join("cache");
+        let cache = parse_cache::ParseCache::new(&cache_dir);
+        let entries = || std::fs::read_dir(&cache_dir).unwrap().count();
+
+        assert!(run_validate(
+            &orb_path,
             false,
             false,
+            Some(&cache),
+            None,
+            ValidateFormat::Text
+        )
+        .is_ok());
+        assert_eq!(entries(), 1);
+        // Lint still runs on a cached parse.
+        let err = run_validate(
+            &orb_path,
+            true,
             false,
-            std::path::Path::new("gen-orb-mcp.toml"),
-            &SignEnvNameOverrides::default(),
-        );
-        std::env::set_current_dir(&original).unwrap();
+            Some(&cache),
+            None,
+            ValidateFormat::Text,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("1 lint warning(s)"), "{err}");
+        assert_eq!(entries(), 1);
+        // Strict parses are cached apart.
+        assert!(run_validate(
+            &orb_path,
+            false,
+            true,
+            Some(&cache),
+            None,
+            ValidateFormat::Text
+        )
+        .is_ok());
+        assert_eq!(entries(), 2);
+
+        std::fs::write(&orb_path, "version: 2.1\ncommands: [\n").unwrap();
+        assert!(run_validate(
+            &orb_path,
+            false,
+            false,
+            Some(&cache),
+            None,
+            ValidateFormat::Text
+        )
+        .is_err());
+        assert_eq!(entries(), 2);
+    }
+
+    #[test]
+    fn test_validate_fail_on_warnings() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ndescription: Toolkit\ncommands:\n  greet:\n    steps: []\n",
+        )
+        .unwrap();
+
+        assert!(run_validate(&orb_path, false, false, None, None, ValidateFormat::Text).is_ok());
+        let err = run_validate(&orb_path, true, false, None, None, ValidateFormat::Text)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("1 lint warning(s)"), "{err}");
         assert!(
-            result.is_ok(),
-            "clean tree should exit 0 without creating a commit: {result:?}"
+            err.contains("commands/greet: command has no description"),
+            "{err}"
         );
     }
 
     #[test]
-    fn test_save_changed_path_creates_commit() {
-        let dir = TempDir::new().unwrap();
-        init_git_repo(dir.path());
-        std::fs::write(dir.path().join("new-file.txt"), "hello").unwrap();
-        let _cwd_guard = CWD_LOCK.lock().unwrap();
-        let original = std::env::current_dir().unwrap();
-        std::env::set_current_dir(dir.path()).unwrap();
-        let result = run_save(
-            &[std::path::PathBuf::from("new-file.txt")],
-            "chore: add generated file",
+    fn test_validate_lint_config_and_inline_allow() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\n\
+             description: Toolkit\n\
+             commands:\n  \
+               greet:\n    steps: []\n  \
+               # gen-orb-mcp: allow(missing-description)\n  \
+               legacy:\n    steps: []\n",
+        )
+        .unwrap();
+        let config_path = temp_dir.path().join(".gen-orb-mcp-lint.toml");
+        std::fs::write(&config_path, "[rules]\nmissing-description = \"error\"\n").unwrap();
+
+        let err = run_validate(
+            &orb_path,
             false,
             false,
+            None,
+            Some(&config_path),
+            ValidateFormat::Text,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(err.contains("1 lint error(s)"), "{err}");
+        assert!(err.contains("✗ commands/greet"), "{err}");
+        assert!(!err.contains("commands/legacy"), "{err}");
+
+        std::fs::write(&config_path, "[rules]\nmissing-description = \"off\"\n").unwrap();
+        assert!(run_validate(
+            &orb_path,
+            true,
             false,
-            std::path::Path::new("gen-orb-mcp.toml"),
-            &SignEnvNameOverrides::default(),
-        );
-        std::env::set_current_dir(&original).unwrap();
+            None,
+            Some(&config_path),
+            ValidateFormat::Text
+        )
+        .is_ok());
+    }
+
+    #[test]
+    fn test_validate_reports_unresolved_references() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\n\
+             description: Toolkit\n\
+             commands:\n  \
+               greet:\n    description: Greet\n    steps: []\n\
+             jobs:\n  \
+               hello:\n    description: Hello\n    steps:\n      - gret\n",
+        )
+        .unwrap();
+
+        let err =
+            run_validate(&orb_path, false, false, None, None, ValidateFormat::Text).unwrap_err();
+        assert_eq!(failure::exit_code(&err), 4);
+        let err = err.to_string();
+        assert!(err.contains("1 unresolved reference(s)"), "{err}");
         assert!(
-            result.is_ok(),
-            "changed path should commit successfully: {result:?}"
+            err.contains("✗ jobs/hello/steps: unknown command 'gret', did you mean 'greet'?"),
+            "{err}"
         );
-        // Verify a commit was created beyond the initial one
-        let log = std::process::Command::new("git")
-            .args(["log", "--oneline"])
-            .current_dir(dir.path())
-            .output()
-            .unwrap();
-        let log_str = String::from_utf8_lossy(&log.stdout);
+        assert!(run_validate(&orb_path, false, false, None, None, ValidateFormat::Sarif).is_err());
+    }
+
+    #[test]
+    fn test_validate_reports_command_invocation_cycle() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\n\
+             description: Toolkit\n\
+             commands:\n  \
+               build:\n    description: Build\n    steps:\n      - test\n  \
+               test:\n    description: Test\n    steps:\n      - build\n",
+        )
+        .unwrap();
+
+        let err =
+            run_validate(&orb_path, false, false, None, None, ValidateFormat::Text).unwrap_err();
+        assert_eq!(failure::exit_code(&err), 4);
+        let err = err.to_string();
         assert!(
-            log_str.lines().count() >= 2,
-            "expected at least 2 commits, got: {log_str}"
+            err.contains(
+                "✗ commands/build/steps: command invocation cycle: build -> test -> build"
+            ),
+            "{err}"
         );
     }
 
     #[test]
-    fn test_save_directory_path_stages_contents() {
-        let dir = TempDir::new().unwrap();
-        init_git_repo(dir.path());
-        // Create a directory with files inside — mirrors the prior-versions/ and
-        // migrations/ case
-        let subdir = dir.path().join("generated");
-        std::fs::create_dir(&subdir).unwrap();
-        std::fs::write(subdir.join("a.json"), r#"{"v": 1}"#).unwrap();
-        std::fs::write(subdir.join("b.json"), r#"{"v": 2}"#).unwrap();
-        let _cwd_guard = CWD_LOCK.lock().unwrap();
-        let original = std::env::current_dir().unwrap();
-        std::env::set_current_dir(dir.path()).unwrap();
-        let result = run_save(
-            &[std::path::PathBuf::from("generated")],
-            "chore: add generated dir",
-            false,
+    fn test_validate_reports_unknown_executor_with_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("jobs")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("@orb.yml"),
+            "version: 2.1\ndescription: Toolkit\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("jobs/build.yml"),
+            "description: Build\nexecutor: rust\nsteps:\n  - checkout\n",
+        )
+        .unwrap();
+
+        let err = run_validate(
+            &temp_dir.path().join("@orb.yml"),
             false,
             false,
-            std::path::Path::new("gen-orb-mcp.toml"),
-            &SignEnvNameOverrides::default(),
-        );
-        std::env::set_current_dir(&original).unwrap();
+            None,
+            None,
+            ValidateFormat::Text,
+        )
+        .unwrap_err()
+        .to_string();
+        let file = temp_dir.path().join("jobs/build.yml");
         assert!(
-            result.is_ok(),
-            "directory path should stage all contents and commit: {result:?}"
+            err.contains(&format!(
+                "✗ jobs/build/executor: unknown executor 'rust' ({}:2)",
+                file.display()
+            )),
+            "{err}"
         );
-        let log = std::process::Command::new("git")
-            .args(["log", "--oneline"])
-            .current_dir(dir.path())
-            .output()
-            .unwrap();
-        let log_str = String::from_utf8_lossy(&log.stdout);
+    }
+
+    #[test]
+    fn test_validate_strict_reports_unknown_keys_with_file() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("commands")).unwrap();
+        std::fs::write(
+            temp_dir.path().join("@orb.yml"),
+            "version: 2.1\ndescription: Toolkit\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("commands/greet.yml"),
+            "decription: Greet\nsteps:\n  - checkout\n",
+        )
+        .unwrap();
+        let orb_path = temp_dir.path().join("@orb.yml");
+
+        assert!(run_validate(&orb_path, false, false, None, None, ValidateFormat::Text).is_ok());
+        let err =
+            run_validate(&orb_path, false, true, None, None, ValidateFormat::Text).unwrap_err();
+        assert_eq!(failure::exit_code(&err), 3, "{err}");
+        let file = temp_dir.path().join("commands/greet.yml");
         assert!(
-            log_str.lines().count() >= 2,
-            "expected at least 2 commits after staging directory, got: {log_str}"
+            err.to_string().contains(&format!(
+                "✗ commands/greet: unknown key 'decription', did you mean 'description'? ({}:1)",
+                file.display()
+            )),
+            "{err}"
         );
+        assert!(run_validate(&orb_path, false, true, None, None, ValidateFormat::Sarif).is_err());
     }
 
     #[test]
-    fn test_save_dry_run_does_not_commit() {
-        let dir = TempDir::new().unwrap();
-        init_git_repo(dir.path());
-        std::fs::write(dir.path().join("artifact.txt"), "generated").unwrap();
-        let _cwd_guard = CWD_LOCK.lock().unwrap();
-        let original = std::env::current_dir().unwrap();
-        std::env::set_current_dir(dir.path()).unwrap();
-        let result = run_save(
-            &[std::path::PathBuf::from("artifact.txt")],
-            "chore: generated",
+    fn test_validate_reports_every_parse_error() {
+        let temp_dir = TempDir::new().unwrap();
+        for dir in ["commands", "jobs"] {
+            std::fs::create_dir_all(temp_dir.path().join(dir)).unwrap();
+        }
+        std::fs::write(
+            temp_dir.path().join("@orb.yml"),
+            "version: 2.1\ndescription: Toolkit\n",
+        )
+        .unwrap();
+        std::fs::write(
+            temp_dir.path().join("commands/greet.yml"),
+            "steps: not-a-list\n",
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("jobs/build.yml"), "steps: [\n").unwrap();
+
+        let err = run_validate(
+            &temp_dir.path().join("@orb.yml"),
             false,
-            true,
             false,
-            std::path::Path::new("gen-orb-mcp.toml"),
-            &SignEnvNameOverrides::default(),
-        );
-        std::env::set_current_dir(&original).unwrap();
-        assert!(result.is_ok(), "dry_run should succeed: {result:?}");
-        // Only the initial commit should exist
-        let log = std::process::Command::new("git")
-            .args(["log", "--oneline"])
-            .current_dir(dir.path())
-            .output()
-            .unwrap();
-        let log_str = String::from_utf8_lossy(&log.stdout);
-        assert_eq!(
-            log_str.lines().count(),
-            1,
-            "dry_run must not create a commit, got: {log_str}"
+            None,
+            None,
+            ValidateFormat::Text,
+        )
+        .unwrap_err()
+        .to_string();
+        assert!(
+            err.starts_with("Orb validation failed: 2 parse error(s):\n"),
+            "{err}"
         );
+        for file in ["commands/greet.yml", "jobs/build.yml"] {
+            let path = temp_dir.path().join(file);
+            assert!(err.contains(&format!("    ✗ {}", path.display())), "{err}");
+        }
     }
 
     #[test]
-    fn test_cli_parse_save_required_paths() {
+    fn test_validate_sarif_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ndescription: Toolkit\ncommands:\n  greet:\n    steps: []\n",
+        )
+        .unwrap();
+
+        assert!(run_validate(&orb_path, false, false, None, None, ValidateFormat::Sarif).is_ok());
+        let err = run_validate(&orb_path, true, false, None, None, ValidateFormat::Sarif)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("1 lint warning(s)"), "{err}");
+
+        std::fs::write(&orb_path, "version: [").unwrap();
+        assert!(run_validate(&orb_path, false, false, None, None, ValidateFormat::Sarif).is_err());
+
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "validate", "--format", "sarif"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Validate {
+                format: ValidateFormat::Sarif,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_lint_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\n\
+             description: Toolkit\n\
+             executors:\n  \
+               default:\n    description: Default\n    docker:\n      - image: cimg/base\n",
+        )
+        .unwrap();
+
         let cli = Cli::try_parse_from([
             "gen-orb-mcp",
-            "save",
-            "--paths",
-            "prior-versions",
-            "--paths",
-            "migrations",
-        ]);
-        assert!(cli.is_ok(), "save with --paths should parse");
+            "lint",
+            "-p",
+            orb_path.to_str().unwrap(),
+            "--fail-on-warnings",
+        ])
+        .unwrap();
+        let err = cli.run().unwrap_err().to_string();
+        assert_eq!(err, "Lint failed: 1 warning(s) (--fail-on-warnings)");
+
+        let config_path = temp_dir.path().join("lint.toml");
+        std::fs::write(&config_path, "[rules]\nunpinned-image = \"error\"\n").unwrap();
+        let err = run_lint(&orb_path, false, Some(&config_path), ValidateFormat::Text)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "Lint failed: 1 error(s)");
+
+        std::fs::write(&config_path, "[rules]\nunpinned-image = \"off\"\n").unwrap();
+        assert!(run_lint(&orb_path, true, Some(&config_path), ValidateFormat::Sarif).is_ok());
     }
 
     #[test]
-    fn test_cli_parse_save_sign_flag() {
-        let cli =
-            Cli::try_parse_from(["gen-orb-mcp", "save", "--paths", "prior-versions", "--sign"]);
-        assert!(
-            cli.is_ok(),
-            "--sign flag should be accepted on save command"
-        );
-        if let Commands::Save { sign, .. } = cli.unwrap().command {
-            assert!(sign, "--sign should be true when flag is passed");
-        } else {
-            panic!("expected Save variant");
+    fn test_output_format_aliases_format() {
+        for command in ["validate", "lint"] {
+            let cli =
+                Cli::try_parse_from(["gen-orb-mcp", command, "--output-format", "sarif"]).unwrap();
+            let format = match cli.command {
+                Commands::Validate { format, .. } | Commands::Lint { format, .. } => format,
+                _ => panic!("Expected {command} command"),
+            };
+            assert_eq!(format, ValidateFormat::Sarif);
         }
+
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(&orb_path, "version: [").unwrap();
+        let err = run_lint(&orb_path, false, None, ValidateFormat::Sarif)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(err, "Lint failed: 1 parse error(s)");
     }
 
     #[test]
-    fn read_sign_env_missing_var_errors_with_resolved_name() {
-        // Use a unique, definitely-absent var name so this is parallel-safe and
-        // independent of the ambient environment.
-        let names = SignEnvNames {
-            gpg_key: "T185_MISSING_GPG_KEY".to_string(),
-            trust: "T185_MISSING_TRUST".to_string(),
-            user_name: "T185_MISSING_UN".to_string(),
-            user_email: "T185_MISSING_UE".to_string(),
-            sign_key: "T185_MISSING_SK".to_string(),
-        };
-        for k in [
-            "T185_MISSING_GPG_KEY",
-            "T185_MISSING_TRUST",
-            "T185_MISSING_UN",
-            "T185_MISSING_UE",
-            "T185_MISSING_SK",
-        ] {
-            std::env::remove_var(k);
-        }
-        let result = read_sign_env(&names);
-        assert!(
-            result.is_err(),
-            "should fail when the resolved var is absent"
-        );
-        let msg = result.unwrap_err().to_string();
-        assert!(
-            msg.contains("T185_MISSING_GPG_KEY"),
-            "error should mention the resolved var name, got: {msg}"
-        );
+    fn test_stats_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ncommands:\n  greet:\n    steps:\n      - run: echo hi\n",
+        )
+        .unwrap();
+
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "stats",
+            "--orb-path",
+            orb_path.to_str().unwrap(),
+            "--json",
+        ])
+        .unwrap();
+        assert!(matches!(cli.command, Commands::Stats { json: true, .. }));
+        assert!(cli.run().is_ok());
+        assert!(run_stats(&temp_dir.path().join("missing.yml"), false).is_err());
     }
 
     #[test]
-    fn test_cli_parse_save_all_flags() {
+    fn test_docs_command_writes_pages() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ncommands:\n  greet:\n    steps:\n      - run: echo hi\n",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("docs");
+
         let cli = Cli::try_parse_from([
             "gen-orb-mcp",
-            "save",
-            "--paths",
-            "prior-versions",
-            "--message",
-            "custom message",
-            "--no-push",
-            "--dry-run",
-        ]);
-        assert!(cli.is_ok(), "save with all flags should parse");
-        if let Commands::Save {
-            paths,
-            message,
-            no_push,
-            dry_run,
-            ..
-        } = cli.unwrap().command
-        {
-            assert_eq!(paths, vec![std::path::PathBuf::from("prior-versions")]);
-            assert_eq!(message, "custom message");
-            assert!(no_push);
-            assert!(dry_run);
-        } else {
-            panic!("expected Save variant");
-        }
+            "docs",
+            "--orb-path",
+            orb_path.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--name",
+            "toolkit",
+        ])
+        .unwrap();
+        assert!(cli.run().is_ok());
+        let page = std::fs::read_to_string(output.join("commands/greet.md")).unwrap();
+        assert!(page.starts_with("# toolkit/greet\n"));
+        assert!(output.join("README.md").is_file());
     }
 
-    // --- publish subcommand tests ---
+    #[test]
+    fn test_inspect_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ncommands:\n  greet:\n    parameters:\n      name:\n        type: string\n    steps:\n      - run: echo << parameters.name >>\n",
+        )
+        .unwrap();
+
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "inspect",
+            "--orb-path",
+            orb_path.to_str().unwrap(),
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Inspect {
+                format: InspectFormat::Json,
+                ..
+            }
+        ));
+        assert!(cli.run().is_ok());
+
+        let orb = OrbParser::parse(&orb_path).unwrap();
+        let json: serde_json::Value =
+            serde_json::from_str(&render_inspect(&orb, InspectFormat::Json).unwrap()).unwrap();
+        assert_eq!(
+            json["commands"]["greet"]["parameters"]["name"]["type"],
+            "string"
+        );
+        assert_eq!(
+            json["commands"]["greet"]["steps"][0]["run"],
+            "echo << parameters.name >>"
+        );
+
+        let yaml = render_inspect(&orb, InspectFormat::Yaml).unwrap();
+        let reparsed = OrbParser::parse_packed_content(&yaml, &orb_path).unwrap();
+        assert!(reparsed.commands.contains_key("greet"));
+    }
 
     #[test]
-    fn test_publish_missing_binary_returns_error() {
-        let dir = TempDir::new().unwrap();
-        let result = PublishJob {
-            name: None,
-            input: std::path::Path::new("."),
-            binary: Some(&dir.path().join("missing-binary")),
-            asset_name: Some("asset.tar.gz"),
-            tag: None,
-            dry_run: false,
-            config_path: std::path::Path::new("no-such-config-185.toml"),
-            tag_env_override: None,
-        }
-        .run();
-        assert!(result.is_err());
-        let msg = result.unwrap_err().to_string();
+    fn test_graph_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ncommands:\n  greet:\n    steps:\n      - run: echo hi\njobs:\n  hello:\n    executor: base\n    steps:\n      - greet\nexecutors:\n  base:\n    docker:\n      - image: cimg/base:current\n",
+        )
+        .unwrap();
+
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "graph",
+            "--orb-path",
+            orb_path.to_str().unwrap(),
+            "--format",
+            "mermaid",
+        ])
+        .unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Graph {
+                format: GraphFormat::Mermaid,
+                ..
+            }
+        ));
+        assert!(cli.run().is_ok());
+
+        let orb = OrbParser::parse(&orb_path).unwrap();
+        let dot = render_graph(&orb, GraphFormat::Dot).unwrap();
         assert!(
-            msg.contains("Binary not found"),
-            "error should mention missing binary, got: {msg}"
+            dot.contains("\"jobs/hello\" -> \"commands/greet\";"),
+            "{dot}"
+        );
+        assert!(
+            dot.contains("\"jobs/hello\" -> \"executors/base\" [style=dashed];"),
+            "{dot}"
         );
+        let json: serde_json::Value =
+            serde_json::from_str(&render_graph(&orb, GraphFormat::Json).unwrap()).unwrap();
+        assert_eq!(json["nodes"].as_array().unwrap().len(), 3);
+        assert_eq!(json["edges"][0]["kind"], "invokes");
     }
 
     #[test]
-    fn test_publish_dry_run_succeeds_without_token() {
-        let dir = TempDir::new().unwrap();
-        let binary = dir.path().join("my-binary");
-        std::fs::write(&binary, b"fake binary").unwrap();
-        // dry_run must succeed without credentials — no API call is made
-        std::env::remove_var("GITHUB_TOKEN");
-        let result = PublishJob {
-            name: None,
-            input: std::path::Path::new("."),
-            binary: Some(&binary),
-            asset_name: Some("my-asset"),
-            tag: Some("v1.0.0"),
-            dry_run: true,
-            config_path: std::path::Path::new("no-such-config-185.toml"),
-            tag_env_override: None,
+    fn test_pack_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_dir = temp_dir.path().join("src");
+        std::fs::create_dir_all(orb_dir.join("commands")).unwrap();
+        std::fs::write(
+            orb_dir.join("@orb.yml"),
+            "version: 2.1
+",
+        )
+        .unwrap();
+        std::fs::write(
+            orb_dir.join("commands/greet.yml"),
+            "steps:\n  - run: echo hello\n",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("orb.yml");
+
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "pack",
+            "--orb-dir",
+            orb_dir.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+        ])
+        .unwrap();
+        cli.run().unwrap();
+
+        let orb = OrbParser::parse_packed(&output).unwrap();
+        assert_eq!(orb.version, "2.1");
+        assert_eq!(orb.commands["greet"].steps.len(), 1);
+    }
+
+    #[test]
+    fn test_init_command() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("toolkit");
+        let init = |extra: &[&str]| {
+            let mut args = vec!["gen-orb-mcp", "init", "toolkit", "--output"];
+            args.push(root.to_str().unwrap());
+            args.extend(extra);
+            Cli::try_parse_from(args).unwrap().run()
+        };
+
+        init(&[]).unwrap();
+        for dir in ["commands", "jobs", "executors", "examples", "scripts"] {
+            assert!(root.join("src").join(dir).is_dir(), "{dir}");
         }
-        .run();
-        assert!(
-            result.is_ok(),
-            "dry_run should not require credentials: {result:?}"
+        let orb_path = root.join("src/@orb.yml");
+        assert!(run_validate(&orb_path, true, true, None, None, ValidateFormat::Text).is_ok());
+        assert_eq!(derive_orb_name(&orb_path), "toolkit");
+
+        let err = init(&[]).unwrap_err().to_string();
+        assert!(err.contains("pass --force to overwrite"), "{err}");
+        std::fs::write(root.join("src/scripts/greet.sh"), "echo edited\n").unwrap();
+        init(&["--force"]).unwrap();
+        assert!(std::fs::read_to_string(root.join("src/scripts/greet.sh"))
+            .unwrap()
+            .contains("PARAM_TO"));
+
+        let err = run_init("../escape", "my-namespace", None, false)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid name '../escape'"), "{err}");
+    }
+
+    #[test]
+    fn test_generate_writes_metadata_sidecar() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_path = temp_dir.path().join("toolkit.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ndescription: Toolkit\ncommands:\n  greet:\n    description: Greet\n    steps:\n      - run: echo hi\n",
+        )
+        .unwrap();
+        let output = temp_dir.path().join("out");
+
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "generate",
+            "--orb-path",
+            orb_path.to_str().unwrap(),
+            "--output",
+            output.to_str().unwrap(),
+            "--crate-version",
+            "1.2.0",
+            "--source-rev",
+            "0123abc",
+            "--only",
+            "commands",
+        ])
+        .unwrap();
+        cli.run().unwrap();
+
+        let metadata = metadata::GenerationMetadata::read_from(&output)
+            .unwrap()
+            .expect("gen-orb-mcp.json");
+        assert_eq!(metadata.generator.version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(metadata.orb.path, orb_path);
+        assert_eq!(metadata.orb.source_rev.as_deref(), Some("0123abc"));
+        assert_eq!(
+            metadata.orb.content_hash,
+            metadata::orb_content_hash(&orb_path).unwrap()
+        );
+        assert_eq!(metadata.output.crate_name, "toolkit_mcp");
+        assert_eq!(metadata.output.version, "1.2.0");
+        assert_eq!(metadata.output.format, "source");
+        assert_eq!(
+            metadata.options.only,
+            [generator::DefinitionCategory::Commands]
         );
     }
 
     #[test]
-    fn test_publish_dry_run_missing_tag_returns_error() {
-        let dir = TempDir::new().unwrap();
-        let binary = dir.path().join("my-binary");
-        std::fs::write(&binary, b"fake binary").unwrap();
-        std::env::set_var("GITHUB_TOKEN", "fake-token");
-        std::env::remove_var("CIRCLE_TAG");
-        // no --tag and no CIRCLE_TAG — should fail with a clear message
-        let result = PublishJob {
-            name: None,
-            input: std::path::Path::new("."),
-            binary: Some(&binary),
-            asset_name: Some("my-asset"),
-            tag: None,
-            dry_run: true,
-            config_path: std::path::Path::new("no-such-config-185.toml"),
-            tag_env_override: None,
-        }
-        .run();
-        std::env::remove_var("GITHUB_TOKEN");
-        assert!(result.is_err());
-        let msg = result.unwrap_err().to_string();
-        assert!(
-            msg.contains("tag") || msg.contains("CIRCLE_TAG"),
-            "error should mention tag or CIRCLE_TAG, got: {msg}"
-        );
+    fn test_cli_parse_fail_on_warnings() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "validate", "--fail-on-warnings"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Validate {
+                fail_on_warnings: true,
+                ..
+            }
+        ));
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--fail-on-warnings"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Commands::Generate {
+                fail_on_warnings: true,
+                ..
+            }
+        ));
     }
 
     #[test]
-    fn test_publish_dry_run_prints_parameters() {
-        let dir = TempDir::new().unwrap();
-        let binary = dir.path().join("my-binary");
-        std::fs::write(&binary, b"fake binary").unwrap();
-        std::env::set_var("GITHUB_TOKEN", "fake-token");
-        std::env::set_var("CIRCLE_PROJECT_USERNAME", "jerus-org");
-        std::env::set_var("CIRCLE_PROJECT_REPONAME", "my-orb");
-        let result = PublishJob {
-            name: None,
-            input: std::path::Path::new("."),
-            binary: Some(&binary),
-            asset_name: Some("my-asset-linux-x86_64"),
-            tag: Some("v1.0.0"),
-            dry_run: true,
-            config_path: std::path::Path::new("no-such-config-185.toml"),
-            tag_env_override: None,
+    fn test_generate_flags_have_env_overrides() {
+        use clap::CommandFactory;
+        let cmd = Cli::command();
+        let generate = cmd.find_subcommand("generate").unwrap();
+        for arg in generate.get_arguments() {
+            let id = arg.get_id().as_str();
+            if id == "help" {
+                continue;
+            }
+            let env = arg
+                .get_env()
+                .unwrap_or_else(|| panic!("--{id} has no env override"))
+                .to_string_lossy();
+            assert!(env.starts_with("GEN_ORB_MCP_"), "--{id} uses {env}");
         }
-        .run();
-        std::env::remove_var("GITHUB_TOKEN");
-        std::env::remove_var("CIRCLE_PROJECT_USERNAME");
-        std::env::remove_var("CIRCLE_PROJECT_REPONAME");
-        assert!(
-            result.is_ok(),
-            "dry_run with all params should succeed: {result:?}"
-        );
     }
 
     #[test]
-    fn test_cli_parse_publish_required_args() {
-        let cli = Cli::try_parse_from([
-            "gen-orb-mcp",
-            "publish",
-            "--binary",
-            "/tmp/my-binary",
-            "--asset-name",
-            "my-binary-linux-x86_64",
-        ]);
-        assert!(cli.is_ok(), "publish with required args should parse");
+    fn test_cli_env_override_server_title() {
+        // Only this test sets GEN_ORB_MCP_SERVER_TITLE.
+        std::env::set_var("GEN_ORB_MCP_SERVER_TITLE", "From Env");
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate"]);
+        std::env::remove_var("GEN_ORB_MCP_SERVER_TITLE");
+        if let Commands::Generate { server_title, .. } = cli.unwrap().command {
+            assert_eq!(server_title.as_deref(), Some("From Env"));
+        } else {
+            panic!("expected Generate variant");
+        }
     }
 
     #[test]
-    fn test_cli_parse_publish_all_flags() {
+    fn test_cli_parse_diff_view_options() {
         let cli = Cli::try_parse_from([
             "gen-orb-mcp",
-            "publish",
-            "--binary",
-            "/tmp/my-binary",
-            "--asset-name",
-            "my-binary-linux-x86_64",
-            "--tag",
-            "v2.0.0",
-            "--dry-run",
-        ]);
-        assert!(cli.is_ok(), "publish with all flags should parse");
-        if let Commands::Publish {
-            binary,
-            asset_name,
-            tag,
-            dry_run,
+            "diff",
+            "--current",
+            "new.yml",
+            "--previous",
+            "old.yml",
+            "--since-version",
+            "2.0.0",
+            "--color",
+            "never",
+            "--side-by-side",
+        ])
+        .unwrap();
+        if let Commands::Diff {
+            color,
+            side_by_side,
             ..
-        } = cli.unwrap().command
+        } = cli.command
         {
-            assert_eq!(
-                binary.as_deref().and_then(|p| p.to_str()),
-                Some("/tmp/my-binary")
-            );
-            assert_eq!(asset_name.as_deref(), Some("my-binary-linux-x86_64"));
-            assert_eq!(tag.as_deref(), Some("v2.0.0"));
-            assert!(dry_run);
+            assert_eq!(color, differ::render::ColorChoice::Never);
+            assert!(side_by_side);
         } else {
-            panic!("expected Publish variant");
+            panic!("expected Diff variant");
         }
     }
 
     #[test]
-    fn test_resolve_publish_target_derives_from_name() {
-        let (binary, asset) = resolve_publish_target(
-            Some("gen-orb-mcp"),
-            std::path::Path::new("/tmp/mcp-server"),
-            None,
-            None,
-        )
-        .expect("derivation from name should succeed");
+    fn test_cli_parse_global_jobs() {
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "generate", "--all", "--jobs", "4"]).unwrap();
+        assert_eq!(cli.jobs.map(|n| n.get()), Some(4));
+
+        let cli = Cli::try_parse_from(["gen-orb-mcp", "-j", "2", "build", "-i", "dist"]).unwrap();
+        assert_eq!(cli.jobs.map(|n| n.get()), Some(2));
+
+        assert!(Cli::try_parse_from(["gen-orb-mcp", "generate", "--jobs", "0"]).is_err());
+    }
+
+    #[test]
+    fn test_cargo_build_args() {
+        assert_eq!(cargo_build_args(None, None), ["build", "--release"]);
         assert_eq!(
-            binary,
-            std::path::PathBuf::from("/tmp/mcp-server/target/release/gen_orb_mcp_mcp")
+            cargo_build_args(Some("aarch64-apple-darwin"), std::num::NonZeroUsize::new(2)),
+            [
+                "build",
+                "--release",
+                "--target",
+                "aarch64-apple-darwin",
+                "-j",
+                "2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_cargo_check_args() {
+        assert_eq!(cargo_check_args(false, None), ["check"]);
+        assert_eq!(
+            cargo_check_args(true, std::num::NonZeroUsize::new(4)),
+            ["check", "--all-targets", "-j", "4"]
+        );
+    }
+
+    #[test]
+    fn test_cargo_package_args() {
+        assert_eq!(cargo_package_args(None, None), ["package", "--allow-dirty"]);
+        assert_eq!(
+            cargo_package_args(Some("internal"), std::num::NonZeroUsize::new(2)),
+            [
+                "package",
+                "--allow-dirty",
+                "--registry",
+                "internal",
+                "-j",
+                "2"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_release_binary_path() {
+        let target_dir = std::path::Path::new("out/target");
+
+        assert_eq!(
+            release_binary_path(target_dir, Some("x86_64-pc-windows-msvc"), "srv"),
+            std::path::PathBuf::from("out/target/x86_64-pc-windows-msvc/release/srv.exe")
+        );
+        assert_eq!(
+            release_binary_path(target_dir, Some("aarch64-apple-darwin"), "srv"),
+            std::path::PathBuf::from("out/target/aarch64-apple-darwin/release/srv")
+        );
+        assert_eq!(
+            release_binary_path(target_dir, None, "srv"),
+            std::path::PathBuf::from(format!(
+                "out/target/release/srv{}",
+                std::env::consts::EXE_SUFFIX
+            ))
+        );
+    }
+
+    #[test]
+    fn test_resolve_publish_target_honours_cargo_target_dir() {
+        let _env = CARGO_TARGET_DIR_ENV.lock().unwrap();
+        let saved = std::env::var_os("CARGO_TARGET_DIR");
+        let shared = TempDir::new().unwrap();
+        let resolve = |target_dir: &std::path::Path| {
+            std::env::set_var("CARGO_TARGET_DIR", target_dir);
+            resolve_publish_target(
+                Some("gen-orb-mcp"),
+                std::path::Path::new("/tmp/mcp-server"),
+                None,
+                None,
+            )
+        };
+        let absolute = resolve(shared.path());
+        // Relative to the crate, as cargo resolves it
+        let relative = resolve(std::path::Path::new("shared-target"));
+        match saved {
+            Some(dir) => std::env::set_var("CARGO_TARGET_DIR", dir),
+            None => std::env::remove_var("CARGO_TARGET_DIR"),
+        }
+        let binary_name = format!("gen_orb_mcp_mcp{}", std::env::consts::EXE_SUFFIX);
+        assert_eq!(
+            absolute.unwrap().0,
+            shared.path().join("release").join(&binary_name)
+        );
+        assert_eq!(
+            relative.unwrap().0,
+            std::path::Path::new("/tmp/mcp-server/shared-target/release").join(&binary_name)
         );
-        assert_eq!(asset, "gen_orb_mcp_mcp-linux-x86_64");
     }
 
     #[test]
@@ -2535,7 +7701,7 @@ mod tests {
     #[test]
     fn test_build_missing_cargo_toml_returns_error() {
         let dir = TempDir::new().unwrap();
-        let result = run_build(dir.path(), None, None, false);
+        let result = run_build(dir.path(), None, None, None, &CargoEnv::default(), false);
         assert!(result.is_err());
         let msg = result.unwrap_err().to_string();
         assert!(
@@ -2550,7 +7716,7 @@ mod tests {
         write_cargo_toml(dir.path(), "my-server");
         // Not a valid Rust project — cargo would fail if invoked.
         // With dry_run=true the function must succeed without running cargo.
-        let result = run_build(dir.path(), None, None, true);
+        let result = run_build(dir.path(), None, None, None, &CargoEnv::default(), true);
         assert!(
             result.is_ok(),
             "dry_run should succeed without invoking cargo: {result:?}"
@@ -2561,7 +7727,14 @@ mod tests {
     fn test_build_name_override_accepted_in_dry_run() {
         let dir = TempDir::new().unwrap();
         write_cargo_toml(dir.path(), "my-server");
-        let result = run_build(dir.path(), Some("custom-name"), None, true);
+        let result = run_build(
+            dir.path(),
+            Some("custom-name"),
+            None,
+            None,
+            &CargoEnv::default(),
+            true,
+        );
         assert!(
             result.is_ok(),
             "name override + dry_run should succeed: {result:?}"
@@ -2572,13 +7745,70 @@ mod tests {
     fn test_build_target_triple_accepted_in_dry_run() {
         let dir = TempDir::new().unwrap();
         write_cargo_toml(dir.path(), "my-server");
-        let result = run_build(dir.path(), None, Some("x86_64-unknown-linux-musl"), true);
+        let result = run_build(
+            dir.path(),
+            None,
+            Some("x86_64-unknown-linux-musl"),
+            None,
+            &CargoEnv::default(),
+            true,
+        );
         assert!(
             result.is_ok(),
             "target + dry_run should succeed: {result:?}"
         );
     }
 
+    #[test]
+    fn test_cargo_env_applies_wrapper_and_shared_target_dir() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "build",
+            "-i",
+            "dist",
+            "--rustc-wrapper",
+            "sccache",
+            "--cargo-target-dir",
+            "shared-target",
+        ])
+        .unwrap();
+        let cargo = CargoEnv::new(cli.rustc_wrapper, cli.cargo_target_dir.as_deref()).unwrap();
+        let target_dir = std::env::current_dir().unwrap().join("shared-target");
+        assert_eq!(cargo.target_dir(std::path::Path::new("dist")), target_dir);
+
+        let command = cargo.command(std::path::Path::new("dist"));
+        let envs: Vec<_> = command.get_envs().collect();
+        assert!(envs.contains(&(
+            std::ffi::OsStr::new("RUSTC_WRAPPER"),
+            Some(std::ffi::OsStr::new("sccache"))
+        )));
+        assert!(envs.contains(&(
+            std::ffi::OsStr::new("CARGO_TARGET_DIR"),
+            Some(target_dir.as_os_str())
+        )));
+        assert_eq!(
+            cargo.env_prefix(),
+            format!(
+                "RUSTC_WRAPPER=sccache CARGO_TARGET_DIR={} ",
+                target_dir.display()
+            )
+        );
+    }
+
+    #[test]
+    fn test_build_rejects_missing_rustc_wrapper() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(dir.path(), "my-server");
+        let cargo = CargoEnv::new(Some("gen-orb-mcp-no-such-wrapper".into()), None).unwrap();
+        let err = run_build(dir.path(), None, None, None, &cargo, false)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("--rustc-wrapper gen-orb-mcp-no-such-wrapper is not available"),
+            "{err}"
+        );
+    }
+
     #[test]
     fn test_parse_package_name_extracts_name() {
         let toml = "[package]\nname = \"my-orb-mcp\"\nversion = \"0.1.0\"\n";
@@ -2646,6 +7876,53 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_cli_parse_verify() {
+        let cli = Cli::try_parse_from([
+            "gen-orb-mcp",
+            "verify",
+            "--orb-path",
+            "orb.yml",
+            "--transport",
+            "http",
+            "--property-tests",
+        ])
+        .unwrap();
+        if let Commands::Verify {
+            orb_path,
+            transport,
+            sandboxed,
+            property_tests,
+            ..
+        } = cli.command
+        {
+            assert_eq!(orb_path, std::path::PathBuf::from("orb.yml"));
+            assert_eq!(transport, generator::Transport::Http);
+            assert!(!sandboxed);
+            assert!(property_tests);
+        } else {
+            panic!("expected Verify variant");
+        }
+    }
+
+    #[test]
+    fn test_verify_rejects_unparseable_orb_before_cargo() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb = temp_dir.path().join("orb.yml");
+        std::fs::write(
+            &orb,
+            "version: 2.1
+jobs: [
+",
+        )
+        .unwrap();
+        let err = Cli::try_parse_from(["gen-orb-mcp", "verify", "-p", orb.to_str().unwrap()])
+            .unwrap()
+            .run()
+            .unwrap_err();
+        assert_eq!(failure::exit_code(&err), 3, "{err}");
+    }
+
     // --- #185: configurable signing / publish env-var names ---
 
     #[test]
@@ -2684,6 +7961,155 @@ mod tests {
         assert_eq!(names.sign_key, "BOT_SIGN_KEY");
     }
 
+    #[test]
+    fn generate_profile_from_config_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("gen-orb-mcp.toml");
+        std::fs::write(
+            &path,
+            "[profiles.minimal]\n\
+             only = [\"commands\"]\n\
+             server_title = \"Toolkit (minimal)\"\n\
+             name_prefix = \"acme\"\n\
+             uri_scheme = \"acme\"\n\
+             [profiles.full]\n\
+             property_tests = true\n\
+             templates_dir = \"mcp-templates\"\n\
+             binstall_pkg_url = \"https://example.com/{ name }\"\n\
+             binstall_pkg_fmt = \"tgz\"\n",
+        )
+        .unwrap();
+
+        let minimal = load_generate_profile(&path, "minimal").unwrap();
+        assert_eq!(minimal.only, [generator::DefinitionCategory::Commands]);
+        assert_eq!(minimal.server_title.as_deref(), Some("Toolkit (minimal)"));
+        assert_eq!(minimal.name_prefix.as_deref(), Some("acme"));
+        assert_eq!(minimal.uri_scheme.as_deref(), Some("acme"));
+        assert_eq!(minimal.crate_suffix, None);
+        assert!(!minimal.property_tests);
+        assert_eq!(minimal.templates_dir, None);
+
+        let full = load_generate_profile(&path, "full").unwrap();
+        assert!(full.only.is_empty());
+        assert!(full.property_tests);
+        assert_eq!(full.binstall_pkg_fmt, Some(generator::BinstallPkgFmt::Tgz));
+        assert_eq!(
+            full.templates_dir.as_deref(),
+            Some(std::path::Path::new("mcp-templates"))
+        );
+
+        let err = load_generate_profile(&path, "internal").unwrap_err();
+        assert!(
+            err.to_string().contains("(available: full, minimal)"),
+            "{err}"
+        );
+
+        let err = load_generate_profile(&path, "minmal").unwrap_err();
+        assert!(
+            err.to_string().contains(", did you mean 'minimal'?"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn generate_profile_rejects_unknown_keys() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("gen-orb-mcp.toml");
+        std::fs::write(&path, "[profiles.odd]\ntransport = \"http\"\n").unwrap();
+        let err = load_generate_profile(&path, "odd").unwrap_err();
+        assert!(err.to_string().contains("Invalid profile `odd`"), "{err}");
+    }
+
+    #[test]
+    fn generate_profile_requires_config_file() {
+        let err = load_generate_profile(std::path::Path::new("no-such-config-235.toml"), "minimal")
+            .unwrap_err();
+        assert!(err.to_string().contains("no-such-config-235.toml"), "{err}");
+    }
+
+    #[test]
+    fn package_config_from_config_file() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("gen-orb-mcp.toml");
+        assert_eq!(
+            load_package_config(&path).unwrap(),
+            PackageConfig::default()
+        );
+
+        std::fs::write(
+            &path,
+            "[package]\n\
+             description = \"MCP server for the toolkit orb\"\n\
+             license = \"MIT OR Apache-2.0\"\n\
+             keywords = [\"circleci\", \"mcp\"]\n\
+             registry = \"internal\"\n",
+        )
+        .unwrap();
+        let package = load_package_config(&path).unwrap();
+        assert_eq!(
+            package.description.as_deref(),
+            Some("MCP server for the toolkit orb")
+        );
+        assert_eq!(package.license.as_deref(), Some("MIT OR Apache-2.0"));
+        assert_eq!(package.keywords, ["circleci", "mcp"]);
+        assert_eq!(package.registry.as_deref(), Some("internal"));
+        assert_eq!(package.repository, None);
+
+        std::fs::write(&path, "[package]\nlicense = \"MIT; x\"\n").unwrap();
+        let err = load_package_config(&path).unwrap_err();
+        assert!(
+            err.to_string().contains("Invalid `[package]` table"),
+            "{err}"
+        );
+
+        std::fs::write(&path, "[package]\nhomepage = \"https://example.com\"\n").unwrap();
+        assert!(load_package_config(&path).is_err());
+    }
+
+    #[test]
+    fn binstall_options_require_pkg_url() {
+        assert_eq!(resolve_binstall(None, None, None).unwrap(), None);
+
+        let source = BinstallSource::PkgUrl("https://example.com/{ name }".into());
+        let binstall = resolve_binstall(Some(source), None, None).unwrap().unwrap();
+        assert_eq!(binstall.pkg_fmt, generator::BinstallPkgFmt::Bin);
+        assert_eq!(binstall.bin_dir, None);
+        assert!(binstall.overrides.is_empty());
+
+        let err = resolve_binstall(None, Some(generator::BinstallPkgFmt::Zip), None).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("require --binstall-pkg-url or --binstall-repo"));
+    }
+
+    #[test]
+    fn binstall_repo_points_at_publish_assets() {
+        let source = BinstallSource::new(
+            None,
+            Some("https://github.com/acme/ci-toolkit".into()),
+            "toolkit-v",
+        )
+        .unwrap();
+        let binstall = resolve_binstall(source, None, None).unwrap().unwrap();
+        assert_eq!(
+            binstall.overrides["x86_64-unknown-linux-gnu"],
+            format!(
+                "https://github.com/acme/ci-toolkit/releases/download/toolkit-v{{ version }}/\
+                 {{ name }}-linux-x86_64"
+            )
+        );
+        assert_eq!(binstall.pkg_fmt, generator::BinstallPkgFmt::Bin);
+        assert_eq!(binstall.overrides.len(), 5);
+
+        let err = BinstallSource::new(
+            Some("https://example.com/{ name }".into()),
+            Some("https://github.com/acme/ci-toolkit".into()),
+            "v",
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("cannot be combined"), "{err}");
+    }
+
     #[test]
     fn sign_env_cli_override_beats_config() {
         let dir = TempDir::new().unwrap();
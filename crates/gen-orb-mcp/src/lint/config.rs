@@ -0,0 +1,444 @@
+//! Lint ruleset files and suppressions.
+//!
+//! A `.gen-orb-mcp-lint.toml` sets the severity of each rule, the limits of
+//! the size-based rules and lists suppressions, so large orbs can adopt
+//! linting one area at a time:
+//!
+//! ```toml
+//! [rules]
+//! missing-description = "error"
+//! undocumented-parameter = "off"
+//!
+//! [limits]
+//! max-run-lines = 40
+//!
+//! [[suppress]]
+//! rule = "missing-description"
+//! path = "jobs/legacy-*"
+//! ```
+//!
+//! The same tables can live under `[lint]` in `gen-orb-mcp.toml` (e.g.
+//! `[lint.rules]`) for projects that prefer a single config file.
+//!
+//! Suppressions can also be written next to a definition in the orb YAML as
+//! `# gen-orb-mcp: allow(rule, ...)`, either on the line above a key or at the
+//! end of it. A comment before the first key of a file covers the whole file.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+
+use super::{default_severity, source, RULES};
+use crate::suggest;
+
+/// Default ruleset file, looked up in the working directory.
+pub const DEFAULT_LINT_CONFIG: &str = ".gen-orb-mcp-lint.toml";
+
+/// How a lint rule is reported.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Reported and always fails `validate` and `generate`
+    Error,
+    /// Reported; fails only with `--fail-on-warnings`
+    #[default]
+    Warn,
+    /// Not reported
+    Off,
+}
+
+/// Suppresses matching findings.
+///
+/// Every field that is set must match. `path` is a glob matched against the
+/// finding's location and each of its ancestors (so `jobs/legacy-*` also
+/// covers the parameters of those jobs); `name` is a glob matched against
+/// the command, job or executor name.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Suppression {
+    /// Rule to suppress; `None` suppresses every rule.
+    pub rule: Option<String>,
+    /// Location glob (e.g. `commands/setup*`).
+    pub path: Option<String>,
+    /// Definition name glob (e.g. `deprecated-*`).
+    pub name: Option<String>,
+}
+
+impl Suppression {
+    /// Whether this suppression covers `rule` at `location`.
+    pub fn matches(&self, rule: &str, location: &str) -> bool {
+        if self.rule.as_deref().is_some_and(|r| r != rule) {
+            return false;
+        }
+        if let Some(path) = &self.path {
+            let Ok(pattern) = glob::Pattern::new(path) else {
+                return false;
+            };
+            if !ancestors(location).any(|prefix| pattern.matches(prefix)) {
+                return false;
+            }
+        }
+        if let Some(name) = &self.name {
+            let Ok(pattern) = glob::Pattern::new(name) else {
+                return false;
+            };
+            if !location
+                .split('/')
+                .nth(1)
+                .is_some_and(|n| pattern.matches(n))
+            {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// `location` and each of its `/`-separated ancestors, shortest first.
+fn ancestors(location: &str) -> impl Iterator<Item = &str> {
+    location
+        .match_indices('/')
+        .map(|(i, _)| &location[..i])
+        .chain(std::iter::once(location))
+}
+
+/// Errors loading a lint ruleset or scanning orb files for suppressions.
+#[derive(Debug, Error)]
+pub enum LintConfigError {
+    /// The ruleset file could not be read or parsed.
+    #[error("failed to load lint config '{path}': {source}")]
+    Load {
+        path: PathBuf,
+        #[source]
+        source: config::ConfigError,
+    },
+
+    /// A rule name in the ruleset is not known.
+    #[error("unknown lint rule '{rule}' in '{path}'{suggestion} (known rules: {known})")]
+    UnknownRule {
+        path: PathBuf,
+        rule: String,
+        /// `", did you mean '...'?"`, or empty.
+        suggestion: String,
+        known: String,
+    },
+
+    /// A suppression glob is malformed.
+    #[error("invalid suppression pattern '{pattern}' in '{path}': {source}")]
+    InvalidPattern {
+        path: PathBuf,
+        pattern: String,
+        #[source]
+        source: glob::PatternError,
+    },
+
+    /// An orb file could not be read while scanning for inline suppressions.
+    #[error("failed to read '{path}': {source}")]
+    FileRead {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// Thresholds of the size-based rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LintLimits {
+    /// Lines a `run` command may have before `long-run-step` reports it.
+    pub max_run_lines: usize,
+    /// Steps that may reference a parameter without a default before
+    /// `widely-used-required-parameter` reports it.
+    pub max_required_parameter_uses: usize,
+}
+
+impl Default for LintLimits {
+    fn default() -> Self {
+        Self {
+            max_run_lines: 30,
+            max_required_parameter_uses: 3,
+        }
+    }
+}
+
+/// Rule severities, limits and suppressions applied to lint findings.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct LintConfig {
+    /// Severity per rule; rules not listed use their
+    /// [`default_severity`].
+    pub rules: HashMap<String, Severity>,
+    /// Thresholds of the size-based rules.
+    pub limits: LintLimits,
+    /// Findings to drop.
+    pub suppress: Vec<Suppression>,
+}
+
+impl LintConfig {
+    /// Load a ruleset file, rejecting unknown rules and malformed globs.
+    pub fn load(path: &Path) -> Result<Self, LintConfigError> {
+        let config: LintConfig = read_toml(path)?;
+        config.checked(path)
+    }
+
+    /// Load the `[lint]` table of a project config file such as
+    /// `gen-orb-mcp.toml`; `None` when the file has no such table.
+    pub fn load_project(path: &Path) -> Result<Option<Self>, LintConfigError> {
+        #[derive(Deserialize)]
+        struct ProjectConfig {
+            #[serde(default)]
+            lint: Option<LintConfig>,
+        }
+
+        let project: ProjectConfig = read_toml(path)?;
+        project.lint.map(|config| config.checked(path)).transpose()
+    }
+
+    /// `self`, once its rule names and suppression globs are known to be
+    /// valid.
+    fn checked(self, path: &Path) -> Result<Self, LintConfigError> {
+        let rule_names = self
+            .rules
+            .keys()
+            .chain(self.suppress.iter().filter_map(|s| s.rule.as_ref()));
+        for rule in rule_names {
+            if !RULES.contains(&rule.as_str()) {
+                return Err(LintConfigError::UnknownRule {
+                    path: path.to_path_buf(),
+                    rule: rule.clone(),
+                    suggestion: suggest::did_you_mean(rule, RULES.iter().copied()),
+                    known: RULES.join(", "),
+                });
+            }
+        }
+        let patterns = self
+            .suppress
+            .iter()
+            .flat_map(|s| [s.path.as_ref(), s.name.as_ref()])
+            .flatten();
+        for pattern in patterns {
+            glob::Pattern::new(pattern).map_err(|source| LintConfigError::InvalidPattern {
+                path: path.to_path_buf(),
+                pattern: pattern.clone(),
+                source,
+            })?;
+        }
+        Ok(self)
+    }
+
+    /// The configured severity of `rule`.
+    pub fn severity(&self, rule: &str) -> Severity {
+        self.rules
+            .get(rule)
+            .copied()
+            .unwrap_or_else(|| default_severity(rule))
+    }
+
+    /// Whether any suppression covers `rule` at `location`.
+    pub fn is_suppressed(&self, rule: &str, location: &str) -> bool {
+        self.suppress.iter().any(|s| s.matches(rule, location))
+    }
+}
+
+fn read_toml<T>(path: &Path) -> Result<T, LintConfigError>
+where
+    T: for<'de> Deserialize<'de>,
+{
+    config::Config::builder()
+        .add_source(config::File::from(path).format(config::FileFormat::Toml))
+        .build()
+        .and_then(config::Config::try_deserialize)
+        .map_err(|source| LintConfigError::Load {
+            path: path.to_path_buf(),
+            source,
+        })
+}
+
+/// Collect `# gen-orb-mcp: allow(...)` comments from the orb at `orb_path`.
+///
+/// In an unpacked orb, comments in `commands/<name>.yml` apply below
+/// `commands/<name>`.
+pub fn inline_suppressions(orb_path: &Path) -> Result<Vec<Suppression>, LintConfigError> {
+    let mut suppressions = Vec::new();
+    for file in source::orb_files(orb_path) {
+        let content =
+            fs::read_to_string(&file.path).map_err(|source| LintConfigError::FileRead {
+                path: file.path.clone(),
+                source,
+            })?;
+        suppressions.extend(scan_yaml(&content, &file.prefix));
+    }
+    Ok(suppressions)
+}
+
+/// Collect `# gen-orb-mcp: allow(...)` comments from packed orb `content`
+/// that was not read from a file (e.g. standard input).
+pub fn packed_suppressions(content: &str) -> Vec<Suppression> {
+    scan_yaml(content, "")
+}
+
+/// Suppressions for the allow comments in YAML `content`.
+fn scan_yaml(content: &str, prefix: &str) -> Vec<Suppression> {
+    let suppression = |rule: &str, path: &str| Suppression {
+        rule: Some(rule.to_string()),
+        path: (!path.is_empty()).then(|| glob::Pattern::escape(path)),
+        name: None,
+    };
+    let outline = source::outline(content, prefix);
+    let header = outline
+        .header_allowed
+        .iter()
+        .map(|rule| suppression(rule, prefix));
+    let keyed = outline
+        .keys
+        .iter()
+        .flat_map(|key| key.allowed.iter().map(|rule| suppression(rule, &key.path)));
+    header.chain(keyed).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_yaml_tracks_key_paths() {
+        let yaml = "\
+version: 2.1
+jobs:
+  # gen-orb-mcp: allow(missing-description)
+  legacy:
+    parameters:
+      tag: # gen-orb-mcp: allow(undocumented-parameter)
+        type: string
+    steps:
+      - run:
+          command: |
+            # gen-orb-mcp: allow(missing-description)
+            echo hi
+";
+        let suppressions = scan_yaml(yaml, "");
+        assert_eq!(
+            suppressions,
+            [
+                Suppression {
+                    rule: Some("missing-description".to_string()),
+                    path: Some("jobs/legacy".to_string()),
+                    name: None,
+                },
+                Suppression {
+                    rule: Some("undocumented-parameter".to_string()),
+                    path: Some("jobs/legacy/parameters/tag".to_string()),
+                    name: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_scan_yaml_file_header_covers_prefix() {
+        let yaml = "# gen-orb-mcp: allow(missing-description, undocumented-parameter)\n\
+                    parameters: {}\n";
+        let suppressions = scan_yaml(yaml, "commands/setup");
+        assert_eq!(suppressions.len(), 2);
+        assert!(suppressions
+            .iter()
+            .all(|s| s.path.as_deref() == Some("commands/setup")));
+        assert!(suppressions[1].matches("undocumented-parameter", "commands/setup/parameters/x"));
+        assert!(!suppressions[1].matches("undocumented-parameter", "commands/setup-two"));
+    }
+
+    #[test]
+    fn test_suppression_matches_path_ancestors_and_names() {
+        let by_path = Suppression {
+            rule: None,
+            path: Some("jobs/legacy-*".to_string()),
+            name: None,
+        };
+        assert!(by_path.matches("undocumented-parameter", "jobs/legacy-build/parameters/tag"));
+        assert!(!by_path.matches("missing-description", "jobs/build"));
+
+        let by_name = Suppression {
+            rule: Some("missing-description".to_string()),
+            path: None,
+            name: Some("deprecated-*".to_string()),
+        };
+        assert!(by_name.matches("missing-description", "commands/deprecated-setup"));
+        assert!(!by_name.matches("undocumented-parameter", "commands/deprecated-setup"));
+        assert!(!by_name.matches("missing-description", "orb"));
+    }
+
+    #[test]
+    fn test_load_rejects_unknown_rules() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(DEFAULT_LINT_CONFIG);
+        fs::write(&path, "[rules]\nmissing-descriptin = \"off\"\n").unwrap();
+
+        let err = LintConfig::load(&path).unwrap_err();
+        assert!(
+            matches!(err, LintConfigError::UnknownRule { ref rule, .. } if rule == "missing-descriptin")
+        );
+        assert!(err.to_string().contains("'missing-descriptin' in"), "{err}");
+        assert!(
+            err.to_string()
+                .contains(", did you mean 'missing-description'?"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_load_reads_severities_and_suppressions() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join(DEFAULT_LINT_CONFIG);
+        fs::write(
+            &path,
+            "[rules]\n\
+             missing-description = \"error\"\n\
+             undocumented-parameter = \"off\"\n\
+             [[suppress]]\n\
+             rule = \"missing-description\"\n\
+             path = \"jobs/legacy-*\"\n",
+        )
+        .unwrap();
+
+        let config = LintConfig::load(&path).unwrap();
+        assert_eq!(config.severity("missing-description"), Severity::Error);
+        assert_eq!(config.severity("undocumented-parameter"), Severity::Off);
+        assert!(config.is_suppressed("missing-description", "jobs/legacy-deploy"));
+        assert_eq!(config.limits, LintLimits::default());
+    }
+
+    #[test]
+    fn test_load_project_reads_lint_table() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("gen-orb-mcp.toml");
+        fs::write(
+            &path,
+            "[sign]\n\
+             gpg_key_env = \"BOT_GPG_KEY\"\n\
+             [lint.rules]\n\
+             unpinned-image = \"error\"\n\
+             [lint.limits]\n\
+             max-run-lines = 10\n",
+        )
+        .unwrap();
+
+        let config = LintConfig::load_project(&path).unwrap().unwrap();
+        assert_eq!(config.severity("unpinned-image"), Severity::Error);
+        assert_eq!(config.limits.max_run_lines, 10);
+        assert_eq!(config.limits.max_required_parameter_uses, 3);
+
+        fs::write(&path, "[sign]\ngpg_key_env = \"BOT_GPG_KEY\"\n").unwrap();
+        assert!(LintConfig::load_project(&path).unwrap().is_none());
+
+        fs::write(&path, "[lint.rules]\nunpinned-images = \"off\"\n").unwrap();
+        assert!(matches!(
+            LintConfig::load_project(&path),
+            Err(LintConfigError::UnknownRule { .. })
+        ));
+    }
+}
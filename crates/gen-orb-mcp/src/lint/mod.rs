@@ -0,0 +1,824 @@
+//! Non-fatal quality checks for parsed orb definitions.
+//!
+//! The parser only rejects orbs it cannot understand. Lint warnings flag
+//! definitions that parse fine but make a poorer MCP server, such as commands
+//! without descriptions or unpinned docker images. `lint`, `validate` and
+//! `generate` report them, and `--fail-on-warnings` turns them into a
+//! non-zero exit. A [`LintConfig`] changes rule severities and limits and
+//! suppresses findings.
+
+pub mod config;
+pub mod resource_class;
+pub mod sarif;
+pub mod source;
+
+use std::{collections::HashMap, fmt};
+
+pub use config::{LintConfig, LintLimits, Severity};
+use serde::Serialize;
+
+use self::resource_class::ExecutorKind;
+use crate::{
+    parser::{
+        DockerImage, ExecutorConfig, ExecutorRef, OrbDefinition, Parameter, RunStep, Step,
+        StructuredStep,
+    },
+    suggest,
+};
+
+/// Names of all lint rules.
+pub const RULES: &[&str] = &[
+    "missing-description",
+    "undocumented-parameter",
+    "unpinned-image",
+    "long-run-step",
+    "widely-used-required-parameter",
+    "undeclared-parameter",
+    "unused-parameter",
+    "unknown-resource-class",
+    "deprecated-resource-class",
+];
+
+/// One-line description of a lint rule, as shown in SARIF reports.
+pub fn rule_description(rule: &str) -> &'static str {
+    match rule {
+        "missing-description" => "Orb, command, job or executor has no description",
+        "undocumented-parameter" => "Parameter has no description",
+        "unpinned-image" => "Docker image has no tag or uses the mutable `latest` tag",
+        "long-run-step" => "Run step command is longer than the configured limit",
+        "widely-used-required-parameter" => {
+            "Parameter without a default is referenced by more steps than the configured limit"
+        }
+        "undeclared-parameter" => {
+            "`<< parameters.<name> >>` names a parameter that is not declared"
+        }
+        "unused-parameter" => "Declared parameter is never referenced",
+        "unknown-resource-class" => {
+            "Resource class is not a CircleCI resource class of the executor type"
+        }
+        "deprecated-resource-class" => "Resource class is deprecated by CircleCI",
+        _ => "Unknown rule",
+    }
+}
+
+/// Severity of a lint rule when no ruleset sets one.
+///
+/// Undeclared parameter references fail when CircleCI compiles the config,
+/// so they are errors; everything else is a warning.
+pub fn default_severity(rule: &str) -> Severity {
+    match rule {
+        "undeclared-parameter" => Severity::Error,
+        _ => Severity::Warn,
+    }
+}
+
+/// A single lint finding.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct LintWarning {
+    /// Identifier of the check that produced the warning (e.g.
+    /// `missing-description`).
+    pub rule: &'static str,
+    /// Path of the offending definition (e.g. `jobs/build/parameters/tag`).
+    pub location: String,
+    /// Human-readable explanation.
+    pub message: String,
+    /// How the finding is reported.
+    pub severity: Severity,
+}
+
+impl fmt::Display for LintWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {} [{}]", self.location, self.message, self.rule)
+    }
+}
+
+/// Run all checks against `orb` with the default [`LintLimits`], returning
+/// warnings sorted by location.
+pub fn lint(orb: &OrbDefinition) -> Vec<LintWarning> {
+    lint_with_limits(orb, &LintLimits::default())
+}
+
+/// Run all checks against `orb`, returning warnings sorted by location.
+pub fn lint_with_limits(orb: &OrbDefinition, limits: &LintLimits) -> Vec<LintWarning> {
+    let mut warnings = Vec::new();
+
+    if is_blank(&orb.description) {
+        warnings.push(missing_description("orb", "orb"));
+    }
+    for (name, command) in &orb.commands {
+        let location = format!("commands/{name}");
+        if is_blank(&command.description) {
+            warnings.push(missing_description(&location, "command"));
+        }
+        check_parameters(&location, &command.parameters, &mut warnings);
+        check_steps(&location, &command.steps, limits, &mut warnings);
+        check_parameter_uses(
+            &location,
+            &command.parameters,
+            &command.steps,
+            limits,
+            &mut warnings,
+        );
+        let mut uses = step_placeholders(&location, &command.steps);
+        check_placeholders(&location, &command.parameters, &mut uses, &mut warnings);
+    }
+    for (name, job) in &orb.jobs {
+        let location = format!("jobs/{name}");
+        if is_blank(&job.description) {
+            warnings.push(missing_description(&location, "job"));
+        }
+        check_parameters(&location, &job.parameters, &mut warnings);
+        check_images(&location, &job.config, &mut warnings);
+        let kind = ExecutorKind::of(&job.config).or_else(|| {
+            let name = match job.executor.as_ref()? {
+                ExecutorRef::Name(name) => name,
+                ExecutorRef::WithParams { name, .. } => name,
+            };
+            ExecutorKind::of(&orb.executors.get(name)?.config)
+        });
+        check_resource_class(&location, &job.config, kind, &mut warnings);
+        check_steps(&location, &job.steps, limits, &mut warnings);
+        check_parameter_uses(
+            &location,
+            &job.parameters,
+            &job.steps,
+            limits,
+            &mut warnings,
+        );
+        let mut uses = step_placeholders(&location, &job.steps);
+        uses.push((location.clone(), rendered_placeholders(&job.executor)));
+        uses.push((location.clone(), rendered_placeholders(&job.config)));
+        check_placeholders(&location, &job.parameters, &mut uses, &mut warnings);
+    }
+    for (name, executor) in &orb.executors {
+        let location = format!("executors/{name}");
+        if is_blank(&executor.description) {
+            warnings.push(missing_description(&location, "executor"));
+        }
+        check_parameters(&location, &executor.parameters, &mut warnings);
+        check_images(&location, &executor.config, &mut warnings);
+        check_resource_class(
+            &location,
+            &executor.config,
+            ExecutorKind::of(&executor.config),
+            &mut warnings,
+        );
+        let mut uses = vec![(location.clone(), rendered_placeholders(&executor.config))];
+        check_placeholders(&location, &executor.parameters, &mut uses, &mut warnings);
+    }
+
+    warnings.sort_by(|a, b| a.location.cmp(&b.location).then(a.rule.cmp(b.rule)));
+    warnings
+}
+
+/// Run all checks against `orb`, applying the severities and suppressions in
+/// `config`. Findings for rules set to `off` are dropped.
+pub fn lint_with_config(orb: &OrbDefinition, config: &LintConfig) -> Vec<LintWarning> {
+    lint_with_limits(orb, &config.limits)
+        .into_iter()
+        .filter(|w| !config.is_suppressed(w.rule, &w.location))
+        .filter_map(|w| match config.severity(w.rule) {
+            Severity::Off => None,
+            severity => Some(LintWarning { severity, ..w }),
+        })
+        .collect()
+}
+
+fn check_parameters(
+    owner: &str,
+    parameters: &HashMap<String, Parameter>,
+    warnings: &mut Vec<LintWarning>,
+) {
+    for (name, parameter) in parameters {
+        if is_blank(&parameter.description) {
+            warnings.push(LintWarning {
+                rule: "undocumented-parameter",
+                location: format!("{owner}/parameters/{name}"),
+                message: "parameter has no description".to_string(),
+                severity: Severity::Warn,
+            });
+        }
+    }
+}
+
+fn check_images(owner: &str, config: &ExecutorConfig, warnings: &mut Vec<LintWarning>) {
+    for image in config.docker.iter().flatten() {
+        let reference = match image {
+            DockerImage::Simple(reference) => reference.as_str(),
+            DockerImage::Full(full) => full.image.as_str(),
+        };
+        if let Some(problem) = unpinned(reference) {
+            warnings.push(LintWarning {
+                rule: "unpinned-image",
+                location: format!("{owner}/docker"),
+                message: format!("docker image '{reference}' {problem}"),
+                severity: Severity::Warn,
+            });
+        }
+    }
+}
+
+/// Report a `resource_class` outside the [`resource_class`] catalog, not
+/// offered on the executor type `kind`, or deprecated.
+fn check_resource_class(
+    owner: &str,
+    config: &ExecutorConfig,
+    kind: Option<ExecutorKind>,
+    warnings: &mut Vec<LintWarning>,
+) {
+    let Some(name) = config.resource_class.as_deref() else {
+        return;
+    };
+    if resource_class::is_unchecked(name) {
+        return;
+    }
+    let location = format!("{owner}/resource_class");
+    let unknown = |message: String| LintWarning {
+        rule: "unknown-resource-class",
+        location: location.clone(),
+        message,
+        severity: Severity::Warn,
+    };
+    let Some(class) = resource_class::find(name) else {
+        warnings.push(unknown(format!(
+            "resource class '{name}' is not a CircleCI resource class{}",
+            suggest::did_you_mean(name, resource_class::current(kind))
+        )));
+        return;
+    };
+    if let Some(kind) = kind.filter(|kind| !class.kinds.contains(kind)) {
+        warnings.push(unknown(format!(
+            "resource class '{name}' is not offered on {} executors",
+            kind.as_str()
+        )));
+    }
+    if let Some(replacement) = class.replacement {
+        warnings.push(LintWarning {
+            rule: "deprecated-resource-class",
+            location,
+            message: format!("resource class '{name}' is deprecated; use '{replacement}'"),
+            severity: Severity::Warn,
+        });
+    }
+}
+
+/// Why the docker image `reference` is not pinned, or `None` when it has a
+/// version tag or digest. Parameterised references are left alone.
+fn unpinned(reference: &str) -> Option<&'static str> {
+    if reference.contains("<<") || reference.contains('@') {
+        return None;
+    }
+    let name = reference.rsplit('/').next().unwrap_or(reference);
+    match name.split_once(':') {
+        None => Some("has no tag"),
+        Some((_, "latest")) => Some("uses the mutable 'latest' tag"),
+        Some(_) => None,
+    }
+}
+
+/// Report `run` steps, including those under `when`/`unless`, whose command
+/// exceeds the line limit.
+fn check_steps(owner: &str, steps: &[Step], limits: &LintLimits, warnings: &mut Vec<LintWarning>) {
+    for (index, step) in steps.iter().enumerate() {
+        let location = format!("{owner}/steps/{index}");
+        let Step::Structured(step) = step else {
+            continue;
+        };
+        match step {
+            StructuredStep::Run(run) => {
+                let command = match run {
+                    RunStep::Simple(command) => command,
+                    RunStep::Full { command, .. } => command,
+                };
+                let lines = command.lines().count();
+                if lines > limits.max_run_lines {
+                    warnings.push(LintWarning {
+                        rule: "long-run-step",
+                        location,
+                        message: format!(
+                            "run step has {lines} lines (limit {})",
+                            limits.max_run_lines
+                        ),
+                        severity: Severity::Warn,
+                    });
+                }
+            }
+            StructuredStep::When(conditional) => {
+                check_steps(
+                    &format!("{location}/when"),
+                    &conditional.steps,
+                    limits,
+                    warnings,
+                );
+            }
+            StructuredStep::Unless(conditional) => {
+                check_steps(
+                    &format!("{location}/unless"),
+                    &conditional.steps,
+                    limits,
+                    warnings,
+                );
+            }
+            StructuredStep::Steps(list) => {
+                check_steps(&format!("{location}/steps"), list.steps(), limits, warnings);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Report parameters without a default that more steps reference than the
+/// limit allows; every caller has to pass them, so they usually deserve a
+/// default.
+fn check_parameter_uses(
+    owner: &str,
+    parameters: &HashMap<String, Parameter>,
+    steps: &[Step],
+    limits: &LintLimits,
+    warnings: &mut Vec<LintWarning>,
+) {
+    let rendered: Vec<String> = steps
+        .iter()
+        .map(|step| serde_yaml::to_string(step).unwrap_or_default())
+        .collect();
+    for (name, parameter) in parameters {
+        if parameter.default.is_some() {
+            continue;
+        }
+        let uses = rendered
+            .iter()
+            .filter(|step| references_parameter(step, name))
+            .count();
+        if uses > limits.max_required_parameter_uses {
+            warnings.push(LintWarning {
+                rule: "widely-used-required-parameter",
+                location: format!("{owner}/parameters/{name}"),
+                message: format!(
+                    "parameter has no default but {uses} steps use it (limit {})",
+                    limits.max_required_parameter_uses
+                ),
+                severity: Severity::Warn,
+            });
+        }
+    }
+}
+
+/// Parameters each step of `owner` references, keyed by the step's location.
+fn step_placeholders(owner: &str, steps: &[Step]) -> Vec<(String, Vec<String>)> {
+    steps
+        .iter()
+        .enumerate()
+        .map(|(index, step)| {
+            (
+                format!("{owner}/steps/{index}"),
+                rendered_placeholders(step),
+            )
+        })
+        .collect()
+}
+
+/// Parameters referenced anywhere in `value`, found in its YAML rendering.
+fn rendered_placeholders(value: &impl Serialize) -> Vec<String> {
+    placeholders(&serde_yaml::to_string(value).unwrap_or_default())
+}
+
+/// Names in the `<< parameters.<name> >>` placeholders of `text`, in order
+/// of appearance. Pipeline values and other `<< ... >>` expressions are
+/// skipped.
+fn placeholders(text: &str) -> Vec<String> {
+    let mut names = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("<<") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find(">>") else {
+            break;
+        };
+        let inner = &rest[..end];
+        if inner.contains('\n') {
+            continue;
+        }
+        if let Some(name) = inner.trim().strip_prefix("parameters.") {
+            let name: String = name
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '-')
+                .collect();
+            if !name.is_empty() {
+                names.push(name);
+            }
+        }
+        rest = &rest[end + 2..];
+    }
+    names
+}
+
+/// Report placeholders naming parameters that `owner` does not declare, at
+/// the location using them, and declared parameters no placeholder uses.
+fn check_placeholders(
+    owner: &str,
+    parameters: &HashMap<String, Parameter>,
+    uses: &mut [(String, Vec<String>)],
+    warnings: &mut Vec<LintWarning>,
+) {
+    for (location, names) in uses.iter_mut() {
+        names.sort();
+        names.dedup();
+        for name in names.iter().filter(|n| !parameters.contains_key(*n)) {
+            warnings.push(LintWarning {
+                rule: "undeclared-parameter",
+                location: location.clone(),
+                message: format!(
+                    "'<< parameters.{name} >>' references a parameter {owner} does not declare"
+                ),
+                severity: default_severity("undeclared-parameter"),
+            });
+        }
+    }
+    for name in parameters.keys() {
+        if !uses.iter().any(|(_, names)| names.contains(name)) {
+            warnings.push(LintWarning {
+                rule: "unused-parameter",
+                location: format!("{owner}/parameters/{name}"),
+                message: "parameter is declared but never used".to_string(),
+                severity: default_severity("unused-parameter"),
+            });
+        }
+    }
+}
+
+/// Whether `text` contains `parameters.<name>` not followed by more of a
+/// longer parameter name.
+fn references_parameter(text: &str, name: &str) -> bool {
+    let needle = format!("parameters.{name}");
+    text.match_indices(&needle).any(|(start, _)| {
+        !text[start + needle.len()..]
+            .starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    })
+}
+
+fn missing_description(location: &str, kind: &str) -> LintWarning {
+    LintWarning {
+        rule: "missing-description",
+        location: location.to_string(),
+        message: format!("{kind} has no description"),
+        severity: Severity::Warn,
+    }
+}
+
+fn is_blank(description: &Option<String>) -> bool {
+    description.as_deref().is_none_or(|d| d.trim().is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lint::config::Suppression,
+        parser::{Command, Job, ParameterType},
+    };
+
+    #[test]
+    fn test_lint_clean_orb_has_no_warnings() {
+        let mut orb = OrbDefinition {
+            description: Some("Toolkit".to_string()),
+            ..Default::default()
+        };
+        orb.commands.insert(
+            "greet".to_string(),
+            Command {
+                description: Some("Say hello".to_string()),
+                ..Default::default()
+            },
+        );
+        assert!(lint(&orb).is_empty());
+    }
+
+    #[test]
+    fn test_lint_reports_missing_descriptions_in_order() {
+        let mut orb = OrbDefinition::default();
+        orb.jobs.insert(
+            "build".to_string(),
+            Job {
+                description: Some("  ".to_string()),
+                parameters: HashMap::from([(
+                    "tag".to_string(),
+                    Parameter {
+                        param_type: ParameterType::String,
+                        description: None,
+                        default: None,
+                        enum_values: None,
+                    },
+                )]),
+                ..Default::default()
+            },
+        );
+        orb.commands.insert("greet".to_string(), Command::default());
+
+        let warnings = lint(&orb);
+        let locations: Vec<_> = warnings.iter().map(|w| w.location.as_str()).collect();
+        assert_eq!(
+            locations,
+            [
+                "commands/greet",
+                "jobs/build",
+                "jobs/build/parameters/tag",
+                "orb"
+            ]
+        );
+        assert_eq!(warnings[2].rule, "undocumented-parameter");
+        assert_eq!(
+            warnings[0].to_string(),
+            "commands/greet: command has no description [missing-description]"
+        );
+    }
+
+    #[test]
+    fn test_lint_with_config_applies_severity_and_suppressions() {
+        let mut orb = OrbDefinition::default();
+        orb.commands.insert("greet".to_string(), Command::default());
+        orb.commands
+            .insert("legacy".to_string(), Command::default());
+
+        let config = LintConfig {
+            rules: HashMap::from([("missing-description".to_string(), Severity::Error)]),
+            suppress: vec![Suppression {
+                path: Some("commands/legacy".to_string()),
+                ..Default::default()
+            }],
+            ..Default::default()
+        };
+        let warnings = lint_with_config(&orb, &config);
+        let found: Vec<_> = warnings
+            .iter()
+            .map(|w| (w.location.as_str(), w.severity))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("commands/greet", Severity::Error),
+                ("orb", Severity::Error)
+            ]
+        );
+
+        let off = LintConfig {
+            rules: HashMap::from([("missing-description".to_string(), Severity::Off)]),
+            ..Default::default()
+        };
+        assert!(lint_with_config(&orb, &off).is_empty());
+    }
+
+    #[test]
+    fn test_lint_flags_unpinned_images() {
+        let executors: HashMap<String, crate::parser::Executor> = serde_yaml::from_str(
+            r#"
+pinned:
+  description: Pinned
+  parameters:
+    tag:
+      type: string
+      description: Node version
+  docker:
+    - image: cimg/rust:1.75
+    - image: cimg/base@sha256:abc
+    - image: cimg/node:<< parameters.tag >>
+bare:
+  description: Bare
+  docker:
+    - image: localhost:5000/cimg/rust
+    - image: cimg/base:latest
+"#,
+        )
+        .unwrap();
+        let orb = OrbDefinition {
+            description: Some("Toolkit".to_string()),
+            executors,
+            ..Default::default()
+        };
+
+        let messages: Vec<_> = lint(&orb)
+            .into_iter()
+            .map(|w| (w.rule, w.location, w.message))
+            .collect();
+        assert_eq!(
+            messages,
+            [
+                (
+                    "unpinned-image",
+                    "executors/bare/docker".to_string(),
+                    "docker image 'localhost:5000/cimg/rust' has no tag".to_string()
+                ),
+                (
+                    "unpinned-image",
+                    "executors/bare/docker".to_string(),
+                    "docker image 'cimg/base:latest' uses the mutable 'latest' tag".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_checks_resource_classes() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+version: "2.1"
+executors:
+  linux:
+    docker: [{image: cimg/base:2024.01}]
+    resource_class: larg
+  windows:
+    machine: {image: windows-server-2022-gui:current}
+    resource_class: windows.medium
+  mac:
+    macos: {xcode: 15.4.0}
+    resource_class: macos.x86.medium.gen2
+  runner:
+    machine: true
+    resource_class: my-org/linux-runner
+jobs:
+  build:
+    executor: linux
+    resource_class: windows.large
+    steps: [checkout]
+  gpu:
+    machine: true
+    resource_class: << parameters.size >>
+    steps: [checkout]
+"#,
+        )
+        .unwrap();
+        let found: Vec<_> = lint(&orb)
+            .into_iter()
+            .filter(|w| w.rule.ends_with("resource-class"))
+            .map(|w| (w.location, w.message))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "executors/linux/resource_class".to_string(),
+                    "resource class 'larg' is not a CircleCI resource class, did you mean 'large'?"
+                        .to_string()
+                ),
+                (
+                    "executors/mac/resource_class".to_string(),
+                    "resource class 'macos.x86.medium.gen2' is deprecated; use 'm4pro.medium'"
+                        .to_string()
+                ),
+                (
+                    "jobs/build/resource_class".to_string(),
+                    "resource class 'windows.large' is not offered on docker executors".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_size_rules_follow_limits() {
+        let command: Command = serde_yaml::from_str(
+            r#"
+description: Deploy
+parameters:
+  target:
+    type: string
+    description: Where to deploy
+  target-dir:
+    type: string
+    description: Unused prefix match
+    default: out
+steps:
+  - run: echo << parameters.target >>
+  - run: echo << parameters.target >>
+  - when:
+      condition: << parameters.target >>
+      steps:
+        - run: |
+            echo one
+            echo two
+            echo three
+"#,
+        )
+        .unwrap();
+        let orb = OrbDefinition {
+            description: Some("Toolkit".to_string()),
+            commands: HashMap::from([("deploy".to_string(), command)]),
+            ..Default::default()
+        };
+        let found: Vec<_> = lint(&orb)
+            .into_iter()
+            .map(|w| (w.rule, w.location))
+            .collect();
+        assert_eq!(
+            found,
+            [(
+                "unused-parameter",
+                "commands/deploy/parameters/target-dir".to_string()
+            )]
+        );
+
+        let limits = LintLimits {
+            max_run_lines: 2,
+            max_required_parameter_uses: 2,
+        };
+        let found: Vec<_> = lint_with_limits(&orb, &limits)
+            .into_iter()
+            .map(|w| (w.rule, w.location))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "widely-used-required-parameter",
+                    "commands/deploy/parameters/target".to_string()
+                ),
+                (
+                    "unused-parameter",
+                    "commands/deploy/parameters/target-dir".to_string()
+                ),
+                (
+                    "long-run-step",
+                    "commands/deploy/steps/2/when/steps/0".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_lint_checks_parameter_placeholders() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+description: Toolkit
+commands:
+  deploy:
+    description: Deploy
+    parameters:
+      target:
+        type: string
+        description: Where to deploy
+      dry-run:
+        type: boolean
+        description: Only print the plan
+        default: false
+    steps:
+      - run: echo << parameters.target >> << pipeline.git.branch >>
+      - run: deploy --to << parameters.taget >> <<parameters.region>>
+jobs:
+  build:
+    description: Build
+    executor:
+      name: default
+      tag: << parameters.tag >>
+    parameters:
+      tag:
+        type: string
+        description: Image tag
+    steps: []
+executors:
+  default:
+    description: Default
+    parameters:
+      tag:
+        type: string
+        description: Image tag
+        default: "1.0"
+    docker:
+      - image: cimg/base:<< parameters.tag >>
+"#,
+        )
+        .unwrap();
+        let found: Vec<_> = lint(&orb)
+            .into_iter()
+            .map(|w| (w.rule, w.location, w.message, w.severity))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                (
+                    "unused-parameter",
+                    "commands/deploy/parameters/dry-run".to_string(),
+                    "parameter is declared but never used".to_string(),
+                    Severity::Warn
+                ),
+                (
+                    "undeclared-parameter",
+                    "commands/deploy/steps/1".to_string(),
+                    "'<< parameters.region >>' references a parameter commands/deploy does not \
+                     declare"
+                        .to_string(),
+                    Severity::Error
+                ),
+                (
+                    "undeclared-parameter",
+                    "commands/deploy/steps/1".to_string(),
+                    "'<< parameters.taget >>' references a parameter commands/deploy does not \
+                     declare"
+                        .to_string(),
+                    Severity::Error
+                ),
+            ]
+        );
+
+        let config = LintConfig::default();
+        assert!(lint_with_config(&orb, &config)
+            .iter()
+            .any(|w| w.severity == Severity::Error));
+    }
+}
@@ -0,0 +1,201 @@
+//! Catalog of CircleCI cloud resource classes.
+//!
+//! `resource_class` is a free-form string to the parser, and CircleCI only
+//! rejects an unknown class when the pipeline runs. The catalog lists the
+//! classes each executor type offers, so lint can flag typos, classes the
+//! executor does not offer (e.g. `windows.medium` on docker) and deprecated
+//! classes. Self-hosted runner classes (`<namespace>/<name>`) and
+//! parameterised values are not checked.
+
+use crate::parser::{ExecutorConfig, MachineConfig};
+
+/// Executor types that offer resource classes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutorKind {
+    /// `docker` images
+    Docker,
+    /// `machine` images: Linux, Arm, Windows and GPU
+    Machine,
+    /// `macos` with an Xcode version
+    MacOs,
+}
+
+impl ExecutorKind {
+    /// The executor type `config` declares, if it declares one; jobs that
+    /// name an executor leave it to the executor.
+    pub fn of(config: &ExecutorConfig) -> Option<Self> {
+        if config.docker.is_some() {
+            Some(ExecutorKind::Docker)
+        } else if matches!(
+            config.machine,
+            Some(MachineConfig::Enabled(true) | MachineConfig::Image { .. })
+        ) {
+            Some(ExecutorKind::Machine)
+        } else if config.macos.is_some() {
+            Some(ExecutorKind::MacOs)
+        } else {
+            None
+        }
+    }
+
+    /// Name used in messages, e.g. `docker`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ExecutorKind::Docker => "docker",
+            ExecutorKind::Machine => "machine",
+            ExecutorKind::MacOs => "macos",
+        }
+    }
+}
+
+/// One resource class of the catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResourceClass {
+    /// Value of `resource_class`, e.g. `arm.large`
+    pub name: &'static str,
+    /// Executor types offering the class
+    pub kinds: &'static [ExecutorKind],
+    /// Class to move to, when this one is deprecated
+    pub replacement: Option<&'static str>,
+}
+
+const DOCKER: &[ExecutorKind] = &[ExecutorKind::Docker];
+const MACHINE: &[ExecutorKind] = &[ExecutorKind::Machine];
+const DOCKER_AND_MACHINE: &[ExecutorKind] = &[ExecutorKind::Docker, ExecutorKind::Machine];
+const MACOS: &[ExecutorKind] = &[ExecutorKind::MacOs];
+
+const fn class(name: &'static str, kinds: &'static [ExecutorKind]) -> ResourceClass {
+    ResourceClass {
+        name,
+        kinds,
+        replacement: None,
+    }
+}
+
+const fn deprecated(
+    name: &'static str,
+    kinds: &'static [ExecutorKind],
+    replacement: &'static str,
+) -> ResourceClass {
+    ResourceClass {
+        name,
+        kinds,
+        replacement: Some(replacement),
+    }
+}
+
+/// Every CircleCI cloud resource class.
+pub const RESOURCE_CLASSES: &[ResourceClass] = &[
+    // Linux x86 (docker and machine)
+    class("small", DOCKER),
+    class("medium", DOCKER_AND_MACHINE),
+    class("medium+", DOCKER),
+    class("large", DOCKER_AND_MACHINE),
+    class("xlarge", DOCKER_AND_MACHINE),
+    class("2xlarge", DOCKER_AND_MACHINE),
+    class("2xlarge+", DOCKER),
+    // Arm (docker and machine)
+    class("arm.medium", DOCKER_AND_MACHINE),
+    class("arm.large", DOCKER_AND_MACHINE),
+    class("arm.xlarge", DOCKER_AND_MACHINE),
+    class("arm.2xlarge", DOCKER_AND_MACHINE),
+    // Windows (machine)
+    class("windows.medium", MACHINE),
+    class("windows.large", MACHINE),
+    class("windows.xlarge", MACHINE),
+    class("windows.2xlarge", MACHINE),
+    // GPU (machine)
+    class("gpu.nvidia.small", MACHINE),
+    class("gpu.nvidia.small.gen2", MACHINE),
+    class("gpu.nvidia.small.multi", MACHINE),
+    class("gpu.nvidia.medium", MACHINE),
+    class("gpu.nvidia.medium.multi", MACHINE),
+    class("gpu.nvidia.large", MACHINE),
+    class("windows.gpu.nvidia.medium", MACHINE),
+    deprecated("gpu.small", MACHINE, "gpu.nvidia.small"),
+    deprecated("gpu.medium", MACHINE, "gpu.nvidia.medium"),
+    deprecated("gpu.large", MACHINE, "gpu.nvidia.large"),
+    // macOS
+    class("m2pro.medium", MACOS),
+    class("m2pro.large", MACOS),
+    class("m4pro.medium", MACOS),
+    class("m4pro.large", MACOS),
+    deprecated("macos.m1.medium.gen1", MACOS, "m4pro.medium"),
+    deprecated("macos.m1.large.gen1", MACOS, "m4pro.large"),
+    deprecated("macos.x86.medium.gen2", MACOS, "m4pro.medium"),
+    deprecated("macos.x86.metal.gen1", MACOS, "m4pro.large"),
+];
+
+/// The catalog entry named `name`.
+pub fn find(name: &str) -> Option<&'static ResourceClass> {
+    RESOURCE_CLASSES.iter().find(|class| class.name == name)
+}
+
+/// Whether `name` is outside the catalog's scope: a self-hosted runner
+/// class or a value containing a `<< ... >>` placeholder.
+pub fn is_unchecked(name: &str) -> bool {
+    name.contains('/') || name.contains("<<")
+}
+
+/// Current (not deprecated) classes offered on `kind`, or on any executor
+/// type when `kind` is `None`.
+pub fn current(kind: Option<ExecutorKind>) -> impl Iterator<Item = &'static str> {
+    RESOURCE_CLASSES
+        .iter()
+        .filter(move |class| {
+            class.replacement.is_none() && kind.is_none_or(|kind| class.kinds.contains(&kind))
+        })
+        .map(|class| class.name)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_catalog_names_are_unique_and_replacements_are_current() {
+        for (index, class) in RESOURCE_CLASSES.iter().enumerate() {
+            assert!(!class.kinds.is_empty());
+            assert!(
+                RESOURCE_CLASSES[..index]
+                    .iter()
+                    .all(|earlier| earlier.name != class.name),
+                "{} is listed twice",
+                class.name
+            );
+            if let Some(replacement) = class.replacement {
+                let replacement = find(replacement).expect("replacement is in the catalog");
+                assert_eq!(replacement.replacement, None);
+                assert!(replacement.kinds.iter().all(|k| class.kinds.contains(k)));
+            }
+        }
+    }
+
+    #[test]
+    fn test_executor_kind_and_current_classes() {
+        let config = |yaml: &str| -> ExecutorConfig { serde_yaml::from_str(yaml).unwrap() };
+        assert_eq!(
+            ExecutorKind::of(&config("docker: [{image: cimg/base:2024.01}]")),
+            Some(ExecutorKind::Docker)
+        );
+        assert_eq!(
+            ExecutorKind::of(&config("machine: {image: windows-server-2022-gui:current}")),
+            Some(ExecutorKind::Machine)
+        );
+        assert_eq!(ExecutorKind::of(&config("machine: false")), None);
+        assert_eq!(
+            ExecutorKind::of(&config("macos: {xcode: 15.4.0}")),
+            Some(ExecutorKind::MacOs)
+        );
+        assert_eq!(ExecutorKind::of(&config("resource_class: large")), None);
+
+        let machine: Vec<_> = current(Some(ExecutorKind::Machine)).collect();
+        assert!(machine.contains(&"windows.medium") && machine.contains(&"arm.large"));
+        assert!(!machine.contains(&"small") && !machine.contains(&"gpu.small"));
+        assert!(current(None).any(|name| name == "m4pro.medium"));
+
+        assert!(is_unchecked("my-org/runner"));
+        assert!(is_unchecked("<< parameters.size >>"));
+        assert!(!is_unchecked("large"));
+    }
+}
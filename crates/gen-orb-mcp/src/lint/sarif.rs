@@ -0,0 +1,230 @@
+//! SARIF 2.1.0 reports for `validate --format sarif`.
+//!
+//! Each lint finding becomes a SARIF result. It has a physical location
+//! (file and line, resolved through [`source::locate`]) where the
+//! definition can be found, and a logical location holding the definition
+//! path. Unresolved command, executor and parameter references are reported as errors
+//! under [`UNRESOLVED_REFERENCE_RULE`]. The report can be uploaded to GitHub
+//! code scanning or any other SARIF consumer.
+
+use std::path::Path;
+
+use serde_json::{json, Value};
+
+use super::{rule_description, source, LintWarning, Severity, RULES};
+use crate::parser::{references::UnresolvedReference, ParseError};
+
+/// Rule id used when the orb cannot be parsed at all.
+pub const PARSE_ERROR_RULE: &str = "parse-error";
+
+/// Rule id for command, executor and parameter references that do not
+/// resolve.
+pub const UNRESOLVED_REFERENCE_RULE: &str = "unresolved-reference";
+
+const SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const INFORMATION_URI: &str = "https://github.com/jerus-org/gen-orb-mcp";
+
+/// Build a SARIF log for the `unresolved` references and lint `warnings` of
+/// the orb at `orb_path`.
+pub fn report(
+    orb_path: &Path,
+    unresolved: &[UnresolvedReference],
+    warnings: &[LintWarning],
+) -> Value {
+    let locate = |location: &str| source::locate_nearest(orb_path, location);
+    let references = unresolved.iter().map(|r| {
+        let (file, line) = locate(&r.location);
+        result(
+            UNRESOLVED_REFERENCE_RULE,
+            "error",
+            &r.message(),
+            &file,
+            line,
+            Some(&r.location),
+        )
+    });
+    let findings = warnings.iter().map(|w| {
+        let level = match w.severity {
+            Severity::Error => "error",
+            _ => "warning",
+        };
+        let (file, line) = locate(&w.location);
+        result(w.rule, level, &w.message, &file, line, Some(&w.location))
+    });
+    log(references.chain(findings).collect())
+}
+
+/// Build a SARIF log with an error for each parse error of an orb that
+/// failed to parse.
+///
+/// Errors in one definition of a packed orb are located at that definition,
+/// and unknown keys of strict parsing at the key.
+pub fn parse_failures(orb_path: &Path, errors: &[ParseError]) -> Value {
+    let results = errors.iter().map(|e| {
+        let (file, line, location) = match e {
+            ParseError::InvalidDefinition { location, .. } => {
+                let (file, line) = source::locate_nearest(orb_path, location);
+                (file, line, Some(location.as_str()))
+            }
+            ParseError::UnknownKey { location, key, .. } => {
+                let (file, line) = source::locate_nearest(orb_path, &format!("{location}/{key}"));
+                (file, line, Some(location.as_str()))
+            }
+            _ => match e.file() {
+                Some((file, line)) => (file.to_path_buf(), line, None),
+                None => (orb_path.to_path_buf(), 1, None),
+            },
+        };
+        result(
+            PARSE_ERROR_RULE,
+            "error",
+            &e.to_string(),
+            &file,
+            line,
+            location,
+        )
+    });
+    log(results.collect())
+}
+
+fn log(results: Vec<Value>) -> Value {
+    let rules: Vec<Value> = RULES
+        .iter()
+        .map(|id| (*id, rule_description(id)))
+        .chain([
+            (PARSE_ERROR_RULE, "The orb could not be parsed"),
+            (
+                UNRESOLVED_REFERENCE_RULE,
+                "Invoked command, job executor or passed parameter is not defined, an enum \
+                 default is not an allowed value, or a condition is malformed",
+            ),
+        ])
+        .map(|(id, description)| {
+            json!({
+                "id": id,
+                "shortDescription": { "text": description },
+            })
+        })
+        .collect();
+    json!({
+        "$schema": SCHEMA,
+        "version": "2.1.0",
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": env!("CARGO_PKG_NAME"),
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }]
+    })
+}
+
+fn result(
+    rule: &str,
+    level: &str,
+    message: &str,
+    file: &Path,
+    line: usize,
+    location: Option<&str>,
+) -> Value {
+    let mut location_json = json!({
+        "physicalLocation": {
+            "artifactLocation": { "uri": artifact_uri(file) },
+            "region": { "startLine": line },
+        }
+    });
+    if let Some(location) = location {
+        location_json["logicalLocations"] = json!([{ "fullyQualifiedName": location }]);
+    }
+    json!({
+        "ruleId": rule,
+        "level": level,
+        "message": { "text": message },
+        "locations": [location_json],
+    })
+}
+
+/// Relative, forward-slash URI for `file` as SARIF consumers expect.
+fn artifact_uri(file: &Path) -> String {
+    let relative = std::env::current_dir()
+        .ok()
+        .and_then(|cwd| file.strip_prefix(cwd).ok())
+        .unwrap_or(file);
+    relative
+        .to_string_lossy()
+        .replace('\\', "/")
+        .trim_start_matches("./")
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_report_locates_findings() {
+        let dir = TempDir::new().unwrap();
+        let orb_path = dir.path().join("orb.yml");
+        std::fs::write(
+            &orb_path,
+            "version: 2.1\ncommands:\n  greet:\n    parameters:\n      who:\n        type: string\n",
+        )
+        .unwrap();
+        let warnings = vec![LintWarning {
+            rule: "undocumented-parameter",
+            location: "commands/greet/parameters/who".to_string(),
+            message: "parameter has no description".to_string(),
+            severity: Severity::Error,
+        }];
+
+        let sarif = report(&orb_path, &[], &warnings);
+        assert_eq!(sarif["version"], "2.1.0");
+        let result = &sarif["runs"][0]["results"][0];
+        assert_eq!(result["ruleId"], "undocumented-parameter");
+        assert_eq!(result["level"], "error");
+        let location = &result["locations"][0];
+        assert_eq!(location["physicalLocation"]["region"]["startLine"], 5);
+        assert!(location["physicalLocation"]["artifactLocation"]["uri"]
+            .as_str()
+            .unwrap()
+            .ends_with("orb.yml"));
+        assert_eq!(
+            location["logicalLocations"][0]["fullyQualifiedName"],
+            "commands/greet/parameters/who"
+        );
+        let rules = sarif["runs"][0]["tool"]["driver"]["rules"]
+            .as_array()
+            .unwrap();
+        assert_eq!(rules.len(), RULES.len() + 2);
+    }
+
+    #[test]
+    fn test_parse_failures_are_errors_at_their_files() {
+        let errors = [
+            ParseError::MissingFile {
+                path: "src/@orb.yml".into(),
+            },
+            ParseError::yaml(
+                Path::new("src/jobs/build.yml"),
+                serde_yaml::from_str::<Vec<u8>>("\n[\n").unwrap_err(),
+            ),
+        ];
+        let sarif = parse_failures(Path::new("src/@orb.yml"), &errors);
+        let results = sarif["runs"][0]["results"].as_array().unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0]["ruleId"], PARSE_ERROR_RULE);
+        let physical = |i: usize| &results[i]["locations"][0]["physicalLocation"];
+        assert_eq!(physical(0)["artifactLocation"]["uri"], "src/@orb.yml");
+        assert_eq!(physical(1)["artifactLocation"]["uri"], "src/jobs/build.yml");
+        assert_eq!(
+            physical(1)["region"]["startLine"],
+            errors[1].file().unwrap().1
+        );
+    }
+}
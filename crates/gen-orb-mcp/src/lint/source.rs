@@ -0,0 +1,406 @@
+//! Line-level view of orb YAML files.
+//!
+//! Lint findings name definitions by location (`jobs/build/parameters/tag`).
+//! This module walks the raw YAML to map such locations back to a file and
+//! line, and to pick up inline `# gen-orb-mcp: allow(...)` comments. The walk
+//! tracks mapping keys by indentation and skips block scalars, which is
+//! enough for the layouts orb authors write by hand.
+
+use std::{
+    collections::BTreeMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A YAML file belonging to an orb, with the location its keys sit under.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrbFile {
+    /// File path.
+    pub path: PathBuf,
+    /// Location prefix (e.g. `commands/greet` for `commands/greet.yml` in an
+    /// unpacked orb); empty for the root file.
+    pub prefix: String,
+}
+
+/// The files making up the orb at `orb_path`.
+///
+/// Accepts the same paths as [`OrbParser::parse`](crate::parser::OrbParser):
+/// a packed orb file, an unpacked orb directory or its `@orb.yml`.
+pub fn orb_files(orb_path: &Path) -> Vec<OrbFile> {
+    let orb_dir = if orb_path.is_dir() {
+        Some(orb_path)
+    } else if orb_path.file_name().is_some_and(|f| f == "@orb.yml") {
+        orb_path.parent()
+    } else {
+        None
+    };
+    let Some(orb_dir) = orb_dir else {
+        return vec![OrbFile {
+            path: orb_path.to_path_buf(),
+            prefix: String::new(),
+        }];
+    };
+
+    let mut files = vec![OrbFile {
+        path: orb_dir.join("@orb.yml"),
+        prefix: String::new(),
+    }];
+    for category in ["commands", "jobs", "executors"] {
+        let Ok(entries) = fs::read_dir(orb_dir.join(category)) else {
+            continue;
+        };
+        let mut paths: Vec<PathBuf> = entries
+            .filter_map(|e| e.ok().map(|e| e.path()))
+            .filter(|p| p.extension().is_some_and(|e| e == "yml" || e == "yaml"))
+            .collect();
+        paths.sort();
+        files.extend(paths.into_iter().map(|path| {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+            OrbFile {
+                prefix: format!("{category}/{stem}"),
+                path,
+            }
+        }));
+    }
+    files
+}
+
+/// A mapping key found in a YAML file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyLine<'a> {
+    /// 1-based line number.
+    pub line: usize,
+    /// Location of the key (prefix and enclosing keys joined with `/`).
+    pub path: String,
+    /// Rules allowed by comments on the preceding lines or at the line end.
+    pub allowed: Vec<&'a str>,
+}
+
+/// Keys and allow comments of one YAML file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct YamlOutline<'a> {
+    /// Rules allowed by comments before the first key (whole file).
+    pub header_allowed: Vec<&'a str>,
+    /// Every mapping key, in file order.
+    pub keys: Vec<KeyLine<'a>>,
+}
+
+/// Walk YAML `content`, recording each mapping key under `prefix`.
+pub fn outline<'a>(content: &'a str, prefix: &str) -> YamlOutline<'a> {
+    let mut outline = YamlOutline::default();
+    let mut stack: Vec<(usize, &str)> = Vec::new();
+    let mut pending: Vec<&str> = Vec::new();
+    let mut block_indent: Option<usize> = None;
+
+    for (index, line) in content.lines().enumerate() {
+        let trimmed = line.trim_start();
+        let indent = line.len() - trimmed.len();
+        if trimmed.is_empty() {
+            continue;
+        }
+        if let Some(block) = block_indent {
+            if indent > block {
+                continue;
+            }
+            block_indent = None;
+        }
+        if trimmed.starts_with('#') {
+            if let Some(rules) = allowed_rules(trimmed) {
+                if outline.keys.is_empty() {
+                    outline.header_allowed.extend(rules);
+                } else {
+                    pending.extend(rules);
+                }
+            }
+            continue;
+        }
+        let Some((key, rest)) = mapping_key(trimmed) else {
+            continue;
+        };
+        while stack.last().is_some_and(|&(i, _)| i >= indent) {
+            stack.pop();
+        }
+        stack.push((indent, key));
+
+        let value = rest.split(" #").next().unwrap_or_default().trim();
+        if value.starts_with('|') || value.starts_with('>') {
+            block_indent = Some(indent);
+        }
+        let trailing = rest.find('#').and_then(|i| allowed_rules(&rest[i..]));
+        let path = (!prefix.is_empty())
+            .then_some(prefix)
+            .into_iter()
+            .chain(stack.iter().map(|&(_, k)| k))
+            .collect::<Vec<_>>()
+            .join("/");
+        outline.keys.push(KeyLine {
+            line: index + 1,
+            path,
+            allowed: pending
+                .drain(..)
+                .chain(trailing.unwrap_or_default())
+                .collect(),
+        });
+    }
+    outline
+}
+
+/// Like [`locate`], falling back to the nearest enclosing location that can
+/// be found, then to the first line of `orb_path`.
+///
+/// Step invocations are list items rather than mapping keys, so they resolve
+/// to their enclosing `steps` key.
+pub fn locate_nearest(orb_path: &Path, location: &str) -> (PathBuf, usize) {
+    std::iter::successors(Some(location), |l| {
+        l.rsplit_once('/').map(|(parent, _)| parent)
+    })
+    .find_map(|l| locate(orb_path, l))
+    .unwrap_or((orb_path.to_path_buf(), 1))
+}
+
+/// The file and 1-based line defining `location` in the orb at `orb_path`.
+///
+/// `orb` maps to the root file's `description` key (or its first line).
+/// Returns `None` when the location cannot be found.
+pub fn locate(orb_path: &Path, location: &str) -> Option<(PathBuf, usize)> {
+    let files = orb_files(orb_path);
+    if location == "orb" {
+        let root = files.first()?;
+        let content = fs::read_to_string(&root.path).ok()?;
+        let line = outline(&content, "")
+            .keys
+            .iter()
+            .find(|k| k.path == "description")
+            .map_or(1, |k| k.line);
+        return Some((root.path.clone(), line));
+    }
+    for file in &files {
+        if file.prefix == location {
+            return Some((file.path.clone(), 1));
+        }
+        if !file.prefix.is_empty() && !location.starts_with(&format!("{}/", file.prefix)) {
+            continue;
+        }
+        let Ok(content) = fs::read_to_string(&file.path) else {
+            continue;
+        };
+        if let Some(key) = outline(&content, &file.prefix)
+            .keys
+            .iter()
+            .find(|k| k.path == location)
+        {
+            return Some((file.path.clone(), key.line));
+        }
+    }
+    None
+}
+
+/// YAML text of each command, job and executor in the orb at `orb_path`,
+/// keyed by location (`commands/greet`).
+///
+/// Unpacked definitions are their whole file. Definitions inline in the root
+/// file are the lines under their key, dedented, with trailing blank and
+/// comment lines dropped. Unreadable files and flow-style definitions are
+/// skipped.
+pub fn definition_sources(orb_path: &Path) -> BTreeMap<String, String> {
+    let mut sources = BTreeMap::new();
+    for file in orb_files(orb_path) {
+        let Ok(content) = fs::read_to_string(&file.path) else {
+            continue;
+        };
+        if file.prefix.is_empty() {
+            sources.extend(inline_definition_sources(&content));
+        } else {
+            sources.insert(file.prefix, content);
+        }
+    }
+    sources
+}
+
+/// YAML text of each command, job and executor defined inline in the root
+/// orb file `content` (e.g. a packed orb), as for [`definition_sources`].
+pub fn inline_definition_sources(content: &str) -> BTreeMap<String, String> {
+    let mut sources = BTreeMap::new();
+    let lines: Vec<&str> = content.lines().collect();
+    let keys = outline(content, "").keys;
+    for (index, key) in keys.iter().enumerate() {
+        let is_definition = key.path.split_once('/').is_some_and(|(category, name)| {
+            ["commands", "jobs", "executors"].contains(&category) && !name.contains('/')
+        });
+        if !is_definition {
+            continue;
+        }
+        let end = keys[index + 1..]
+            .iter()
+            .find(|k| k.path.matches('/').count() < 2)
+            .map_or(lines.len(), |k| k.line - 1);
+        let key_indent = indent_of(lines[key.line - 1]);
+        let mut body = &lines[key.line..end];
+        while let Some((last, rest)) = body.split_last() {
+            let trimmed = last.trim_start();
+            if !trimmed.is_empty() && (indent_of(last) > key_indent || !trimmed.starts_with('#')) {
+                break;
+            }
+            body = rest;
+        }
+        let Some(dedent) = body
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| indent_of(l))
+            .min()
+        else {
+            continue;
+        };
+        let yaml: String = body
+            .iter()
+            .map(|l| format!("{}\n", l.get(dedent..).unwrap_or_default()))
+            .collect();
+        sources.insert(key.path.clone(), yaml);
+    }
+    sources
+}
+
+/// Number of leading spaces on `line`.
+fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start().len()
+}
+
+/// Rules named by a `# gen-orb-mcp: allow(a, b)` comment.
+fn allowed_rules(comment: &str) -> Option<Vec<&str>> {
+    let body = comment.trim_start_matches('#').trim();
+    let args = body
+        .strip_prefix("gen-orb-mcp:")?
+        .trim()
+        .strip_prefix("allow(")?;
+    let args = &args[..args.find(')')?];
+    Some(
+        args.split(',')
+            .map(str::trim)
+            .filter(|r| !r.is_empty())
+            .collect(),
+    )
+}
+
+/// Split a `key: value` line into the unquoted key and the rest.
+fn mapping_key(line: &str) -> Option<(&str, &str)> {
+    if line.starts_with('-') {
+        return None;
+    }
+    let colon = line
+        .find(": ")
+        .or_else(|| line.strip_suffix(':').map(str::len))?;
+    let key = line[..colon].trim().trim_matches(|c| c == '"' || c == '\'');
+    (!key.is_empty() && !key.contains(' ')).then(|| (key, &line[colon + 1..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    const PACKED: &str = "\
+version: 2.1
+description: Toolkit
+jobs:
+  # gen-orb-mcp: allow(missing-description)
+  legacy:
+    parameters:
+      tag: # gen-orb-mcp: allow(undocumented-parameter)
+        type: string
+    steps:
+      - run:
+          command: |
+            # gen-orb-mcp: allow(missing-description)
+            echo: hi
+";
+
+    #[test]
+    fn test_outline_tracks_key_paths_and_allows() {
+        let outline = outline(PACKED, "");
+        let paths: Vec<_> = outline
+            .keys
+            .iter()
+            .map(|k| (k.line, k.path.as_str()))
+            .collect();
+        assert_eq!(
+            paths,
+            [
+                (1, "version"),
+                (2, "description"),
+                (3, "jobs"),
+                (5, "jobs/legacy"),
+                (6, "jobs/legacy/parameters"),
+                (7, "jobs/legacy/parameters/tag"),
+                (8, "jobs/legacy/parameters/tag/type"),
+                (9, "jobs/legacy/steps"),
+                (11, "jobs/legacy/steps/command"),
+            ]
+        );
+        assert_eq!(outline.keys[3].allowed, ["missing-description"]);
+        assert_eq!(outline.keys[5].allowed, ["undocumented-parameter"]);
+        assert!(outline.header_allowed.is_empty());
+    }
+
+    #[test]
+    fn test_locate_packed_and_unpacked() {
+        let dir = TempDir::new().unwrap();
+        let packed = dir.path().join("orb.yml");
+        fs::write(&packed, PACKED).unwrap();
+        assert_eq!(
+            locate(&packed, "jobs/legacy/parameters/tag"),
+            Some((packed.clone(), 7))
+        );
+        assert_eq!(locate(&packed, "orb"), Some((packed.clone(), 2)));
+        assert_eq!(locate(&packed, "jobs/missing"), None);
+
+        let unpacked = dir.path().join("src");
+        fs::create_dir_all(unpacked.join("commands")).unwrap();
+        fs::write(unpacked.join("@orb.yml"), "version: 2.1\n").unwrap();
+        let greet = unpacked.join("commands/greet.yml");
+        fs::write(&greet, "parameters:\n  who:\n    type: string\n").unwrap();
+        assert_eq!(
+            locate(&unpacked.join("@orb.yml"), "commands/greet/parameters/who"),
+            Some((greet.clone(), 2))
+        );
+        assert_eq!(locate(&unpacked, "commands/greet"), Some((greet, 1)));
+    }
+
+    #[test]
+    fn test_definition_sources_packed_and_unpacked() {
+        let dir = TempDir::new().unwrap();
+        let packed = dir.path().join("orb.yml");
+        fs::write(&packed, PACKED).unwrap();
+        let sources = definition_sources(&packed);
+        assert_eq!(sources.keys().collect::<Vec<_>>(), ["jobs/legacy"]);
+        assert_eq!(
+            sources["jobs/legacy"],
+            concat!(
+                "parameters:\n",
+                "  tag: # gen-orb-mcp: allow(undocumented-parameter)\n",
+                "    type: string\n",
+                "steps:\n",
+                "  - run:\n",
+                "      command: |\n",
+                "        # gen-orb-mcp: allow(missing-description)\n",
+                "        echo: hi\n",
+            )
+        );
+
+        let unpacked = dir.path().join("src");
+        fs::create_dir_all(unpacked.join("commands")).unwrap();
+        fs::write(
+            unpacked.join("@orb.yml"),
+            "version: 2.1\nexecutors:\n  default:\n    docker:\n      - image: cimg/base\n\n# trailing\n",
+        )
+        .unwrap();
+        let greet = "parameters:\n  who:\n    type: string\n";
+        fs::write(unpacked.join("commands/greet.yml"), greet).unwrap();
+        let sources = definition_sources(&unpacked);
+        assert_eq!(sources["commands/greet"], greet);
+        assert_eq!(
+            sources["executors/default"],
+            "docker:\n  - image: cimg/base\n"
+        );
+    }
+}
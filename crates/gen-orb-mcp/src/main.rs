@@ -1,9 +1,10 @@
-use anyhow::Result;
+use std::process::ExitCode;
+
 use clap::Parser;
-use gen_orb_mcp::Cli;
+use gen_orb_mcp::{failure, Cli};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-fn main() -> Result<()> {
+fn main() -> ExitCode {
     // tracing_subscriber::init() calls LogTracer::init() automatically when
     // the tracing-log feature is active (unified via dependency tree).
     // Calling it manually beforehand causes a SetLoggerError panic.
@@ -12,9 +13,17 @@ fn main() -> Result<()> {
             tracing_subscriber::EnvFilter::try_from_default_env()
                 .unwrap_or_else(|_| "gen_orb_mcp=info".into()),
         )
-        .with(tracing_subscriber::fmt::layer())
+        // Logs go to stderr so stdout carries only results (e.g. --json).
+        .with(tracing_subscriber::fmt::layer().with_writer(std::io::stderr))
         .init();
 
     let cli = Cli::parse();
-    cli.run()
+    match cli.run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            // The same report `fn main() -> Result<()>` prints.
+            eprintln!("Error: {e:?}");
+            ExitCode::from(failure::exit_code(&e))
+        }
+    }
 }
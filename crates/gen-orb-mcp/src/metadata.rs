@@ -0,0 +1,333 @@
+//! Generation metadata written next to generated output.
+//!
+//! `generate` writes `gen-orb-mcp.json` into the output directory. It records
+//! the generator version, where the orb came from (path, revision and a hash
+//! of its source files), the hash of the generated files and the options
+//! that shaped them, so later runs and external tooling can tell how an
+//! output was produced and whether it is stale.
+//!
+//! The `generated_at` timestamp honours `SOURCE_DATE_EPOCH`, so reproducible
+//! builds produce byte-identical metadata.
+
+use std::{
+    fs, io,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::generator::{DefinitionCategory, Language, Transport};
+
+/// File name of the metadata sidecar in the output directory.
+pub const METADATA_FILE: &str = "gen-orb-mcp.json";
+
+/// Contents of `gen-orb-mcp.json`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GenerationMetadata {
+    /// The tool that produced the output.
+    pub generator: GeneratorInfo,
+    /// The orb the output was generated from.
+    pub orb: OrbSource,
+    /// The generated crate or package.
+    pub output: OutputInfo,
+    /// Resolved `generate` options (after profiles and defaults).
+    pub options: ResolvedOptions,
+    /// RFC 3339 UTC time of generation, or of `SOURCE_DATE_EPOCH` when set.
+    pub generated_at: String,
+}
+
+/// Name and version of gen-orb-mcp.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GeneratorInfo {
+    /// Always `gen-orb-mcp`.
+    pub name: String,
+    /// gen-orb-mcp version.
+    pub version: String,
+}
+
+impl Default for GeneratorInfo {
+    fn default() -> Self {
+        Self {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+        }
+    }
+}
+
+/// Where the orb came from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OrbSource {
+    /// Orb path as given on the command line, or the `--orb-ref` registry
+    /// reference pinned to the fetched version.
+    pub path: PathBuf,
+    /// Orb repository revision, when known.
+    pub source_rev: Option<String>,
+    /// SHA-256 of the orb source files (see [`orb_content_hash`]).
+    pub content_hash: String,
+    /// Further orbs merged into the same server by repeating `--orb-path`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub merged: Vec<OrbSource>,
+}
+
+/// The generated crate.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OutputInfo {
+    /// Orb name used for the server.
+    pub orb_name: String,
+    /// Generated crate (or npm package) name.
+    pub crate_name: String,
+    /// Generated crate version.
+    pub version: String,
+    /// `source` or `binary`.
+    pub format: String,
+    /// SHA-256 of the generated files
+    /// ([`GeneratedServer::content_hash`](crate::generator::GeneratedServer::content_hash)).
+    pub content_hash: String,
+}
+
+/// `generate` options that affect the generated files.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ResolvedOptions {
+    /// Language of the generated server.
+    #[serde(default)]
+    pub language: Language,
+    /// Directory of template overrides.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub templates_dir: Option<PathBuf>,
+    /// Definition categories exposed; empty means all.
+    pub only: Vec<DefinitionCategory>,
+    /// `<category>/<name>` globs a definition had to match.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub include: Vec<String>,
+    /// `<category>/<name>` globs that hid definitions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exclude: Vec<String>,
+    /// Prior versions embedded alongside the current one.
+    pub prior_versions: Vec<String>,
+    /// Migrations directory supplying conformance rules.
+    pub migrations: Option<PathBuf>,
+    /// Previous orb summarised in `orb://changes`.
+    pub previous_orb: Option<PathBuf>,
+    /// Friendly server title.
+    pub server_title: Option<String>,
+    /// Custom server instructions.
+    pub server_instructions: Option<String>,
+    /// Whether invalid orb names were rejected rather than sanitized.
+    pub strict_names: bool,
+    /// Whether a proptest suite was emitted.
+    pub property_tests: bool,
+    /// SPDX license expression.
+    pub license: Option<String>,
+    /// License text copied into the crate.
+    pub license_file: Option<PathBuf>,
+    /// Logo path or URL.
+    pub logo: Option<String>,
+    /// cargo-binstall download URL template.
+    pub binstall_pkg_url: Option<String>,
+    /// Resource split threshold in bytes.
+    pub max_resource_bytes: Option<usize>,
+    /// Whether the server was generated sandboxed.
+    pub sandboxed: bool,
+    /// Transport the server speaks MCP over.
+    #[serde(default)]
+    pub transport: Transport,
+    /// Default bind address of an HTTP server.
+    #[serde(default)]
+    pub http_bind: Option<SocketAddr>,
+    /// Whether an HTTP server compresses its responses.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub http_compression: bool,
+    /// Largest request body an HTTP server accepts, in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub http_body_limit: Option<usize>,
+    /// Imported orbs served alongside the orb, pinned to the fetched
+    /// versions.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub imported_orbs: Vec<String>,
+}
+
+impl GenerationMetadata {
+    /// Write the metadata to `dir/gen-orb-mcp.json`.
+    pub fn write_to(&self, dir: &Path) -> io::Result<PathBuf> {
+        let path = dir.join(METADATA_FILE);
+        let mut json = serde_json::to_string_pretty(self).map_err(io::Error::other)?;
+        json.push('\n');
+        fs::write(&path, json)?;
+        Ok(path)
+    }
+
+    /// Read `dir/gen-orb-mcp.json`, or `None` when the output has none.
+    pub fn read_from(dir: &Path) -> io::Result<Option<Self>> {
+        let path = dir.join(METADATA_FILE);
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&path)?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// SHA-256 over the orb sources at `orb_path`, as a hex string.
+///
+/// A packed orb hashes its single file. An unpacked orb (a directory or its
+/// `@orb.yml`) hashes every non-hidden file under the orb directory, with
+/// relative paths, in path order.
+pub fn orb_content_hash(orb_path: &Path) -> io::Result<String> {
+    let root = if orb_path.is_dir() {
+        Some(orb_path)
+    } else if orb_path.file_name().is_some_and(|f| f == "@orb.yml") {
+        orb_path.parent()
+    } else {
+        None
+    };
+    let mut files = Vec::new();
+    match root {
+        Some(root) => collect_files(root, &mut files)?,
+        None => files.push(orb_path.to_path_buf()),
+    }
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for file in &files {
+        let content = fs::read(file)?;
+        let relative = root.and_then(|r| file.strip_prefix(r).ok()).unwrap_or(file);
+        hash_file(&mut hasher, relative, &content);
+    }
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+/// SHA-256 of packed orb `content` that was not read from a file, hashed as
+/// [`orb_content_hash`] hashes a packed orb at `source_path`.
+pub fn packed_content_hash(source_path: &Path, content: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hash_file(&mut hasher, source_path, content);
+    format!("{:x}", hasher.finalize())
+}
+
+fn hash_file(hasher: &mut Sha256, path: &Path, content: &[u8]) {
+    hasher.update(path.to_string_lossy().replace('\\', "/").as_bytes());
+    hasher.update([0]);
+    hasher.update((content.len() as u64).to_le_bytes());
+    hasher.update(content);
+}
+
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .file_name()
+            .is_some_and(|n| n.to_string_lossy().starts_with('.'))
+        {
+            continue;
+        }
+        if path.is_dir() {
+            collect_files(&path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// RFC 3339 UTC timestamp for `generated_at`: `SOURCE_DATE_EPOCH` when set
+/// to a valid number of seconds, otherwise the current time.
+pub fn generated_at() -> String {
+    let seconds = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|s| s.trim().parse::<i64>().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs() as i64)
+                .unwrap_or_default()
+        });
+    format_timestamp(seconds)
+}
+
+fn format_timestamp(seconds: i64) -> String {
+    chrono::DateTime::from_timestamp(seconds, 0)
+        .unwrap_or_default()
+        .to_rfc3339_opts(chrono::SecondsFormat::Secs, true)
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_orb_content_hash_covers_unpacked_sources() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("commands")).unwrap();
+        fs::write(dir.path().join("@orb.yml"), "version: 2.1\n").unwrap();
+        fs::write(dir.path().join("commands/greet.yml"), "steps: []\n").unwrap();
+        fs::write(dir.path().join(".DS_Store"), "ignored").unwrap();
+
+        let entry = dir.path().join("@orb.yml");
+        let hash = orb_content_hash(&entry).unwrap();
+        assert_eq!(hash, orb_content_hash(dir.path()).unwrap());
+
+        fs::write(dir.path().join(".DS_Store"), "still ignored").unwrap();
+        assert_eq!(hash, orb_content_hash(&entry).unwrap());
+
+        fs::write(dir.path().join("commands/greet.yml"), "steps: [checkout]\n").unwrap();
+        assert_ne!(hash, orb_content_hash(&entry).unwrap());
+    }
+
+    #[test]
+    fn test_packed_content_hash_matches_file_hash() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("orb.yml");
+        fs::write(&path, "version: 2.1\n").unwrap();
+        assert_eq!(
+            packed_content_hash(&path, b"version: 2.1\n"),
+            orb_content_hash(&path).unwrap()
+        );
+        assert_ne!(
+            packed_content_hash(Path::new("<stdin>"), b"version: 2.1\n"),
+            orb_content_hash(&path).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_metadata_round_trips() {
+        let dir = TempDir::new().unwrap();
+        assert_eq!(GenerationMetadata::read_from(dir.path()).unwrap(), None);
+
+        let metadata = GenerationMetadata {
+            generator: GeneratorInfo::default(),
+            orb: OrbSource {
+                path: PathBuf::from("src/@orb.yml"),
+                source_rev: Some("0123abc".to_string()),
+                content_hash: "ab".repeat(32),
+                merged: vec![],
+            },
+            output: OutputInfo {
+                orb_name: "toolkit".to_string(),
+                crate_name: "toolkit_mcp".to_string(),
+                version: "1.2.0".to_string(),
+                format: "source".to_string(),
+                content_hash: "cd".repeat(32),
+            },
+            options: ResolvedOptions {
+                only: vec![DefinitionCategory::Jobs],
+                ..Default::default()
+            },
+            generated_at: format_timestamp(1_700_000_000),
+        };
+        let path = metadata.write_to(dir.path()).unwrap();
+        assert_eq!(path, dir.path().join(METADATA_FILE));
+        let json = fs::read_to_string(&path).unwrap();
+        assert!(json.contains("\"generated_at\": \"2023-11-14T22:13:20Z\""));
+        assert!(json.contains("\"only\": [\n      \"jobs\"\n    ]"));
+        assert_eq!(
+            GenerationMetadata::read_from(dir.path()).unwrap(),
+            Some(metadata)
+        );
+    }
+}
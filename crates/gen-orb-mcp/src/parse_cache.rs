@@ -0,0 +1,270 @@
+//! Content-addressed cache of parsed orbs.
+//!
+//! `validate`, `generate` and `diff` with `--parse-cache` key each parse by a
+//! hash of the orb's source tree (every file under an unpacked orb's
+//! directory, or the single file of a packed orb, config, action or
+//! template), the kind of source, whether it was parsed strictly and the
+//! gen-orb-mcp version. When `<DIR>/<key>.json` already holds the parsed
+//! definition it is read instead of the YAML, so the later steps of a
+//! pipeline do not parse an orb an earlier step has already read.
+//!
+//! ```text
+//! <DIR>/
+//! └── 9a04…7c.json
+//! ```
+//!
+//! Only successful parses are stored; a source that fails to parse is
+//! parsed again each time so its errors are reported in full.
+
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use sha2::{Digest, Sha256};
+
+use crate::parser::{OrbDefinition, SourceKind};
+
+/// A cache directory of parsed orb definitions.
+#[derive(Debug, Clone)]
+pub struct ParseCache {
+    dir: PathBuf,
+}
+
+impl ParseCache {
+    /// Use `dir` as the cache root; it is created on first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `$XDG_CACHE_HOME/gen-orb-mcp/parsed`, falling back to
+    /// `$HOME/.cache/gen-orb-mcp/parsed` and then the temporary directory.
+    pub fn default_dir() -> PathBuf {
+        crate::registry::cache_home().join("parsed")
+    }
+
+    /// Cache key for parsing the source at `path` as `kind`, strictly or not.
+    ///
+    /// An unpacked orb (a directory or its `@orb.yml`) is hashed with every
+    /// file under its directory, by relative path, so partials and
+    /// `<<include(...)>>` targets are covered.
+    pub fn key(path: &Path, kind: SourceKind, strict: bool) -> io::Result<String> {
+        let mut hasher = Sha256::new();
+        hasher.update(env!("CARGO_PKG_VERSION").as_bytes());
+        hasher.update([0]);
+        hasher.update(format!("{kind:?}").as_bytes());
+        hasher.update([u8::from(strict)]);
+        let orb_dir = match kind {
+            SourceKind::Orb if path.is_dir() => Some(path),
+            SourceKind::Orb if path.file_name().is_some_and(|f| f == "@orb.yml") => {
+                Some(path.parent().unwrap_or(path))
+            }
+            _ => None,
+        };
+        match orb_dir {
+            Some(dir) => {
+                let mut files = Vec::new();
+                list_files(dir, &mut files)?;
+                files.sort();
+                for file in files {
+                    let relative = file.strip_prefix(dir).unwrap_or(&file);
+                    hash_file(&mut hasher, relative, &file)?;
+                }
+            }
+            None => hash_file(&mut hasher, Path::new(""), path)?,
+        }
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    /// The cached definition for `key`, if present and readable.
+    pub fn lookup(&self, key: &str) -> Option<OrbDefinition> {
+        let content = fs::read(self.path_of(key)).ok()?;
+        serde_json::from_slice(&content).ok()
+    }
+
+    /// Store `orb` under `key`.
+    ///
+    /// The entry is written to a temporary name and renamed, so concurrent
+    /// pipelines never read a partial definition.
+    pub fn store(&self, key: &str, orb: &OrbDefinition) -> io::Result<()> {
+        fs::create_dir_all(&self.dir)?;
+        let content = serde_json::to_vec(orb)?;
+        let partial = self
+            .dir
+            .join(format!(".{key}.{}.partial", std::process::id()));
+        fs::write(&partial, content)?;
+        fs::rename(&partial, self.path_of(key))
+    }
+
+    /// The definition `parse` returns for the source at `path`, read from the
+    /// cache when the source is unchanged since it was stored.
+    ///
+    /// A source that cannot be hashed is parsed without the cache, and a
+    /// failure to store is logged rather than returned.
+    pub fn parse<E>(
+        &self,
+        path: &Path,
+        kind: SourceKind,
+        strict: bool,
+        parse: impl FnOnce() -> Result<OrbDefinition, E>,
+    ) -> Result<OrbDefinition, E> {
+        let key = match Self::key(path, kind, strict) {
+            Ok(key) => key,
+            Err(e) => {
+                tracing::debug!(path = %path.display(), error = %e, "Cannot hash orb source");
+                return parse();
+            }
+        };
+        if let Some(orb) = self.lookup(&key) {
+            tracing::info!(path = %path.display(), %key, "Reusing cached orb parse");
+            return Ok(orb);
+        }
+        let orb = parse()?;
+        if let Err(e) = self.store(&key, &orb) {
+            tracing::warn!(dir = %self.dir.display(), error = %e, "Failed to cache orb parse");
+        }
+        Ok(orb)
+    }
+
+    fn path_of(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+}
+
+/// Push every file under `dir` onto `files`, without following symlinked
+/// directories.
+fn list_files(dir: &Path, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            list_files(&entry.path(), files)?;
+        } else if entry.path().is_file() {
+            files.push(entry.path());
+        }
+    }
+    Ok(())
+}
+
+fn hash_file(hasher: &mut Sha256, name: &Path, file: &Path) -> io::Result<()> {
+    let content = fs::read(file)?;
+    hasher.update(name.to_string_lossy().as_bytes());
+    hasher.update([0]);
+    hasher.update((content.len() as u64).to_le_bytes());
+    hasher.update(&content);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::parser::OrbParser;
+
+    fn write_unpacked_orb(dir: &Path) {
+        fs::create_dir_all(dir.join("commands")).unwrap();
+        fs::create_dir_all(dir.join("scripts")).unwrap();
+        fs::write(
+            dir.join("@orb.yml"),
+            "version: \"2.1\"\ndescription: Test\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.join("commands/greet.yml"),
+            "steps:\n  - run: <<include(scripts/greet.sh)>>\n",
+        )
+        .unwrap();
+        fs::write(dir.join("scripts/greet.sh"), "echo hello\n").unwrap();
+    }
+
+    #[test]
+    fn test_key_follows_every_file_of_an_unpacked_orb() {
+        let dir = TempDir::new().unwrap();
+        write_unpacked_orb(dir.path());
+        let orb_yml = dir.path().join("@orb.yml");
+
+        let key = ParseCache::key(&orb_yml, SourceKind::Orb, false).unwrap();
+        assert_eq!(key.len(), 64);
+        assert_eq!(
+            key,
+            ParseCache::key(dir.path(), SourceKind::Orb, false).unwrap()
+        );
+        assert_ne!(
+            key,
+            ParseCache::key(&orb_yml, SourceKind::Orb, true).unwrap()
+        );
+
+        fs::write(dir.path().join("scripts/greet.sh"), "echo bye\n").unwrap();
+        assert_ne!(
+            key,
+            ParseCache::key(&orb_yml, SourceKind::Orb, false).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_key_of_a_file_ignores_its_location() {
+        let dir = TempDir::new().unwrap();
+        let a = dir.path().join("a/orb.yml");
+        let b = dir.path().join("b/orb.yml");
+        for file in [&a, &b] {
+            fs::create_dir_all(file.parent().unwrap()).unwrap();
+            fs::write(file, "version: \"2.1\"\n").unwrap();
+        }
+        let key = ParseCache::key(&a, SourceKind::Orb, false).unwrap();
+        assert_eq!(key, ParseCache::key(&b, SourceKind::Orb, false).unwrap());
+        assert_ne!(key, ParseCache::key(&a, SourceKind::Config, false).unwrap());
+        assert!(ParseCache::key(&dir.path().join("missing.yml"), SourceKind::Orb, false).is_err());
+    }
+
+    #[test]
+    fn test_parse_reuses_the_stored_definition() {
+        let dir = TempDir::new().unwrap();
+        let orb_dir = dir.path().join("src");
+        write_unpacked_orb(&orb_dir);
+        let orb_yml = orb_dir.join("@orb.yml");
+        let cache = ParseCache::new(dir.path().join("cache"));
+
+        let parsed = cache
+            .parse(&orb_yml, SourceKind::Orb, false, || {
+                OrbParser::parse(&orb_yml)
+            })
+            .unwrap();
+        assert_eq!(fs::read_dir(dir.path().join("cache")).unwrap().count(), 1);
+
+        let cached = cache
+            .parse(&orb_yml, SourceKind::Orb, false, || -> Result<_, ()> {
+                panic!("an unchanged orb is not parsed again")
+            })
+            .unwrap();
+        assert_eq!(
+            serde_json::to_value(&cached).unwrap(),
+            serde_json::to_value(&parsed).unwrap()
+        );
+
+        // An edit is a miss.
+        fs::write(orb_dir.join("commands/greet.yml"), "steps: [checkout]\n").unwrap();
+        let mut parses = 0;
+        cache
+            .parse(&orb_yml, SourceKind::Orb, false, || {
+                parses += 1;
+                OrbParser::parse(&orb_yml)
+            })
+            .unwrap();
+        assert_eq!(parses, 1);
+    }
+
+    #[test]
+    fn test_parse_does_not_store_failures() {
+        let dir = TempDir::new().unwrap();
+        let orb_file = dir.path().join("orb.yml");
+        fs::write(&orb_file, "commands: [\n").unwrap();
+        let cache = ParseCache::new(dir.path().join("cache"));
+
+        assert!(cache
+            .parse(&orb_file, SourceKind::Orb, false, || OrbParser::parse(
+                &orb_file
+            ))
+            .is_err());
+        assert!(!dir.path().join("cache").exists());
+    }
+}
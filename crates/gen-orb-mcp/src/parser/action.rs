@@ -0,0 +1,418 @@
+//! GitHub Actions composite actions.
+//!
+//! An `action.yml` declaring `runs.using: composite` is read as an orb with a
+//! single command, named after the action, so the generator serves it like
+//! any orb command:
+//!
+//! - each input becomes a `string` parameter, with its default. Optional
+//!   inputs without a default get an empty default, as GitHub passes them;
+//!   required inputs without one stay required. A `deprecationMessage` is
+//!   appended to the description.
+//! - the outputs are listed at the end of the command description, since
+//!   orbs have no outputs.
+//! - `run` steps become `run` steps, keeping their name, shell, working
+//!   directory and environment. `uses` steps become invocations of the used
+//!   action, with its `with` arguments. A step's `if` expression wraps it in
+//!   a `when` step with the expression as its condition.
+//!
+//! `${{ inputs.<name> }}` expressions are rewritten to
+//! `<< parameters.<name> >>`, so parameter references are linted and
+//! rendered as for orbs. Other expressions are kept as written.
+
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::Deserialize;
+use serde_yaml::Value;
+
+use super::{
+    Command, Condition, ConditionalStep, OrbDefinition, Parameter, ParameterType, ParseError,
+    RunStep, Step, StructuredStep,
+};
+
+/// The metadata file of an action.
+#[derive(Debug, Deserialize)]
+struct Action {
+    name: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    inputs: BTreeMap<String, Input>,
+    #[serde(default)]
+    outputs: BTreeMap<String, Output>,
+    runs: Runs,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "camelCase")]
+struct Input {
+    description: Option<String>,
+    required: bool,
+    default: Option<Value>,
+    deprecation_message: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+struct Output {
+    description: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Runs {
+    using: String,
+    #[serde(default)]
+    steps: Vec<ActionStep>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, rename_all = "kebab-case")]
+struct ActionStep {
+    name: Option<String>,
+    #[serde(rename = "if")]
+    condition: Option<String>,
+    run: Option<String>,
+    shell: Option<String>,
+    working_directory: Option<String>,
+    env: BTreeMap<String, String>,
+    uses: Option<String>,
+    with: BTreeMap<String, Value>,
+}
+
+/// Parse the composite action metadata file at `path`.
+pub fn parse(path: &Path) -> Result<OrbDefinition, ParseError> {
+    let content = fs::read_to_string(path).map_err(|e| ParseError::FileRead {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    parse_content(&content, path)
+}
+
+/// Parse composite action metadata read from `source_path`.
+pub fn parse_content(content: &str, source_path: &Path) -> Result<OrbDefinition, ParseError> {
+    let action: Action =
+        serde_yaml::from_str(content).map_err(|e| ParseError::yaml(source_path, e))?;
+    if action.runs.using != "composite" {
+        return Err(ParseError::InvalidStructure {
+            message: format!(
+                "{} is a `{}` action; only composite actions can be read",
+                source_path.display(),
+                action.runs.using
+            ),
+        });
+    }
+
+    let parameters = action
+        .inputs
+        .iter()
+        .map(|(name, input)| (name.clone(), parameter(input)))
+        .collect();
+    let steps = action
+        .runs
+        .steps
+        .iter()
+        .map(|step| convert_step(source_path, step))
+        .collect::<Result<_, _>>()?;
+    let command = Command {
+        description: command_description(&action),
+        parameters,
+        steps,
+    };
+
+    Ok(OrbDefinition {
+        version: "2.1".to_string(),
+        description: action.description.clone(),
+        commands: [(command_name(&action.name), command)].into(),
+        ..Default::default()
+    })
+}
+
+/// Kebab-case command name for the action named `name`.
+fn command_name(name: &str) -> String {
+    let words: Vec<String> = name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_ascii_lowercase)
+        .collect();
+    if words.is_empty() {
+        "action".to_string()
+    } else {
+        words.join("-")
+    }
+}
+
+/// The action description followed by its outputs.
+fn command_description(action: &Action) -> Option<String> {
+    let mut description = action.description.clone().unwrap_or_default();
+    if !action.outputs.is_empty() {
+        if !description.is_empty() {
+            description.push_str("\n\n");
+        }
+        description.push_str("Outputs:\n");
+        for (name, output) in &action.outputs {
+            match output.description.as_deref().map(str::trim) {
+                Some(text) if !text.is_empty() => {
+                    description.push_str(&format!("- `{name}`: {text}\n"))
+                }
+                _ => description.push_str(&format!("- `{name}`\n")),
+            }
+        }
+    }
+    (!description.is_empty()).then_some(description)
+}
+
+fn parameter(input: &Input) -> Parameter {
+    let mut description = input.description.clone();
+    if let Some(message) = &input.deprecation_message {
+        let text = description.get_or_insert_with(String::new);
+        if !text.is_empty() {
+            text.push_str("\n\n");
+        }
+        text.push_str(&format!("Deprecated: {message}"));
+    }
+    let default = match &input.default {
+        Some(value) => Some(Value::String(inputs_to_parameters(&scalar(value)))),
+        None if input.required => None,
+        None => Some(Value::String(String::new())),
+    };
+    Parameter {
+        param_type: ParameterType::String,
+        description,
+        default,
+        enum_values: None,
+    }
+}
+
+fn convert_step(path: &Path, step: &ActionStep) -> Result<Step, ParseError> {
+    let converted = match (&step.run, &step.uses) {
+        (Some(command), None) => Step::Structured(StructuredStep::Run(RunStep::Full {
+            command: inputs_to_parameters(command),
+            name: step.name.clone(),
+            working_directory: step.working_directory.as_deref().map(inputs_to_parameters),
+            environment: step
+                .env
+                .iter()
+                .map(|(k, v)| (k.clone(), inputs_to_parameters(v)))
+                .collect(),
+            shell: step.shell.clone(),
+            background: None,
+            no_output_timeout: None,
+            when: None,
+        })),
+        (None, Some(uses)) if step.with.is_empty() => Step::Simple(uses.clone()),
+        (None, Some(uses)) => {
+            let with = step
+                .with
+                .iter()
+                .map(|(k, v)| {
+                    (
+                        Value::String(k.clone()),
+                        Value::String(inputs_to_parameters(&scalar(v))),
+                    )
+                })
+                .collect();
+            Step::Structured(StructuredStep::CommandInvocation(
+                [(uses.clone(), Value::Mapping(with))].into(),
+            ))
+        }
+        _ => {
+            return Err(ParseError::InvalidStructure {
+                message: format!(
+                    "{}: step {} must have exactly one of `run` and `uses`",
+                    path.display(),
+                    step.name.as_deref().unwrap_or("without a name")
+                ),
+            })
+        }
+    };
+    Ok(match &step.condition {
+        Some(condition) => Step::Structured(StructuredStep::When(ConditionalStep {
+            condition: Condition::Literal(Value::String(expression_inputs_to_parameters(
+                strip_expression(condition),
+            ))),
+            steps: vec![converted],
+        })),
+        None => converted,
+    })
+}
+
+/// A YAML scalar as the string GitHub passes for it.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// `expression` without a surrounding `${{ }}`, which `if` allows to omit.
+fn strip_expression(expression: &str) -> &str {
+    let trimmed = expression.trim();
+    trimmed
+        .strip_prefix("${{")
+        .and_then(|rest| rest.strip_suffix("}}"))
+        .filter(|inner| !inner.contains("${{"))
+        .map_or(trimmed, str::trim)
+}
+
+/// Rewrite each `inputs.<name>` in the expression `expression` to
+/// `<< parameters.<name> >>`.
+fn expression_inputs_to_parameters(expression: &str) -> String {
+    let is_name = |c: char| c.is_ascii_alphanumeric() || c == '-' || c == '_';
+    let mut out = String::with_capacity(expression.len());
+    let mut rest = expression;
+    while let Some(start) = rest.find("inputs.") {
+        let name_len = rest[start + 7..]
+            .find(|c: char| !is_name(c))
+            .unwrap_or(rest.len() - start - 7);
+        let standalone = !rest[..start].ends_with(|c: char| is_name(c) || c == '.');
+        out.push_str(&rest[..start]);
+        if standalone && name_len > 0 {
+            let name = &rest[start + 7..start + 7 + name_len];
+            out.push_str(&format!("<< parameters.{name} >>"));
+        } else {
+            out.push_str(&rest[start..start + 7 + name_len]);
+        }
+        rest = &rest[start + 7 + name_len..];
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Rewrite `${{ inputs.<name> }}` in `text` to `<< parameters.<name> >>`.
+fn inputs_to_parameters(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${{") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 3..];
+        let Some(end) = after.find("}}") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let inner = after[..end].trim();
+        match inner.strip_prefix("inputs.").filter(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        }) {
+            Some(name) => out.push_str(&format!("<< parameters.{name} >>")),
+            None => out.push_str(&rest[start..start + 3 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_composite_action() {
+        let orb = parse_content(
+            r#"
+name: Setup Greeter
+description: Install the greeter
+inputs:
+  version:
+    description: Greeter version
+    required: true
+  who:
+    description: Who to greet
+    default: world
+  verbose:
+    description: Log more
+    deprecationMessage: Use RUNNER_DEBUG instead
+outputs:
+  path:
+    description: Install location
+    value: ${{ steps.install.outputs.path }}
+runs:
+  using: composite
+  steps:
+    - uses: actions/checkout@v4
+    - uses: actions/cache@v4
+      with:
+        key: greeter-${{ inputs.version }}
+    - name: Install
+      id: install
+      if: ${{ inputs.verbose == 'true' }}
+      run: ./install.sh "${{ inputs.version }}" "${{ github.token }}"
+      shell: bash
+      env:
+        WHO: ${{inputs.who}}
+"#,
+            Path::new("action.yml"),
+        )
+        .unwrap();
+
+        assert_eq!(orb.description.as_deref(), Some("Install the greeter"));
+        let command = &orb.commands["setup-greeter"];
+        assert_eq!(
+            command.description.as_deref(),
+            Some("Install the greeter\n\nOutputs:\n- `path`: Install location\n")
+        );
+        assert_eq!(command.parameters["version"].default, None);
+        assert_eq!(
+            command.parameters["who"].default,
+            Some(Value::from("world"))
+        );
+        let verbose = &command.parameters["verbose"];
+        assert_eq!(verbose.default, Some(Value::from("")));
+        assert_eq!(
+            verbose.description.as_deref(),
+            Some("Log more\n\nDeprecated: Use RUNNER_DEBUG instead")
+        );
+
+        assert_eq!(command.steps[0], Step::Simple("actions/checkout@v4".into()));
+        let Step::Structured(StructuredStep::CommandInvocation(cache)) = &command.steps[1] else {
+            panic!("expected an invocation, got {:?}", command.steps[1]);
+        };
+        assert_eq!(
+            cache["actions/cache@v4"]["key"],
+            Value::from("greeter-<< parameters.version >>")
+        );
+        let Step::Structured(StructuredStep::When(when)) = &command.steps[2] else {
+            panic!("expected a when step, got {:?}", command.steps[2]);
+        };
+        assert_eq!(
+            when.condition,
+            Condition::Literal(Value::from("<< parameters.verbose >> == 'true'"))
+        );
+        let [Step::Structured(StructuredStep::Run(RunStep::Full {
+            command,
+            name,
+            shell,
+            environment,
+            ..
+        }))] = when.steps.as_slice()
+        else {
+            panic!("expected a run step, got {:?}", when.steps);
+        };
+        assert_eq!(
+            command,
+            "./install.sh \"<< parameters.version >>\" \"${{ github.token }}\""
+        );
+        assert_eq!(name.as_deref(), Some("Install"));
+        assert_eq!(shell.as_deref(), Some("bash"));
+        assert_eq!(environment["WHO"], "<< parameters.who >>");
+    }
+
+    #[test]
+    fn test_parse_rejects_non_composite_actions() {
+        let err = parse_content(
+            "name: Node action\nruns:\n  using: node20\n  main: index.js\n",
+            Path::new("action.yml"),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid orb structure: action.yml is a `node20` action; only composite actions \
+             can be read"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! `when`/`unless` logic statements.
+//!
+//! Conditional steps and workflows take a logic statement: a literal value,
+//! a parameter or pipeline value reference, or one of the operators `and`,
+//! `or`, `not`, `equal` and `matches` applied to further statements.
+//! [`Condition`] holds the parsed statement. Statements that do not fit the
+//! grammar are kept as written in [`Condition::Invalid`] so the orb still
+//! parses and validation can report them.
+//!
+//! ```
+//! use gen_orb_mcp::parser::Condition;
+//!
+//! let yaml = "and:\n  - << parameters.publish >>\n  - not: << parameters.dry-run >>\n";
+//! let condition: Condition = serde_yaml::from_str(yaml).unwrap();
+//! assert_eq!(
+//!     condition.describe(),
+//!     "parameters.publish and not parameters.dry-run"
+//! );
+//! ```
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_yaml::{Mapping, Value};
+
+/// A parsed `when`/`unless` logic statement.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Condition {
+    /// A literal value: a boolean, string, number or null
+    Literal(Value),
+    /// A value written as a single `<< ... >>` placeholder, holding the path
+    /// inside it (e.g. `parameters.publish` or `pipeline.git.branch`)
+    Reference(String),
+    /// True when every statement is (`and: [...]`)
+    And(Vec<Condition>),
+    /// True when any statement is (`or: [...]`)
+    Or(Vec<Condition>),
+    /// True when the statement is not (`not: ...`)
+    Not(Box<Condition>),
+    /// True when every value is equal (`equal: [...]`)
+    Equal(Vec<Condition>),
+    /// True when `value` matches the regular expression `pattern`
+    Matches {
+        /// Regular expression, as written
+        pattern: String,
+        /// Value matched against the pattern
+        value: String,
+    },
+    /// A statement outside the logic statement grammar, kept as written
+    Invalid {
+        /// The statement as written
+        statement: Value,
+        /// Why it is not a logic statement
+        reason: String,
+    },
+}
+
+impl Default for Condition {
+    fn default() -> Self {
+        Condition::Literal(Value::Null)
+    }
+}
+
+impl From<Value> for Condition {
+    fn from(value: Value) -> Self {
+        Condition::parse(value)
+    }
+}
+
+impl Condition {
+    /// Parse a logic statement. Anything outside the grammar becomes
+    /// [`Condition::Invalid`].
+    pub fn parse(value: Value) -> Self {
+        match value {
+            Value::String(s) => match reference(&s) {
+                Some(path) => Condition::Reference(path.to_string()),
+                None => Condition::Literal(Value::String(s)),
+            },
+            Value::Mapping(mapping) if mapping.len() == 1 => {
+                let (key, operand) = mapping.iter().next().expect("mapping has one entry");
+                match key.as_str() {
+                    Some(operator @ ("and" | "or" | "equal")) => {
+                        let Value::Sequence(items) = operand else {
+                            return invalid(&mapping, format!("`{operator}` takes a list"));
+                        };
+                        let items = items.iter().cloned().map(Condition::parse).collect();
+                        match operator {
+                            "and" => Condition::And(items),
+                            "or" => Condition::Or(items),
+                            _ => Condition::Equal(items),
+                        }
+                    }
+                    Some("not") => match operand {
+                        Value::Sequence(_) => {
+                            invalid(&mapping, "`not` takes a single statement".to_string())
+                        }
+                        operand => Condition::Not(Box::new(Condition::parse(operand.clone()))),
+                    },
+                    Some("matches") => {
+                        let field = |name| operand.get(name).and_then(Value::as_str);
+                        match (field("pattern"), field("value")) {
+                            (Some(pattern), Some(value)) => Condition::Matches {
+                                pattern: pattern.to_string(),
+                                value: value.to_string(),
+                            },
+                            _ => invalid(
+                                &mapping,
+                                "`matches` takes a `pattern` and a `value`".to_string(),
+                            ),
+                        }
+                    }
+                    Some(other) => invalid(&mapping, format!("unknown operator `{other}`")),
+                    None => invalid(&mapping, "operator is not a string".to_string()),
+                }
+            }
+            Value::Mapping(mapping) => invalid(
+                &mapping,
+                "an operator mapping takes exactly one operator".to_string(),
+            ),
+            Value::Sequence(_) | Value::Tagged(_) => Condition::Invalid {
+                statement: value,
+                reason: "not a value or an operator".to_string(),
+            },
+            scalar => Condition::Literal(scalar),
+        }
+    }
+
+    /// The statement as YAML, in the form CircleCI reads.
+    pub fn to_value(&self) -> Value {
+        let operator = |name: &str, operand: Value| {
+            Value::Mapping(Mapping::from_iter([(Value::from(name), operand)]))
+        };
+        let list =
+            |items: &[Condition]| Value::Sequence(items.iter().map(Self::to_value).collect());
+        match self {
+            Condition::Literal(value) => value.clone(),
+            Condition::Reference(path) => Value::String(format!("<< {path} >>")),
+            Condition::And(items) => operator("and", list(items)),
+            Condition::Or(items) => operator("or", list(items)),
+            Condition::Not(inner) => operator("not", inner.to_value()),
+            Condition::Equal(items) => operator("equal", list(items)),
+            Condition::Matches { pattern, value } => operator(
+                "matches",
+                Value::Mapping(Mapping::from_iter([
+                    (Value::from("pattern"), Value::from(pattern.as_str())),
+                    (Value::from("value"), Value::from(value.as_str())),
+                ])),
+            ),
+            Condition::Invalid { statement, .. } => statement.clone(),
+        }
+    }
+
+    /// A one-line English reading of the statement, e.g.
+    /// `parameters.publish and "main" equals pipeline.git.branch`.
+    pub fn describe(&self) -> String {
+        match self {
+            Condition::Literal(Value::String(s)) => format!("{s:?}"),
+            Condition::Literal(value) => inline_yaml(value),
+            Condition::Reference(path) => path.clone(),
+            Condition::And(items) => join(items, " and "),
+            Condition::Or(items) => join(items, " or "),
+            Condition::Not(inner) => format!("not {}", inner.describe_operand()),
+            Condition::Equal(items) if items.len() == 2 => format!(
+                "{} equals {}",
+                items[0].describe_operand(),
+                items[1].describe_operand()
+            ),
+            Condition::Equal(items) => format!("all equal: {}", join(items, ", ")),
+            Condition::Matches { pattern, value } => {
+                let value = reference(value).unwrap_or(value);
+                format!("{value} matches /{pattern}/")
+            }
+            Condition::Invalid { statement, .. } => inline_yaml(statement),
+        }
+    }
+
+    /// Why each invalid statement in this statement is invalid, outermost
+    /// first.
+    pub fn errors(&self) -> Vec<&str> {
+        let mut errors = Vec::new();
+        self.collect_errors(&mut errors);
+        errors
+    }
+
+    fn collect_errors<'a>(&'a self, errors: &mut Vec<&'a str>) {
+        match self {
+            Condition::And(items) | Condition::Or(items) | Condition::Equal(items) => {
+                items.iter().for_each(|item| item.collect_errors(errors));
+            }
+            Condition::Not(inner) => inner.collect_errors(errors),
+            Condition::Invalid { reason, .. } => errors.push(reason),
+            Condition::Literal(_) | Condition::Reference(_) | Condition::Matches { .. } => {}
+        }
+    }
+
+    /// [`describe`](Self::describe), parenthesised when it would otherwise
+    /// read ambiguously inside another statement.
+    fn describe_operand(&self) -> String {
+        match self {
+            Condition::And(items) | Condition::Or(items) | Condition::Equal(items)
+                if items.len() > 1 =>
+            {
+                format!("({})", self.describe())
+            }
+            Condition::Matches { .. } => format!("({})", self.describe()),
+            _ => self.describe(),
+        }
+    }
+}
+
+impl Serialize for Condition {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Condition {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(Condition::parse)
+    }
+}
+
+/// The path inside `s` when it is exactly one `<< ... >>` placeholder.
+fn reference(s: &str) -> Option<&str> {
+    let path = s.trim().strip_prefix("<<")?.strip_suffix(">>")?.trim();
+    (!path.is_empty() && !path.contains("<<") && !path.contains(">>")).then_some(path)
+}
+
+fn invalid(mapping: &Mapping, reason: String) -> Condition {
+    Condition::Invalid {
+        statement: Value::Mapping(mapping.clone()),
+        reason,
+    }
+}
+
+fn join(items: &[Condition], separator: &str) -> String {
+    items
+        .iter()
+        .map(Condition::describe_operand)
+        .collect::<Vec<_>>()
+        .join(separator)
+}
+
+/// `value` as single-line YAML.
+fn inline_yaml(value: &Value) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| {
+        serde_yaml::to_string(value)
+            .unwrap_or_default()
+            .trim()
+            .replace('\n', " ")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(yaml: &str) -> Condition {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_parse_logic_statements() {
+        assert_eq!(parse("true"), Condition::Literal(Value::Bool(true)));
+        assert_eq!(
+            parse("<< parameters.publish >>"),
+            Condition::Reference("parameters.publish".to_string())
+        );
+        assert_eq!(
+            parse("release-<< parameters.name >>"),
+            Condition::Literal(Value::from("release-<< parameters.name >>"))
+        );
+        assert_eq!(
+            parse(
+                r#"
+or:
+  - not: << parameters.skip >>
+  - equal: [main, << pipeline.git.branch >>]
+  - matches: {pattern: "^v.*", value: << pipeline.git.tag >>}
+"#
+            ),
+            Condition::Or(vec![
+                Condition::Not(Box::new(Condition::Reference(
+                    "parameters.skip".to_string()
+                ))),
+                Condition::Equal(vec![
+                    Condition::Literal(Value::from("main")),
+                    Condition::Reference("pipeline.git.branch".to_string()),
+                ]),
+                Condition::Matches {
+                    pattern: "^v.*".to_string(),
+                    value: "<< pipeline.git.tag >>".to_string(),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn test_invalid_statements_are_kept_with_a_reason() {
+        let condition = parse("and:\n  - not: [a, b]\n  - matches: {pattern: x}\n  - nand: [a]\n");
+        assert_eq!(
+            condition.errors(),
+            [
+                "`not` takes a single statement",
+                "`matches` takes a `pattern` and a `value`",
+                "unknown operator `nand`",
+            ]
+        );
+        assert_eq!(parse("and: true").errors(), ["`and` takes a list"]);
+        assert_eq!(
+            parse("{and: [], or: []}").errors(),
+            ["an operator mapping takes exactly one operator"]
+        );
+        assert!(parse("equal: [a, b]").errors().is_empty());
+    }
+
+    #[test]
+    fn test_serialization_round_trips() {
+        let yaml = "and:\n- << parameters.publish >>\n- nand: x\n- matches:\n    pattern: ^v\n    value: << pipeline.git.tag >>\n";
+        let condition = parse(yaml);
+        assert_eq!(serde_yaml::to_string(&condition).unwrap(), yaml);
+        assert_eq!(
+            parse(&serde_yaml::to_string(&condition).unwrap()),
+            condition
+        );
+    }
+
+    #[test]
+    fn test_describe() {
+        assert_eq!(
+            parse("<< parameters.publish >>").describe(),
+            "parameters.publish"
+        );
+        assert_eq!(parse("false").describe(), "false");
+        assert_eq!(
+            parse("equal: [main, << pipeline.git.branch >>]").describe(),
+            "\"main\" equals pipeline.git.branch"
+        );
+        assert_eq!(
+            parse(
+                "or:\n  - and: [<< parameters.a >>, << parameters.b >>]\n  - not: {matches: {pattern: '^v', value: << pipeline.git.tag >>}}\n"
+            )
+            .describe(),
+            "(parameters.a and parameters.b) or not (pipeline.git.tag matches /^v/)"
+        );
+        assert_eq!(
+            parse("equal: [a, b, c]").describe(),
+            "all equal: \"a\", \"b\", \"c\""
+        );
+    }
+}
@@ -1,6 +1,6 @@
 //! Parser-specific error types.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use thiserror::Error;
 
@@ -16,9 +16,27 @@ pub enum ParseError {
     },
 
     /// Failed to parse YAML content.
-    #[error("failed to parse YAML in '{path}': {source}")]
+    ///
+    /// Rendered as `file.yml:12:3: message` when the position is known.
+    #[error("{}{}: {}", .path.display(), position(*.line, *.column), message(.source))]
     YamlParse {
         path: PathBuf,
+        /// 1-based line of the problem, when known.
+        line: Option<usize>,
+        /// 1-based column of the problem, when known.
+        column: Option<usize>,
+        #[source]
+        source: serde_yaml::Error,
+    },
+
+    /// A definition in a packed orb does not match the orb schema.
+    ///
+    /// Rendered as `orb.yml: commands/greet: message`.
+    #[error("{}: {location}: {}", .path.display(), message(.source))]
+    InvalidDefinition {
+        path: PathBuf,
+        /// `<category>/<name>` of the definition.
+        location: String,
         #[source]
         source: serde_yaml::Error,
     },
@@ -31,6 +49,52 @@ pub enum ParseError {
     #[error("invalid orb structure: {message}")]
     InvalidStructure { message: String },
 
+    /// An `x-include` names a partial that does not exist.
+    #[error("unknown partial '{name}' included from '{path}'{suggestion}")]
+    UnknownPartial {
+        path: PathBuf,
+        name: String,
+        /// `", did you mean '...'?"`, or empty.
+        suggestion: String,
+    },
+
+    /// A partial or an `x-include` value is malformed.
+    #[error("invalid partial in '{path}': {message}")]
+    InvalidPartial { path: PathBuf, message: String },
+
+    /// An `<<include(...)>>` directive names a missing or out-of-orb file.
+    #[error("invalid include '{include}' in '{path}': {message}")]
+    InvalidInclude {
+        path: PathBuf,
+        include: String,
+        message: String,
+    },
+
+    /// A `<<` merge key takes something other than a mapping or a list of
+    /// mappings.
+    #[error(
+        "invalid merge key in '{path}' at {location}: `<<` takes a mapping or a list of mappings"
+    )]
+    InvalidMerge {
+        path: PathBuf,
+        /// Path of the merge key, e.g. `executors/large/<<`.
+        location: String,
+    },
+
+    /// A key the orb schema does not define, reported by strict parsing.
+    ///
+    /// Rendered as `commands/greet: unknown key 'decription', did you mean
+    /// 'description'?`; the key's file and line are found from `location`.
+    #[error("{location}: unknown key '{key}'{suggestion}")]
+    UnknownKey {
+        path: PathBuf,
+        /// Path of the mapping holding the key, e.g. `jobs/build/docker/0`.
+        location: String,
+        key: String,
+        /// `", did you mean '...'?"`, or empty.
+        suggestion: String,
+    },
+
     /// Failed to read directory.
     #[error("failed to read directory '{path}': {source}")]
     DirectoryRead {
@@ -39,3 +103,97 @@ pub enum ParseError {
         source: std::io::Error,
     },
 }
+
+impl ParseError {
+    /// The file the error is in and its 1-based line (1 when unknown), or
+    /// `None` for errors not tied to one file.
+    pub fn file(&self) -> Option<(&Path, usize)> {
+        match self {
+            Self::YamlParse { path, line, .. } => Some((path, line.unwrap_or(1))),
+            Self::FileRead { path, .. }
+            | Self::InvalidDefinition { path, .. }
+            | Self::MissingFile { path }
+            | Self::UnknownPartial { path, .. }
+            | Self::InvalidPartial { path, .. }
+            | Self::InvalidInclude { path, .. }
+            | Self::InvalidMerge { path, .. }
+            | Self::UnknownKey { path, .. }
+            | Self::DirectoryRead { path, .. } => Some((path, 1)),
+            Self::InvalidStructure { .. } => None,
+        }
+    }
+
+    /// A [`ParseError::YamlParse`] for `path` at the position serde_yaml
+    /// reports.
+    pub(crate) fn yaml(path: &Path, source: serde_yaml::Error) -> Self {
+        let location = source.location();
+        Self::YamlParse {
+            path: path.to_path_buf(),
+            line: location.as_ref().map(|l| l.line()),
+            column: location.as_ref().map(|l| l.column()),
+            source,
+        }
+    }
+
+    /// A [`ParseError::YamlParse`] for `path` without a position, for YAML
+    /// re-serialized after expanding partials or includes, whose positions
+    /// do not match the file.
+    pub(crate) fn yaml_unlocated(path: &Path, source: serde_yaml::Error) -> Self {
+        Self::YamlParse {
+            path: path.to_path_buf(),
+            line: None,
+            column: None,
+            source,
+        }
+    }
+}
+
+/// `:line:column`, or empty when the position is unknown.
+fn position(line: Option<usize>, column: Option<usize>) -> String {
+    match (line, column) {
+        (Some(line), Some(column)) => format!(":{line}:{column}"),
+        _ => String::new(),
+    }
+}
+
+/// serde_yaml's message without the ` at line L column C` it appends, which
+/// [`position`] already reports.
+fn message(source: &serde_yaml::Error) -> String {
+    let text = source.to_string();
+    match source.location() {
+        Some(location) => text.replacen(
+            &format!(" at line {} column {}", location.line(), location.column()),
+            "",
+            1,
+        ),
+        None => text,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_yaml_error_reports_line_and_column() {
+        #[derive(Debug, serde::Deserialize)]
+        #[serde(deny_unknown_fields)]
+        #[allow(dead_code)]
+        struct Command {
+            description: String,
+        }
+
+        let source =
+            serde_yaml::from_str::<Command>("description: Greet\nstpes: []\n").unwrap_err();
+        let err = ParseError::yaml(Path::new("commands/greet.yml"), source);
+        assert_eq!(
+            err.to_string(),
+            "commands/greet.yml:2:1: unknown field `stpes`, expected `description`"
+        );
+
+        let source = serde_yaml::from_str::<Command>("description: [\n").unwrap_err();
+        let err = ParseError::yaml_unlocated(Path::new("commands/greet.yml"), source);
+        assert!(err.to_string().starts_with("commands/greet.yml: "), "{err}");
+        assert!(!err.to_string().contains(" at line "), "{err}");
+    }
+}
@@ -0,0 +1,515 @@
+//! GitLab CI templates.
+//!
+//! A template, such as `Docker.gitlab-ci.yml`, a CI/CD component under
+//! `templates/` or a project's `.gitlab-ci.yml`, is read like a project
+//! config:
+//!
+//! - `spec:inputs` in the header document and the global `variables` become
+//!   pipeline parameters. Inputs keep their type (`number` as `integer`, and
+//!   `enum` with `options`); variables are strings, with the `description`,
+//!   `value` and `options` of their expanded form.
+//! - visible jobs become jobs. Hidden jobs (`.name`), which other jobs
+//!   `extends`, become commands named without the dot.
+//! - `before_script`, `script` and `after_script` lines become `run` steps,
+//!   with `after_script` lines run `when: always`. A job's `image` (else the
+//!   default one) becomes its docker image and its `variables` its
+//!   environment. The stage, `extends` and `trigger` are given in the
+//!   description. Other keywords (`rules`, `needs`, `artifacts`, ...) have no
+//!   orb equivalent and are dropped.
+//!
+//! `$[[ inputs.<name> ]]` interpolation is rewritten to
+//! `<< pipeline.parameters.<name> >>`. `$VARIABLE` references are runtime
+//! environment variables and are kept as written.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    path::Path,
+};
+
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+use super::{
+    merge, Command, DockerImage, ExecutorConfig, Job, OrbDefinition, Parameter, ParameterType,
+    ParseError, RunStep, RunWhen, Step, StructuredStep,
+};
+
+/// Top-level keys that configure the pipeline rather than name a job.
+const GLOBAL_KEYS: &[&str] = &[
+    "after_script",
+    "before_script",
+    "cache",
+    "default",
+    "image",
+    "include",
+    "services",
+    "spec",
+    "stages",
+    "types",
+    "variables",
+    "workflow",
+];
+
+/// Parse the GitLab CI template at `path`.
+pub fn parse(path: &Path) -> Result<OrbDefinition, ParseError> {
+    let content = fs::read_to_string(path).map_err(|e| ParseError::FileRead {
+        path: path.to_path_buf(),
+        source: e,
+    })?;
+    parse_content(&content, path)
+}
+
+/// Parse a GitLab CI template read from `source_path`.
+pub fn parse_content(content: &str, source_path: &Path) -> Result<OrbDefinition, ParseError> {
+    let mut documents = serde_yaml::Deserializer::from_str(content)
+        .map(Value::deserialize)
+        .collect::<Result<Vec<Value>, serde_yaml::Error>>()
+        .map_err(|e| ParseError::yaml(source_path, e))?;
+    for document in &mut documents {
+        merge::resolve(document, source_path)?;
+    }
+    // A component puts its `spec` in a header document before the jobs
+    let header = match documents.first().and_then(Value::as_mapping) {
+        Some(first) if documents.len() == 2 && first.len() == 1 && first.contains_key("spec") => {
+            Some(documents.remove(0))
+        }
+        _ => None,
+    };
+    if documents.len() > 1 {
+        return Err(ParseError::InvalidStructure {
+            message: format!(
+                "{} holds {} YAML documents; a template has at most a `spec` header and its \
+                 jobs",
+                source_path.display(),
+                documents.len()
+            ),
+        });
+    }
+    let body = match documents.pop() {
+        Some(Value::Mapping(body)) => body,
+        None | Some(Value::Null) => Mapping::new(),
+        Some(_) => {
+            return Err(ParseError::InvalidStructure {
+                message: format!("{} is not a mapping", source_path.display()),
+            })
+        }
+    };
+    let spec = header
+        .as_ref()
+        .and_then(|header| header.get("spec"))
+        .or_else(|| body.get("spec"));
+
+    let mut orb = OrbDefinition {
+        version: "2.1".to_string(),
+        ..Default::default()
+    };
+    if let Some(inputs) = spec
+        .and_then(|spec| spec.get("inputs"))
+        .and_then(Value::as_mapping)
+    {
+        for (name, input) in inputs {
+            if let Some(name) = name.as_str() {
+                orb.parameters
+                    .insert(name.to_string(), input_parameter(input));
+            }
+        }
+    }
+    if let Some(variables) = body.get("variables").and_then(Value::as_mapping) {
+        for (name, variable) in variables {
+            if let Some(name) = name.as_str() {
+                orb.parameters
+                    .entry(name.to_string())
+                    .or_insert_with(|| variable_parameter(variable));
+            }
+        }
+    }
+
+    let defaults = body.get("default").and_then(Value::as_mapping);
+    let default = |key: &str| {
+        defaults
+            .and_then(|defaults| defaults.get(key))
+            .or_else(|| body.get(key))
+    };
+    let default_image = default("image");
+    let default_before = default("before_script");
+    let default_after = default("after_script");
+
+    for (name, job) in &body {
+        let (Some(name), Some(job)) = (name.as_str(), job.as_mapping()) else {
+            continue;
+        };
+        if GLOBAL_KEYS.contains(&name) {
+            continue;
+        }
+        let mut steps = script_steps(job.get("before_script").or(default_before), None);
+        steps.extend(script_steps(job.get("script"), None));
+        steps.extend(script_steps(
+            job.get("after_script").or(default_after),
+            Some(RunWhen::Always),
+        ));
+        let description = job_description(job);
+        match name.strip_prefix('.') {
+            Some(template) => {
+                orb.commands.insert(
+                    template.to_string(),
+                    Command {
+                        description,
+                        parameters: HashMap::new(),
+                        steps,
+                    },
+                );
+            }
+            None => {
+                let image = job.get("image").or(default_image).and_then(image_name);
+                let environment = job
+                    .get("variables")
+                    .and_then(Value::as_mapping)
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|(name, variable)| {
+                        let value = match variable {
+                            Value::Mapping(expanded) => expanded.get("value")?,
+                            value => value,
+                        };
+                        Some((name.as_str()?.to_string(), interpolate(&scalar(value))))
+                    })
+                    .collect::<BTreeMap<_, _>>();
+                orb.jobs.insert(
+                    name.to_string(),
+                    Job {
+                        description,
+                        config: ExecutorConfig {
+                            docker: image.map(|image| vec![DockerImage::Simple(image)]),
+                            environment,
+                            ..Default::default()
+                        },
+                        steps,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+    }
+    Ok(orb)
+}
+
+/// A pipeline parameter for a `spec:inputs` entry.
+fn input_parameter(input: &Value) -> Parameter {
+    let text = |key: &str| input.get(key).map(scalar);
+    let options = options(input);
+    let param_type = match (options.is_some(), text("type").as_deref()) {
+        (true, _) => ParameterType::Enum,
+        (false, Some("boolean")) => ParameterType::Boolean,
+        (false, Some("number")) => ParameterType::Integer,
+        _ => ParameterType::String,
+    };
+    Parameter {
+        param_type,
+        description: text("description"),
+        default: input.get("default").cloned(),
+        enum_values: options,
+    }
+}
+
+/// A pipeline parameter for a global variable, written as a value or as a
+/// mapping with `value`, `description` and `options`.
+fn variable_parameter(variable: &Value) -> Parameter {
+    let (value, description, options) = match variable {
+        Value::Mapping(expanded) => (
+            expanded.get("value"),
+            expanded.get("description").map(scalar),
+            options(variable),
+        ),
+        value => (Some(value), None, None),
+    };
+    Parameter {
+        param_type: if options.is_some() {
+            ParameterType::Enum
+        } else {
+            ParameterType::String
+        },
+        description,
+        default: Some(Value::String(
+            value.map(|v| interpolate(&scalar(v))).unwrap_or_default(),
+        )),
+        enum_values: options,
+    }
+}
+
+fn options(value: &Value) -> Option<Vec<String>> {
+    value
+        .get("options")
+        .and_then(Value::as_sequence)
+        .map(|options| options.iter().map(scalar).collect())
+}
+
+/// Stage, `extends` and `trigger` of a job, as its description.
+fn job_description(job: &Mapping) -> Option<String> {
+    let mut sentences = Vec::new();
+    if let Some(stage) = job.get("stage") {
+        sentences.push(format!("Runs in stage `{}`.", interpolate(&scalar(stage))));
+    }
+    let extends: Vec<String> = match job.get("extends") {
+        Some(Value::Sequence(names)) => names.iter().map(scalar).collect(),
+        Some(name) => vec![scalar(name)],
+        None => vec![],
+    };
+    if !extends.is_empty() {
+        let names: Vec<String> = extends.iter().map(|name| format!("`{name}`")).collect();
+        sentences.push(format!("Extends {}.", names.join(", ")));
+    }
+    match job.get("trigger") {
+        Some(Value::Mapping(trigger)) => {
+            if let Some(project) = trigger.get("project") {
+                sentences.push(format!("Triggers a pipeline in `{}`.", scalar(project)));
+            } else {
+                sentences.push("Triggers a child pipeline.".to_string());
+            }
+        }
+        Some(project) => {
+            sentences.push(format!("Triggers a pipeline in `{}`.", scalar(project)));
+        }
+        None => {}
+    }
+    (!sentences.is_empty()).then(|| sentences.join(" "))
+}
+
+/// The image name of an `image` keyword.
+fn image_name(image: &Value) -> Option<String> {
+    match image {
+        Value::Mapping(image) => image.get("name").map(scalar),
+        image => Some(scalar(image)),
+    }
+    .map(|name| interpolate(&name))
+}
+
+/// A `run` step for each line of a script, which may nest lists.
+fn script_steps(script: Option<&Value>, when: Option<RunWhen>) -> Vec<Step> {
+    let mut lines = Vec::new();
+    if let Some(script) = script {
+        script_lines(script, &mut lines);
+    }
+    lines
+        .into_iter()
+        .map(|line| {
+            let command = interpolate(&line);
+            let run = match &when {
+                Some(when) => RunStep::Full {
+                    command,
+                    name: None,
+                    working_directory: None,
+                    environment: BTreeMap::new(),
+                    shell: None,
+                    background: None,
+                    no_output_timeout: None,
+                    when: Some(when.clone()),
+                },
+                None => RunStep::Simple(command),
+            };
+            Step::Structured(StructuredStep::Run(run))
+        })
+        .collect()
+}
+
+fn script_lines(script: &Value, lines: &mut Vec<String>) {
+    match script {
+        Value::Sequence(items) => {
+            for item in items {
+                script_lines(item, lines);
+            }
+        }
+        Value::Null => {}
+        line => lines.push(scalar(line)),
+    }
+}
+
+/// A YAML scalar as text. Tagged values such as `!reference [.setup,
+/// script]` are written back as the tag and its items.
+fn scalar(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => String::new(),
+        Value::Tagged(tagged) => {
+            let items = match &tagged.value {
+                Value::Sequence(items) => items.iter().map(scalar).collect(),
+                value => vec![scalar(value)],
+            };
+            format!("{} [{}]", tagged.tag, items.join(", "))
+        }
+        other => serde_yaml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Rewrite `$[[ inputs.<name> ]]` in `text` to
+/// `<< pipeline.parameters.<name> >>`. Interpolation applying functions
+/// (`$[[ inputs.name | truncate(0,8) ]]`) is kept as written.
+fn interpolate(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("$[[") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 3..];
+        let Some(end) = after.find("]]") else {
+            out.push_str(&rest[start..]);
+            return out;
+        };
+        let inner = after[..end].trim();
+        match inner.strip_prefix("inputs.").filter(|name| {
+            !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        }) {
+            Some(name) => out.push_str(&format!("<< pipeline.parameters.{name} >>")),
+            None => out.push_str(&rest[start..start + 3 + end + 2]),
+        }
+        rest = &after[end + 2..];
+    }
+    out.push_str(rest);
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_component_template() {
+        let orb = parse_content(
+            r#"
+spec:
+  inputs:
+    stage:
+      default: test
+    image:
+      description: Image to scan
+    strict:
+      type: boolean
+      default: false
+    level:
+      options: [low, high]
+      default: low
+---
+variables:
+  SCAN_ARGS: --quiet
+  REPORT:
+    value: report.json
+    description: Where to write the report
+default:
+  image: alpine:3.20
+  before_script:
+    - apk add scanner
+.scan-base:
+  script:
+    - scanner $SCAN_ARGS
+scan:
+  extends: .scan-base
+  stage: $[[ inputs.stage ]]
+  image:
+    name: $[[ inputs.image ]]
+  variables:
+    LEVEL: $[[ inputs.level ]]
+  script:
+    - scanner --level "$[[ inputs.level ]]" > $REPORT
+    - - echo nested
+  after_script: echo done
+lint:
+  script: make lint
+"#,
+            Path::new("scan.gitlab-ci.yml"),
+        )
+        .unwrap();
+
+        let stage = &orb.parameters["stage"];
+        assert_eq!(stage.param_type, ParameterType::String);
+        assert_eq!(stage.default, Some(Value::from("test")));
+        assert_eq!(orb.parameters["image"].default, None);
+        assert_eq!(orb.parameters["strict"].param_type, ParameterType::Boolean);
+        let level = &orb.parameters["level"];
+        assert_eq!(level.param_type, ParameterType::Enum);
+        assert_eq!(
+            level.enum_values.as_deref(),
+            Some(&["low", "high"].map(String::from)[..])
+        );
+        assert_eq!(
+            orb.parameters["SCAN_ARGS"].default,
+            Some(Value::from("--quiet"))
+        );
+        assert_eq!(
+            orb.parameters["REPORT"].description.as_deref(),
+            Some("Where to write the report")
+        );
+
+        let base = &orb.commands["scan-base"];
+        assert_eq!(base.steps.len(), 2);
+
+        let scan = &orb.jobs["scan"];
+        assert_eq!(
+            scan.description.as_deref(),
+            Some("Runs in stage `<< pipeline.parameters.stage >>`. Extends `.scan-base`.")
+        );
+        assert!(matches!(
+            scan.config.docker.as_deref(),
+            Some([DockerImage::Simple(image)]) if image == "<< pipeline.parameters.image >>"
+        ));
+        assert_eq!(
+            scan.config.environment["LEVEL"],
+            "<< pipeline.parameters.level >>"
+        );
+        let commands: Vec<(String, Option<RunWhen>)> = scan
+            .steps
+            .iter()
+            .map(|step| match step {
+                Step::Structured(StructuredStep::Run(RunStep::Simple(command))) => {
+                    (command.clone(), None)
+                }
+                Step::Structured(StructuredStep::Run(RunStep::Full { command, when, .. })) => {
+                    (command.clone(), when.clone())
+                }
+                other => panic!("expected a run step, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            commands,
+            vec![
+                ("apk add scanner".to_string(), None),
+                (
+                    "scanner --level \"<< pipeline.parameters.level >>\" > $REPORT".to_string(),
+                    None
+                ),
+                ("echo nested".to_string(), None),
+                ("echo done".to_string(), Some(RunWhen::Always)),
+            ]
+        );
+
+        let lint = &orb.jobs["lint"];
+        assert_eq!(lint.description, None);
+        assert!(matches!(
+            lint.config.docker.as_deref(),
+            Some([DockerImage::Simple(image)]) if image == "alpine:3.20"
+        ));
+        assert_eq!(lint.steps.len(), 2);
+    }
+
+    #[test]
+    fn test_parse_keeps_references_and_rejects_extra_documents() {
+        let orb = parse_content(
+            ".setup:\n  script: [make setup]\ntest:\n  script:\n    - !reference [.setup, \
+             script]\n    - make test\n",
+            Path::new(".gitlab-ci.yml"),
+        )
+        .unwrap();
+        assert_eq!(
+            orb.jobs["test"].steps[0],
+            Step::Structured(StructuredStep::Run(RunStep::Simple(
+                "!reference [.setup, script]".to_string()
+            )))
+        );
+
+        let err = parse_content("a: 1\n---\nb: 2\n---\nc: 3\n", Path::new("t.yml")).unwrap_err();
+        assert!(err.to_string().contains("holds 3 YAML documents"), "{err}");
+    }
+}
@@ -0,0 +1,139 @@
+//! `<<include(file)>>` directives in unpacked orbs.
+//!
+//! The CircleCI CLI's `orb pack` replaces any YAML string whose whole value
+//! is `<<include(path)>>` with the contents of `path`, relative to the orb
+//! source directory. Run scripts are commonly kept this way:
+//!
+//! ```yaml
+//! # commands/greet.yml
+//! steps:
+//!   - run:
+//!       name: Greet
+//!       command: <<include(scripts/greet.sh)>>
+//! ```
+//!
+//! Resolving the directives while parsing gives generated resources the real
+//! script rather than the directive. Packed orbs have already been resolved.
+
+use std::{
+    fs,
+    path::{Component, Path},
+};
+
+use serde_yaml::Value;
+
+use super::ParseError;
+
+/// Opening of an include directive.
+pub const DIRECTIVE_PREFIX: &str = "<<include(";
+
+/// The file named by `value` when it is exactly an include directive.
+pub fn directive_target(value: &str) -> Option<&str> {
+    value
+        .trim()
+        .strip_prefix(DIRECTIVE_PREFIX)?
+        .strip_suffix(")>>")
+        .map(str::trim)
+}
+
+/// Replace every include directive in `value`, read from `path`, with the
+/// contents of the file it names under `orb_dir`.
+pub fn resolve(value: &mut Value, orb_dir: &Path, path: &Path) -> Result<(), ParseError> {
+    match value {
+        Value::String(text) => {
+            if let Some(target) = directive_target(text) {
+                *text = read_target(target, orb_dir, path)?;
+            }
+        }
+        Value::Mapping(mapping) => {
+            for (_, child) in mapping.iter_mut() {
+                resolve(child, orb_dir, path)?;
+            }
+        }
+        Value::Sequence(items) => {
+            for item in items {
+                resolve(item, orb_dir, path)?;
+            }
+        }
+        Value::Tagged(tagged) => resolve(&mut tagged.value, orb_dir, path)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn read_target(target: &str, orb_dir: &Path, path: &Path) -> Result<String, ParseError> {
+    let relative = Path::new(target);
+    let inside = !target.is_empty()
+        && relative
+            .components()
+            .all(|c| matches!(c, Component::Normal(_) | Component::CurDir));
+    if !inside {
+        return Err(ParseError::InvalidInclude {
+            path: path.to_path_buf(),
+            include: target.to_string(),
+            message: "the path must be relative to the orb directory".to_string(),
+        });
+    }
+    let file = orb_dir.join(relative);
+    fs::read_to_string(&file).map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            ParseError::InvalidInclude {
+                path: path.to_path_buf(),
+                include: target.to_string(),
+                message: format!("{} does not exist", file.display()),
+            }
+        } else {
+            ParseError::FileRead {
+                path: file.clone(),
+                source: e,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn test_directive_target() {
+        assert_eq!(
+            directive_target("<<include(scripts/greet.sh)>>"),
+            Some("scripts/greet.sh")
+        );
+        assert_eq!(
+            directive_target("  <<include( scripts/a.sh )>>\n"),
+            Some("scripts/a.sh")
+        );
+        assert_eq!(directive_target("echo <<include(scripts/a.sh)>>"), None);
+        assert_eq!(directive_target("<< parameters.name >>"), None);
+    }
+
+    #[test]
+    fn test_resolve_replaces_directives_and_rejects_missing_files() {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join("scripts")).unwrap();
+        fs::write(dir.path().join("scripts/greet.sh"), "echo hello\n").unwrap();
+        let path = dir.path().join("commands/greet.yml");
+
+        let mut value: Value = serde_yaml::from_str(
+            "steps:\n  - run:\n      command: <<include(scripts/greet.sh)>>\n  - checkout\n",
+        )
+        .unwrap();
+        resolve(&mut value, dir.path(), &path).unwrap();
+        assert_eq!(value["steps"][0]["run"]["command"], "echo hello\n");
+        assert_eq!(value["steps"][1], "checkout");
+
+        let mut value = Value::String("<<include(scripts/missing.sh)>>".to_string());
+        let err = resolve(&mut value, dir.path(), &path).unwrap_err();
+        assert!(matches!(
+            &err,
+            ParseError::InvalidInclude { include, .. } if include == "scripts/missing.sh"
+        ));
+
+        let mut value = Value::String("<<include(../secret.sh)>>".to_string());
+        assert!(resolve(&mut value, dir.path(), &path).is_err());
+    }
+}
@@ -0,0 +1,181 @@
+//! Injection points of `steps` parameters.
+//!
+//! A `steps` parameter is an extension point: the steps an invocation passes
+//! run wherever the command or job splices them in with a
+//! `steps: << parameters.x >>` step, possibly inside a `when`/`unless` or an
+//! inline `steps:` list, or wherever it hands them on as an argument to
+//! another command (typically that command's own `steps` parameter).
+//! [`injection_points`] finds each such place in a step list.
+
+use super::{Step, StepList, StructuredStep};
+
+/// How the steps of a parameter reach an injection point.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Injection {
+    /// A `steps: << parameters.x >>` step runs them in place
+    Spliced,
+    /// They are passed on as `argument` of an invocation of `command`
+    Forwarded {
+        /// The invoked command (e.g. `build` or `node/install`)
+        command: String,
+        /// The argument receiving the steps
+        argument: String,
+    },
+}
+
+/// One place a parameter's steps are injected.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InjectionPoint {
+    /// Name of the parameter whose steps are injected
+    pub parameter: String,
+    /// Index of the step in the definition's step list, followed by the
+    /// index within each `when`/`unless` or inline `steps:` list nesting it
+    pub path: Vec<usize>,
+    /// How the steps get there
+    pub via: Injection,
+}
+
+impl InjectionPoint {
+    /// Whether the injection sits inside another step rather than being a
+    /// step of the definition itself.
+    pub fn is_nested(&self) -> bool {
+        self.path.len() > 1
+    }
+}
+
+/// Every place `steps` passes on a `<< parameters.x >>` placeholder, in step
+/// order.
+///
+/// Placeholders of any parameter are reported; callers keep those of
+/// parameters declared with type `steps`.
+pub fn injection_points(steps: &[Step]) -> Vec<InjectionPoint> {
+    let mut points = Vec::new();
+    collect(steps, &mut Vec::new(), &mut points);
+    points
+}
+
+fn collect(steps: &[Step], path: &mut Vec<usize>, points: &mut Vec<InjectionPoint>) {
+    for (index, step) in steps.iter().enumerate() {
+        path.push(index);
+        match step {
+            Step::Structured(StructuredStep::Steps(list)) => match list.parameter() {
+                Some(parameter) => points.push(InjectionPoint {
+                    parameter: parameter.to_string(),
+                    path: path.clone(),
+                    via: Injection::Spliced,
+                }),
+                None => collect(list.steps(), path, points),
+            },
+            Step::Structured(StructuredStep::When(c) | StructuredStep::Unless(c)) => {
+                collect(&c.steps, path, points)
+            }
+            Step::Structured(StructuredStep::CommandInvocation(invocation)) => {
+                for (command, args) in invocation {
+                    let Some(args) = args.as_mapping() else {
+                        continue;
+                    };
+                    for (argument, value) in args {
+                        let Some(argument) = argument.as_str() else {
+                            continue;
+                        };
+                        let Some(parameter) = StepList::from_value(value)
+                            .as_ref()
+                            .and_then(|list| list.parameter().map(str::to_string))
+                        else {
+                            continue;
+                        };
+                        points.push(InjectionPoint {
+                            parameter,
+                            path: path.clone(),
+                            via: Injection::Forwarded {
+                                command: command.clone(),
+                                argument: argument.to_string(),
+                            },
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+        path.pop();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn steps(yaml: &str) -> Vec<Step> {
+        serde_yaml::from_str(yaml).unwrap()
+    }
+
+    #[test]
+    fn test_injection_points_follow_nested_steps() {
+        let points = injection_points(&steps(
+            r#"
+- checkout
+- steps: << parameters.setup >>
+- when:
+    condition: << parameters.test >>
+    steps:
+      - run: cargo test
+      - steps: << parameters.after-test >>
+- steps:
+    - run: echo inline
+    - steps: << parameters.teardown >>
+"#,
+        ));
+        let found: Vec<(&str, &[usize])> = points
+            .iter()
+            .map(|p| (p.parameter.as_str(), p.path.as_slice()))
+            .collect();
+        assert_eq!(
+            found,
+            [
+                ("setup", &[1][..]),
+                ("after-test", &[2, 1][..]),
+                ("teardown", &[3, 1][..]),
+            ]
+        );
+        assert!(points.iter().all(|p| p.via == Injection::Spliced));
+        assert!(!points[0].is_nested());
+        assert!(points[1].is_nested());
+    }
+
+    #[test]
+    fn test_injection_points_include_forwarded_arguments() {
+        let points = injection_points(&steps(
+            r#"
+- build:
+    release: true
+    after-build: << parameters.after-build >>
+- unless:
+    condition: false
+    steps:
+      - node/install:
+          post-install: << parameters.post-install >>
+"#,
+        ));
+        assert_eq!(
+            points,
+            [
+                InjectionPoint {
+                    parameter: "after-build".to_string(),
+                    path: vec![0],
+                    via: Injection::Forwarded {
+                        command: "build".to_string(),
+                        argument: "after-build".to_string(),
+                    },
+                },
+                InjectionPoint {
+                    parameter: "post-install".to_string(),
+                    path: vec![1, 0],
+                    via: Injection::Forwarded {
+                        command: "node/install".to_string(),
+                        argument: "post-install".to_string(),
+                    },
+                },
+            ]
+        );
+    }
+}
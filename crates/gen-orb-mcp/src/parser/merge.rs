@@ -0,0 +1,164 @@
+//! YAML merge keys.
+//!
+//! serde_yaml expands `*alias` references to their `&anchor` while parsing,
+//! but keeps a `<<: *defaults` merge key as an ordinary `<<` entry. Typed
+//! parsing then drops that entry, and with it everything it should have
+//! merged, such as the docker images an executor shares with another.
+//! [`resolve`] merges those entries into their mappings first, the way
+//! CircleCI reads them:
+//!
+//! ```yaml
+//! executors:
+//!   base: &base
+//!     docker:
+//!       - image: cimg/base:2024.01
+//!     resource_class: small
+//!   large:
+//!     <<: *base
+//!     resource_class: large # wins over the merged `small`
+//! ```
+//!
+//! A merge key takes a mapping or a list of mappings. Keys written in the
+//! mapping win over merged ones, and earlier mappings of a list over later
+//! ones. Merged mappings may merge in others, to any depth.
+
+use std::path::Path;
+
+use serde_yaml::{Mapping, Value};
+
+use super::ParseError;
+
+/// Mapping key whose mappings are merged into the enclosing mapping.
+pub const MERGE_KEY: &str = "<<";
+
+/// Whether any mapping in `value` has a `<<` merge key.
+pub fn has_merge_keys(value: &Value) -> bool {
+    match value {
+        Value::Mapping(mapping) => {
+            mapping.contains_key(MERGE_KEY) || mapping.values().any(has_merge_keys)
+        }
+        Value::Sequence(items) => items.iter().any(has_merge_keys),
+        Value::Tagged(tagged) => has_merge_keys(&tagged.value),
+        _ => false,
+    }
+}
+
+/// Replace every `<<` entry in `value`, read from `path`, with the keys it
+/// merges.
+pub fn resolve(value: &mut Value, path: &Path) -> Result<(), ParseError> {
+    resolve_at(value, "", path)
+}
+
+fn resolve_at(value: &mut Value, location: &str, path: &Path) -> Result<(), ParseError> {
+    match value {
+        Value::Mapping(mapping) => {
+            if let Some(mut merged) = mapping.remove(MERGE_KEY) {
+                let location = child(location, MERGE_KEY);
+                resolve_at(&mut merged, &location, path)?;
+                for source in sources(merged, &location, path)? {
+                    for (key, value) in source {
+                        if !mapping.contains_key(&key) {
+                            mapping.insert(key, value);
+                        }
+                    }
+                }
+            }
+            for (key, value) in mapping.iter_mut() {
+                let location = child(location, key.as_str().unwrap_or("?"));
+                resolve_at(value, &location, path)?;
+            }
+        }
+        Value::Sequence(items) => {
+            for (index, item) in items.iter_mut().enumerate() {
+                resolve_at(item, &child(location, &index.to_string()), path)?;
+            }
+        }
+        Value::Tagged(tagged) => resolve_at(&mut tagged.value, location, path)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+/// The mappings a merge key at `location` takes, in priority order.
+fn sources(merged: Value, location: &str, path: &Path) -> Result<Vec<Mapping>, ParseError> {
+    let invalid = || ParseError::InvalidMerge {
+        path: path.to_path_buf(),
+        location: location.to_string(),
+    };
+    match merged {
+        Value::Mapping(mapping) => Ok(vec![mapping]),
+        Value::Sequence(items) => items
+            .into_iter()
+            .map(|item| match item {
+                Value::Mapping(mapping) => Ok(mapping),
+                _ => Err(invalid()),
+            })
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+fn child(location: &str, key: &str) -> String {
+    if location.is_empty() {
+        key.to_string()
+    } else {
+        format!("{location}/{key}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn resolved(yaml: &str) -> Value {
+        let mut value: Value = serde_yaml::from_str(yaml).unwrap();
+        resolve(&mut value, Path::new("orb.yml")).unwrap();
+        value
+    }
+
+    #[test]
+    fn test_explicit_keys_win_over_merged_ones() {
+        let value = resolved(
+            r#"
+base: &base
+  docker:
+    - image: cimg/base:2024.01
+  resource_class: small
+large:
+  <<: *base
+  resource_class: large
+"#,
+        );
+        assert!(!has_merge_keys(&value));
+        assert_eq!(value["large"]["resource_class"], "large");
+        assert_eq!(value["large"]["docker"][0]["image"], "cimg/base:2024.01");
+    }
+
+    #[test]
+    fn test_chained_and_listed_merges() {
+        let value = resolved(
+            r#"
+a: &a {x: 1, y: 1}
+b: &b {<<: *a, y: 2}
+c: {<<: *b, z: 3}
+d: {<<: [{w: 4, <<: {v: 5}}, *b], x: 0}
+"#,
+        );
+        assert_eq!(value["c"], resolved("{x: 1, y: 2, z: 3}"));
+        assert_eq!(value["d"], resolved("{v: 5, w: 4, x: 0, y: 2}"));
+        assert!(!has_merge_keys(&value));
+    }
+
+    #[test]
+    fn test_merge_of_a_scalar_is_rejected() {
+        let mut value: Value =
+            serde_yaml::from_str("jobs:\n  build:\n    <<: [{a: 1}, nope]\n").unwrap();
+        assert!(has_merge_keys(&value));
+        let err = resolve(&mut value, Path::new("orb.yml")).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid merge key in 'orb.yml' at jobs/build/<<: `<<` takes a mapping or a list \
+             of mappings"
+        );
+    }
+}
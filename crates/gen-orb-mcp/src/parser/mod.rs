@@ -18,14 +18,67 @@
 //! let orb = OrbParser::parse(Path::new("./orb.yml")).unwrap();
 //! ```
 
+pub mod action;
+pub mod condition;
 pub mod error;
+pub mod gitlab;
+pub mod includes;
+pub mod injection;
+pub mod merge;
+pub mod partials;
+pub mod references;
+pub mod strict;
 pub mod types;
 
 use std::{fs, path::Path};
 
+pub use condition::Condition;
 pub use error::ParseError;
+use partials::Partials;
 pub use types::*;
 
+/// What a parsed YAML source describes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum SourceKind {
+    /// A CircleCI orb, packed or unpacked
+    Orb,
+    /// A project config such as `.circleci/config.yml`
+    Config,
+    /// A GitHub Actions composite action (`action.yml`)
+    GhaAction,
+    /// A GitLab CI template (`*.gitlab-ci.yml`)
+    GitlabTemplate,
+}
+
+impl SourceKind {
+    /// Detect the kind of source at `path`: a file directly inside a
+    /// `.circleci` directory is a project config, a file named `action.yml`
+    /// or `action.yaml` a GitHub action, a file named `.gitlab-ci.yml` or
+    /// `<name>.gitlab-ci.yml` a GitLab CI template, anything else an orb.
+    pub fn detect(path: &Path) -> Self {
+        let in_circleci = path
+            .parent()
+            .and_then(Path::file_name)
+            .is_some_and(|dir| dir == ".circleci");
+        let is_action = path
+            .file_name()
+            .is_some_and(|f| f == "action.yml" || f == "action.yaml");
+        let is_gitlab = path
+            .file_name()
+            .and_then(|f| f.to_str())
+            .is_some_and(|f| f.ends_with(".gitlab-ci.yml"));
+        if in_circleci && path.is_file() {
+            SourceKind::Config
+        } else if is_action && path.is_file() {
+            SourceKind::GhaAction
+        } else if is_gitlab && path.is_file() {
+            SourceKind::GitlabTemplate
+        } else {
+            SourceKind::Orb
+        }
+    }
+}
+
 /// Parser for CircleCI orb definitions.
 ///
 /// Supports both packed (single YAML file) and unpacked (directory structure)
@@ -54,6 +107,75 @@ impl OrbParser {
         }
     }
 
+    /// Parse `path` as `kind` of source.
+    pub fn parse_as(path: &Path, kind: SourceKind) -> Result<OrbDefinition, ParseError> {
+        match kind {
+            SourceKind::Orb => Self::parse(path),
+            SourceKind::Config => Self::parse_config(path),
+            SourceKind::GhaAction => action::parse(path),
+            SourceKind::GitlabTemplate => gitlab::parse(path),
+        }
+    }
+
+    /// Parse `path` like [`parse`](Self::parse), but keep going past broken
+    /// definitions and return every error.
+    ///
+    /// Unpacked orbs report each `@orb.yml`, command, job, executor and
+    /// example file that fails to parse. Packed orbs report each command,
+    /// job, executor and example that does not match the orb schema, plus
+    /// the remaining top-level keys; invalid YAML is still a single error.
+    pub fn parse_all(path: &Path) -> Result<OrbDefinition, Vec<ParseError>> {
+        if path.is_dir() {
+            Self::parse_unpacked_all(path)
+        } else if path.file_name().is_some_and(|f| f == "@orb.yml") {
+            Self::parse_unpacked_all(path.parent().unwrap_or(path))
+        } else {
+            Self::parse_packed_all(path)
+        }
+    }
+
+    /// Parse `path` like [`parse_all`](Self::parse_all), then reject every
+    /// key the orb schema does not define (see [`strict`]).
+    ///
+    /// Unpacked orbs are checked after packing, with partials and includes
+    /// expanded.
+    pub fn parse_all_strict(path: &Path) -> Result<OrbDefinition, Vec<ParseError>> {
+        let orb = Self::parse_all(path)?;
+        let raw = if path.is_dir() {
+            Self::pack(path)
+        } else if path.file_name().is_some_and(|f| f == "@orb.yml") {
+            Self::pack(path.parent().unwrap_or(path))
+        } else {
+            fs::read_to_string(path)
+                .map_err(|e| ParseError::FileRead {
+                    path: path.to_path_buf(),
+                    source: e,
+                })
+                .and_then(|content| Self::parse_yaml(&content, path))
+        }
+        .map_err(|e| vec![e])?;
+        let errors = strict::unknown_keys(path, &raw);
+        if errors.is_empty() {
+            Ok(orb)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parse a project config such as `.circleci/config.yml`.
+    ///
+    /// Configs share the packed orb schema, adding `setup` and pipeline
+    /// `parameters`. A config must define at least one workflow.
+    pub fn parse_config(path: &Path) -> Result<OrbDefinition, ParseError> {
+        let config = Self::parse_packed(path)?;
+        if config.workflows.is_empty() {
+            return Err(ParseError::InvalidStructure {
+                message: format!("{} defines no workflows", path.display()),
+            });
+        }
+        Ok(config)
+    }
+
     /// Parse an unpacked orb from a directory structure.
     ///
     /// Expects the standard CircleCI orb directory layout:
@@ -64,51 +186,150 @@ impl OrbParser {
     /// │   └── *.yml
     /// ├── jobs/              # Job definitions
     /// │   └── *.yml
-    /// └── executors/         # Executor definitions
-    ///     └── *.yml
+    /// ├── executors/         # Executor definitions
+    /// │   └── *.yml
+    /// ├── examples/          # Usage examples
+    /// │   └── *.yml
+    /// ├── partials/          # Optional fragments merged via `x-include`
+    /// │   └── *.yml
+    /// └── scripts/           # Files pulled in by `<<include(...)>>`
     /// ```
     pub fn parse_unpacked(orb_dir: &Path) -> Result<OrbDefinition, ParseError> {
+        Self::parse_unpacked_all(orb_dir).map_err(|mut errors| errors.remove(0))
+    }
+
+    /// Parse an unpacked orb, collecting the errors of every file that
+    /// fails to parse.
+    fn parse_unpacked_all(orb_dir: &Path) -> Result<OrbDefinition, Vec<ParseError>> {
         let orb_yml_path = orb_dir.join("@orb.yml");
+        let mut errors = Vec::new();
 
         // Read and parse @orb.yml for root metadata
-        let orb_yml_content = fs::read_to_string(&orb_yml_path).map_err(|e| {
-            if e.kind() == std::io::ErrorKind::NotFound {
-                ParseError::MissingFile {
+        let orb_yml_content = match fs::read_to_string(&orb_yml_path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+                errors.push(ParseError::MissingFile {
                     path: orb_yml_path.clone(),
-                }
-            } else {
-                ParseError::FileRead {
+                });
+                None
+            }
+            Err(e) => {
+                errors.push(ParseError::FileRead {
                     path: orb_yml_path.clone(),
                     source: e,
-                }
+                });
+                None
             }
-        })?;
-
-        let mut orb: OrbDefinition =
-            serde_yaml::from_str(&orb_yml_content).map_err(|e| ParseError::YamlParse {
-                path: orb_yml_path,
-                source: e,
-            })?;
+        };
+
+        // Without partials no file can be expanded
+        let partials = match Partials::load(orb_dir) {
+            Ok(partials) => partials,
+            Err(e) => {
+                errors.push(e);
+                return Err(errors);
+            }
+        };
+        let mut orb: OrbDefinition = match orb_yml_content
+            .map(|content| Self::parse_with_partials(&content, &orb_yml_path, orb_dir, &partials))
+        {
+            Some(Ok(orb)) => orb,
+            Some(Err(e)) => {
+                errors.push(e);
+                OrbDefinition::default()
+            }
+            None => OrbDefinition::default(),
+        };
 
         // Parse commands directory
         let commands_dir = orb_dir.join("commands");
         if commands_dir.is_dir() {
-            orb.commands = Self::parse_directory(&commands_dir)?;
+            orb.commands =
+                Self::parse_directory_all(&commands_dir, orb_dir, &partials, &mut errors);
         }
 
         // Parse jobs directory
         let jobs_dir = orb_dir.join("jobs");
         if jobs_dir.is_dir() {
-            orb.jobs = Self::parse_directory(&jobs_dir)?;
+            orb.jobs = Self::parse_directory_all(&jobs_dir, orb_dir, &partials, &mut errors);
         }
 
         // Parse executors directory
         let executors_dir = orb_dir.join("executors");
         if executors_dir.is_dir() {
-            orb.executors = Self::parse_directory(&executors_dir)?;
+            orb.executors =
+                Self::parse_directory_all(&executors_dir, orb_dir, &partials, &mut errors);
         }
 
-        Ok(orb)
+        // Parse examples directory
+        let examples_dir = orb_dir.join("examples");
+        if examples_dir.is_dir() {
+            orb.examples =
+                Self::parse_directory_all(&examples_dir, orb_dir, &partials, &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(orb)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Pack an unpacked orb into the single YAML document `circleci orb pack`
+    /// produces.
+    ///
+    /// Each file in `commands/`, `jobs/`, `executors/` and `examples/` is
+    /// added to `@orb.yml` under its file stem, in name order. Partials and
+    /// `<<include(...)>>` directives are expanded, so the result can be
+    /// published without gen-orb-mcp.
+    pub fn pack(orb_dir: &Path) -> Result<serde_yaml::Value, ParseError> {
+        let orb_yml_path = orb_dir.join("@orb.yml");
+        let orb_yml_content = fs::read_to_string(&orb_yml_path).map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                ParseError::MissingFile {
+                    path: orb_yml_path.clone(),
+                }
+            } else {
+                ParseError::FileRead {
+                    path: orb_yml_path.clone(),
+                    source: e,
+                }
+            }
+        })?;
+
+        let partials = Partials::load(orb_dir)?;
+        let mut packed: serde_yaml::Value =
+            Self::parse_with_partials(&orb_yml_content, &orb_yml_path, orb_dir, &partials)?;
+        let root = packed
+            .as_mapping_mut()
+            .ok_or_else(|| ParseError::InvalidStructure {
+                message: format!("{} is not a mapping", orb_yml_path.display()),
+            })?;
+
+        for category in ["commands", "jobs", "executors", "examples"] {
+            let dir = orb_dir.join(category);
+            if !dir.is_dir() {
+                continue;
+            }
+            let mut definitions: Vec<(String, serde_yaml::Value)> =
+                Self::parse_directory(&dir, orb_dir, &partials)?
+                    .into_iter()
+                    .collect();
+            definitions.sort_by(|a, b| a.0.cmp(&b.0));
+            let entry = root
+                .entry(category.into())
+                .or_insert_with(|| serde_yaml::Mapping::new().into());
+            let mapping = entry
+                .as_mapping_mut()
+                .ok_or_else(|| ParseError::InvalidStructure {
+                    message: format!("`{category}` in @orb.yml is not a mapping"),
+                })?;
+            for (name, definition) in definitions {
+                mapping.insert(name.into(), definition);
+            }
+        }
+
+        Ok(packed)
     }
 
     /// Parse a packed orb from a single YAML file.
@@ -121,47 +342,207 @@ impl OrbParser {
         Self::parse_packed_content(&content, path)
     }
 
+    /// Parse a packed orb, checking each command, job, executor and example
+    /// separately when the orb does not parse as a whole.
+    fn parse_packed_all(path: &Path) -> Result<OrbDefinition, Vec<ParseError>> {
+        let content = fs::read_to_string(path).map_err(|e| {
+            vec![ParseError::FileRead {
+                path: path.to_path_buf(),
+                source: e,
+            }]
+        })?;
+        let whole_error = match Self::parse_packed_content(&content, path) {
+            Ok(orb) => return Ok(orb),
+            Err(e) => e,
+        };
+        let Ok(mut value) = serde_yaml::from_str::<serde_yaml::Value>(&content) else {
+            return Err(vec![whole_error]);
+        };
+        if merge::resolve(&mut value, path).is_err() {
+            return Err(vec![whole_error]);
+        }
+        let serde_yaml::Value::Mapping(mut root) = value else {
+            return Err(vec![whole_error]);
+        };
+
+        fn check<T: for<'de> serde::Deserialize<'de>>(
+            path: &Path,
+            category: &str,
+            definitions: serde_yaml::Value,
+            errors: &mut Vec<ParseError>,
+        ) {
+            let serde_yaml::Value::Mapping(definitions) = definitions else {
+                // A malformed category is reported by the root check.
+                return;
+            };
+            for (name, definition) in definitions {
+                if let Err(e) = serde_yaml::from_value::<T>(definition) {
+                    errors.push(ParseError::InvalidDefinition {
+                        path: path.to_path_buf(),
+                        location: format!("{category}/{}", name.as_str().unwrap_or("?")),
+                        source: e,
+                    });
+                }
+            }
+        }
+
+        let mut errors = Vec::new();
+        let mut categories = Vec::new();
+        for category in ["commands", "jobs", "executors", "examples"] {
+            match root.remove(category) {
+                Some(value @ serde_yaml::Value::Mapping(_)) => categories.push((category, value)),
+                Some(value) => {
+                    root.insert(category.into(), value);
+                }
+                None => {}
+            }
+        }
+        if let Err(e) = serde_yaml::from_value::<OrbDefinition>(root.into()) {
+            errors.push(ParseError::yaml_unlocated(path, e));
+        }
+        for (category, definitions) in categories {
+            match category {
+                "commands" => check::<Command>(path, category, definitions, &mut errors),
+                "jobs" => check::<Job>(path, category, definitions, &mut errors),
+                "executors" => check::<Executor>(path, category, definitions, &mut errors),
+                _ => check::<Example>(path, category, definitions, &mut errors),
+            }
+        }
+        errors.sort_by_key(|e| match e {
+            ParseError::InvalidDefinition { location, .. } => location.clone(),
+            _ => String::new(),
+        });
+        if errors.is_empty() {
+            errors.push(whole_error);
+        }
+        Err(errors)
+    }
+
     /// Parse a packed orb from YAML content string.
     pub fn parse_packed_content(
         content: &str,
         source_path: &Path,
     ) -> Result<OrbDefinition, ParseError> {
-        serde_yaml::from_str(content).map_err(|e| ParseError::YamlParse {
-            path: source_path.to_path_buf(),
-            source: e,
-        })
+        Self::parse_yaml(content, source_path)
     }
 
-    /// Parse all YAML files in a directory into a HashMap.
-    fn parse_directory<T>(dir: &Path) -> Result<std::collections::HashMap<String, T>, ParseError>
+    /// Parse YAML `content` from `path`, resolving `<<` merge keys first
+    /// (see [`merge`]).
+    ///
+    /// Content without merge keys is parsed directly, so errors keep their
+    /// line and column.
+    fn parse_yaml<T>(content: &str, path: &Path) -> Result<T, ParseError>
     where
         T: for<'de> serde::Deserialize<'de>,
     {
-        let mut items = std::collections::HashMap::new();
+        let yaml_error = |e| ParseError::yaml(path, e);
+        if content.contains(merge::MERGE_KEY) {
+            let mut value: serde_yaml::Value = serde_yaml::from_str(content).map_err(yaml_error)?;
+            if merge::has_merge_keys(&value) {
+                merge::resolve(&mut value, path)?;
+                return Self::reparse(&value, path);
+            }
+        }
+        serde_yaml::from_str(content).map_err(yaml_error)
+    }
 
-        let entries = fs::read_dir(dir).map_err(|e| ParseError::DirectoryRead {
-            path: dir.to_path_buf(),
-            source: e,
-        })?;
+    /// Parse YAML `content` from `path`, expanding `x-include` partials,
+    /// then `<<include(...)>>` directives relative to `orb_dir`, then `<<`
+    /// merge keys first.
+    fn parse_with_partials<T>(
+        content: &str,
+        path: &Path,
+        orb_dir: &Path,
+        partials: &Partials,
+    ) -> Result<T, ParseError>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        let yaml_error = |e| ParseError::yaml(path, e);
+        if !content.contains(partials::INCLUDE_KEY) && !content.contains(includes::DIRECTIVE_PREFIX)
+        {
+            return Self::parse_yaml(content, path);
+        }
+        let mut value: serde_yaml::Value = serde_yaml::from_str(content).map_err(yaml_error)?;
+        partials.expand(&mut value, path)?;
+        includes::resolve(&mut value, orb_dir, path)?;
+        merge::resolve(&mut value, path)?;
+        Self::reparse(&value, path)
+    }
 
-        for entry in entries {
-            let entry = entry.map_err(|e| ParseError::DirectoryRead {
-                path: dir.to_path_buf(),
-                source: e,
-            })?;
+    /// Parse `value`, expanded from the YAML at `path`, into `T`.
+    ///
+    /// Expanded YAML is re-serialized and parsed as text, so scalars keep the
+    /// coercions of direct parsing (e.g. `version: 2.1` into a string).
+    fn reparse<T>(value: &serde_yaml::Value, path: &Path) -> Result<T, ParseError>
+    where
+        T: for<'de> serde::Deserialize<'de>,
+    {
+        // Positions in the expanded text do not match the file.
+        let expanded_error = |e| ParseError::yaml_unlocated(path, e);
+        let expanded = serde_yaml::to_string(value).map_err(expanded_error)?;
+        serde_yaml::from_str(&expanded).map_err(expanded_error)
+    }
+
+    /// Parse all YAML files in a directory into a HashMap.
+    fn parse_directory<T>(
+        dir: &Path,
+        orb_dir: &Path,
+        partials: &Partials,
+    ) -> Result<std::collections::HashMap<String, T>, ParseError>
+    where
+        T: for<'de> serde::Deserialize<'de> + Send,
+    {
+        let mut errors = Vec::new();
+        let items = Self::parse_directory_all(dir, orb_dir, partials, &mut errors);
+        match errors.into_iter().next() {
+            Some(e) => Err(e),
+            None => Ok(items),
+        }
+    }
 
-            let path = entry.path();
+    /// Parse all YAML files in a directory into a HashMap, in file name
+    /// order, pushing the error of each file that fails to parse onto
+    /// `errors`.
+    ///
+    /// Files are parsed concurrently, one worker per available core; results
+    /// are merged in file name order, so items and errors do not depend on
+    /// which worker finished first.
+    fn parse_directory_all<T>(
+        dir: &Path,
+        orb_dir: &Path,
+        partials: &Partials,
+        errors: &mut Vec<ParseError>,
+    ) -> std::collections::HashMap<String, T>
+    where
+        T: for<'de> serde::Deserialize<'de> + Send,
+    {
+        let mut items = std::collections::HashMap::new();
 
-            // Skip non-YAML files and directories
-            if path.is_dir() {
-                continue;
+        let dir_error = |e| ParseError::DirectoryRead {
+            path: dir.to_path_buf(),
+            source: e,
+        };
+        let mut paths = match fs::read_dir(dir).and_then(|entries| {
+            entries
+                .map(|e| e.map(|e| e.path()))
+                .collect::<Result<Vec<_>, _>>()
+        }) {
+            Ok(paths) => paths,
+            Err(e) => {
+                errors.push(dir_error(e));
+                return items;
             }
+        };
+        paths.sort();
 
+        // Skip non-YAML files and directories
+        paths.retain(|path| {
             let extension = path.extension().and_then(|e| e.to_str());
-            if extension != Some("yml") && extension != Some("yaml") {
-                continue;
-            }
+            !path.is_dir() && (extension == Some("yml") || extension == Some("yaml"))
+        });
 
+        let parse_file = |path: &Path| -> Result<(String, T), ParseError> {
             // Get name from filename (without extension)
             let name = path
                 .file_stem()
@@ -170,21 +551,52 @@ impl OrbParser {
                     message: format!("invalid filename: {}", path.display()),
                 })?
                 .to_string();
-
-            let content = fs::read_to_string(&path).map_err(|e| ParseError::FileRead {
-                path: path.clone(),
+            let content = fs::read_to_string(path).map_err(|e| ParseError::FileRead {
+                path: path.to_path_buf(),
                 source: e,
             })?;
-
-            let item: T = serde_yaml::from_str(&content).map_err(|e| ParseError::YamlParse {
-                path: path.clone(),
-                source: e,
-            })?;
-
-            items.insert(name, item);
+            let item = Self::parse_with_partials(&content, path, orb_dir, partials)?;
+            Ok((name, item))
+        };
+
+        // Workers pull files from a shared index and record each result in
+        // its file's slot.
+        let workers = std::thread::available_parallelism()
+            .map_or(1, |n| n.get())
+            .min(paths.len());
+        let results: Vec<Result<(String, T), ParseError>> = if workers <= 1 {
+            paths.iter().map(|path| parse_file(path)).collect()
+        } else {
+            let next = std::sync::atomic::AtomicUsize::new(0);
+            let slots: Vec<_> = paths.iter().map(|_| std::sync::Mutex::new(None)).collect();
+            std::thread::scope(|scope| {
+                for _ in 0..workers {
+                    scope.spawn(|| loop {
+                        let i = next.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+                        let Some(path) = paths.get(i) else {
+                            break;
+                        };
+                        let result = parse_file(path);
+                        *slots[i].lock().unwrap_or_else(|e| e.into_inner()) = Some(result);
+                    });
+                }
+            });
+            slots
+                .into_iter()
+                .filter_map(|slot| slot.into_inner().unwrap_or_else(|e| e.into_inner()))
+                .collect()
+        };
+
+        for result in results {
+            match result {
+                Ok((name, item)) => {
+                    items.insert(name, item);
+                }
+                Err(e) => errors.push(e),
+            }
         }
 
-        Ok(items)
+        items
     }
 }
 
@@ -289,6 +701,137 @@ resource_class: medium
         assert!(default_exec.config.docker.is_some());
     }
 
+    #[test]
+    fn test_parse_unpacked_orb_examples() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unpacked_orb(temp_dir.path());
+        fs::create_dir(temp_dir.path().join("examples")).unwrap();
+        fs::write(
+            temp_dir.path().join("examples/build.yml"),
+            r#"
+description: "Build a release"
+usage:
+  version: 2.1
+  orbs:
+    toolkit: my-org/toolkit@1.0.0
+  workflows:
+    main:
+      jobs:
+        - toolkit/build:
+            release: true
+"#,
+        )
+        .unwrap();
+
+        let orb = OrbParser::parse_unpacked(temp_dir.path()).unwrap();
+
+        let example = &orb.examples["build"];
+        assert_eq!(example.description.as_deref(), Some("Build a release"));
+        assert_eq!(
+            example.usage["orbs"]["toolkit"].as_str(),
+            Some("my-org/toolkit@1.0.0")
+        );
+        assert!(example.result.is_none());
+    }
+
+    #[test]
+    fn test_parse_unpacked_orb_with_partials() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unpacked_orb(temp_dir.path());
+        let partials_dir = temp_dir.path().join("partials");
+        fs::create_dir_all(&partials_dir).unwrap();
+        fs::write(
+            partials_dir.join("release.yml"),
+            "release:\n  type: boolean\n  default: false\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("commands/push.yml"),
+            "parameters:\n  x-include: release\nsteps:\n  - run: echo push\n",
+        )
+        .unwrap();
+
+        // Unquoted versions must still parse once the file is expanded
+        fs::write(
+            temp_dir.path().join("@orb.yml"),
+            "version: 2.1\ndescription: Test orb\nx-include: []\n",
+        )
+        .unwrap();
+
+        let orb = OrbParser::parse_unpacked(temp_dir.path()).unwrap();
+        assert_eq!(orb.version, "2.1");
+        let push = &orb.commands["push"];
+        assert_eq!(
+            push.parameters["release"].param_type,
+            ParameterType::Boolean
+        );
+        assert!(!orb.commands.contains_key("release"));
+
+        fs::write(
+            temp_dir.path().join("jobs/deploy.yml"),
+            "parameters:\n  x-include: relase\n",
+        )
+        .unwrap();
+        let err = OrbParser::parse_unpacked(temp_dir.path()).unwrap_err();
+        assert!(matches!(err, ParseError::UnknownPartial { ref name, .. } if name == "relase"));
+    }
+
+    #[test]
+    fn test_parse_unpacked_orb_resolves_script_includes() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unpacked_orb(temp_dir.path());
+        fs::create_dir_all(temp_dir.path().join("scripts")).unwrap();
+        fs::write(
+            temp_dir.path().join("scripts/greet.sh"),
+            "#!/bin/bash\necho \"Hello, ${NAME}\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp_dir.path().join("commands/greet.yml"),
+            "steps:\n  - run:\n      name: Greet\n      command: <<include(scripts/greet.sh)>>\n",
+        )
+        .unwrap();
+
+        let orb = OrbParser::parse_unpacked(temp_dir.path()).unwrap();
+        let Step::Structured(StructuredStep::Run(RunStep::Full { command, .. })) =
+            &orb.commands["greet"].steps[0]
+        else {
+            panic!("expected a run step");
+        };
+        assert_eq!(command, "#!/bin/bash\necho \"Hello, ${NAME}\"\n");
+
+        fs::remove_file(temp_dir.path().join("scripts/greet.sh")).unwrap();
+        let err = OrbParser::parse_unpacked(temp_dir.path()).unwrap_err();
+        assert!(
+            err.to_string()
+                .starts_with("invalid include 'scripts/greet.sh' in '"),
+            "got: {err}"
+        );
+    }
+
+    #[test]
+    fn test_pack_matches_unpacked_orb() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unpacked_orb(temp_dir.path());
+        fs::create_dir(temp_dir.path().join("examples")).unwrap();
+        fs::write(
+            temp_dir.path().join("examples/basic.yml"),
+            "description: Basic usage\nusage:\n  version: 2.1\n",
+        )
+        .unwrap();
+
+        let packed = OrbParser::pack(temp_dir.path()).unwrap();
+        assert_eq!(packed["examples"]["basic"]["description"], "Basic usage");
+
+        let content = serde_yaml::to_string(&packed).unwrap();
+        let from_packed = OrbParser::parse_packed_content(&content, Path::new("orb.yml")).unwrap();
+        let from_unpacked = OrbParser::parse_unpacked(temp_dir.path()).unwrap();
+        assert_eq!(
+            serde_json::to_value(&from_packed).unwrap(),
+            serde_json::to_value(&from_unpacked).unwrap()
+        );
+    }
+
     #[test]
     fn test_parse_via_orb_yml_path() {
         let temp_dir = TempDir::new().unwrap();
@@ -337,6 +880,106 @@ executors:
         assert!(orb.executors.contains_key("rust"));
     }
 
+    #[test]
+    fn test_parse_packed_orb_with_anchors_and_merge_keys() {
+        let packed_yaml = r#"
+version: "2.1"
+description: "Packed test orb"
+
+executors:
+  base: &base
+    docker:
+      - &image
+        image: cimg/rust:1.80
+        auth:
+          username: bot
+          password: $TOKEN
+    resource_class: small
+    environment: &env
+      CARGO_TERM_COLOR: always
+  large:
+    <<: *base
+    resource_class: large
+  services:
+    <<: [*base]
+    docker:
+      - <<: *image
+        name: primary
+      - image: cimg/postgres:16.0
+    environment:
+      <<: *env
+      DATABASE_URL: postgres://localhost
+
+jobs:
+  ci:
+    <<: *base
+    steps:
+      - checkout
+"#;
+        let temp_dir = TempDir::new().unwrap();
+        let orb_file = temp_dir.path().join("orb.yml");
+        fs::write(&orb_file, packed_yaml).unwrap();
+
+        let image =
+            |config: &ExecutorConfig, index: usize| match &config.docker.as_ref().unwrap()[index] {
+                DockerImage::Full(full) => full.clone(),
+                DockerImage::Simple(reference) => panic!("unexpected short form {reference}"),
+            };
+        for orb in [
+            OrbParser::parse_packed(&orb_file).unwrap(),
+            OrbParser::parse_all_strict(&orb_file).unwrap(),
+        ] {
+            let large = &orb.executors["large"].config;
+            assert_eq!(large.resource_class.as_deref(), Some("large"));
+            assert_eq!(image(large, 0).image, "cimg/rust:1.80");
+            assert_eq!(large.environment["CARGO_TERM_COLOR"], "always");
+
+            let services = &orb.executors["services"].config;
+            assert_eq!(services.resource_class.as_deref(), Some("small"));
+            assert_eq!(image(services, 0).name.as_deref(), Some("primary"));
+            assert!(image(services, 0).auth.is_some());
+            assert_eq!(image(services, 1).image, "cimg/postgres:16.0");
+            assert_eq!(services.environment.len(), 2);
+
+            let ci = &orb.jobs["ci"].config;
+            assert_eq!(image(ci, 0).image, "cimg/rust:1.80");
+        }
+
+        fs::write(&orb_file, "version: \"2.1\"\njobs:\n  ci:\n    <<: 3\n").unwrap();
+        let err = OrbParser::parse_packed(&orb_file).unwrap_err();
+        assert!(
+            matches!(&err, ParseError::InvalidMerge { location, .. } if location == "jobs/ci/<<"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_parse_unpacked_orb_resolves_merge_keys_in_each_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_dir = temp_dir.path();
+        fs::create_dir_all(orb_dir.join("executors")).unwrap();
+        fs::write(orb_dir.join("@orb.yml"), "version: \"2.1\"\n").unwrap();
+        fs::write(
+            orb_dir.join("executors/default.yml"),
+            "docker:\n  \
+               - &primary\n    image: cimg/base:2024.01\n    environment:\n      TZ: UTC\n  \
+               - <<: *primary\n    image: cimg/redis:7.2\n    name: cache\n",
+        )
+        .unwrap();
+
+        let orb = OrbParser::parse(orb_dir).unwrap();
+        let docker = orb.executors["default"].config.docker.clone().unwrap();
+        let DockerImage::Full(cache) = &docker[1] else {
+            panic!("unexpected short form");
+        };
+        assert_eq!(cache.image, "cimg/redis:7.2");
+        assert_eq!(cache.name.as_deref(), Some("cache"));
+        assert_eq!(cache.environment["TZ"], "UTC");
+
+        let packed = OrbParser::pack(orb_dir).unwrap();
+        assert!(!merge::has_merge_keys(&packed));
+    }
+
     #[test]
     fn test_parse_auto_detect_packed() {
         let packed_yaml = r#"
@@ -355,6 +998,102 @@ commands:
         assert!(orb.commands.contains_key("hello"));
     }
 
+    #[test]
+    fn test_parse_project_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let circleci = temp_dir.path().join(".circleci");
+        fs::create_dir(&circleci).unwrap();
+        let config_file = circleci.join("config.yml");
+        fs::write(
+            &config_file,
+            r#"
+version: 2.1
+setup: true
+orbs:
+  node: circleci/node@5.1.0
+parameters:
+  run-deploy:
+    type: boolean
+    default: false
+jobs:
+  build:
+    docker:
+      - image: cimg/base:2024.01
+    steps:
+      - checkout
+workflows:
+  main:
+    jobs:
+      - build
+      - node/test:
+          requires: [build]
+"#,
+        )
+        .unwrap();
+
+        assert_eq!(SourceKind::detect(&config_file), SourceKind::Config);
+        let config = OrbParser::parse_as(&config_file, SourceKind::Config).unwrap();
+        assert!(config.setup);
+        assert_eq!(
+            config.parameters["run-deploy"].param_type,
+            ParameterType::Boolean
+        );
+        assert!(config.jobs.contains_key("build"));
+        assert_eq!(config.workflows["main"].jobs[1].job, "node/test");
+        assert_eq!(config.orbs["node"], "circleci/node@5.1.0");
+
+        fs::write(&config_file, "version: 2.1\njobs: {}\n").unwrap();
+        let err = OrbParser::parse_config(&config_file).unwrap_err();
+        assert!(err.to_string().contains("defines no workflows"));
+
+        let orb_file = temp_dir.path().join("orb.yml");
+        fs::write(&orb_file, "version: 2.1\n").unwrap();
+        assert_eq!(SourceKind::detect(&orb_file), SourceKind::Orb);
+    }
+
+    #[test]
+    fn test_parse_as_gha_action() {
+        let temp_dir = TempDir::new().unwrap();
+        let action_file = temp_dir.path().join("action.yml");
+        fs::write(
+            &action_file,
+            "name: Say hello
+description: Greets
+inputs:
+  who:
+    required: true
+             runs:
+  using: composite
+  steps:
+    - run: echo ${{ inputs.who }}
+                   shell: bash
+",
+        )
+        .unwrap();
+
+        assert_eq!(SourceKind::detect(&action_file), SourceKind::GhaAction);
+        let orb = OrbParser::parse_as(&action_file, SourceKind::GhaAction).unwrap();
+        assert_eq!(orb.description.as_deref(), Some("Greets"));
+        assert!(orb.commands["say-hello"].parameters.contains_key("who"));
+    }
+
+    #[test]
+    fn test_parse_as_gitlab_template() {
+        let temp_dir = TempDir::new().unwrap();
+        let template = temp_dir.path().join("Lint.gitlab-ci.yml");
+        fs::write(
+            &template,
+            "variables:\n  LINT_ARGS: --strict\nlint:\n  image: node:20\n  script: npm run \
+             lint $LINT_ARGS\n",
+        )
+        .unwrap();
+
+        assert_eq!(SourceKind::detect(&template), SourceKind::GitlabTemplate);
+        let orb = OrbParser::parse_as(&template, SourceKind::GitlabTemplate).unwrap();
+        assert!(orb.parameters.contains_key("LINT_ARGS"));
+        assert_eq!(orb.jobs["lint"].steps.len(), 1);
+    }
+
     #[test]
     fn test_parse_missing_orb_yml() {
         let temp_dir = TempDir::new().unwrap();
@@ -370,7 +1109,110 @@ commands:
         fs::write(&orb_file, "{ invalid yaml [[[").unwrap();
 
         let result = OrbParser::parse_packed(&orb_file);
-        assert!(matches!(result, Err(ParseError::YamlParse { .. })));
+        assert!(matches!(
+            result,
+            Err(ParseError::YamlParse {
+                line: Some(1),
+                column: Some(_),
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_parse_all_collects_every_unpacked_error() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unpacked_orb(temp_dir.path());
+        fs::write(
+            temp_dir.path().join("commands/broken.yml"),
+            "steps: not-a-list\n",
+        )
+        .unwrap();
+        fs::write(temp_dir.path().join("jobs/broken.yml"), "steps: [\n").unwrap();
+
+        let errors = OrbParser::parse_all(temp_dir.path()).unwrap_err();
+        let files: Vec<_> = errors
+            .iter()
+            .map(|e| e.file().unwrap().0.strip_prefix(temp_dir.path()).unwrap())
+            .collect();
+        assert_eq!(
+            files,
+            [
+                Path::new("commands/broken.yml"),
+                Path::new("jobs/broken.yml")
+            ]
+        );
+        // parse stops at the first of them
+        assert_eq!(
+            OrbParser::parse(temp_dir.path()).unwrap_err().to_string(),
+            errors[0].to_string()
+        );
+
+        fs::remove_file(temp_dir.path().join("commands/broken.yml")).unwrap();
+        fs::remove_file(temp_dir.path().join("jobs/broken.yml")).unwrap();
+        assert!(OrbParser::parse_all(temp_dir.path()).is_ok());
+    }
+
+    #[test]
+    fn test_parse_unpacked_orb_merges_many_files_in_name_order() {
+        let temp_dir = TempDir::new().unwrap();
+        create_unpacked_orb(temp_dir.path());
+        for i in 0..64 {
+            fs::write(
+                temp_dir.path().join(format!("commands/step-{i:02}.yml")),
+                format!("steps:\n  - run: echo {i}\n"),
+            )
+            .unwrap();
+        }
+
+        let orb = OrbParser::parse_unpacked(temp_dir.path()).unwrap();
+        assert_eq!(orb.commands.len(), 65);
+        assert_eq!(orb.commands["step-42"].steps.len(), 1);
+
+        for name in ["step-50", "step-07", "step-31"] {
+            fs::write(
+                temp_dir.path().join(format!("commands/{name}.yml")),
+                "steps: not-a-list\n",
+            )
+            .unwrap();
+        }
+        let errors = OrbParser::parse_all(temp_dir.path()).unwrap_err();
+        let files: Vec<_> = errors
+            .iter()
+            .map(|e| e.file().unwrap().0.file_name().unwrap().to_owned())
+            .collect();
+        assert_eq!(files, ["step-07.yml", "step-31.yml", "step-50.yml"]);
+    }
+
+    #[test]
+    fn test_parse_all_checks_each_packed_definition() {
+        let temp_dir = TempDir::new().unwrap();
+        let orb_file = temp_dir.path().join("orb.yml");
+        fs::write(
+            &orb_file,
+            "version: \"2.1\"\n\
+             display: 3\n\
+             commands:\n  good:\n    steps: []\n  bad:\n    steps: not-a-list\n\
+             jobs:\n  worse:\n    steps: 3\n",
+        )
+        .unwrap();
+
+        let errors = OrbParser::parse_all(&orb_file).unwrap_err();
+        let locations: Vec<_> = errors
+            .iter()
+            .map(|e| match e {
+                ParseError::InvalidDefinition { location, .. } => location.as_str(),
+                _ => "orb",
+            })
+            .collect();
+        assert_eq!(locations, ["orb", "commands/bad", "jobs/worse"]);
+        assert!(errors[1].to_string().starts_with(&format!(
+            "{}: commands/bad: invalid type",
+            orb_file.display()
+        )));
+
+        fs::write(&orb_file, "{ invalid yaml [[[").unwrap();
+        assert_eq!(OrbParser::parse_all(&orb_file).unwrap_err().len(), 1);
     }
 
     #[test]
@@ -0,0 +1,257 @@
+//! Shared YAML fragments for unpacked orbs.
+//!
+//! Files in `partials/` hold mappings that definitions pull in with an
+//! `x-include` key, so a parameter block repeated across many commands can be
+//! written once:
+//!
+//! ```yaml
+//! # partials/registry-params.yml
+//! registry:
+//!   type: string
+//!   default: ghcr.io
+//!
+//! # commands/push.yml
+//! parameters:
+//!   x-include: registry-params
+//!   tag:
+//!     type: string
+//! ```
+//!
+//! `x-include` takes a partial name or a list of names and may appear in any
+//! mapping. The partial's keys are merged into that mapping before typed
+//! parsing; keys written next to `x-include` win, and nested mappings merge
+//! recursively. Partials may include other partials.
+
+use std::{
+    collections::HashMap,
+    fs,
+    path::{Path, PathBuf},
+};
+
+use serde_yaml::{Mapping, Value};
+
+use super::ParseError;
+use crate::suggest;
+
+/// Directory of an unpacked orb holding partials.
+pub const PARTIALS_DIR: &str = "partials";
+
+/// Mapping key that merges partials into the enclosing mapping.
+pub const INCLUDE_KEY: &str = "x-include";
+
+/// The partials of one orb, keyed by file stem.
+#[derive(Debug, Default)]
+pub struct Partials {
+    fragments: HashMap<String, (PathBuf, Mapping)>,
+}
+
+impl Partials {
+    /// Load `partials/*.yml` from `orb_dir`; no directory means no partials.
+    pub fn load(orb_dir: &Path) -> Result<Self, ParseError> {
+        let dir = orb_dir.join(PARTIALS_DIR);
+        let mut partials = Self::default();
+        if !dir.is_dir() {
+            return Ok(partials);
+        }
+        let entries = fs::read_dir(&dir).map_err(|e| ParseError::DirectoryRead {
+            path: dir.clone(),
+            source: e,
+        })?;
+        for entry in entries {
+            let path = entry
+                .map_err(|e| ParseError::DirectoryRead {
+                    path: dir.clone(),
+                    source: e,
+                })?
+                .path();
+            let extension = path.extension().and_then(|e| e.to_str());
+            if path.is_dir() || (extension != Some("yml") && extension != Some("yaml")) {
+                continue;
+            }
+            let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                continue;
+            };
+            let content = fs::read_to_string(&path).map_err(|e| ParseError::FileRead {
+                path: path.clone(),
+                source: e,
+            })?;
+            let value: Value =
+                serde_yaml::from_str(&content).map_err(|e| ParseError::yaml(&path, e))?;
+            let Value::Mapping(mapping) = value else {
+                return Err(ParseError::InvalidPartial {
+                    path,
+                    message: "a partial must be a YAML mapping".to_string(),
+                });
+            };
+            partials
+                .fragments
+                .insert(name.to_string(), (path.clone(), mapping));
+        }
+        Ok(partials)
+    }
+
+    /// Replace every `x-include` in `value`, read from `path`, with the
+    /// partials it names.
+    pub fn expand(&self, value: &mut Value, path: &Path) -> Result<(), ParseError> {
+        self.expand_value(value, path, &mut Vec::new())
+    }
+
+    fn expand_value(
+        &self,
+        value: &mut Value,
+        path: &Path,
+        including: &mut Vec<String>,
+    ) -> Result<(), ParseError> {
+        match value {
+            Value::Mapping(mapping) => {
+                if let Some(include) = mapping.remove(INCLUDE_KEY) {
+                    for name in include_names(&include, path)? {
+                        let fragment = self.resolve(&name, path, including)?;
+                        merge(mapping, fragment);
+                    }
+                }
+                for (_, child) in mapping.iter_mut() {
+                    self.expand_value(child, path, including)?;
+                }
+            }
+            Value::Sequence(items) => {
+                for item in items {
+                    self.expand_value(item, path, including)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// The fully expanded partial `name`, included from `path`.
+    fn resolve(
+        &self,
+        name: &str,
+        path: &Path,
+        including: &mut Vec<String>,
+    ) -> Result<Mapping, ParseError> {
+        let Some((partial_path, fragment)) = self.fragments.get(name) else {
+            let mut known: Vec<&str> = self.fragments.keys().map(String::as_str).collect();
+            known.sort_unstable();
+            return Err(ParseError::UnknownPartial {
+                path: path.to_path_buf(),
+                name: name.to_string(),
+                suggestion: suggest::did_you_mean(name, known),
+            });
+        };
+        if including.iter().any(|n| n == name) {
+            return Err(ParseError::InvalidPartial {
+                path: partial_path.clone(),
+                message: format!("include cycle: {} -> {name}", including.join(" -> ")),
+            });
+        }
+        including.push(name.to_string());
+        let mut expanded = Value::Mapping(fragment.clone());
+        self.expand_value(&mut expanded, partial_path, including)?;
+        including.pop();
+        let Value::Mapping(mapping) = expanded else {
+            unreachable!("expanding a mapping yields a mapping");
+        };
+        Ok(mapping)
+    }
+}
+
+/// Partial names from an `x-include` value (a string or list of strings).
+fn include_names(include: &Value, path: &Path) -> Result<Vec<String>, ParseError> {
+    let invalid = || ParseError::InvalidPartial {
+        path: path.to_path_buf(),
+        message: format!("`{INCLUDE_KEY}` must be a partial name or a list of names"),
+    };
+    match include {
+        Value::String(name) => Ok(vec![name.clone()]),
+        Value::Sequence(names) => names
+            .iter()
+            .map(|n| n.as_str().map(str::to_string).ok_or_else(invalid))
+            .collect(),
+        _ => Err(invalid()),
+    }
+}
+
+/// Merge `fragment` into `target`, keeping `target`'s values on conflict.
+fn merge(target: &mut Mapping, fragment: Mapping) {
+    for (key, value) in fragment {
+        match (target.get_mut(&key), value) {
+            (None, value) => {
+                target.insert(key, value);
+            }
+            (Some(Value::Mapping(existing)), Value::Mapping(fragment)) => merge(existing, fragment),
+            (Some(_), _) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+
+    fn partials(files: &[(&str, &str)]) -> (TempDir, Partials) {
+        let dir = TempDir::new().unwrap();
+        fs::create_dir_all(dir.path().join(PARTIALS_DIR)).unwrap();
+        for (name, content) in files {
+            fs::write(dir.path().join(PARTIALS_DIR).join(name), content).unwrap();
+        }
+        let partials = Partials::load(dir.path()).unwrap();
+        (dir, partials)
+    }
+
+    #[test]
+    fn test_expand_merges_nested_partials_with_local_keys_winning() {
+        let (_dir, partials) = partials(&[
+            (
+                "registry.yml",
+                "registry:\n  type: string\n  default: ghcr.io\nx-include: tag\n",
+            ),
+            ("tag.yml", "tag:\n  type: string\n  default: latest\n"),
+        ]);
+        let mut value: Value = serde_yaml::from_str(
+            "parameters:\n  x-include: [registry]\n  tag:\n    default: edge\n",
+        )
+        .unwrap();
+
+        partials.expand(&mut value, Path::new("push.yml")).unwrap();
+        let expected: Value = serde_yaml::from_str(
+            "parameters:\n  tag:\n    default: edge\n    type: string\n  \
+             registry:\n    type: string\n    default: ghcr.io\n",
+        )
+        .unwrap();
+        assert_eq!(value, expected);
+    }
+
+    #[test]
+    fn test_expand_reports_unknown_partial_with_suggestion() {
+        let (_dir, partials) = partials(&[("registry.yml", "registry: {}\n")]);
+        let mut value: Value =
+            serde_yaml::from_str("parameters:\n  x-include: regsitry\n").unwrap();
+
+        let err = partials
+            .expand(&mut value, Path::new("commands/push.yml"))
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "unknown partial 'regsitry' included from 'commands/push.yml', did you mean 'registry'?"
+        );
+    }
+
+    #[test]
+    fn test_expand_rejects_cycles() {
+        let (_dir, partials) =
+            partials(&[("a.yml", "x-include: b\n"), ("b.yml", "x-include: a\n")]);
+        let mut value: Value = serde_yaml::from_str("x-include: a\n").unwrap();
+
+        let err = partials
+            .expand(&mut value, Path::new("@orb.yml"))
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("include cycle: a -> b -> a"),
+            "{err}"
+        );
+    }
+}
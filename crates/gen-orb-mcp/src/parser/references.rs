@@ -0,0 +1,595 @@
+//! Resolution of names referenced inside an orb.
+//!
+//! Deserialization accepts any step key other than a built-in step as a
+//! command invocation and any string as a job's executor. This pass checks that invoked commands exist
+//! (as a built-in step, a command of this orb or a command of an imported
+//! orb), that job executors exist (in this orb or an imported orb), and that
+//! arguments passed to this orb's commands and executors name declared
+//! parameters, following steps passed to `steps` parameters and to
+//! `pre-steps`/`post-steps`. It also checks that the default of every `enum` parameter is
+//! one of its allowed values, which CircleCI enforces when it compiles the
+//! config, that every `when`/`unless` condition is a well-formed logic
+//! statement, and that no command invokes itself through the
+//! [dependency graph](crate::graph), which CircleCI only rejects when the orb
+//! is published. Each unresolved name carries the closest existing name as a
+//! suggestion.
+
+use std::{collections::HashMap, fmt};
+
+use super::{
+    Condition, ExecutorRef, OrbDefinition, Parameter, ParameterType, Step, StepList, StructuredStep,
+};
+use crate::{graph, suggest};
+
+/// Step names CircleCI provides without an orb.
+pub const BUILTIN_STEPS: &[&str] = &[
+    "add_ssh_keys",
+    "attach_workspace",
+    "checkout",
+    "deploy",
+    "persist_to_workspace",
+    "restore_cache",
+    "run",
+    "save_cache",
+    "setup_remote_docker",
+    "steps",
+    "store_artifacts",
+    "store_test_results",
+    "unless",
+    "when",
+];
+
+/// What kind of name failed to resolve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// A step invoking a command
+    Command,
+    /// A job's executor
+    Executor,
+    /// An argument passed to a command or executor
+    Parameter,
+    /// The default of an `enum` parameter
+    EnumDefault,
+    /// A `when`/`unless` logic statement
+    Condition,
+    /// A command invoking itself, directly or through other commands
+    Cycle,
+}
+
+impl fmt::Display for ReferenceKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Command => "command",
+            Self::Executor => "executor",
+            Self::Parameter => "parameter",
+            Self::EnumDefault => "enum default",
+            Self::Condition => "condition",
+            Self::Cycle => "cycle",
+        })
+    }
+}
+
+/// A referenced name with no matching definition.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnresolvedReference {
+    /// Location of the reference (e.g. `jobs/build/steps`,
+    /// `jobs/build/executor` or `jobs/build/parameters/level`).
+    pub location: String,
+    /// Kind of the referenced name.
+    pub kind: ReferenceKind,
+    /// The name as written, for a condition why it is malformed, or for a
+    /// cycle the commands along it (e.g. `build -> test -> build`).
+    pub name: String,
+    /// Closest existing name, when one is a plausible typo.
+    pub suggestion: Option<String>,
+}
+
+impl UnresolvedReference {
+    /// The error without its location, e.g. `unknown command 'gret', did you
+    /// mean 'greet'?`.
+    pub fn message(&self) -> String {
+        let mut message = match self.kind {
+            ReferenceKind::EnumDefault => {
+                format!("default '{}' is not one of the enum values", self.name)
+            }
+            ReferenceKind::Condition => format!("invalid condition: {}", self.name),
+            ReferenceKind::Cycle => format!("command invocation cycle: {}", self.name),
+            kind => format!("unknown {kind} '{}'", self.name),
+        };
+        if let Some(suggestion) = &self.suggestion {
+            message.push_str(&format!(", did you mean '{suggestion}'?"));
+        }
+        message
+    }
+}
+
+impl fmt::Display for UnresolvedReference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.location, self.message())
+    }
+}
+
+/// Every unresolved command, executor and parameter reference, every `enum`
+/// default outside its allowed values, every malformed condition and every
+/// command invocation cycle in `orb`, sorted by location.
+pub fn unresolved(orb: &OrbDefinition) -> Vec<UnresolvedReference> {
+    let mut commands: Vec<&str> = orb.commands.keys().map(String::as_str).collect();
+    commands.extend(BUILTIN_STEPS);
+    commands.sort_unstable();
+
+    let mut found = Vec::new();
+    check_enum_defaults("parameters", &orb.parameters, &mut found);
+    for (name, command) in &orb.commands {
+        let location = format!("commands/{name}/steps");
+        check_steps(orb, &commands, &location, &command.steps, &mut found);
+        let location = format!("commands/{name}/parameters");
+        check_enum_defaults(&location, &command.parameters, &mut found);
+    }
+    for (name, job) in &orb.jobs {
+        let location = format!("jobs/{name}/steps");
+        check_steps(orb, &commands, &location, &job.steps, &mut found);
+        if let Some(executor) = &job.executor {
+            check_executor(orb, &format!("jobs/{name}/executor"), executor, &mut found);
+        }
+        let location = format!("jobs/{name}/parameters");
+        check_enum_defaults(&location, &job.parameters, &mut found);
+    }
+    for (name, executor) in &orb.executors {
+        let location = format!("executors/{name}/parameters");
+        check_enum_defaults(&location, &executor.parameters, &mut found);
+    }
+    for (name, workflow) in &orb.workflows {
+        if let Some(condition) = &workflow.when {
+            check_condition(&format!("workflows/{name}/when"), condition, &mut found);
+        }
+        if let Some(condition) = &workflow.unless {
+            check_condition(&format!("workflows/{name}/unless"), condition, &mut found);
+        }
+        for invocation in &workflow.jobs {
+            let options = &invocation.options;
+            let location = format!("workflows/{name}/jobs/{}", invocation.job);
+            for (key, list) in [
+                ("pre-steps", &options.pre_steps),
+                ("post-steps", &options.post_steps),
+            ] {
+                if let Some(list) = list {
+                    let location = format!("{location}/{key}");
+                    check_steps(orb, &commands, &location, list.steps(), &mut found);
+                }
+            }
+            if let Some(job) = orb.jobs.get(&invocation.job) {
+                let args = options.parameters.iter().map(|(k, v)| (k.as_str(), v));
+                check_step_arguments(orb, &commands, &location, &job.parameters, args, &mut found);
+            }
+        }
+    }
+    for cycle in graph::build(orb).command_cycles() {
+        found.push(UnresolvedReference {
+            location: format!("commands/{}/steps", cycle[0]),
+            kind: ReferenceKind::Cycle,
+            name: cycle.join(" -> "),
+            suggestion: None,
+        });
+    }
+    found.sort_by(|a, b| (&a.location, &a.name).cmp(&(&b.location, &b.name)));
+    found
+}
+
+fn check_steps(
+    orb: &OrbDefinition,
+    commands: &[&str],
+    location: &str,
+    steps: &[Step],
+    found: &mut Vec<UnresolvedReference>,
+) {
+    for step in steps {
+        match step {
+            Step::Simple(name) => check_command(orb, commands, location, name, None, found),
+            Step::Structured(StructuredStep::When(c) | StructuredStep::Unless(c)) => {
+                check_condition(location, &c.condition, found);
+                check_steps(orb, commands, location, &c.steps, found);
+            }
+            Step::Structured(StructuredStep::Steps(list)) => {
+                check_steps(orb, commands, location, list.steps(), found);
+            }
+            Step::Structured(StructuredStep::CommandInvocation(invocation)) => {
+                for (name, args) in invocation {
+                    check_command(orb, commands, location, name, Some(args), found);
+                }
+            }
+            Step::Structured(_) => {}
+        }
+    }
+}
+
+fn check_command(
+    orb: &OrbDefinition,
+    commands: &[&str],
+    location: &str,
+    name: &str,
+    args: Option<&serde_yaml::Value>,
+    found: &mut Vec<UnresolvedReference>,
+) {
+    // Imported orb commands, parameter placeholders and built-ins are not
+    // ours to resolve.
+    if name.contains('/') || name.contains("<<") || BUILTIN_STEPS.contains(&name) {
+        return;
+    }
+    let Some(command) = orb.commands.get(name) else {
+        found.push(UnresolvedReference {
+            location: location.to_string(),
+            kind: ReferenceKind::Command,
+            name: name.to_string(),
+            suggestion: suggest::closest(name, commands.iter().copied()).map(str::to_string),
+        });
+        return;
+    };
+    if let Some(serde_yaml::Value::Mapping(args)) = args {
+        let location = format!("{location}/{name}");
+        let keys = args.keys().filter_map(serde_yaml::Value::as_str);
+        check_arguments(&command.parameters, &location, keys, found);
+        let args = args.iter().filter_map(|(k, v)| Some((k.as_str()?, v)));
+        check_step_arguments(orb, commands, &location, &command.parameters, args, found);
+    }
+}
+
+/// Check the steps passed as arguments to the `steps` parameters among
+/// `declared`.
+fn check_step_arguments<'a>(
+    orb: &OrbDefinition,
+    commands: &[&str],
+    location: &str,
+    declared: &HashMap<String, Parameter>,
+    args: impl Iterator<Item = (&'a str, &'a serde_yaml::Value)>,
+    found: &mut Vec<UnresolvedReference>,
+) {
+    for (arg, value) in args {
+        if declared
+            .get(arg)
+            .is_none_or(|p| p.param_type != ParameterType::Steps)
+        {
+            continue;
+        }
+        if let Some(list) = StepList::from_value(value) {
+            let location = format!("{location}/{arg}");
+            check_steps(orb, commands, &location, list.steps(), found);
+        }
+    }
+}
+
+fn check_executor(
+    orb: &OrbDefinition,
+    location: &str,
+    executor: &ExecutorRef,
+    found: &mut Vec<UnresolvedReference>,
+) {
+    let (name, args) = match executor {
+        ExecutorRef::Name(name) => (name, None),
+        ExecutorRef::WithParams { name, parameters } => (name, Some(parameters)),
+    };
+    // Imported orb executors and parameter placeholders are not ours to
+    // resolve.
+    if name.contains('/') || name.contains("<<") {
+        return;
+    }
+    let Some(definition) = orb.executors.get(name) else {
+        found.push(UnresolvedReference {
+            location: location.to_string(),
+            kind: ReferenceKind::Executor,
+            name: name.to_string(),
+            suggestion: suggest::closest(name, orb.executors.keys().map(String::as_str))
+                .map(str::to_string),
+        });
+        return;
+    };
+    if let Some(args) = args {
+        let args = args.keys().map(String::as_str);
+        check_arguments(&definition.parameters, location, args, found);
+    }
+}
+
+fn check_arguments<'a>(
+    declared: &HashMap<String, Parameter>,
+    location: &str,
+    args: impl Iterator<Item = &'a str>,
+    found: &mut Vec<UnresolvedReference>,
+) {
+    let mut parameters: Vec<&str> = declared.keys().map(String::as_str).collect();
+    parameters.sort_unstable();
+    for arg in args {
+        if !declared.contains_key(arg) {
+            found.push(UnresolvedReference {
+                location: location.to_string(),
+                kind: ReferenceKind::Parameter,
+                name: arg.to_string(),
+                suggestion: suggest::closest(arg, parameters.iter().copied()).map(str::to_string),
+            });
+        }
+    }
+}
+
+fn check_condition(location: &str, condition: &Condition, found: &mut Vec<UnresolvedReference>) {
+    for error in condition.errors() {
+        found.push(UnresolvedReference {
+            location: location.to_string(),
+            kind: ReferenceKind::Condition,
+            name: error.to_string(),
+            suggestion: None,
+        });
+    }
+}
+
+/// Report `enum` parameters under `location` whose default is not one of
+/// their values. Non-scalar defaults are left to CircleCI's type check.
+fn check_enum_defaults(
+    location: &str,
+    parameters: &HashMap<String, Parameter>,
+    found: &mut Vec<UnresolvedReference>,
+) {
+    for (name, parameter) in parameters {
+        if parameter.param_type != ParameterType::Enum {
+            continue;
+        }
+        let (Some(values), Some(default)) = (&parameter.enum_values, &parameter.default) else {
+            continue;
+        };
+        let default = match default {
+            serde_yaml::Value::String(s) => s.clone(),
+            serde_yaml::Value::Number(n) => n.to_string(),
+            serde_yaml::Value::Bool(b) => b.to_string(),
+            _ => continue,
+        };
+        if !values.contains(&default) {
+            found.push(UnresolvedReference {
+                location: format!("{location}/{name}"),
+                kind: ReferenceKind::EnumDefault,
+                suggestion: suggest::closest(&default, values.iter().map(String::as_str))
+                    .map(str::to_string),
+                name: default,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORB: &str = r#"
+version: 2.1
+orbs:
+  node: circleci/node@5
+commands:
+  greet:
+    parameters:
+      who:
+        type: string
+        default: world
+    steps:
+      - run: echo << parameters.who >>
+jobs:
+  hello:
+    steps:
+      - checkout
+      - gret
+      - greet:
+          whom: team
+      - when:
+          condition: true
+          steps:
+            - chekout
+      - unless:
+          condition:
+            not: [<< parameters.skip >>]
+          steps:
+            - checkout
+      - node/install
+      - greet:
+          who: team
+"#;
+
+    #[test]
+    fn test_unresolved_commands_and_parameters_with_suggestions() {
+        let orb: OrbDefinition = serde_yaml::from_str(ORB).unwrap();
+        let messages: Vec<String> = unresolved(&orb).iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "jobs/hello/steps: invalid condition: `not` takes a single statement",
+                "jobs/hello/steps: unknown command 'chekout', did you mean 'checkout'?",
+                "jobs/hello/steps: unknown command 'gret', did you mean 'greet'?",
+                "jobs/hello/steps/greet: unknown parameter 'whom', did you mean 'who'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unresolved_without_close_match_has_no_suggestion() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            "version: 2.1\njobs:\n  j:\n    steps:\n      - deploy_everything\n",
+        )
+        .unwrap();
+        let found = unresolved(&orb);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].suggestion, None);
+        assert_eq!(
+            found[0].to_string(),
+            "jobs/j/steps: unknown command 'deploy_everything'"
+        );
+    }
+
+    #[test]
+    fn test_unresolved_executors_and_executor_parameters() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+version: 2.1
+executors:
+  rust:
+    parameters:
+      tag:
+        type: string
+        default: stable
+    docker:
+      - image: rust:<< parameters.tag >>
+jobs:
+  build:
+    executor: rsut
+    steps: [checkout]
+  test:
+    executor:
+      name: rust
+      tagg: nightly
+  lint:
+    executor: node/default
+    steps: [checkout]
+  docs:
+    executor: << parameters.executor >>
+    steps: [checkout]
+"#,
+        )
+        .unwrap();
+        let messages: Vec<String> = unresolved(&orb).iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "jobs/build/executor: unknown executor 'rsut', did you mean 'rust'?",
+                "jobs/test/executor: unknown parameter 'tagg', did you mean 'tag'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_steps_passed_to_steps_parameters_are_checked() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+version: 2.1
+commands:
+  wrap:
+    parameters:
+      body:
+        type: steps
+        default: []
+      label:
+        type: string
+        default: gret
+    steps:
+      - steps: << parameters.body >>
+jobs:
+  build:
+    parameters:
+      setup:
+        type: steps
+        default: []
+    steps:
+      - steps: << parameters.setup >>
+      - wrap:
+          label: gret
+          body:
+            - chekout
+      - steps:
+          - gret
+workflows:
+  main:
+    jobs:
+      - build:
+          setup:
+            - checkot
+          pre-steps:
+            - wrap
+            - rnu: echo
+"#,
+        )
+        .unwrap();
+        let messages: Vec<String> = unresolved(&orb).iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "jobs/build/steps: unknown command 'gret'",
+                "jobs/build/steps/wrap/body: unknown command 'chekout', did you mean 'checkout'?",
+                "workflows/main/jobs/build/pre-steps: unknown command 'rnu'",
+                "workflows/main/jobs/build/setup: unknown command 'checkot', did you mean \
+                 'checkout'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_command_invocation_cycles() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+version: 2.1
+commands:
+  build:
+    steps:
+      - test
+  test:
+    steps:
+      - run: cargo test
+      - build
+  retry:
+    steps:
+      - unless:
+          condition: false
+          steps:
+            - retry
+"#,
+        )
+        .unwrap();
+        let found = unresolved(&orb);
+        assert!(found.iter().all(|r| r.kind == ReferenceKind::Cycle));
+        let messages: Vec<String> = found.iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "commands/build/steps: command invocation cycle: build -> test -> build",
+                "commands/retry/steps: command invocation cycle: retry -> retry",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_enum_defaults_outside_their_values() {
+        let orb: OrbDefinition = serde_yaml::from_str(
+            r#"
+version: 2.1
+parameters:
+  mode:
+    type: enum
+    enum: [fast, full]
+    default: full
+commands:
+  log:
+    parameters:
+      level:
+        type: enum
+        enum: [debug, info, warn]
+        default: inof
+      format:
+        type: enum
+        enum: [json, text]
+        default: yaml
+    steps:
+      - run: echo << parameters.level >> << parameters.format >>
+executors:
+  py:
+    parameters:
+      version:
+        type: enum
+        enum: ["3.11", "3.12"]
+        default: 3.12
+    docker:
+      - image: cimg/python:<< parameters.version >>
+"#,
+        )
+        .unwrap();
+        let messages: Vec<String> = unresolved(&orb).iter().map(|r| r.to_string()).collect();
+        assert_eq!(
+            messages,
+            [
+                "commands/log/parameters/format: default 'yaml' is not one of the enum values",
+                "commands/log/parameters/level: default 'inof' is not one of the enum values, did \
+                 you mean 'info'?",
+            ]
+        );
+    }
+}
@@ -0,0 +1,359 @@
+//! Strict parsing: rejection of keys the orb schema does not define.
+//!
+//! The orb types default every optional field and ignore unknown keys, so a
+//! typo such as `decription:` parses without complaint and the description
+//! is silently lost. `#[serde(deny_unknown_fields)]` cannot be applied to
+//! them: jobs and executors flatten their execution environment, and steps,
+//! docker images and executor references are untagged enums, all of which
+//! buffer unknown keys instead of reporting them. This pass walks the raw
+//! YAML of a packed orb (or of an unpacked orb after
+//! [`OrbParser::pack`](super::OrbParser::pack)) against the keys each
+//! mapping may hold, and reports every other key with its location and the
+//! closest allowed key.
+//!
+//! Only mappings whose keys are fixed by the schema are checked. Keys naming
+//! definitions, parameters, arguments of command invocations and workflow
+//! job parameters are checked by [`references`](super::references) instead,
+//! and example `usage` is a consumer config outside the orb.
+
+use std::path::Path;
+
+use serde_yaml::{Mapping, Value};
+
+use super::ParseError;
+use crate::suggest;
+
+const ROOT_KEYS: &[&str] = &[
+    "version",
+    "description",
+    "display",
+    "x-version",
+    "orbs",
+    "commands",
+    "jobs",
+    "executors",
+    "examples",
+    "workflows",
+    "setup",
+    "parameters",
+];
+const DISPLAY_KEYS: &[&str] = &["home_url", "source_url", "version"];
+const COMMAND_KEYS: &[&str] = &["description", "parameters", "steps"];
+/// Execution environment keys shared by jobs and executors.
+const ENVIRONMENT_KEYS: &[&str] = &[
+    "docker",
+    "machine",
+    "macos",
+    "resource_class",
+    "working_directory",
+    "environment",
+    "shell",
+];
+const JOB_KEYS: &[&str] = &[
+    "description",
+    "executor",
+    "parameters",
+    "steps",
+    "parallelism",
+    "circleci_ip_ranges",
+];
+const EXECUTOR_KEYS: &[&str] = &["description", "parameters"];
+const EXAMPLE_KEYS: &[&str] = &["description", "usage", "result"];
+const PARAMETER_KEYS: &[&str] = &["type", "description", "default", "enum"];
+const DOCKER_IMAGE_KEYS: &[&str] = &[
+    "image",
+    "auth",
+    "aws_auth",
+    "name",
+    "entrypoint",
+    "command",
+    "user",
+    "environment",
+];
+const MACHINE_KEYS: &[&str] = &["image", "docker_layer_caching"];
+const MACOS_KEYS: &[&str] = &["xcode"];
+const WORKFLOW_KEYS: &[&str] = &["when", "unless", "triggers", "jobs"];
+
+/// Keys of the built-in steps whose body is a mapping.
+fn step_keys(step: &str) -> Option<&'static [&'static str]> {
+    Some(match step {
+        "run" => &[
+            "command",
+            "name",
+            "working_directory",
+            "environment",
+            "shell",
+            "background",
+            "no_output_timeout",
+            "when",
+        ],
+        "checkout" => &["path"],
+        "restore_cache" => &["key", "keys", "name"],
+        "save_cache" => &["key", "paths", "name", "when"],
+        "when" | "unless" => &["condition", "steps"],
+        "persist_to_workspace" => &["root", "paths"],
+        "attach_workspace" => &["at"],
+        "store_test_results" => &["path"],
+        "store_artifacts" => &["path", "destination"],
+        "add_ssh_keys" => &["fingerprints"],
+        "setup_remote_docker" => &["version", "docker_layer_caching"],
+        _ => return None,
+    })
+}
+
+/// Every key in the packed orb `orb`, read from `path`, that the orb schema
+/// does not define, sorted by location.
+pub fn unknown_keys(path: &Path, orb: &Value) -> Vec<ParseError> {
+    let mut found = Checker {
+        path,
+        errors: Vec::new(),
+    };
+    let Some(root) = orb.as_mapping() else {
+        return found.errors;
+    };
+    found.check("orb", root, &[ROOT_KEYS]);
+    if let Some(display) = root.get("display").and_then(Value::as_mapping) {
+        found.check("display", display, &[DISPLAY_KEYS]);
+    }
+    found.parameters("parameters", root.get("parameters"));
+    for (name, command) in definitions(root, "commands") {
+        let location = format!("commands/{name}");
+        found.check(&location, command, &[COMMAND_KEYS]);
+        found.parameters(&format!("{location}/parameters"), command.get("parameters"));
+        found.steps(&format!("{location}/steps"), command.get("steps"));
+    }
+    for (name, job) in definitions(root, "jobs") {
+        let location = format!("jobs/{name}");
+        found.check(&location, job, &[JOB_KEYS, ENVIRONMENT_KEYS]);
+        found.environment(&location, job);
+        found.parameters(&format!("{location}/parameters"), job.get("parameters"));
+        found.steps(&format!("{location}/steps"), job.get("steps"));
+    }
+    for (name, executor) in definitions(root, "executors") {
+        let location = format!("executors/{name}");
+        found.check(&location, executor, &[EXECUTOR_KEYS, ENVIRONMENT_KEYS]);
+        found.environment(&location, executor);
+        found.parameters(
+            &format!("{location}/parameters"),
+            executor.get("parameters"),
+        );
+    }
+    for (name, example) in definitions(root, "examples") {
+        found.check(&format!("examples/{name}"), example, &[EXAMPLE_KEYS]);
+    }
+    for (name, workflow) in definitions(root, "workflows") {
+        found.check(&format!("workflows/{name}"), workflow, &[WORKFLOW_KEYS]);
+    }
+
+    found.errors.sort_by(|a, b| location(a).cmp(location(b)));
+    found.errors
+}
+
+/// Named mapping definitions under `category`. Other entries, such as the
+/// `version: 2` of a 2.0 `workflows` section, are left to deserialization.
+fn definitions<'a>(
+    root: &'a Mapping,
+    category: &str,
+) -> impl Iterator<Item = (&'a str, &'a Mapping)> {
+    root.get(category)
+        .and_then(Value::as_mapping)
+        .into_iter()
+        .flatten()
+        .filter_map(|(name, value)| Some((name.as_str()?, value.as_mapping()?)))
+}
+
+fn location(error: &ParseError) -> &str {
+    match error {
+        ParseError::UnknownKey { location, .. } => location,
+        _ => "",
+    }
+}
+
+struct Checker<'a> {
+    path: &'a Path,
+    errors: Vec<ParseError>,
+}
+
+impl Checker<'_> {
+    /// Report each key of `mapping` at `location` outside `allowed`.
+    fn check(&mut self, location: &str, mapping: &Mapping, allowed: &[&[&str]]) {
+        let allowed = || allowed.iter().flat_map(|keys| keys.iter().copied());
+        for key in mapping.keys() {
+            let Some(key) = key.as_str() else {
+                continue;
+            };
+            if !allowed().any(|k| k == key) {
+                self.errors.push(ParseError::UnknownKey {
+                    path: self.path.to_path_buf(),
+                    location: location.to_string(),
+                    key: key.to_string(),
+                    suggestion: suggest::did_you_mean(key, allowed()),
+                });
+            }
+        }
+    }
+
+    /// Check each parameter declared in the `parameters` mapping.
+    fn parameters(&mut self, location: &str, parameters: Option<&Value>) {
+        let Some(parameters) = parameters.and_then(Value::as_mapping) else {
+            return;
+        };
+        for (name, parameter) in parameters {
+            if let (Some(name), Some(parameter)) = (name.as_str(), parameter.as_mapping()) {
+                self.check(&format!("{location}/{name}"), parameter, &[PARAMETER_KEYS]);
+            }
+        }
+    }
+
+    /// Check the docker images, machine and macOS settings of a job or
+    /// executor.
+    fn environment(&mut self, location: &str, definition: &Mapping) {
+        if let Some(images) = definition.get("docker").and_then(Value::as_sequence) {
+            for (i, image) in images.iter().enumerate() {
+                if let Some(image) = image.as_mapping() {
+                    self.check(
+                        &format!("{location}/docker/{i}"),
+                        image,
+                        &[DOCKER_IMAGE_KEYS],
+                    );
+                }
+            }
+        }
+        if let Some(machine) = definition.get("machine").and_then(Value::as_mapping) {
+            self.check(&format!("{location}/machine"), machine, &[MACHINE_KEYS]);
+        }
+        if let Some(macos) = definition.get("macos").and_then(Value::as_mapping) {
+            self.check(&format!("{location}/macos"), macos, &[MACOS_KEYS]);
+        }
+    }
+
+    /// Check the bodies of built-in steps, following `when`/`unless` and
+    /// `steps` into the steps they hold.
+    fn steps(&mut self, location: &str, steps: Option<&Value>) {
+        let Some(steps) = steps.and_then(Value::as_sequence) else {
+            return;
+        };
+        for step in steps {
+            let Some((name, body)) = step.as_mapping().and_then(|m| m.iter().next()) else {
+                continue;
+            };
+            let Some(name) = name.as_str() else {
+                continue;
+            };
+            let step_location = format!("{location}/{name}");
+            match (step_keys(name), body) {
+                (Some(keys), Value::Mapping(body)) => {
+                    self.check(&step_location, body, &[keys]);
+                    if name == "when" || name == "unless" {
+                        self.steps(&format!("{step_location}/steps"), body.get("steps"));
+                    }
+                }
+                (None, Value::Sequence(_)) if name == "steps" => {
+                    self.steps(&step_location, Some(body));
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages(yaml: &str) -> Vec<String> {
+        let orb: Value = serde_yaml::from_str(yaml).unwrap();
+        unknown_keys(Path::new("orb.yml"), &orb)
+            .iter()
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_unknown_keys_reports_typos_with_locations() {
+        let found = messages(
+            r#"
+version: "2.1"
+decription: Greets
+commands:
+  greet:
+    description: Say hello
+    parameters:
+      who:
+        type: string
+        defualt: world
+    steps:
+      - run:
+          comand: echo hi
+      - when:
+          condition: true
+          steps:
+            - checkout:
+                paht: src
+jobs:
+  hello:
+    docker:
+      - image: cimg/base:stable
+        enviroment:
+          A: "1"
+    resource_clas: large
+    steps:
+      - greet:
+          who: you
+executors:
+  default:
+    machine:
+      image: ubuntu-2204:current
+      docker_layer_cache: true
+"#,
+        );
+        assert_eq!(
+            found,
+            vec![
+                "commands/greet/parameters/who: unknown key 'defualt', did you mean 'default'?",
+                "commands/greet/steps/run: unknown key 'comand', did you mean 'command'?",
+                "commands/greet/steps/when/steps/checkout: unknown key 'paht', did you mean \
+                 'path'?",
+                "executors/default/machine: unknown key 'docker_layer_cache', did you mean \
+                 'docker_layer_caching'?",
+                "jobs/hello: unknown key 'resource_clas', did you mean 'resource_class'?",
+                "jobs/hello/docker/0: unknown key 'enviroment', did you mean 'environment'?",
+                "orb: unknown key 'decription', did you mean 'description'?",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unknown_keys_accepts_schema_and_free_form_keys() {
+        let found = messages(
+            r#"
+version: "2.1"
+x-version: 1.0.0
+orbs:
+  node: circleci/node@5
+commands:
+  greet:
+    parameters:
+      anything-goes:
+        type: steps
+        default: []
+    steps:
+      - node/install:
+          any-argument: true
+      - steps: << parameters.anything-goes >>
+examples:
+  basic:
+    usage:
+      version: "2.1"
+      anything: here
+workflows:
+  version: 2
+  main:
+    jobs:
+      - hello:
+          custom-parameter: 1
+"#,
+        );
+        assert!(found.is_empty(), "{found:?}");
+    }
+}
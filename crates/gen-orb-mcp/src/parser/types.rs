@@ -1,8 +1,19 @@
 //! Core data structures for parsed CircleCI orb definitions.
+//!
+//! Project configs (`.circleci/config.yml`) share the orb schema, so they
+//! parse into the same [`OrbDefinition`] with `setup` and pipeline
+//! `parameters` filled in.
+//!
+//! Definition maps (commands, jobs, parameters, ...) are keyed lookups and
+//! consumers sort them before output. Maps that are embedded verbatim in
+//! generated resources (environment variables, executor and command
+//! arguments) are `BTreeMap`s so they serialise in a stable order.
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::Condition;
 
 /// Root structure representing a complete orb definition.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -19,6 +30,10 @@ pub struct OrbDefinition {
     #[serde(default)]
     pub display: Option<DisplayInfo>,
 
+    /// Release version declared by the orb author (`x-version` extension key)
+    #[serde(rename = "x-version", default, skip_serializing_if = "Option::is_none")]
+    pub x_version: Option<String>,
+
     /// Imported orbs (name -> orb reference)
     #[serde(default)]
     pub orbs: HashMap<String, String>,
@@ -34,6 +49,26 @@ pub struct OrbDefinition {
     /// Executor definitions
     #[serde(default)]
     pub executors: HashMap<String, Executor>,
+
+    /// Usage examples
+    #[serde(default)]
+    pub examples: HashMap<String, Example>,
+
+    /// Workflows, as found in packed orbs and project configs
+    #[serde(
+        default,
+        deserialize_with = "deserialize_workflows",
+        skip_serializing_if = "HashMap::is_empty"
+    )]
+    pub workflows: HashMap<String, Workflow>,
+
+    /// Whether a project config is a dynamic-configuration setup config
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub setup: bool,
+
+    /// Pipeline parameters of a project config
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub parameters: HashMap<String, Parameter>,
 }
 
 /// Display metadata for orb registry listings.
@@ -46,6 +81,10 @@ pub struct DisplayInfo {
     /// URL to source code repository
     #[serde(default)]
     pub source_url: Option<String>,
+
+    /// Release version of the orb, read by `--version-from orb`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
 }
 
 /// A reusable command definition.
@@ -88,8 +127,8 @@ pub struct ExecutorConfig {
     pub working_directory: Option<String>,
 
     /// Environment variables
-    #[serde(default)]
-    pub environment: HashMap<String, String>,
+    #[serde(default, deserialize_with = "environment")]
+    pub environment: BTreeMap<String, String>,
 
     /// Shell to use
     #[serde(default)]
@@ -144,6 +183,22 @@ pub struct Executor {
     pub parameters: HashMap<String, Parameter>,
 }
 
+/// A usage example shown in the orb registry.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Example {
+    /// Human-readable description
+    #[serde(default)]
+    pub description: Option<String>,
+
+    /// Consumer config demonstrating the orb
+    #[serde(default)]
+    pub usage: serde_yaml::Value,
+
+    /// Config the usage expands to, when given
+    #[serde(default)]
+    pub result: Option<serde_yaml::Value>,
+}
+
 /// Reference to an executor with optional parameter overrides.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -156,7 +211,7 @@ pub enum ExecutorRef {
         name: String,
         /// Parameter values to pass
         #[serde(flatten)]
-        parameters: HashMap<String, serde_yaml::Value>,
+        parameters: BTreeMap<String, serde_yaml::Value>,
     },
 }
 
@@ -208,8 +263,23 @@ pub enum ParameterType {
     Executor,
 }
 
+impl ParameterType {
+    /// The type as written in the orb, e.g. `env_var_name`.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ParameterType::String => "string",
+            ParameterType::Boolean => "boolean",
+            ParameterType::Integer => "integer",
+            ParameterType::Enum => "enum",
+            ParameterType::EnvVarName => "env_var_name",
+            ParameterType::Steps => "steps",
+            ParameterType::Executor => "executor",
+        }
+    }
+}
+
 /// A step in a command or job.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum Step {
     /// Simple string step (e.g., "checkout")
@@ -224,51 +294,89 @@ impl Default for Step {
     }
 }
 
+impl<'de> Deserialize<'de> for Step {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        match serde_yaml::Value::deserialize(d)? {
+            serde_yaml::Value::String(name) => Ok(Self::Simple(name)),
+            value => serde_yaml::from_value(value)
+                .map(Self::Structured)
+                .map_err(serde::de::Error::custom),
+        }
+    }
+}
+
 /// Structured step definitions.
 ///
-/// Deserialization uses serde's default externally-tagged derive.
+/// Deserialization reads a single-key mapping and parses the body of a
+/// built-in step (`run`, `checkout`, ...) as that step, so a malformed body
+/// is an error rather than an invocation of a command named after the step.
 /// Serialization uses a hand-written impl that always produces a single-key
 /// mapping (`{run: …}`) rather than a YAML tag (`!run …`).  serde_yaml 0.9
 /// serialises externally-tagged enum variants as YAML tags, which cannot be
 /// deserialised back into an `#[serde(untagged)]` enum.
-#[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "snake_case")]
+#[derive(Debug, Clone, PartialEq)]
 pub enum StructuredStep {
     /// Run a shell command
     Run(RunStep),
     /// Checkout code
     Checkout(CheckoutStep),
     /// Restore cached files
-    #[serde(rename = "restore_cache")]
     RestoreCache(CacheStep),
     /// Save files to cache
-    #[serde(rename = "save_cache")]
     SaveCache(SaveCacheStep),
     /// Conditional step
     When(ConditionalStep),
     /// Negative conditional step
     Unless(ConditionalStep),
     /// Persist files to workspace
-    #[serde(rename = "persist_to_workspace")]
     PersistToWorkspace(WorkspaceStep),
     /// Attach workspace files
-    #[serde(rename = "attach_workspace")]
     AttachWorkspace(AttachWorkspaceStep),
     /// Store test results
-    #[serde(rename = "store_test_results")]
     StoreTestResults(StoreTestResultsStep),
     /// Store artifacts
-    #[serde(rename = "store_artifacts")]
     StoreArtifacts(StoreArtifactsStep),
     /// Add SSH keys
-    #[serde(rename = "add_ssh_keys")]
     AddSshKeys(AddSshKeysStep),
     /// Set up remote Docker
-    #[serde(rename = "setup_remote_docker")]
     SetupRemoteDocker(SetupRemoteDockerStep),
+    /// Run the steps of a `steps` parameter (`steps: << parameters.x >>`)
+    Steps(StepList),
     /// Invoke another command or orb command
-    #[serde(untagged)]
-    CommandInvocation(HashMap<String, serde_yaml::Value>),
+    CommandInvocation(BTreeMap<String, serde_yaml::Value>),
+}
+
+impl<'de> Deserialize<'de> for StructuredStep {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        fn body<T: serde::de::DeserializeOwned>(
+            body: &serde_yaml::Value,
+        ) -> Result<T, serde_yaml::Error> {
+            serde_yaml::from_value(body.clone())
+        }
+
+        let invocation: BTreeMap<String, serde_yaml::Value> = Deserialize::deserialize(d)?;
+        let mut entries = invocation.iter();
+        let (Some((key, value)), None) = (entries.next(), entries.next()) else {
+            return Ok(Self::CommandInvocation(invocation));
+        };
+        let step = match key.as_str() {
+            "run" => body(value).map(Self::Run),
+            "checkout" => body(value).map(Self::Checkout),
+            "restore_cache" => body(value).map(Self::RestoreCache),
+            "save_cache" => body(value).map(Self::SaveCache),
+            "when" => body(value).map(Self::When),
+            "unless" => body(value).map(Self::Unless),
+            "persist_to_workspace" => body(value).map(Self::PersistToWorkspace),
+            "attach_workspace" => body(value).map(Self::AttachWorkspace),
+            "store_test_results" => body(value).map(Self::StoreTestResults),
+            "store_artifacts" => body(value).map(Self::StoreArtifacts),
+            "add_ssh_keys" => body(value).map(Self::AddSshKeys),
+            "setup_remote_docker" => body(value).map(Self::SetupRemoteDocker),
+            "steps" => body(value).map(Self::Steps),
+            _ => return Ok(Self::CommandInvocation(invocation)),
+        };
+        step.map_err(|e| serde::de::Error::custom(format!("`{key}` step: {e}")))
+    }
 }
 
 impl serde::Serialize for StructuredStep {
@@ -337,47 +445,183 @@ impl serde::Serialize for StructuredStep {
                 m.serialize_entry("setup_remote_docker", v)?;
                 m.end()
             }
+            Self::Steps(v) => {
+                let mut m = s.serialize_map(Some(1))?;
+                m.serialize_entry("steps", v)?;
+                m.end()
+            }
             Self::CommandInvocation(v) => v.serialize(s),
         }
     }
 }
 
+/// Steps given where a list of steps is expected: a `steps:` step, a
+/// `pre-steps`/`post-steps` value or an argument to a `steps` parameter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum StepList {
+    /// A value passed on from elsewhere, usually a `<< parameters.x >>`
+    /// placeholder of a `steps` parameter
+    Placeholder(String),
+    /// Steps written out in place
+    Steps(Vec<Step>),
+}
+
+impl Default for StepList {
+    fn default() -> Self {
+        Self::Steps(Vec::new())
+    }
+}
+
+impl StepList {
+    /// Parse a `steps` argument; `None` when it is neither a placeholder nor
+    /// a list of steps.
+    pub fn from_value(value: &serde_yaml::Value) -> Option<Self> {
+        serde_yaml::from_value(value.clone()).ok()
+    }
+
+    /// Name of the parameter a `<< parameters.x >>` placeholder passes on.
+    pub fn parameter(&self) -> Option<&str> {
+        let Self::Placeholder(placeholder) = self else {
+            return None;
+        };
+        placeholder
+            .trim()
+            .strip_prefix("<<")?
+            .strip_suffix(">>")?
+            .trim()
+            .strip_prefix("parameters.")
+    }
+
+    /// The steps written out in place; empty for a placeholder.
+    pub fn steps(&self) -> &[Step] {
+        match self {
+            Self::Placeholder(_) => &[],
+            Self::Steps(steps) => steps,
+        }
+    }
+}
+
 /// Run step configuration.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize)]
 #[serde(untagged)]
 pub enum RunStep {
     /// Simple command string
     Simple(String),
     /// Full run configuration
     Full {
-        /// Command to execute
+        /// Command to execute, with the lines of a list-form command joined
+        /// by newlines; empty when the step only names a script set up
+        /// elsewhere (e.g. through `environment`)
         command: String,
         /// Step name
-        #[serde(default)]
         name: Option<String>,
         /// Working directory
-        #[serde(default)]
         working_directory: Option<String>,
         /// Environment variables
-        #[serde(default)]
-        environment: HashMap<String, String>,
+        environment: BTreeMap<String, String>,
         /// Shell to use
-        #[serde(default)]
         shell: Option<String>,
         /// Background execution
-        #[serde(default)]
         background: Option<bool>,
         /// Timeout in seconds
-        #[serde(default)]
         no_output_timeout: Option<String>,
         /// Condition for execution
-        #[serde(default)]
-        when: Option<String>,
+        when: Option<RunWhen>,
     },
 }
 
+/// The mapping form of a `run` step, parsed on its own so that why it is
+/// invalid (e.g. an unknown `when` value) is reported rather than lost
+/// among untagged variants.
+#[derive(Deserialize)]
+struct RunMapping {
+    #[serde(default, deserialize_with = "command_lines")]
+    command: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    working_directory: Option<String>,
+    #[serde(default, deserialize_with = "environment")]
+    environment: BTreeMap<String, String>,
+    #[serde(default)]
+    shell: Option<String>,
+    #[serde(default)]
+    background: Option<bool>,
+    #[serde(default)]
+    no_output_timeout: Option<String>,
+    #[serde(default)]
+    when: Option<RunWhen>,
+}
+
+impl<'de> Deserialize<'de> for RunStep {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let value = match serde_yaml::Value::deserialize(d)? {
+            serde_yaml::Value::String(command) => return Ok(Self::Simple(command)),
+            value => value,
+        };
+        let run: RunMapping = serde_yaml::from_value(value).map_err(serde::de::Error::custom)?;
+        Ok(Self::Full {
+            command: run.command,
+            name: run.name,
+            working_directory: run.working_directory,
+            environment: run.environment,
+            shell: run.shell,
+            background: run.background,
+            no_output_timeout: run.no_output_timeout,
+            when: run.when,
+        })
+    }
+}
+
+/// When a `run` step runs, given the outcome of the steps before it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunWhen {
+    /// Only while every previous step succeeded (CircleCI's default)
+    OnSuccess,
+    /// Whatever the outcome, e.g. to collect logs
+    Always,
+    /// Only once a previous step failed
+    OnFail,
+    /// Chosen at invocation through a `<< parameters.x >>` placeholder
+    Placeholder(String),
+}
+
+impl RunWhen {
+    /// The value as written in the orb, e.g. `on_fail`.
+    pub fn as_str(&self) -> &str {
+        match self {
+            RunWhen::OnSuccess => "on_success",
+            RunWhen::Always => "always",
+            RunWhen::OnFail => "on_fail",
+            RunWhen::Placeholder(placeholder) => placeholder,
+        }
+    }
+}
+
+impl Serialize for RunWhen {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        s.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for RunWhen {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let value = String::deserialize(d)?;
+        match value.as_str() {
+            "on_success" => Ok(RunWhen::OnSuccess),
+            "always" => Ok(RunWhen::Always),
+            "on_fail" => Ok(RunWhen::OnFail),
+            _ if value.contains("<<") => Ok(RunWhen::Placeholder(value)),
+            _ => Err(serde::de::Error::custom(format!(
+                "unknown `when` value `{value}`: expected always, on_success or on_fail"
+            ))),
+        }
+    }
+}
+
 /// Checkout step configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct CheckoutStep {
     /// Path to checkout to
     #[serde(default)]
@@ -385,7 +629,7 @@ pub struct CheckoutStep {
 }
 
 /// Cache restore step configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct CacheStep {
     /// Cache key or keys
     #[serde(default)]
@@ -399,7 +643,7 @@ pub struct CacheStep {
 }
 
 /// Cache save step configuration.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct SaveCacheStep {
     /// Cache key
     pub key: String,
@@ -415,17 +659,17 @@ pub struct SaveCacheStep {
 }
 
 /// Conditional step (when/unless).
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct ConditionalStep {
     /// Condition to evaluate
-    pub condition: serde_yaml::Value,
+    pub condition: Condition,
     /// Steps to run if condition is met
     #[serde(default)]
     pub steps: Vec<Step>,
 }
 
 /// Workspace persistence step.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct WorkspaceStep {
     /// Root directory
     pub root: String,
@@ -435,21 +679,21 @@ pub struct WorkspaceStep {
 }
 
 /// Workspace attachment step.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct AttachWorkspaceStep {
     /// Path to attach at
     pub at: String,
 }
 
 /// Store test results step.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct StoreTestResultsStep {
     /// Path to test results
     pub path: String,
 }
 
 /// Store artifacts step.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct StoreArtifactsStep {
     /// Path to artifacts
     pub path: String,
@@ -459,7 +703,7 @@ pub struct StoreArtifactsStep {
 }
 
 /// Add SSH keys step.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct AddSshKeysStep {
     /// Fingerprints of keys to add
     #[serde(default)]
@@ -467,7 +711,7 @@ pub struct AddSshKeysStep {
 }
 
 /// Setup remote Docker step.
-#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct SetupRemoteDockerStep {
     /// Docker version
     #[serde(default)]
@@ -511,8 +755,8 @@ pub struct DockerImageFull {
     #[serde(default)]
     pub user: Option<String>,
     /// Environment variables
-    #[serde(default)]
-    pub environment: HashMap<String, String>,
+    #[serde(default, deserialize_with = "environment")]
+    pub environment: BTreeMap<String, String>,
 }
 
 /// Docker registry authentication.
@@ -561,6 +805,454 @@ pub struct MacOsConfig {
     pub xcode: String,
 }
 
+/// A workflow: the jobs a pipeline runs and the order they run in.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct Workflow {
+    /// Condition under which the workflow runs
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub when: Option<Condition>,
+
+    /// Condition under which the workflow is skipped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unless: Option<Condition>,
+
+    /// Scheduled triggers (legacy `triggers:` key)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub triggers: Vec<serde_yaml::Value>,
+
+    /// Job invocations, in the order they are listed
+    #[serde(default)]
+    pub jobs: Vec<WorkflowJob>,
+}
+
+/// One entry of a workflow's `jobs` list.
+///
+/// Written either as a bare job name (`- build`) or as a single-key mapping
+/// from the job name to its options (`- build: {requires: [test]}`).
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct WorkflowJob {
+    /// Job invoked: a local job (`build`) or an imported one (`node/test`)
+    pub job: String,
+
+    /// Options and arguments of the invocation
+    pub options: WorkflowJobOptions,
+}
+
+/// Options of a workflow job invocation.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct WorkflowJobOptions {
+    /// Name the invocation is known by in `requires` (defaults to the job)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+
+    /// Jobs that must finish first. Status requirements
+    /// (`- build: [success, canceled]`) are reduced to the job name.
+    #[serde(
+        default,
+        deserialize_with = "requirement_names",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub requires: Vec<String>,
+
+    /// Contexts whose environment variables the job receives
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub context: Vec<String>,
+
+    /// Job type, e.g. `approval`
+    #[serde(rename = "type", default, skip_serializing_if = "Option::is_none")]
+    pub job_type: Option<String>,
+
+    /// Branch and tag filters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub filters: Option<WorkflowFilters>,
+
+    /// Matrix of parameter values to fan the job out over
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub matrix: Option<Matrix>,
+
+    /// Steps run before the job's own steps, ahead of `checkout`
+    #[serde(rename = "pre-steps", default, skip_serializing_if = "Option::is_none")]
+    pub pre_steps: Option<StepList>,
+
+    /// Steps run after the job's own steps
+    #[serde(
+        rename = "post-steps",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub post_steps: Option<StepList>,
+
+    /// Parameter arguments, by name
+    #[serde(flatten)]
+    pub parameters: BTreeMap<String, serde_yaml::Value>,
+}
+
+/// Filters limiting which pipelines run a workflow job.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum WorkflowFilters {
+    /// Branch and tag patterns
+    Refs {
+        /// Branches the job runs (or does not run) on
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        branches: Option<RefFilter>,
+        /// Tags the job runs (or does not run) on
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        tags: Option<RefFilter>,
+    },
+    /// Filter expression, e.g. `pipeline.git.branch == "main"`
+    Expression(String),
+}
+
+/// `only`/`ignore` patterns of a branch or tag filter. Patterns wrapped in
+/// `/` are regular expressions.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct RefFilter {
+    /// Patterns that select refs
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub only: Vec<String>,
+
+    /// Patterns that exclude refs
+    #[serde(
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub ignore: Vec<String>,
+}
+
+impl WorkflowJobOptions {
+    /// Which branches and tags run the job, e.g. `branches main; no tags`.
+    ///
+    /// Without filters a job runs on every branch and no tag, as CircleCI
+    /// only runs tag pipelines for jobs with a `tags` filter.
+    pub fn runs_on(&self) -> String {
+        match &self.filters {
+            Some(filters) => filters.describe(),
+            None => "all branches; no tags".to_string(),
+        }
+    }
+}
+
+impl WorkflowFilters {
+    /// A one-line reading of the filters, e.g.
+    /// `branches main; all tags except /^v.*/`.
+    pub fn describe(&self) -> String {
+        match self {
+            WorkflowFilters::Refs { branches, tags } => {
+                let branches = match branches {
+                    Some(filter) => filter.describe("branches"),
+                    None => "all branches".to_string(),
+                };
+                let tags = match tags {
+                    Some(filter) => filter.describe("tags"),
+                    None => "no tags".to_string(),
+                };
+                format!("{branches}; {tags}")
+            }
+            WorkflowFilters::Expression(expression) => format!("when {expression}"),
+        }
+    }
+}
+
+impl RefFilter {
+    /// The refs of `kind` (`branches` or `tags`) the filter selects.
+    fn describe(&self, kind: &str) -> String {
+        match (self.only.is_empty(), self.ignore.is_empty()) {
+            (true, true) => format!("all {kind}"),
+            (true, false) => format!("all {kind} except {}", self.ignore.join(", ")),
+            (false, true) => format!("{kind} {}", self.only.join(", ")),
+            (false, false) => format!(
+                "{kind} {} except {}",
+                self.only.join(", "),
+                self.ignore.join(", ")
+            ),
+        }
+    }
+}
+
+/// A workflow job's `matrix`: the job runs once for every combination of
+/// parameter values, less the excluded ones.
+///
+/// Parameters keep the order they are written in, which is the order
+/// CircleCI joins values in when naming the expanded jobs.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Matrix {
+    /// Values of each parameter, by name. A single value is a one-item list.
+    pub parameters: Vec<(String, Vec<serde_yaml::Value>)>,
+
+    /// Combinations to leave out, each giving a value for every parameter
+    pub exclude: Vec<BTreeMap<String, serde_yaml::Value>>,
+
+    /// Name `requires` can use for every expanded job at once (defaults to
+    /// the job name)
+    pub alias: Option<String>,
+}
+
+/// How a [`Matrix`] is written in YAML.
+#[derive(Serialize, Deserialize)]
+struct RawMatrix {
+    parameters: serde_yaml::Mapping,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    exclude: Vec<BTreeMap<String, serde_yaml::Value>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    alias: Option<String>,
+}
+
+impl Matrix {
+    /// Every combination of parameter values that is not excluded, in
+    /// expansion order (the last parameter varies fastest).
+    pub fn combinations(&self) -> Vec<Vec<(&str, &serde_yaml::Value)>> {
+        let mut combinations: Vec<Vec<(&str, &serde_yaml::Value)>> = vec![vec![]];
+        for (name, values) in &self.parameters {
+            combinations = combinations
+                .into_iter()
+                .flat_map(|combination| {
+                    values.iter().map(move |value| {
+                        let mut combination = combination.clone();
+                        combination.push((name.as_str(), value));
+                        combination
+                    })
+                })
+                .collect();
+        }
+        combinations.retain(|combination| {
+            !self.exclude.iter().any(|excluded| {
+                excluded.len() == combination.len()
+                    && combination
+                        .iter()
+                        .all(|(name, value)| excluded.get(*name) == Some(*value))
+            })
+        });
+        combinations
+    }
+
+    /// Names of the jobs the matrix expands `name` into.
+    ///
+    /// A name with `<< matrix.<parameter> >>` placeholders has them filled
+    /// in; otherwise the values are appended, e.g. `test-3.11-linux`.
+    pub fn job_names(&self, name: &str) -> Vec<String> {
+        self.combinations()
+            .into_iter()
+            .map(|combination| {
+                if name.contains("<< matrix.") {
+                    combination
+                        .iter()
+                        .fold(name.to_string(), |name, (parameter, value)| {
+                            name.replace(
+                                &format!("<< matrix.{parameter} >>"),
+                                &scalar_string(value),
+                            )
+                        })
+                } else {
+                    std::iter::once(name.to_string())
+                        .chain(combination.iter().map(|(_, value)| scalar_string(value)))
+                        .collect::<Vec<_>>()
+                        .join("-")
+                }
+            })
+            .collect()
+    }
+}
+
+impl Serialize for Matrix {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        RawMatrix {
+            parameters: self
+                .parameters
+                .iter()
+                .map(|(name, values)| {
+                    (
+                        serde_yaml::Value::from(name.as_str()),
+                        serde_yaml::Value::Sequence(values.clone()),
+                    )
+                })
+                .collect(),
+            exclude: self.exclude.clone(),
+            alias: self.alias.clone(),
+        }
+        .serialize(s)
+    }
+}
+
+impl<'de> Deserialize<'de> for Matrix {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        let raw = RawMatrix::deserialize(d)?;
+        let parameters = raw
+            .parameters
+            .into_iter()
+            .map(|(name, values)| {
+                let name = name.as_str().map(str::to_string).ok_or_else(|| {
+                    serde::de::Error::custom("matrix parameter names must be strings")
+                })?;
+                let values = match values {
+                    serde_yaml::Value::Sequence(values) => values,
+                    value => vec![value],
+                };
+                Ok((name, values))
+            })
+            .collect::<Result<_, D::Error>>()?;
+        Ok(Self {
+            parameters,
+            exclude: raw.exclude,
+            alias: raw.alias,
+        })
+    }
+}
+
+/// A matrix value as it appears in an expanded job name.
+fn scalar_string(value: &serde_yaml::Value) -> String {
+    match value {
+        serde_yaml::Value::String(s) => s.clone(),
+        serde_yaml::Value::Number(n) => n.to_string(),
+        serde_yaml::Value::Bool(b) => b.to_string(),
+        serde_yaml::Value::Null => String::new(),
+        other => serde_yaml::to_string(other)
+            .unwrap_or_default()
+            .trim()
+            .to_string(),
+    }
+}
+
+impl<'de> Deserialize<'de> for WorkflowJob {
+    fn deserialize<D: Deserializer<'de>>(d: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Entry {
+            Name(String),
+            Invocation(BTreeMap<String, Option<WorkflowJobOptions>>),
+        }
+
+        match Entry::deserialize(d)? {
+            Entry::Name(job) => Ok(Self {
+                job,
+                options: WorkflowJobOptions::default(),
+            }),
+            Entry::Invocation(invocation) => {
+                let mut entries = invocation.into_iter();
+                match (entries.next(), entries.next()) {
+                    (Some((job, options)), None) => Ok(Self {
+                        job,
+                        options: options.unwrap_or_default(),
+                    }),
+                    _ => Err(serde::de::Error::custom(
+                        "a workflow job entry must name exactly one job",
+                    )),
+                }
+            }
+        }
+    }
+}
+
+impl Serialize for WorkflowJob {
+    fn serialize<S: Serializer>(&self, s: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+        if self.options == WorkflowJobOptions::default() {
+            return s.serialize_str(&self.job);
+        }
+        let mut m = s.serialize_map(Some(1))?;
+        m.serialize_entry(&self.job, &self.options)?;
+        m.end()
+    }
+}
+
+/// Workflows keyed by name, skipping the legacy `version: 2` entry.
+fn deserialize_workflows<'de, D: Deserializer<'de>>(
+    d: D,
+) -> Result<HashMap<String, Workflow>, D::Error> {
+    let raw: HashMap<String, serde_yaml::Value> = Deserialize::deserialize(d)?;
+    raw.into_iter()
+        .filter(|(name, _)| name != "version")
+        .map(|(name, value)| {
+            let workflow = serde_yaml::from_value(value)
+                .map_err(|e| serde::de::Error::custom(format!("workflow `{name}`: {e}")))?;
+            Ok((name, workflow))
+        })
+        .collect()
+}
+
+/// A string or a list of strings, as a list.
+fn one_or_many<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(d)? {
+        OneOrMany::One(value) => vec![value],
+        OneOrMany::Many(values) => values,
+    })
+}
+
+/// A `run` command written as one string, kept as is (so `command: |`
+/// blocks keep their lines), or as a list of lines joined by newlines.
+fn command_lines<'de, D: Deserializer<'de>>(d: D) -> Result<String, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Command {
+        Script(String),
+        Lines(Vec<String>),
+    }
+
+    Ok(match Command::deserialize(d)? {
+        Command::Script(script) => script,
+        Command::Lines(lines) => lines.join("\n"),
+    })
+}
+
+/// Environment variables, with number and boolean values as written in
+/// YAML turned into strings (`RETRIES: 3` into `"3"`) and empty values into
+/// empty strings.
+fn environment<'de, D: Deserializer<'de>>(d: D) -> Result<BTreeMap<String, String>, D::Error> {
+    let raw: BTreeMap<String, serde_yaml::Value> = Deserialize::deserialize(d)?;
+    raw.into_iter()
+        .map(|(name, value)| {
+            let value = match value {
+                serde_yaml::Value::String(value) => value,
+                serde_yaml::Value::Number(value) => value.to_string(),
+                serde_yaml::Value::Bool(value) => value.to_string(),
+                serde_yaml::Value::Null => String::new(),
+                _ => {
+                    return Err(serde::de::Error::custom(format!(
+                        "environment variable `{name}`: expected a string, number or boolean"
+                    )))
+                }
+            };
+            Ok((name, value))
+        })
+        .collect()
+}
+
+/// Job names of a `requires` list whose entries are names or
+/// `{name: status}` mappings.
+fn requirement_names<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Requirement {
+        Name(String),
+        WithStatus(BTreeMap<String, serde_yaml::Value>),
+    }
+
+    let requirements: Vec<Requirement> = Deserialize::deserialize(d)?;
+    Ok(requirements
+        .into_iter()
+        .flat_map(|requirement| match requirement {
+            Requirement::Name(name) => vec![name],
+            Requirement::WithStatus(map) => map.into_keys().collect(),
+        })
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -598,7 +1290,7 @@ mod tests {
     #[test]
     fn test_step_when_serde_roundtrip() {
         let step = Step::Structured(StructuredStep::When(ConditionalStep {
-            condition: serde_yaml::Value::String("always".to_string()),
+            condition: Condition::Literal(serde_yaml::Value::from("always")),
             steps: vec![Step::Simple("checkout".to_string())],
         }));
         let yaml = serde_yaml::to_string(&step).unwrap();
@@ -613,7 +1305,7 @@ mod tests {
     #[test]
     fn test_step_unless_serde_roundtrip() {
         let step = Step::Structured(StructuredStep::Unless(ConditionalStep {
-            condition: serde_yaml::Value::Bool(false),
+            condition: Condition::Literal(serde_yaml::Value::Bool(false)),
             steps: vec![],
         }));
         let yaml = serde_yaml::to_string(&step).unwrap();
@@ -622,6 +1314,29 @@ mod tests {
         matches!(back, Step::Structured(StructuredStep::Unless(_)));
     }
 
+    #[test]
+    fn test_step_steps_serde_roundtrip() {
+        let steps: Vec<Step> = serde_yaml::from_str(
+            "- steps: << parameters.setup >>\n- steps:\n    - checkout\n    - run: make\n",
+        )
+        .unwrap();
+        let Step::Structured(StructuredStep::Steps(placeholder)) = &steps[0] else {
+            panic!("expected a steps step, got {:?}", steps[0]);
+        };
+        assert_eq!(placeholder.parameter(), Some("setup"));
+        assert!(placeholder.steps().is_empty());
+        let Step::Structured(StructuredStep::Steps(inline)) = &steps[1] else {
+            panic!("expected a steps step, got {:?}", steps[1]);
+        };
+        assert_eq!(inline.parameter(), None);
+        assert_eq!(inline.steps().len(), 2);
+
+        let yaml = serde_yaml::to_string(&steps).unwrap();
+        assert!(!yaml.contains("!steps"));
+        let back: Vec<Step> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(back, steps);
+    }
+
     #[test]
     fn test_orb_definition_serde_roundtrip() {
         // Simulate a command with a run step followed by a when step — the
@@ -637,7 +1352,7 @@ mod tests {
                         "echo hello".to_string(),
                     ))),
                     Step::Structured(StructuredStep::When(ConditionalStep {
-                        condition: serde_yaml::Value::String("on_success".to_string()),
+                        condition: Condition::Literal(serde_yaml::Value::from("on_success")),
                         steps: vec![Step::Simple("checkout".to_string())],
                     })),
                 ],
@@ -760,6 +1475,148 @@ run:
         }
     }
 
+    #[test]
+    fn test_environment_values_become_strings() {
+        let yaml = r#"
+version: "2.1"
+executors:
+  default:
+    docker:
+      - image: cimg/postgres:16.0
+        environment:
+          POSTGRES_PORT: 5432
+    environment:
+      RETRIES: 3
+      VERBOSE: true
+      RATIO: 0.5
+      EMPTY:
+jobs:
+  test:
+    executor: default
+    environment:
+      CI_NODE: 1
+    steps:
+      - run:
+          command: cargo test
+          environment:
+            RUST_BACKTRACE: 1
+            QUIET: false
+"#;
+        let orb: OrbDefinition = serde_yaml::from_str(yaml).unwrap();
+        let executor = &orb.executors["default"].config;
+        let expected: BTreeMap<String, String> = [
+            ("EMPTY", ""),
+            ("RATIO", "0.5"),
+            ("RETRIES", "3"),
+            ("VERBOSE", "true"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(executor.environment, expected);
+        let Some(DockerImage::Full(image)) = executor.docker.as_ref().and_then(|d| d.first())
+        else {
+            panic!("Expected Full variant");
+        };
+        assert_eq!(image.environment["POSTGRES_PORT"], "5432");
+
+        let job = &orb.jobs["test"];
+        assert_eq!(job.config.environment["CI_NODE"], "1");
+        match &job.steps[0] {
+            Step::Structured(StructuredStep::Run(RunStep::Full { environment, .. })) => {
+                assert_eq!(environment["RUST_BACKTRACE"], "1");
+                assert_eq!(environment["QUIET"], "false");
+            }
+            other => panic!("Expected Run with Full variant, got {other:?}"),
+        }
+
+        let err = serde_yaml::from_str::<Executor>("environment:\n  PATHS: [a, b]\n").unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("environment variable `PATHS`: expected a string, number or boolean"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_run_step_forms() {
+        let yaml = r#"
+- run:
+    name: Build
+    command: |
+      cargo build
+        --release
+
+      echo done
+    when: on_fail
+- run:
+    command:
+      - cargo fmt --check
+      - cargo clippy
+    when: always
+- run:
+    name: Report
+    environment:
+      SCRIPT: report.sh
+    when: << parameters.when >>
+"#;
+        let steps: Vec<Step> = serde_yaml::from_str(yaml).unwrap();
+        let runs: Vec<(&str, Option<&RunWhen>)> = steps
+            .iter()
+            .map(|step| match step {
+                Step::Structured(StructuredStep::Run(RunStep::Full { command, when, .. })) => {
+                    (command.as_str(), when.as_ref())
+                }
+                other => panic!("Expected Run with Full variant, got {other:?}"),
+            })
+            .collect();
+        assert_eq!(
+            runs,
+            [
+                (
+                    "cargo build\n  --release\n\necho done\n",
+                    Some(&RunWhen::OnFail)
+                ),
+                ("cargo fmt --check\ncargo clippy", Some(&RunWhen::Always)),
+                (
+                    "",
+                    Some(&RunWhen::Placeholder("<< parameters.when >>".to_string()))
+                ),
+            ]
+        );
+
+        let yaml = serde_yaml::to_string(&steps).unwrap();
+        assert!(yaml.contains("when: on_fail"), "{yaml}");
+        let reparsed: Vec<Step> = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(reparsed, steps);
+
+        let err = serde_yaml::from_str::<RunWhen>("on_failure").unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "unknown `when` value `on_failure`: expected always, on_success or on_fail"
+            ),
+            "{err}"
+        );
+
+        let yaml = r#"
+- checkout
+- when:
+    condition: true
+    steps:
+      - run: {command: make, when: on_failure}
+"#;
+        let err = serde_yaml::from_str::<Vec<Step>>(yaml).unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "`when` step: `run` step: unknown `when` value `on_failure`: expected always, on_success or on_fail"
+            ),
+            "{err}"
+        );
+        let yaml = "- run:\n    command: make\n    environment: [A=1]\n";
+        let err = serde_yaml::from_str::<Vec<Step>>(yaml).unwrap_err();
+        assert!(err.to_string().contains("`run` step: "), "{err}");
+    }
+
     #[test]
     fn test_orb_definition_empty() {
         let yaml = r#"
@@ -771,4 +1628,183 @@ version: "2.1"
         assert!(orb.jobs.is_empty());
         assert!(orb.executors.is_empty());
     }
+
+    #[test]
+    fn test_orb_definition_x_version() {
+        let yaml = r#"
+version: "2.1"
+x-version: "4.9.6"
+"#;
+        let orb: OrbDefinition = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(orb.x_version.as_deref(), Some("4.9.6"));
+
+        let out = serde_yaml::to_string(&OrbDefinition::default()).unwrap();
+        assert!(!out.contains("x-version"));
+    }
+
+    #[test]
+    fn test_orb_definition_workflows() {
+        let yaml = r#"
+version: "2.1"
+workflows:
+  version: 2
+  main:
+    jobs:
+      - build
+      - hold:
+          type: approval
+          requires:
+            - build: [success, canceled]
+      - deploy:
+          name: deploy-prod
+          requires: [hold]
+          context: org-global
+          release: true
+          filters:
+            branches:
+              only: main
+            tags:
+              ignore: /^v.*/
+  nightly:
+    when:
+      equal: [scheduled_pipeline, << pipeline.trigger_source >>]
+    jobs:
+      - lint:
+"#;
+        let orb: OrbDefinition = serde_yaml::from_str(yaml).unwrap();
+        assert_eq!(orb.workflows.len(), 2);
+
+        let main = &orb.workflows["main"];
+        assert!(main.when.is_none());
+        assert_eq!(main.jobs[0].job, "build");
+        assert_eq!(main.jobs[0].options, WorkflowJobOptions::default());
+        assert_eq!(main.jobs[1].options.job_type.as_deref(), Some("approval"));
+        assert_eq!(main.jobs[1].options.requires, vec!["build"]);
+
+        let deploy = &main.jobs[2].options;
+        assert_eq!(deploy.name.as_deref(), Some("deploy-prod"));
+        assert_eq!(deploy.context, vec!["org-global"]);
+        assert_eq!(deploy.parameters["release"], serde_yaml::Value::Bool(true));
+        assert_eq!(
+            deploy.filters,
+            Some(WorkflowFilters::Refs {
+                branches: Some(RefFilter {
+                    only: vec!["main".to_string()],
+                    ignore: vec![],
+                }),
+                tags: Some(RefFilter {
+                    only: vec![],
+                    ignore: vec!["/^v.*/".to_string()],
+                }),
+            })
+        );
+
+        let nightly = &orb.workflows["nightly"];
+        assert_eq!(
+            nightly.when.as_ref().map(Condition::describe).as_deref(),
+            Some("\"scheduled_pipeline\" equals pipeline.trigger_source")
+        );
+        assert_eq!(nightly.jobs[0].job, "lint");
+
+        // Bare job names stay bare; everything else round-trips.
+        let out = serde_yaml::to_string(&orb).unwrap();
+        assert!(out.contains("- build\n"));
+        let reparsed: OrbDefinition = serde_yaml::from_str(&out).unwrap();
+        assert_eq!(reparsed.workflows["main"].jobs, main.jobs);
+    }
+
+    #[test]
+    fn test_workflow_job_pre_and_post_steps() {
+        let job: WorkflowJob = serde_yaml::from_str(
+            r#"
+deploy:
+  requires: [build]
+  target: prod
+  pre-steps:
+    - checkout
+  post-steps: << pipeline.parameters.notify >>
+"#,
+        )
+        .unwrap();
+        assert_eq!(
+            job.options.pre_steps,
+            Some(StepList::Steps(vec![Step::Simple("checkout".to_string())]))
+        );
+        assert_eq!(
+            job.options.post_steps,
+            Some(StepList::Placeholder(
+                "<< pipeline.parameters.notify >>".to_string()
+            ))
+        );
+        assert_eq!(job.options.post_steps.as_ref().unwrap().parameter(), None);
+        assert_eq!(
+            job.options.parameters.keys().collect::<Vec<_>>(),
+            ["target"]
+        );
+    }
+
+    #[test]
+    fn test_workflow_job_matrix_and_filters() {
+        let job: WorkflowJob = serde_yaml::from_str(
+            r#"
+test:
+  name: test-<< matrix.python >>-<< matrix.os >>
+  matrix:
+    alias: test-all
+    parameters:
+      python: ["3.11", "3.12"]
+      os: [linux, macos]
+    exclude:
+      - {python: "3.11", os: macos}
+  filters:
+    branches:
+      ignore: /^wip\/.*/
+    tags:
+      only: /^v.*/
+"#,
+        )
+        .unwrap();
+        let matrix = job.options.matrix.as_ref().unwrap();
+        assert_eq!(matrix.alias.as_deref(), Some("test-all"));
+        assert_eq!(
+            matrix
+                .parameters
+                .iter()
+                .map(|(name, values)| (name.as_str(), values.len()))
+                .collect::<Vec<_>>(),
+            [("python", 2), ("os", 2)]
+        );
+        assert_eq!(matrix.combinations().len(), 3);
+        assert_eq!(
+            matrix.job_names(job.options.name.as_deref().unwrap()),
+            ["test-3.11-linux", "test-3.12-linux", "test-3.12-macos"]
+        );
+        assert_eq!(
+            job.options.runs_on(),
+            "all branches except /^wip\\/.*/; tags /^v.*/"
+        );
+
+        // Without a templated name the values are appended; single values
+        // are one-item lists.
+        let matrix: Matrix =
+            serde_yaml::from_str("parameters: {version: [1, 2], debug: true}").unwrap();
+        assert_eq!(matrix.job_names("build"), ["build-1-true", "build-2-true"]);
+        let reparsed: Matrix =
+            serde_yaml::from_str(&serde_yaml::to_string(&matrix).unwrap()).unwrap();
+        assert_eq!(reparsed, matrix);
+
+        assert_eq!(
+            WorkflowJobOptions::default().runs_on(),
+            "all branches; no tags"
+        );
+        let filters: WorkflowFilters =
+            serde_yaml::from_str("branches: {only: [main, develop]}").unwrap();
+        assert_eq!(filters.describe(), "branches main, develop; no tags");
+    }
+
+    #[test]
+    fn test_workflow_job_rejects_several_jobs() {
+        let err = serde_yaml::from_str::<WorkflowJob>("{build: {}, test: {}}").unwrap_err();
+        assert!(err.to_string().contains("exactly one job"));
+    }
 }
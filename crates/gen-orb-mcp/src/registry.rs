@@ -0,0 +1,373 @@
+//! Fetching published orbs from the CircleCI orb registry.
+//!
+//! `generate --orb-ref circleci/node@5.1.0` downloads the packed source of a
+//! published orb through the CircleCI GraphQL API, so servers can be
+//! generated for orbs a team consumes but does not host. Private orbs and
+//! CircleCI server installations are reached with a `CIRCLECI_TOKEN` and a
+//! custom host.
+//!
+//! `generate --resolve-orbs` fetches the orbs an orb imports the same way.
+//! Pinned versions are kept in an [`OrbCache`], so regenerating does not hit
+//! the API again:
+//!
+//! ```text
+//! <DIR>/
+//! └── circleci/
+//!     └── node@5.1.0.yml
+//! ```
+
+use std::{
+    fmt, fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use serde_json::json;
+use thiserror::Error;
+
+/// Default CircleCI host.
+pub const DEFAULT_HOST: &str = "https://circleci.com";
+
+/// Environment variable holding a CircleCI API token for private orbs.
+pub const TOKEN_ENV: &str = "CIRCLECI_TOKEN";
+
+const ORB_VERSION_QUERY: &str =
+    "query($orbVersionRef: String!) { orbVersion(orbVersionRef: $orbVersionRef) { version source } }";
+
+/// Errors fetching an orb from the registry.
+#[derive(Debug, Error)]
+pub enum RegistryError {
+    /// The reference is not `<namespace>/<name>[@<version>]`.
+    #[error("invalid orb reference '{reference}': expected <namespace>/<name>[@<version>]")]
+    InvalidRef { reference: String },
+
+    /// The HTTP request failed.
+    #[error("failed to query {host} for {reference}: {source}")]
+    Request {
+        host: String,
+        reference: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    /// The API answered with errors or an unexpected body.
+    #[error("CircleCI API error for {reference}: {message}")]
+    Api { reference: String, message: String },
+
+    /// The orb or version does not exist (or is private without a token).
+    #[error("orb {reference} not found (set {TOKEN_ENV} for private orbs)")]
+    NotFound { reference: String },
+
+    /// The orb cache could not be read or written.
+    #[error("orb cache {path}: {source}")]
+    Cache {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+}
+
+/// A published orb version, e.g. `circleci/node@5.1.0`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OrbRef {
+    /// Registry namespace (e.g. `circleci`).
+    pub namespace: String,
+    /// Orb name within the namespace (e.g. `node`).
+    pub name: String,
+    /// Version, `volatile` for the latest release, or `dev:<label>`.
+    pub version: String,
+}
+
+impl FromStr for OrbRef {
+    type Err = RegistryError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || RegistryError::InvalidRef {
+            reference: s.to_string(),
+        };
+        let (path, version) = s.split_once('@').unwrap_or((s, "volatile"));
+        let (namespace, name) = path.split_once('/').ok_or_else(invalid)?;
+        let valid = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        };
+        if !valid(namespace) || !valid(name) || version.is_empty() {
+            return Err(invalid());
+        }
+        Ok(Self {
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+            version: version.to_string(),
+        })
+    }
+}
+
+impl OrbRef {
+    /// Whether the reference names one immutable release (`x.y.z`), as
+    /// opposed to `volatile`, a partial version such as `5` or a dev label.
+    pub fn is_pinned(&self) -> bool {
+        semver::Version::parse(&self.version).is_ok()
+    }
+}
+
+impl fmt::Display for OrbRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}@{}", self.namespace, self.name, self.version)
+    }
+}
+
+/// Packed source of a published orb version.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FetchedOrb {
+    /// The requested reference.
+    pub orb_ref: OrbRef,
+    /// The resolved version (differs from the reference for `volatile`).
+    pub version: String,
+    /// Packed orb YAML.
+    pub source: String,
+}
+
+impl FetchedOrb {
+    /// The reference pinned to the resolved version, e.g.
+    /// `circleci/node@5.1.0` for `circleci/node@volatile`.
+    pub fn resolved_ref(&self) -> String {
+        format!(
+            "{}/{}@{}",
+            self.orb_ref.namespace, self.orb_ref.name, self.version
+        )
+    }
+}
+
+/// Client for the CircleCI orb registry.
+#[derive(Debug, Clone)]
+pub struct RegistryClient {
+    host: String,
+    token: Option<String>,
+}
+
+impl RegistryClient {
+    /// A client for `host` (e.g. `https://circleci.com`), authenticated with
+    /// `CIRCLECI_TOKEN` when it is set.
+    pub fn new(host: &str) -> Self {
+        Self {
+            host: host.trim_end_matches('/').to_string(),
+            token: std::env::var(TOKEN_ENV).ok().filter(|t| !t.is_empty()),
+        }
+    }
+
+    /// Download the packed source of `orb_ref`.
+    pub fn fetch(&self, orb_ref: &OrbRef) -> Result<FetchedOrb, RegistryError> {
+        let reference = orb_ref.to_string();
+        let request_error = |source| RegistryError::Request {
+            host: self.host.clone(),
+            reference: reference.clone(),
+            source,
+        };
+        let body = json!({
+            "query": ORB_VERSION_QUERY,
+            "variables": { "orbVersionRef": reference },
+        })
+        .to_string();
+
+        let rt = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| RegistryError::Api {
+                reference: reference.clone(),
+                message: e.to_string(),
+            })?;
+        let text = rt
+            .block_on(async {
+                let mut request = reqwest::Client::new()
+                    .post(format!("{}/graphql-unstable", self.host))
+                    .header("Content-Type", "application/json")
+                    .body(body);
+                if let Some(token) = &self.token {
+                    request = request.header("Circle-Token", token);
+                }
+                request.send().await?.error_for_status()?.text().await
+            })
+            .map_err(request_error)?;
+        parse_response(orb_ref, &text)
+    }
+}
+
+/// `$XDG_CACHE_HOME/gen-orb-mcp`, falling back to `$HOME/.cache/gen-orb-mcp`
+/// and then the temporary directory; the root of the default caches.
+pub fn cache_home() -> PathBuf {
+    let non_empty = |var| std::env::var_os(var).filter(|v| !v.is_empty());
+    non_empty("XDG_CACHE_HOME")
+        .map(PathBuf::from)
+        .or_else(|| non_empty("HOME").map(|home| Path::new(&home).join(".cache")))
+        .unwrap_or_else(std::env::temp_dir)
+        .join("gen-orb-mcp")
+}
+
+/// Directory of fetched orb sources, keyed by pinned reference.
+///
+/// Only pinned references are cached; `volatile`, partial versions and dev
+/// labels can move, so they are always fetched.
+#[derive(Debug, Clone)]
+pub struct OrbCache {
+    dir: PathBuf,
+}
+
+impl OrbCache {
+    /// Use `dir` as the cache root; it is created on first store.
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// `$XDG_CACHE_HOME/gen-orb-mcp/orbs`, falling back to
+    /// `$HOME/.cache/gen-orb-mcp/orbs` and then the temporary directory.
+    pub fn default_dir() -> PathBuf {
+        cache_home().join("orbs")
+    }
+
+    /// The cached source of `orb_ref`, fetching and storing it with
+    /// `client` on a miss.
+    pub fn fetch(
+        &self,
+        client: &RegistryClient,
+        orb_ref: &OrbRef,
+    ) -> Result<FetchedOrb, RegistryError> {
+        if !orb_ref.is_pinned() {
+            return client.fetch(orb_ref);
+        }
+        let path = self.path_of(orb_ref);
+        let cache_error = |source| RegistryError::Cache {
+            path: path.clone(),
+            source,
+        };
+        if path.is_file() {
+            let source = fs::read_to_string(&path).map_err(cache_error)?;
+            return Ok(FetchedOrb {
+                orb_ref: orb_ref.clone(),
+                version: orb_ref.version.clone(),
+                source,
+            });
+        }
+        let fetched = client.fetch(orb_ref)?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(cache_error)?;
+        }
+        fs::write(&path, &fetched.source).map_err(cache_error)?;
+        Ok(fetched)
+    }
+
+    fn path_of(&self, orb_ref: &OrbRef) -> PathBuf {
+        self.dir
+            .join(&orb_ref.namespace)
+            .join(format!("{}@{}.yml", orb_ref.name, orb_ref.version))
+    }
+}
+
+/// Extract the orb version from a GraphQL response body.
+fn parse_response(orb_ref: &OrbRef, body: &str) -> Result<FetchedOrb, RegistryError> {
+    let reference = orb_ref.to_string();
+    let api_error = |message: String| RegistryError::Api {
+        reference: reference.clone(),
+        message,
+    };
+    let response: serde_json::Value =
+        serde_json::from_str(body).map_err(|e| api_error(e.to_string()))?;
+    if let Some(errors) = response["errors"].as_array().filter(|e| !e.is_empty()) {
+        let messages: Vec<&str> = errors
+            .iter()
+            .filter_map(|e| e["message"].as_str())
+            .collect();
+        return Err(api_error(messages.join("; ")));
+    }
+    let orb_version = &response["data"]["orbVersion"];
+    if orb_version.is_null() {
+        return Err(RegistryError::NotFound { reference });
+    }
+    let field = |name: &str| {
+        orb_version[name]
+            .as_str()
+            .map(str::to_string)
+            .ok_or_else(|| api_error(format!("response has no {name}")))
+    };
+    Ok(FetchedOrb {
+        orb_ref: orb_ref.clone(),
+        version: field("version")?,
+        source: field("source")?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_orb_ref_parsing() {
+        let orb_ref: OrbRef = "circleci/node@5.1.0".parse().unwrap();
+        assert_eq!(orb_ref.namespace, "circleci");
+        assert_eq!(orb_ref.name, "node");
+        assert_eq!(orb_ref.version, "5.1.0");
+
+        let latest: OrbRef = "jerus-org/gen-orb-mcp".parse().unwrap();
+        assert_eq!(latest.to_string(), "jerus-org/gen-orb-mcp@volatile");
+
+        for invalid in ["node@5.1.0", "circleci/@1.0.0", "circleci/node@", "a/b/c@1"] {
+            assert!(invalid.parse::<OrbRef>().is_err(), "{invalid}");
+        }
+    }
+
+    #[test]
+    fn test_parse_response() {
+        let orb_ref: OrbRef = "circleci/node@volatile".parse().unwrap();
+        let fetched = parse_response(
+            &orb_ref,
+            r#"{"data":{"orbVersion":{"version":"5.1.0","source":"version: 2.1\n"}}}"#,
+        )
+        .unwrap();
+        assert_eq!(fetched.version, "5.1.0");
+        assert_eq!(fetched.source, "version: 2.1\n");
+        assert_eq!(fetched.resolved_ref(), "circleci/node@5.1.0");
+
+        let err = parse_response(&orb_ref, r#"{"data":{"orbVersion":null}}"#).unwrap_err();
+        assert!(matches!(err, RegistryError::NotFound { .. }));
+
+        let err = parse_response(
+            &orb_ref,
+            r#"{"data":null,"errors":[{"message":"Orb not found"}]}"#,
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "CircleCI API error for circleci/node@volatile: Orb not found"
+        );
+    }
+
+    #[test]
+    fn test_orb_cache_serves_pinned_versions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = OrbCache::new(temp_dir.path());
+        // Nothing listens here, so any fetch fails.
+        let client = RegistryClient::new("http://127.0.0.1:9");
+
+        let pinned: OrbRef = "circleci/node@5.1.0".parse().unwrap();
+        assert!(pinned.is_pinned());
+        assert!(cache.fetch(&client, &pinned).is_err());
+
+        let cached = temp_dir.path().join("circleci/node@5.1.0.yml");
+        fs::create_dir_all(cached.parent().unwrap()).unwrap();
+        fs::write(&cached, "version: 2.1\n").unwrap();
+        let fetched = cache.fetch(&client, &pinned).unwrap();
+        assert_eq!(fetched.source, "version: 2.1\n");
+        assert_eq!(fetched.resolved_ref(), "circleci/node@5.1.0");
+
+        // Moving references bypass the cache.
+        fs::write(
+            temp_dir.path().join("circleci/node@5.yml"),
+            "version: 2.1\n",
+        )
+        .unwrap();
+        let moving: OrbRef = "circleci/node@5".parse().unwrap();
+        assert!(!moving.is_pinned());
+        assert!(cache.fetch(&client, &moving).is_err());
+    }
+}
@@ -0,0 +1,156 @@
+//! Starter files for the `init` subcommand.
+//!
+//! Lays out a new unpacked orb the way [`OrbParser`](crate::parser::OrbParser)
+//! and `circleci orb pack` expect: one command whose `run` step includes a
+//! script, a job running it on an executor, and a usage example. The files
+//! validate cleanly, so a new orb starts without lint findings.
+//!
+//! ```text
+//! <name>/
+//! └── src/
+//!     ├── @orb.yml
+//!     ├── commands/greet.yml
+//!     ├── jobs/hello.yml
+//!     ├── executors/default.yml
+//!     ├── examples/example.yml
+//!     └── scripts/greet.sh
+//! ```
+
+use std::path::PathBuf;
+
+const ORB_YML: &str = r#"version: 2.1
+description: >
+  {name}: describe what the orb provides here. The first sentence opens the
+  orb's page in the registry and its MCP server's overview.
+display:
+  home_url: "https://github.com/{namespace}/{name}"
+  source_url: "https://github.com/{namespace}/{name}"
+"#;
+
+const GREET_COMMAND: &str = r#"description: >
+  Print a greeting. Replace this with the orb's first command.
+parameters:
+  to:
+    type: string
+    description: Who to greet
+    default: World
+steps:
+  - run:
+      name: Greet << parameters.to >>
+      command: <<include(scripts/greet.sh)>>
+      environment:
+        PARAM_TO: << parameters.to >>
+"#;
+
+const GREET_SCRIPT: &str = r#"#!/bin/bash
+set -euo pipefail
+
+echo "Hello, ${PARAM_TO}!"
+"#;
+
+const HELLO_JOB: &str = r#"description: >
+  Check out the project and print a greeting.
+executor: default
+parameters:
+  to:
+    type: string
+    description: Who to greet
+    default: World
+steps:
+  - checkout
+  - greet:
+      to: << parameters.to >>
+"#;
+
+const DEFAULT_EXECUTOR: &str = r#"description: >
+  CircleCI's base convenience image.
+docker:
+  - image: cimg/base:stable
+"#;
+
+const EXAMPLE: &str = r#"description: >
+  Greet the team on every commit.
+usage:
+  version: 2.1
+  orbs:
+    {name}: {namespace}/{name}@1.0.0
+  workflows:
+    main:
+      jobs:
+        - {name}/hello:
+            to: Team
+"#;
+
+/// Whether `name` can name an orb: ASCII letters, digits, `-` and `_`.
+pub fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The starter files of an orb called `name` published under `namespace`,
+/// as `(path relative to the orb's root, content)` in path order.
+pub fn files(name: &str, namespace: &str) -> Vec<(PathBuf, String)> {
+    let fill = |template: &str| {
+        template
+            .replace("{name}", name)
+            .replace("{namespace}", namespace)
+    };
+    let mut files: Vec<(PathBuf, String)> = [
+        ("src/@orb.yml", ORB_YML),
+        ("src/commands/greet.yml", GREET_COMMAND),
+        ("src/executors/default.yml", DEFAULT_EXECUTOR),
+        ("src/examples/example.yml", EXAMPLE),
+        ("src/jobs/hello.yml", HELLO_JOB),
+        ("src/scripts/greet.sh", GREET_SCRIPT),
+    ]
+    .into_iter()
+    .map(|(path, template)| (PathBuf::from(path), fill(template)))
+    .collect();
+    files.sort();
+    files
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::TempDir;
+
+    use super::*;
+    use crate::{lint, parser::OrbParser};
+
+    #[test]
+    fn test_is_valid_name() {
+        assert!(is_valid_name("my-orb"));
+        assert!(is_valid_name("orb_2"));
+        assert!(!is_valid_name(""));
+        assert!(!is_valid_name("../orb"));
+        assert!(!is_valid_name("my orb"));
+    }
+
+    #[test]
+    fn test_files_parse_strictly_without_findings() {
+        let dir = TempDir::new().unwrap();
+        for (path, content) in files("toolkit", "my-org") {
+            let path = dir.path().join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        }
+        let orb_path = dir.path().join("src/@orb.yml");
+
+        let orb = OrbParser::parse_all_strict(&orb_path).unwrap();
+        assert_eq!(
+            orb.display.unwrap().source_url.as_deref(),
+            Some("https://github.com/my-org/toolkit")
+        );
+        assert!(orb.commands.contains_key("greet"));
+        assert!(orb.jobs.contains_key("hello"));
+        assert!(orb.executors.contains_key("default"));
+        assert_eq!(
+            orb.examples["example"].usage["orbs"]["toolkit"].as_str(),
+            Some("my-org/toolkit@1.0.0")
+        );
+        assert!(crate::parser::references::unresolved(&orb).is_empty());
+        assert_eq!(lint::lint(&orb), vec![]);
+    }
+}
@@ -0,0 +1,504 @@
+//! Orb complexity metrics for the `stats` subcommand.
+//!
+//! Counts parameters, steps, step nesting depth and `run` script lines for
+//! every command, job and executor, plus orb-wide totals, the parameter type
+//! distribution, the largest commands, the docker images in use and the orbs
+//! imported. Maintainers can use the numbers to spot bloated definitions and,
+//! with `--json`, to track orb growth across releases.
+
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt,
+};
+
+use serde::Serialize;
+
+use crate::parser::{
+    DockerImage, ExecutorConfig, OrbDefinition, Parameter, RunStep, Step, StructuredStep,
+};
+
+/// Number of commands listed under [`OrbStats::largest_commands`].
+pub const LARGEST_COMMANDS: usize = 5;
+
+/// Metrics for one command, job or executor.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct DefinitionStats {
+    /// `commands`, `jobs` or `executors`.
+    pub category: &'static str,
+    /// Definition name.
+    pub name: String,
+    /// Number of declared parameters.
+    pub parameters: usize,
+    /// Number of steps, including those nested in `when`/`unless`.
+    pub steps: usize,
+    /// Deepest step nesting (1 for a flat step list, 0 without steps).
+    pub max_depth: usize,
+    /// Non-blank lines across all `run` commands.
+    pub script_lines: usize,
+}
+
+/// Orb-wide totals.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct Totals {
+    pub commands: usize,
+    pub jobs: usize,
+    pub executors: usize,
+    pub examples: usize,
+    pub workflows: usize,
+    pub parameters: usize,
+    pub steps: usize,
+    pub max_depth: usize,
+    pub script_lines: usize,
+}
+
+/// How the parameters of commands, jobs and executors are typed.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct ParameterStats {
+    /// Parameter count per type (`string`, `boolean`, ...).
+    pub by_type: BTreeMap<&'static str, usize>,
+    /// Parameters without a default, which every invocation must pass.
+    pub required: usize,
+    /// Parameters with a default.
+    pub optional: usize,
+}
+
+/// A docker image and the definitions that declare it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ImageUse {
+    /// Image reference as written, e.g. `cimg/rust:1.80`.
+    pub image: String,
+    /// Jobs and executors declaring the image, e.g. `executors/rust`.
+    pub used_by: Vec<String>,
+}
+
+/// An orb imported under `orbs:`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrbDependency {
+    /// Alias the orb is imported under.
+    pub alias: String,
+    /// Orb reference, e.g. `circleci/node@5.1.0`.
+    pub reference: String,
+}
+
+/// Metrics for a whole orb.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OrbStats {
+    /// Per-definition metrics, sorted by category and name.
+    pub definitions: Vec<DefinitionStats>,
+    /// Totals over all definitions.
+    pub totals: Totals,
+    /// Parameter type distribution.
+    pub parameters: ParameterStats,
+    /// Up to [`LARGEST_COMMANDS`] commands with the most steps, largest
+    /// first.
+    pub largest_commands: Vec<DefinitionStats>,
+    /// Docker images declared by jobs and executors, sorted by reference.
+    pub docker_images: Vec<ImageUse>,
+    /// Imported orbs, sorted by alias.
+    pub orbs: Vec<OrbDependency>,
+}
+
+/// Compute metrics for every definition in `orb`.
+pub fn compute(orb: &OrbDefinition) -> OrbStats {
+    let mut definitions = Vec::new();
+    for (name, command) in &orb.commands {
+        definitions.push(definition(
+            "commands",
+            name,
+            command.parameters.len(),
+            &command.steps,
+        ));
+    }
+    for (name, job) in &orb.jobs {
+        definitions.push(definition("jobs", name, job.parameters.len(), &job.steps));
+    }
+    for (name, executor) in &orb.executors {
+        definitions.push(definition(
+            "executors",
+            name,
+            executor.parameters.len(),
+            &[],
+        ));
+    }
+    definitions.sort_by(|a, b| (a.category, &a.name).cmp(&(b.category, &b.name)));
+
+    let totals = Totals {
+        commands: orb.commands.len(),
+        jobs: orb.jobs.len(),
+        executors: orb.executors.len(),
+        examples: orb.examples.len(),
+        workflows: orb.workflows.len(),
+        parameters: definitions.iter().map(|d| d.parameters).sum(),
+        steps: definitions.iter().map(|d| d.steps).sum(),
+        max_depth: definitions.iter().map(|d| d.max_depth).max().unwrap_or(0),
+        script_lines: definitions.iter().map(|d| d.script_lines).sum(),
+    };
+
+    let mut parameters = ParameterStats::default();
+    let declared = orb
+        .commands
+        .values()
+        .map(|c| &c.parameters)
+        .chain(orb.jobs.values().map(|j| &j.parameters))
+        .chain(orb.executors.values().map(|e| &e.parameters));
+    for parameter in declared.flat_map(HashMap::values) {
+        count_parameter(parameter, &mut parameters);
+    }
+
+    let mut largest_commands: Vec<DefinitionStats> = definitions
+        .iter()
+        .filter(|d| d.category == "commands")
+        .cloned()
+        .collect();
+    largest_commands.sort_by(|a, b| b.steps.cmp(&a.steps).then_with(|| a.name.cmp(&b.name)));
+    largest_commands.truncate(LARGEST_COMMANDS);
+
+    let mut images: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    let configs = orb
+        .jobs
+        .iter()
+        .map(|(name, job)| (format!("jobs/{name}"), &job.config))
+        .chain(
+            orb.executors
+                .iter()
+                .map(|(name, executor)| (format!("executors/{name}"), &executor.config)),
+        );
+    for (owner, config) in configs {
+        for image in docker_images(config) {
+            images
+                .entry(image.to_string())
+                .or_default()
+                .push(owner.clone());
+        }
+    }
+    let docker_images = images
+        .into_iter()
+        .map(|(image, mut used_by)| {
+            used_by.sort();
+            used_by.dedup();
+            ImageUse { image, used_by }
+        })
+        .collect();
+
+    let mut orbs: Vec<OrbDependency> = orb
+        .orbs
+        .iter()
+        .map(|(alias, reference)| OrbDependency {
+            alias: alias.clone(),
+            reference: reference.clone(),
+        })
+        .collect();
+    orbs.sort_by(|a, b| a.alias.cmp(&b.alias));
+
+    OrbStats {
+        definitions,
+        totals,
+        parameters,
+        largest_commands,
+        docker_images,
+        orbs,
+    }
+}
+
+fn count_parameter(parameter: &Parameter, stats: &mut ParameterStats) {
+    *stats
+        .by_type
+        .entry(parameter.param_type.as_str())
+        .or_default() += 1;
+    if parameter.default.is_some() {
+        stats.optional += 1;
+    } else {
+        stats.required += 1;
+    }
+}
+
+/// Image references of the docker executor `config` declares.
+fn docker_images(config: &ExecutorConfig) -> impl Iterator<Item = &str> {
+    config.docker.iter().flatten().map(|image| match image {
+        DockerImage::Simple(reference) => reference.as_str(),
+        DockerImage::Full(full) => full.image.as_str(),
+    })
+}
+
+fn definition(
+    category: &'static str,
+    name: &str,
+    parameters: usize,
+    steps: &[Step],
+) -> DefinitionStats {
+    let mut stats = DefinitionStats {
+        category,
+        name: name.to_string(),
+        parameters,
+        steps: 0,
+        max_depth: 0,
+        script_lines: 0,
+    };
+    walk_steps(steps, 1, &mut stats);
+    stats
+}
+
+fn walk_steps(steps: &[Step], depth: usize, stats: &mut DefinitionStats) {
+    for step in steps {
+        stats.steps += 1;
+        stats.max_depth = stats.max_depth.max(depth);
+        let Step::Structured(step) = step else {
+            continue;
+        };
+        match step {
+            StructuredStep::Run(RunStep::Simple(command))
+            | StructuredStep::Run(RunStep::Full { command, .. }) => {
+                stats.script_lines += command.lines().filter(|l| !l.trim().is_empty()).count();
+            }
+            StructuredStep::When(conditional) | StructuredStep::Unless(conditional) => {
+                walk_steps(&conditional.steps, depth + 1, stats);
+            }
+            StructuredStep::Steps(list) => walk_steps(list.steps(), depth + 1, stats),
+            _ => {}
+        }
+    }
+}
+
+impl fmt::Display for OrbStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self
+            .definitions
+            .iter()
+            .map(|d| d.category.len() + 1 + d.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("DEFINITION".len());
+        writeln!(
+            f,
+            "{:<width$}  {:>6}  {:>5}  {:>5}  {:>12}",
+            "DEFINITION", "PARAMS", "STEPS", "DEPTH", "SCRIPT LINES"
+        )?;
+        for d in &self.definitions {
+            writeln!(
+                f,
+                "{:<width$}  {:>6}  {:>5}  {:>5}  {:>12}",
+                format!("{}/{}", d.category, d.name),
+                d.parameters,
+                d.steps,
+                d.max_depth,
+                d.script_lines
+            )?;
+        }
+        let t = &self.totals;
+        writeln!(
+            f,
+            "{:<width$}  {:>6}  {:>5}  {:>5}  {:>12}",
+            "TOTAL", t.parameters, t.steps, t.max_depth, t.script_lines
+        )?;
+        write!(
+            f,
+            "{} command(s), {} job(s), {} executor(s), {} example(s), {} workflow(s)",
+            t.commands, t.jobs, t.executors, t.examples, t.workflows
+        )?;
+
+        let p = &self.parameters;
+        if !p.by_type.is_empty() {
+            writeln!(f, "\n")?;
+            writeln!(f, "{:<14}  {:>5}", "PARAMETER TYPE", "COUNT")?;
+            for (param_type, count) in &p.by_type {
+                writeln!(f, "{param_type:<14}  {count:>5}")?;
+            }
+            write!(f, "{} required, {} with a default", p.required, p.optional)?;
+        }
+
+        if !self.largest_commands.is_empty() {
+            let width = self
+                .largest_commands
+                .iter()
+                .map(|d| d.name.len())
+                .max()
+                .unwrap_or(0)
+                .max("LARGEST COMMANDS".len());
+            writeln!(f, "\n")?;
+            write!(f, "{:<width$}  {:>5}", "LARGEST COMMANDS", "STEPS")?;
+            for d in &self.largest_commands {
+                write!(f, "\n{:<width$}  {:>5}", d.name, d.steps)?;
+            }
+        }
+
+        if !self.docker_images.is_empty() {
+            let width = self
+                .docker_images
+                .iter()
+                .map(|i| i.image.len())
+                .max()
+                .unwrap_or(0)
+                .max("DOCKER IMAGE".len());
+            writeln!(f, "\n")?;
+            write!(f, "{:<width$}  USED BY", "DOCKER IMAGE")?;
+            for i in &self.docker_images {
+                write!(f, "\n{:<width$}  {}", i.image, i.used_by.join(", "))?;
+            }
+        }
+
+        if !self.orbs.is_empty() {
+            writeln!(f, "\n")?;
+            write!(f, "ORB DEPENDENCIES")?;
+            for o in &self.orbs {
+                write!(f, "\n{}: {}", o.alias, o.reference)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ORB: &str = r#"
+version: 2.1
+orbs:
+  node: circleci/node@5.1.0
+commands:
+  build:
+    parameters:
+      release:
+        type: boolean
+        default: false
+      target:
+        type: string
+        default: ""
+    steps:
+      - checkout
+      - when:
+          condition: << parameters.release >>
+          steps:
+            - run:
+                name: Release build
+                command: |
+                  cargo build --release
+
+                  strip target/release/app
+      - run: cargo test
+jobs:
+  test:
+    docker:
+      - image: cimg/base:2024.01
+    steps:
+      - build
+executors:
+  rust:
+    parameters:
+      tag:
+        type: string
+        default: latest
+    docker:
+      - image: rust:<< parameters.tag >>
+"#;
+
+    #[test]
+    fn test_compute_counts_nested_steps_and_script_lines() {
+        let orb: OrbDefinition = serde_yaml::from_str(ORB).unwrap();
+        let stats = compute(&orb);
+
+        let names: Vec<_> = stats
+            .definitions
+            .iter()
+            .map(|d| format!("{}/{}", d.category, d.name))
+            .collect();
+        assert_eq!(names, ["commands/build", "executors/rust", "jobs/test"]);
+
+        let build = &stats.definitions[0];
+        assert_eq!(build.parameters, 2);
+        assert_eq!(build.steps, 4);
+        assert_eq!(build.max_depth, 2);
+        assert_eq!(build.script_lines, 3);
+
+        assert_eq!(stats.definitions[1].steps, 0);
+        assert_eq!(stats.definitions[1].max_depth, 0);
+        assert_eq!(
+            stats.totals,
+            Totals {
+                commands: 1,
+                jobs: 1,
+                executors: 1,
+                examples: 0,
+                workflows: 0,
+                parameters: 3,
+                steps: 5,
+                max_depth: 2,
+                script_lines: 3,
+            }
+        );
+    }
+
+    #[test]
+    fn test_display_lists_definitions_and_totals() {
+        let orb: OrbDefinition = serde_yaml::from_str(ORB).unwrap();
+        let text = compute(&orb).to_string();
+        assert!(text.starts_with("DEFINITION"), "{text}");
+        assert!(
+            text.contains("commands/build       2      4      2             3"),
+            "{text}"
+        );
+        assert!(
+            text.contains("1 command(s), 1 job(s), 1 executor(s), 0 example(s), 0 workflow(s)\n\n"),
+            "{text}"
+        );
+        assert!(text.contains("PARAMETER TYPE  COUNT\nboolean             1\nstring              2\n0 required, 3 with a default"), "{text}");
+        assert!(
+            text.contains("LARGEST COMMANDS  STEPS\nbuild                 4"),
+            "{text}"
+        );
+        assert!(
+            text.contains(
+                "cimg/base:2024.01          jobs/test\nrust:<< parameters.tag >>  executors/rust"
+            ),
+            "{text}"
+        );
+        assert!(
+            text.ends_with("ORB DEPENDENCIES\nnode: circleci/node@5.1.0"),
+            "{text}"
+        );
+    }
+
+    #[test]
+    fn test_compute_reports_parameters_images_and_orbs() {
+        let orb: OrbDefinition = serde_yaml::from_str(ORB).unwrap();
+        let stats = compute(&orb);
+
+        assert_eq!(
+            stats.parameters,
+            ParameterStats {
+                by_type: BTreeMap::from([("boolean", 1), ("string", 2)]),
+                required: 0,
+                optional: 3,
+            }
+        );
+        let largest: Vec<_> = stats
+            .largest_commands
+            .iter()
+            .map(|d| (d.name.as_str(), d.steps))
+            .collect();
+        assert_eq!(largest, [("build", 4)]);
+        assert_eq!(
+            stats.docker_images,
+            [
+                ImageUse {
+                    image: "cimg/base:2024.01".to_string(),
+                    used_by: vec!["jobs/test".to_string()],
+                },
+                ImageUse {
+                    image: "rust:<< parameters.tag >>".to_string(),
+                    used_by: vec!["executors/rust".to_string()],
+                },
+            ]
+        );
+        assert_eq!(
+            stats.orbs,
+            [OrbDependency {
+                alias: "node".to_string(),
+                reference: "circleci/node@5.1.0".to_string(),
+            }]
+        );
+
+        let empty = compute(&OrbDefinition::default()).to_string();
+        assert!(empty.ends_with("0 workflow(s)"), "{empty}");
+    }
+}
@@ -0,0 +1,72 @@
+//! "Did you mean" suggestions for names that fail to resolve.
+//!
+//! Unknown executors, commands, parameters, lint rules and profiles are
+//! usually typos of a name that does exist. Error messages append the closest
+//! candidate by Levenshtein distance, provided it is close enough to be a
+//! plausible typo.
+
+/// Edit distance between `a` and `b` (insertions, deletions, substitutions).
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let above = row[j + 1];
+            row[j + 1] = if ca == *cb {
+                diagonal
+            } else {
+                1 + diagonal.min(above).min(row[j])
+            };
+            diagonal = above;
+        }
+    }
+    row[b.len()]
+}
+
+/// The candidate closest to `name`, if any is within a third of its length
+/// (rounded up). Ties go to the first candidate in iteration order.
+pub fn closest<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> Option<&'a str> {
+    let limit = name.chars().count().div_ceil(3);
+    candidates
+        .into_iter()
+        .filter(|c| *c != name)
+        .map(|c| (levenshtein(name, c), c))
+        .filter(|(distance, _)| *distance <= limit)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, c)| c)
+}
+
+/// `", did you mean 'x'?"` for the closest candidate, or an empty string.
+pub fn did_you_mean<'a>(name: &str, candidates: impl IntoIterator<Item = &'a str>) -> String {
+    closest(name, candidates)
+        .map(|c| format!(", did you mean '{c}'?"))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein() {
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("defautl", "default"), 2);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("same", "same"), 0);
+    }
+
+    #[test]
+    fn test_closest_prefers_nearest_plausible_candidate() {
+        let candidates = ["default", "macos", "docker-large"];
+        assert_eq!(closest("defautl", candidates), Some("default"));
+        assert_eq!(closest("macso", candidates), Some("macos"));
+        assert_eq!(closest("windows", candidates), None);
+        assert_eq!(
+            did_you_mean("defautl", candidates),
+            ", did you mean 'default'?"
+        );
+        assert_eq!(did_you_mean("windows", candidates), "");
+    }
+}
@@ -1,92 +1,338 @@
-/// Integration test: generated MCP server source compiles with cargo build.
+/// Integration test: generated MCP server source compiles and passes its own
+/// integration tests with cargo test.
 ///
 /// This test exercises the full generation pipeline — template rendering plus
 /// dependency resolution — to catch rmcp API or feature-flag mismatches that
 /// content-only string assertions cannot detect.
-use std::{collections::HashMap, process::Command};
+use std::{
+    net::{Ipv4Addr, SocketAddr},
+    path::Path,
+    process::Command,
+};
 
 use gen_orb_mcp::{
-    generator::CodeGenerator,
-    parser::{Command as OrbCommand, Job, OrbDefinition, Parameter, ParameterType},
+    generator::{
+        generate_in_memory, CodeGenerator, GenerateOptions, GeneratedServer, Logo, Transport,
+    },
+    parser::OrbDefinition,
 };
 use tempfile::TempDir;
 
+/// YAML of the fixture orb, also read at runtime by the dev-mode server.
+const FIXTURE_ORB: &str = r#"
+version: 2.1
+description: >-
+  Fixture orb for compilation tests — exercises commands, jobs, executors,
+  examples, workflows and the tools path
+executors:
+  default:
+    description: Base image at the requested tag
+    parameters:
+      tag:
+        type: string
+        default: current
+        description: Image tag
+    docker:
+      - image: cimg/base:<< parameters.tag >>
+commands:
+  print:
+    description: Print a message
+    parameters:
+      message:
+        type: string
+        default: hello
+        description: Message to print
+      level:
+        type: enum
+        enum: [info, warn, error]
+        default: info
+        description: Log level
+      loud:
+        type: boolean
+        default: false
+    steps:
+      - run: echo "[<< parameters.level >>] << parameters.message >>"
+jobs:
+  run-print:
+    description: Run the print command
+    executor: default
+    parameters:
+      tag:
+        type: string
+        default: latest
+        description: Docker image tag
+      retries:
+        type: integer
+        default: 1
+    steps:
+      - checkout
+      - print:
+          level: warn
+examples:
+  basic:
+    description: Print on every push
+    usage:
+      version: 2.1
+      orbs:
+        fixture: acme/fixture-orb@1.0.0
+      workflows:
+        main:
+          jobs:
+            - fixture/run-print
+workflows:
+  main:
+    jobs:
+      - run-print
+"#;
+
 fn fixture_orb() -> OrbDefinition {
-    let mut orb = OrbDefinition {
-        version: "2.1".to_string(),
-        description: Some(
-            "Fixture orb for compilation tests — exercises commands, jobs, and tools path"
-                .to_string(),
+    serde_yaml::from_str(FIXTURE_ORB).expect("fixture orb")
+}
+
+#[test]
+fn generated_server_compiles() {
+    let opts = GenerateOptions {
+        source_rev: Some("0123abc".to_string()),
+        logo: Some(
+            Logo::from_bytes(b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>".to_vec())
+                .expect("Logo::from_bytes"),
         ),
-        ..Default::default()
+        ..GenerateOptions::new("fixture-orb", "1.0.0")
+    };
+    let server = generate_in_memory(&fixture_orb(), &opts).expect("generate");
+    assert_generated_tests_pass(&server, &[]);
+}
+
+#[test]
+fn generated_http_server_compiles() {
+    let opts = GenerateOptions {
+        http_compression: true,
+        http_body_limit: Some(1 << 20),
+        ..GenerateOptions::new("fixture-http", "1.0.0")
+            .with_transport(Transport::Http)
+            .with_http_bind(SocketAddr::from((Ipv4Addr::LOCALHOST, 8080)))
+    };
+    let server = generate_in_memory(&fixture_orb(), &opts).expect("generate");
+    assert_generated_tests_pass(&server, &[]);
+}
+
+#[test]
+fn generated_dev_mode_server_compiles() {
+    let orb_dir = TempDir::new().expect("TempDir::new");
+    let orb_path = orb_dir.path().join("orb.yml");
+    std::fs::write(&orb_path, FIXTURE_ORB).expect("write fixture orb");
+    let opts = GenerateOptions::new("fixture-dev", "1.0.0").with_dev_orb_path(&orb_path);
+    let server = generate_in_memory(&fixture_orb(), &opts).expect("generate");
+    assert_generated_tests_pass(&server, &[]);
+}
+
+#[test]
+fn generated_property_tests_pass() {
+    let opts = GenerateOptions {
+        property_tests: true,
+        ..GenerateOptions::new("fixture-props", "1.0.0")
+    };
+    let server = generate_in_memory(&fixture_orb(), &opts).expect("generate");
+    assert!(server
+        .files
+        .keys()
+        .any(|p| p.ends_with("tests/properties.rs")));
+    assert_generated_tests_pass(&server, &[]);
+}
+
+#[test]
+fn generated_sandboxed_server_with_split_resources_compiles() {
+    let opts = GenerateOptions {
+        sandboxed: true,
+        max_resource_bytes: Some(256),
+        ..GenerateOptions::new("fixture-sandboxed", "1.0.0")
+    };
+    let server = generate_in_memory(&fixture_orb(), &opts).expect("generate");
+    assert_generated_tests_pass(&server, &[]);
+}
+
+#[test]
+fn generated_multi_orb_server_compiles() {
+    let orbs = vec![
+        ("fixture".to_string(), fixture_orb()),
+        ("validation".to_string(), validation_orb()),
+    ];
+    let server = CodeGenerator::from_options(&GenerateOptions::new("platform", "1.0.0"))
+        .expect("generator")
+        .generate_multi(&orbs, "platform", "1.0.0")
+        .expect("generate");
+    assert_generated_tests_pass(&server, &[]);
+}
+
+/// An orb whose `deploy` command and job share a name, so that every
+/// `validate_invocation` error code can be produced.
+fn validation_orb() -> OrbDefinition {
+    serde_yaml::from_str(
+        r#"
+version: 2.1
+commands:
+  deploy:
+    parameters:
+      target: {type: string}
+  notify:
+    parameters:
+      channel: {type: string, default: general}
+      level: {type: enum, enum: [info, error], default: info}
+      retries: {type: integer, default: 1}
+jobs:
+  deploy:
+    steps: [checkout]
+"#,
+    )
+    .expect("validation orb")
+}
+
+/// Integration test of the generated crate calling `validate_invocation`.
+const VALIDATE_INVOCATION_TESTS: &str = r#"
+use serde_json::{json, Value};
+use CRATE_NAME::OrbServer;
+
+fn validate(args: Value) -> Value {
+    let Value::Object(args) = args else {
+        panic!("arguments must be an object");
     };
+    let result = OrbServer::new()
+        .call("validate_invocation", args)
+        .expect("validate_invocation");
+    let text = &result.content[0].as_text().expect("text content").text;
+    serde_json::from_str(text).expect("JSON result")
+}
 
-    // Command with a string parameter (exercises has_tools path in template)
-    let mut cmd_params = HashMap::new();
-    cmd_params.insert(
-        "message".to_string(),
-        Parameter {
-            param_type: ParameterType::String,
-            description: Some("Message to print".to_string()),
-            default: Some(serde_yaml::Value::String("hello".to_string())),
-            enum_values: None,
-        },
+#[test]
+fn unknown_definition() {
+    let result = validate(json!({"name": "build", "kind": "job"}));
+    assert_eq!(
+        result,
+        json!({
+            "valid": false,
+            "name": "build",
+            "errors": [{
+                "code": "unknown_definition",
+                "message": "no job named `build`",
+                "expected": ["deploy"],
+            }],
+        })
     );
-    orb.commands.insert(
-        "print".to_string(),
-        OrbCommand {
-            description: Some("Print a message".to_string()),
-            parameters: cmd_params,
-            steps: vec![],
-        },
+}
+
+#[test]
+fn ambiguous_definition() {
+    let result = validate(json!({"name": "deploy"}));
+    assert_eq!(
+        result,
+        json!({
+            "valid": false,
+            "name": "deploy",
+            "errors": [{
+                "code": "ambiguous_definition",
+                "message": "both a command and a job are named `deploy`; pass `kind`",
+                "expected": ["command", "job"],
+            }],
+        })
     );
+}
 
-    // Job (exercises resource generation)
-    let mut job_params = HashMap::new();
-    job_params.insert(
-        "tag".to_string(),
-        Parameter {
-            param_type: ParameterType::String,
-            description: Some("Docker image tag".to_string()),
-            default: Some(serde_yaml::Value::String("latest".to_string())),
-            enum_values: None,
-        },
+#[test]
+fn missing_required() {
+    let result = validate(json!({"name": "deploy", "kind": "command"}));
+    assert_eq!(
+        result,
+        json!({
+            "valid": false,
+            "kind": "command",
+            "name": "deploy",
+            "errors": [{
+                "parameter": "target",
+                "code": "missing_required",
+                "message": "missing required parameter `target`",
+                "expected": "string",
+            }],
+            "schema": "orb://schemas/commands/deploy",
+        })
     );
-    orb.jobs.insert(
-        "run-print".to_string(),
-        Job {
-            description: Some("Run the print command".to_string()),
-            parameters: job_params,
-            steps: vec![],
-            executor: None,
-            config: Default::default(),
-            parallelism: None,
-            circleci_ip_ranges: None,
-        },
+}
+
+#[test]
+fn unknown_parameter_wrong_type_and_not_in_enum() {
+    let result = validate(json!({
+        "name": "notify",
+        "parameters": {"colour": "red", "level": "debug", "retries": "three"},
+    }));
+    assert_eq!(result["valid"], false);
+    assert_eq!(
+        result["errors"],
+        json!([
+            {
+                "parameter": "colour",
+                "code": "unknown_parameter",
+                "message": "unknown parameter `colour`",
+                "expected": ["channel", "level", "retries"],
+            },
+            {
+                "parameter": "level",
+                "code": "not_in_enum",
+                "message": "parameter `level` must be one of ["info","error"]",
+                "expected": ["info", "error"],
+            },
+            {
+                "parameter": "retries",
+                "code": "wrong_type",
+                "message": "parameter `retries` must be integer",
+                "expected": ["integer"],
+            },
+        ])
     );
+}
 
-    orb
+#[test]
+fn valid_invocation() {
+    let result = validate(json!({
+        "name": "notify",
+        "kind": "command",
+        "parameters": {"level": "error", "retries": "<< pipeline.parameters.retries >>"},
+    }));
+    assert_eq!(result["valid"], true);
+    assert_eq!(result["errors"], json!([]));
 }
+"#;
 
 #[test]
-fn generated_server_compiles() {
-    let generator = CodeGenerator::new().expect("CodeGenerator::new");
-    let orb = fixture_orb();
-    let server = generator
-        .generate(&orb, "fixture-orb", "1.0.0")
-        .expect("generate");
+fn generated_validate_invocation_reports_each_error() {
+    let opts = GenerateOptions::new("validation-orb", "1.0.0");
+    let server = generate_in_memory(&validation_orb(), &opts).expect("generate");
+    let tests = VALIDATE_INVOCATION_TESTS.replace("CRATE_NAME", &server.crate_name);
+    assert_generated_tests_pass(&server, &[("tests/validate_invocation.rs", &tests)]);
+}
 
+/// Write `server` and `extra_files` to a temporary directory and run
+/// `cargo test` there, sharing one target directory between servers so that
+/// their dependencies are built once.
+fn assert_generated_tests_pass(server: &GeneratedServer, extra_files: &[(&str, &str)]) {
     let tmp = TempDir::new().expect("TempDir::new");
     server.write_to(tmp.path()).expect("write_to");
+    for (path, content) in extra_files {
+        let path = tmp.path().join(path);
+        std::fs::create_dir_all(path.parent().unwrap_or(Path::new("."))).expect("create_dir_all");
+        std::fs::write(&path, content).expect("write extra file");
+    }
 
     let status = Command::new("cargo")
-        .args(["build", "--color", "never"])
+        .args(["test", "--color", "never"])
         .current_dir(tmp.path())
+        .env(
+            "CARGO_TARGET_DIR",
+            Path::new(env!("CARGO_TARGET_TMPDIR")).join("generated"),
+        )
         .status()
-        .expect("failed to run cargo build");
+        .expect("failed to run cargo test");
 
     assert!(
         status.success(),
-        "generated MCP server did not compile — check template rmcp version and import paths"
+        "generated MCP server did not compile or its tests failed — check template rmcp version and import paths"
     );
 }